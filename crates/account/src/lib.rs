@@ -23,6 +23,7 @@ mod auth;
 mod storage;
 mod storage_key;
 mod types;
+mod vesting;
 
 pub use auth::AccountPublicKeysMap;
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -37,6 +38,7 @@ use serde::{Deserialize, Serialize};
 pub use storage::*;
 pub use storage_key::*;
 pub use types::*;
+pub use vesting::VestingSchedule;
 
 #[derive(
     Debug,
@@ -1,7 +1,7 @@
 //! Cryptographic signature keys storage API
 
 use namada_core::storage;
-use namada_storage::{Result, ResultExt, StorageRead, StorageWrite};
+use namada_storage::{Error, Result, ResultExt, StorageRead, StorageWrite};
 
 use super::*;
 
@@ -38,6 +38,33 @@ where
     storage.write(&threshold_key, threshold)
 }
 
+/// Init the subspace of a new vesting account
+pub fn init_vesting_account_storage<S>(
+    storage: &mut S,
+    owner: &Address,
+    public_keys: &[common::PublicKey],
+    threshold: u8,
+    schedule: &VestingSchedule,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    schedule.validate().map_err(Error::new_alloc)?;
+    init_account_storage(storage, owner, public_keys, threshold)?;
+    storage.write(&vesting_schedule_key(owner), schedule)
+}
+
+/// Get the vesting schedule of an account, if any
+pub fn vesting_schedule<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<Option<VestingSchedule>>
+where
+    S: StorageRead,
+{
+    storage.read(&vesting_schedule_key(owner))
+}
+
 /// Get the threshold associated with an account
 pub fn threshold<S>(storage: &S, owner: &Address) -> Result<Option<u8>>
 where
@@ -11,6 +11,7 @@ struct Keys {
     public_keys: &'static str,
     threshold: &'static str,
     protocol_public_keys: &'static str,
+    vesting_schedule: &'static str,
 }
 
 /// Obtain a storage key for user's public key.
@@ -89,3 +90,26 @@ pub fn is_protocol_pk_key(key: &storage::Key) -> Option<&Address> {
         _ => None,
     }
 }
+
+/// Obtain the storage key for an account's vesting schedule
+pub fn vesting_schedule_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.vesting_schedule.to_string()),
+        ],
+    }
+}
+
+/// Check if the given storage key is a vesting schedule key. If it is, returns
+/// the owner.
+pub fn is_vesting_schedule_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(key)]
+            if key.as_str() == Keys::VALUES.vesting_schedule =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
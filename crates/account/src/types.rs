@@ -7,6 +7,8 @@ use namada_macros::BorshDeserializer;
 use namada_migrations::*;
 use serde::{Deserialize, Serialize};
 
+use crate::VestingSchedule;
+
 /// A tx data type to initialize a new established account
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(
@@ -31,6 +33,33 @@ pub struct InitAccount {
     pub threshold: u8,
 }
 
+/// A tx data type to initialize a new established account whose tokens are
+/// locked by a vesting schedule
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct InitVestingAccount {
+    /// Public keys to be written into the account's storage. This can be used
+    /// for signature verification of transactions for the newly created
+    /// account.
+    pub public_keys: Vec<common::PublicKey>,
+    /// The VP code hash
+    pub vp_code_hash: Hash,
+    /// The account signature threshold
+    pub threshold: u8,
+    /// The vesting schedule of the account's tokens
+    pub schedule: VestingSchedule,
+}
+
 /// A tx data type to update an account's validity predicate
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(
@@ -62,8 +91,10 @@ pub struct UpdateAccount {
 /// Tests and strategies for accounts
 pub mod tests {
     use namada_core::address::testing::arb_non_internal_address;
+    use namada_core::chain::Epoch;
     use namada_core::hash::testing::arb_hash;
     use namada_core::key::testing::arb_common_pk;
+    use namada_core::token::testing::arb_amount;
     use proptest::prelude::Just;
     use proptest::{collection, option, prop_compose};
 
@@ -86,6 +117,32 @@ pub mod tests {
         }
     }
 
+    prop_compose! {
+        /// Generate a vesting account initialization
+        pub fn arb_init_vesting_account()(
+            init in arb_init_account(),
+            token in arb_non_internal_address(),
+            amount in arb_amount(),
+            (start, cliff, end) in (0..1_000_u64, 0..1_000_u64, 0..1_000_u64),
+        ) -> InitVestingAccount {
+            let mut epochs = [start, cliff, end];
+            epochs.sort_unstable();
+            let [start, cliff, end] = epochs;
+            InitVestingAccount {
+                public_keys: init.public_keys,
+                vp_code_hash: init.vp_code_hash,
+                threshold: init.threshold,
+                schedule: VestingSchedule {
+                    token,
+                    amount,
+                    start: Epoch(start),
+                    cliff: Epoch(cliff),
+                    end: Epoch(end),
+                },
+            }
+        }
+    }
+
     prop_compose! {
         /// Generate an arbitrary account update
         pub fn arb_update_account()(
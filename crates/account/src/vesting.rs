@@ -0,0 +1,137 @@
+//! Time-locked vesting of the tokens held by an account.
+
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::chain::Epoch;
+use namada_core::token;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use serde::{Deserialize, Serialize};
+
+/// A vesting schedule of an account's token balance.
+///
+/// No tokens are vested before the `cliff` epoch. From the `cliff` onwards,
+/// the vested amount grows linearly from the `start` epoch until the whole
+/// `amount` is vested at the `end` epoch. A pure cliff schedule has its
+/// `cliff` equal to its `end` and a pure linear schedule has its `cliff`
+/// equal to its `start`.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct VestingSchedule {
+    /// The vested token
+    pub token: Address,
+    /// The total amount of tokens subject to vesting
+    pub amount: token::Amount,
+    /// The epoch from which the linear vesting is computed
+    pub start: Epoch,
+    /// The first epoch at which any tokens are vested
+    pub cliff: Epoch,
+    /// The epoch at which all the tokens are vested
+    pub end: Epoch,
+}
+
+impl VestingSchedule {
+    /// Check that the epochs of the schedule are ordered as
+    /// `start <= cliff <= end`
+    pub fn validate(&self) -> Result<(), String> {
+        if self.start > self.cliff || self.cliff > self.end {
+            return Err(format!(
+                "The vesting schedule epochs must be ordered as start <= \
+                 cliff <= end, got start {}, cliff {} and end {}",
+                self.start, self.cliff, self.end
+            ));
+        }
+        Ok(())
+    }
+
+    /// The amount of tokens vested at the given epoch
+    pub fn vested_amount(&self, epoch: Epoch) -> token::Amount {
+        if epoch < self.cliff {
+            return token::Amount::zero();
+        }
+        if epoch >= self.end {
+            return self.amount;
+        }
+        // Here `start <= cliff <= epoch < end`, so neither subtraction can
+        // underflow and the duration is non-zero
+        let elapsed = epoch.checked_sub(self.start).unwrap_or_default();
+        let duration = self.end.checked_sub(self.start).unwrap_or_default();
+        self.amount
+            .checked_mul(token::Amount::from_u64(elapsed.0))
+            .and_then(|scaled| scaled.checked_div_u64(duration.0))
+            // On overflow, fall back to the more restrictive cliff behavior
+            .unwrap_or_default()
+    }
+
+    /// The amount of tokens that are still locked at the given epoch
+    pub fn locked_amount(&self, epoch: Epoch) -> token::Amount {
+        self.amount
+            .checked_sub(self.vested_amount(epoch))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address::testing::nam;
+
+    use super::*;
+
+    fn schedule(start: u64, cliff: u64, end: u64) -> VestingSchedule {
+        VestingSchedule {
+            token: nam(),
+            amount: token::Amount::from_u64(1_000),
+            start: Epoch(start),
+            cliff: Epoch(cliff),
+            end: Epoch(end),
+        }
+    }
+
+    #[test]
+    fn test_vesting_schedule_validation() {
+        assert!(schedule(0, 0, 0).validate().is_ok());
+        assert!(schedule(1, 5, 10).validate().is_ok());
+        assert!(schedule(5, 1, 10).validate().is_err());
+        assert!(schedule(1, 10, 5).validate().is_err());
+    }
+
+    #[test]
+    fn test_vested_amount() {
+        let amount = |x: u64| token::Amount::from_u64(x);
+
+        // Linear schedule
+        let linear = schedule(10, 10, 20);
+        assert_eq!(linear.vested_amount(Epoch(0)), amount(0));
+        assert_eq!(linear.vested_amount(Epoch(10)), amount(0));
+        assert_eq!(linear.vested_amount(Epoch(13)), amount(300));
+        assert_eq!(linear.vested_amount(Epoch(20)), amount(1_000));
+        assert_eq!(linear.vested_amount(Epoch(100)), amount(1_000));
+        assert_eq!(linear.locked_amount(Epoch(13)), amount(700));
+
+        // Cliff schedule
+        let cliff = schedule(0, 10, 10);
+        assert_eq!(cliff.vested_amount(Epoch(9)), amount(0));
+        assert_eq!(cliff.vested_amount(Epoch(10)), amount(1_000));
+        assert_eq!(cliff.locked_amount(Epoch(10)), amount(0));
+
+        // Linear schedule with a cliff, catching up on the amount vested since
+        // the start at the cliff
+        let both = schedule(0, 4, 10);
+        assert_eq!(both.vested_amount(Epoch(3)), amount(0));
+        assert_eq!(both.vested_amount(Epoch(4)), amount(400));
+        assert_eq!(both.vested_amount(Epoch(5)), amount(500));
+        assert_eq!(both.locked_amount(Epoch(5)), amount(500));
+    }
+}
@@ -170,6 +170,9 @@ pub fn main() -> Result<()> {
             cmds::NodeUtils::TestGenesis(TestGenesis(args)) => {
                 node::utils::test_genesis(args, global_args)
             }
+            cmds::NodeUtils::PrecompileWasm(cmds::PrecompileWasm(args)) => {
+                node::utils::precompile_wasm(args, global_args)
+            }
         },
     }
     Ok(())
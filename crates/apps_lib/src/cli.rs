@@ -182,19 +182,22 @@ pub mod cmds {
     pub enum NamadaRelayer {
         EthBridgePool(EthBridgePool),
         ValidatorSet(ValidatorSet),
+        Daemon(RelayerDaemon),
     }
 
     impl Cmd for NamadaRelayer {
         fn add_sub(app: App) -> App {
             app.subcommand(EthBridgePool::def())
                 .subcommand(ValidatorSet::def())
+                .subcommand(RelayerDaemon::def())
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             let eth_bridge_pool =
                 SubCmd::parse(matches).map(Self::EthBridgePool);
             let validator_set = SubCmd::parse(matches).map(Self::ValidatorSet);
-            eth_bridge_pool.or(validator_set)
+            let daemon = SubCmd::parse(matches).map(Self::Daemon);
+            eth_bridge_pool.or(validator_set).or(daemon)
         }
     }
 
@@ -241,7 +244,9 @@ pub mod cmds {
                 .subcommand(TxIbcTransfer::def().display_order(1))
                 .subcommand(TxUpdateAccount::def().display_order(1))
                 .subcommand(TxInitAccount::def().display_order(1))
+                .subcommand(TxInitVestingAccount::def().display_order(1))
                 .subcommand(TxRevealPk::def().display_order(1))
+                .subcommand(TxSchedule::def().display_order(1))
                 // Governance transactions
                 .subcommand(TxInitProposal::def().display_order(1))
                 .subcommand(TxVoteProposal::def().display_order(1))
@@ -256,6 +261,7 @@ pub mod cmds {
                 .subcommand(Withdraw::def().display_order(2))
                 .subcommand(Redelegate::def().display_order(2))
                 .subcommand(ClaimRewards::def().display_order(2))
+                .subcommand(AutoCompound::def().display_order(2))
                 .subcommand(TxCommissionRateChange::def().display_order(2))
                 .subcommand(TxChangeConsensusKey::def().display_order(2))
                 .subcommand(TxMetadataChange::def().display_order(2))
@@ -295,7 +301,10 @@ pub mod cmds {
                 .subcommand(QueryStakingRewardsRate::def().display_order(5))
                 // Actions
                 .subcommand(SignTx::def().display_order(6))
+                .subcommand(MultisigCreate::def().display_order(6))
+                .subcommand(MultisigSubmit::def().display_order(6))
                 .subcommand(ShieldedSync::def().display_order(6))
+                .subcommand(ShieldedHistory::def().display_order(6))
                 .subcommand(GenIbcShieldingTransfer::def().display_order(6))
                 // Utils
                 .subcommand(ClientUtils::def().display_order(7))
@@ -316,6 +325,8 @@ pub mod cmds {
             let tx_update_account =
                 Self::parse_with_ctx(matches, TxUpdateAccount);
             let tx_init_account = Self::parse_with_ctx(matches, TxInitAccount);
+            let tx_init_vesting_account =
+                Self::parse_with_ctx(matches, TxInitVestingAccount);
             let tx_become_validator =
                 Self::parse_with_ctx(matches, TxBecomeValidator);
             let tx_init_validator =
@@ -327,6 +338,7 @@ pub mod cmds {
             let tx_reactivate_validator =
                 Self::parse_with_ctx(matches, TxReactivateValidator);
             let tx_reveal_pk = Self::parse_with_ctx(matches, TxRevealPk);
+            let tx_schedule = Self::parse_with_ctx(matches, TxSchedule);
             let tx_init_proposal =
                 Self::parse_with_ctx(matches, TxInitProposal);
             let tx_vote_proposal =
@@ -346,6 +358,7 @@ pub mod cmds {
             let withdraw = Self::parse_with_ctx(matches, Withdraw);
             let redelegate = Self::parse_with_ctx(matches, Redelegate);
             let claim_rewards = Self::parse_with_ctx(matches, ClaimRewards);
+            let auto_compound = Self::parse_with_ctx(matches, AutoCompound);
             let query_epoch = Self::parse_with_ctx(matches, QueryEpoch);
             let query_next_epoch_info =
                 Self::parse_with_ctx(matches, QueryNextEpochInfo);
@@ -392,8 +405,12 @@ pub mod cmds {
             let add_to_eth_bridge_pool =
                 Self::parse_with_ctx(matches, AddToEthBridgePool);
             let shielded_sync = Self::parse_with_ctx(matches, ShieldedSync);
+            let shielded_history =
+                Self::parse_with_ctx(matches, ShieldedHistory);
             let gen_ibc_shielding =
                 Self::parse_with_ctx(matches, GenIbcShieldingTransfer);
+            let multisig_create = Self::parse_with_ctx(matches, MultisigCreate);
+            let multisig_submit = Self::parse_with_ctx(matches, MultisigSubmit);
             let utils = SubCmd::parse(matches).map(Self::WithoutContext);
             tx_custom
                 .or(tx_transparent_transfer)
@@ -403,7 +420,9 @@ pub mod cmds {
                 .or(tx_ibc_transfer)
                 .or(tx_update_account)
                 .or(tx_init_account)
+                .or(tx_init_vesting_account)
                 .or(tx_reveal_pk)
+                .or(tx_schedule)
                 .or(tx_init_proposal)
                 .or(tx_vote_proposal)
                 .or(tx_become_validator)
@@ -419,6 +438,7 @@ pub mod cmds {
                 .or(withdraw)
                 .or(redelegate)
                 .or(claim_rewards)
+                .or(auto_compound)
                 .or(add_to_eth_bridge_pool)
                 .or(tx_update_steward_commission)
                 .or(tx_resign_steward)
@@ -451,7 +471,10 @@ pub mod cmds {
                 .or(query_staking_rewards_rate)
                 .or(query_account)
                 .or(shielded_sync)
+                .or(shielded_history)
                 .or(gen_ibc_shielding)
+                .or(multisig_create)
+                .or(multisig_submit)
                 .or(utils)
         }
     }
@@ -498,6 +521,8 @@ pub mod cmds {
         QueryResult(QueryResult),
         TxUpdateAccount(TxUpdateAccount),
         TxInitAccount(TxInitAccount),
+        TxInitVestingAccount(TxInitVestingAccount),
+        TxSchedule(TxSchedule),
         TxBecomeValidator(TxBecomeValidator),
         TxInitValidator(TxInitValidator),
         TxCommissionRateChange(TxCommissionRateChange),
@@ -513,6 +538,7 @@ pub mod cmds {
         Unbond(Unbond),
         Withdraw(Withdraw),
         ClaimRewards(ClaimRewards),
+        AutoCompound(AutoCompound),
         Redelegate(Redelegate),
         AddToEthBridgePool(AddToEthBridgePool),
         TxUpdateStewardCommission(TxUpdateStewardCommission),
@@ -545,7 +571,10 @@ pub mod cmds {
         QueryValidatorState(QueryValidatorState),
         QueryRewards(QueryRewards),
         ShieldedSync(ShieldedSync),
+        ShieldedHistory(ShieldedHistory),
         GenIbcShieldingTransfer(GenIbcShieldingTransfer),
+        MultisigCreate(MultisigCreate),
+        MultisigSubmit(MultisigSubmit),
     }
 
     #[allow(clippy::large_enum_variant)]
@@ -1478,6 +1507,55 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxInitVestingAccount(
+        pub args::TxInitVestingAccount<args::CliTypes>,
+    );
+
+    impl SubCmd for TxInitVestingAccount {
+        const CMD: &'static str = "init-vesting-account";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxInitVestingAccount(args::TxInitVestingAccount::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Send a signed transaction to create a new established \
+                     account whose tokens are locked by a vesting schedule."
+                ))
+                .arg_required_else_help(true)
+                .add_args::<args::TxInitVestingAccount<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxSchedule(pub args::ScheduleTx<args::CliTypes>);
+
+    impl SubCmd for TxSchedule {
+        const CMD: &'static str = "schedule-tx";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| TxSchedule(args::ScheduleTx::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Send a signed transaction to schedule a signed raw \
+                     transaction for execution at a future block height or \
+                     epoch, escrowing its fees."
+                ))
+                .arg_required_else_help(true)
+                .add_args::<args::ScheduleTx<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxBecomeValidator(pub args::TxBecomeValidator<args::CliTypes>);
 
@@ -1616,6 +1694,31 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct ShieldedHistory(pub args::ShieldedHistory<args::CliTypes>);
+
+    impl SubCmd for ShieldedHistory {
+        const CMD: &'static str = "shielded-history";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                ShieldedHistory(args::ShieldedHistory::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Export the history of the notes received and spent by a \
+                     viewing key, with their memos and transparent \
+                     counterparts. Please run shielded-sync first for best \
+                     results. The notes synced before the history was tracked \
+                     are missing from it."
+                ))
+                .add_args::<args::ShieldedHistory<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct Bond(pub args::Bond<args::CliTypes>);
 
@@ -1697,6 +1800,28 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct AutoCompound(pub args::AutoCompound<args::CliTypes>);
+
+    impl SubCmd for AutoCompound {
+        const CMD: &'static str = "auto-compound";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| AutoCompound(args::AutoCompound::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Enable or disable the automatic re-bonding of the \
+                     rewards of a bond at the start of every epoch."
+                ))
+                .add_args::<args::AutoCompound<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct Redelegate(pub args::Redelegate<args::CliTypes>);
 
@@ -1961,6 +2086,50 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct MultisigCreate(pub args::MultisigCreate<args::CliTypes>);
+
+    impl SubCmd for MultisigCreate {
+        const CMD: &'static str = "multisig-create";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                MultisigCreate(args::MultisigCreate::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Create a bundle to collect the signatures of a multisig \
+                     account's transaction."
+                ))
+                .add_args::<args::MultisigCreate<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct MultisigSubmit(pub args::MultisigSubmit<args::CliTypes>);
+
+    impl SubCmd for MultisigSubmit {
+        const CMD: &'static str = "multisig-submit";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                MultisigSubmit(args::MultisigSubmit::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Check a multisig bundle against the account's on-chain \
+                     threshold, wrap it and submit it."
+                ))
+                .add_args::<args::MultisigSubmit<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryValidatorState(
         pub args::QueryValidatorState<args::CliTypes>,
@@ -2487,6 +2656,7 @@ pub mod cmds {
     #[derive(Clone, Debug)]
     pub enum NodeUtils {
         TestGenesis(TestGenesis),
+        PrecompileWasm(PrecompileWasm),
     }
 
     impl SubCmd for NodeUtils {
@@ -2494,7 +2664,11 @@ pub mod cmds {
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).and_then(|matches| {
-                SubCmd::parse(matches).map(Self::TestGenesis)
+                let test_genesis =
+                    SubCmd::parse(matches).map(Self::TestGenesis);
+                let precompile_wasm =
+                    SubCmd::parse(matches).map(Self::PrecompileWasm);
+                test_genesis.or(precompile_wasm)
             })
         }
 
@@ -2502,6 +2676,7 @@ pub mod cmds {
             App::new(Self::CMD)
                 .about(wrap!("Utilities."))
                 .subcommand(TestGenesis::def())
+                .subcommand(PrecompileWasm::def())
                 .subcommand_required(true)
                 .arg_required_else_help(true)
         }
@@ -2518,6 +2693,7 @@ pub mod cmds {
         InitGenesisValidator(InitGenesisValidator),
         PkToTmAddress(PkToTmAddress),
         SignOffline(SignOffline),
+        MultisigSign(MultisigSign),
         DefaultBaseDir(DefaultBaseDir),
         EpochSleep(EpochSleep),
         ValidateGenesisTemplates(ValidateGenesisTemplates),
@@ -2550,6 +2726,8 @@ pub mod cmds {
                     SubCmd::parse(matches).map(Self::PkToTmAddress);
                 let sign_offline =
                     SubCmd::parse(matches).map(Self::SignOffline);
+                let multisig_sign =
+                    SubCmd::parse(matches).map(Self::MultisigSign);
                 let default_base_dir =
                     SubCmd::parse(matches).map(Self::DefaultBaseDir);
                 let epoch_sleep = SubCmd::parse(matches).map(Self::EpochSleep);
@@ -2577,6 +2755,7 @@ pub mod cmds {
                     .or(genesis_tx)
                     .or(parse_migrations_json)
                     .or(sign_offline)
+                    .or(multisig_sign)
                     .or(derive_ibc_token)
                     .or(pubkey_to_addr)
             })
@@ -2594,6 +2773,7 @@ pub mod cmds {
                 .subcommand(InitGenesisValidator::def())
                 .subcommand(PkToTmAddress::def())
                 .subcommand(SignOffline::def())
+                .subcommand(MultisigSign::def())
                 .subcommand(DefaultBaseDir::def())
                 .subcommand(EpochSleep::def())
                 .subcommand(ValidateGenesisTemplates::def())
@@ -2839,6 +3019,31 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct PrecompileWasm(pub args::PrecompileWasm);
+
+    impl SubCmd for PrecompileWasm {
+        const CMD: &'static str = "precompile-wasm";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::PrecompileWasm::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Compile ahead of time the WASMs of the chain into the \
+                     compilation cache of the node, such that they don't have \
+                     to be compiled on first use. The compiled modules can \
+                     also be exported to a bundle, which can be imported when \
+                     joining the network."
+                ))
+                .add_args::<args::PrecompileWasm>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct SignGenesisTxs(pub args::SignGenesisTxs);
 
@@ -3286,6 +3491,29 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct RelayerDaemon(pub args::RelayerDaemon<args::CliTypes>);
+
+    impl SubCmd for RelayerDaemon {
+        const CMD: &'static str = "daemon";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::RelayerDaemon::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Run a relayer daemon, which continuously relays \
+                     profitable Bridge pool batches and validator set updates \
+                     to Namada's Ethereum bridge smart contracts."
+                ))
+                .add_args::<args::RelayerDaemon<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct PkToTmAddress(pub args::PkToTmAddress);
 
@@ -3327,6 +3555,28 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct MultisigSign(pub args::MultisigSign<CliTypes>);
+
+    impl SubCmd for MultisigSign {
+        const CMD: &'static str = "multisig-sign";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::MultisigSign::<CliTypes>::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Offline add signatures to a multisig bundle and show its \
+                     status."
+                ))
+                .add_args::<args::MultisigSign<CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct DefaultBaseDir(pub args::DefaultBaseDir);
 
@@ -3376,15 +3626,16 @@ pub mod args {
     use namada_sdk::token::NATIVE_MAX_DECIMAL_PLACES;
     use namada_sdk::tx::data::GasLimit;
     pub use namada_sdk::tx::{
-        TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
-        TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-        TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
-        TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-        TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-        TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+        TX_AUTO_COMPOUND_WASM, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
+        TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+        TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+        TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
+        TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_INIT_VESTING_ACCOUNT_WASM,
+        TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+        TX_REVEAL_PK, TX_SCHEDULE_WASM, TX_TRANSFER_WASM, TX_UNBOND_WASM,
         TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
         TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-        VP_USER_WASM,
+        VP_USER_WASM, VP_VESTING_WASM,
     };
     use namada_sdk::{token, DEFAULT_GAS_LIMIT};
 
@@ -3442,10 +3693,12 @@ pub mod args {
     );
     pub const BRIDGE_POOL_TARGET: Arg<EthAddress> = arg("target");
     pub const BROADCAST_ONLY: ArgFlag = flag("broadcast-only");
+    pub const BUNDLE_PATH: Arg<PathBuf> = arg("bundle-path");
     pub const CHAIN_ID: Arg<ChainId> = arg("chain-id");
     pub const CHAIN_ID_OPT: ArgOpt<ChainId> = CHAIN_ID.opt();
     pub const CHAIN_ID_PREFIX: Arg<ChainIdPrefix> = arg("chain-prefix");
     pub const CHANNEL_ID: Arg<ChannelId> = arg("channel-id");
+    pub const CLIFF_EPOCH_OPT: ArgOpt<Epoch> = arg_opt("cliff-epoch");
     pub const CODE_PATH: Arg<PathBuf> = arg("code-path");
     pub const CODE_PATH_OPT: ArgOpt<PathBuf> = CODE_PATH.opt();
     pub const COMMISSION_RATE: Arg<Dec> = arg("commission-rate");
@@ -3475,18 +3728,23 @@ pub mod args {
     pub const DISPOSABLE_SIGNING_KEY: ArgFlag = flag("disposable-gas-payer");
     pub const DESTINATION_VALIDATOR: Arg<WalletAddress> =
         arg("destination-validator");
+    pub const DISABLE: ArgFlag = flag("disable");
     pub const DISCORD_OPT: ArgOpt<String> = arg_opt("discord-handle");
     pub const DO_IT: ArgFlag = flag("do-it");
     pub const DRY_RUN_TX: ArgFlag = flag("dry-run");
+    pub const DRY_RUN_DIFF: ArgFlag = flag("dry-run-diff");
+    pub const DRY_RUN_TRACE: ArgFlag = flag("dry-run-trace");
     pub const DRY_RUN_WRAPPER_TX: ArgFlag = flag("dry-run-wrapper");
     pub const DUMP_TX: ArgFlag = flag("dump-tx");
     pub const DUMP_WRAPPER_TX: ArgFlag = flag("dump-wrapper-tx");
     pub const DUMP_CONVERSION_TREE: ArgFlag = flag("dump-conversion-tree");
+    pub const END_EPOCH: Arg<Epoch> = arg("end-epoch");
     pub const EPOCH: ArgOpt<Epoch> = arg_opt("epoch");
     pub const ERC20: Arg<EthAddress> = arg("erc20");
     pub const ETH_CONFIRMATIONS: Arg<u64> = arg("confirmations");
     pub const ETH_GAS: ArgOpt<u64> = arg_opt("eth-gas");
     pub const ETH_GAS_PRICE: ArgOpt<u64> = arg_opt("eth-gas-price");
+    pub const ETH_MAX_GAS_PRICE: ArgOpt<u64> = arg_opt("max-eth-gas-price");
     pub const ETH_ADDRESS: Arg<EthAddress> = arg("ethereum-address");
     pub const ETH_ADDRESS_OPT: ArgOpt<EthAddress> = ETH_ADDRESS.opt();
     pub const ETH_RPC_ENDPOINT: ArgDefault<String> = arg_default(
@@ -3495,6 +3753,7 @@ pub mod args {
     );
     pub const ETH_SYNC: ArgFlag = flag("sync");
     pub const EXPIRATION_OPT: ArgOpt<DateTimeUtc> = arg_opt("expiration");
+    pub const EXPORT_BUNDLE: ArgOpt<PathBuf> = arg_opt("export-bundle");
     pub const EMAIL: Arg<String> = arg("email");
     pub const EMAIL_OPT: ArgOpt<String> = EMAIL.opt();
     pub const FEE_AMOUNT_OPT: ArgOpt<token::DenominatedAmount> =
@@ -3527,6 +3786,8 @@ pub mod args {
         flag("allow-non-compliant");
     pub const HD_PROMPT_BIP39_PASSPHRASE: ArgFlag = flag("bip39-passphrase");
     pub const HISTORIC: ArgFlag = flag("historic");
+    pub const HISTORY_FORMAT: ArgDefault<HistoryFormat> =
+        arg_default("format", DefaultFn(|| HistoryFormat::Csv));
     pub const IBC_SHIELDING_DATA_PATH: ArgOpt<PathBuf> =
         arg_opt("ibc-shielding-data");
     pub const IBC_DENOM: Arg<String> = arg("ibc-denom");
@@ -3584,6 +3845,7 @@ pub mod args {
         arg_multi("secret-keys");
     pub const PROPOSAL_PGF_STEWARD: ArgFlag = flag("pgf-stewards");
     pub const PROPOSAL_PGF_FUNDING: ArgFlag = flag("pgf-funding");
+    pub const PROPOSAL_PARAM_CHANGE: ArgFlag = flag("param-change");
     pub const PROTOCOL_KEY: ArgOpt<WalletPublicKey> = arg_opt("protocol-key");
     pub const PRE_GENESIS_PATH: ArgOpt<PathBuf> = arg_opt("pre-genesis-path");
     pub const PUBLIC_KEY: Arg<WalletPublicKey> = arg("public-key");
@@ -3608,10 +3870,17 @@ pub mod args {
     pub const RAW_PUBLIC_KEY_HASH_OPT: ArgOpt<String> =
         RAW_PUBLIC_KEY_HASH.opt();
     pub const RECEIVER: Arg<String> = arg("receiver");
+    pub const RECOVER_ACCOUNTS: ArgOpt<u32> = arg_opt("recover-accounts");
     pub const REFUND_TARGET: ArgOpt<WalletTransferTarget> =
         arg_opt("refund-target");
     pub const RELAYER: Arg<Address> = arg("relayer");
+    pub const RELAYER_STATE_PATH: Arg<PathBuf> = arg("state-path");
+    pub const RESUBMIT_AFTER_BLOCKS: ArgDefault<u64> =
+        arg_default("resubmit-after-blocks", DefaultFn(|| 10));
     pub const RETRIES: ArgOpt<u64> = arg_opt("retries");
+    pub const SCHEDULED_GAS_LIMIT: Arg<GasLimit> = arg("scheduled-gas-limit");
+    pub const SCHEDULED_SIGNATURES: ArgMulti<PathBuf, GlobStar> =
+        arg_multi("scheduled-signatures");
     pub const SCHEME: ArgDefault<SchemeType> =
         arg_default("scheme", DefaultFn(|| SchemeType::Ed25519));
     pub const SHELL: Arg<Shell> = arg("shell");
@@ -3627,6 +3896,7 @@ pub mod args {
     pub const SOURCE: Arg<WalletAddress> = arg("source");
     pub const SOURCE_OPT: ArgOpt<WalletAddress> = SOURCE.opt();
     pub const SOURCE_VALIDATOR: Arg<WalletAddress> = arg("source-validator");
+    pub const START_EPOCH: Arg<Epoch> = arg("start-epoch");
     pub const SPENDING_KEY_SOURCE: Arg<WalletSpendingKey> = arg("source");
     pub const SPENDING_KEYS: ArgMulti<WalletSpendingKey, GlobStar> =
         arg_multi("spending-keys");
@@ -3674,6 +3944,9 @@ pub mod args {
     pub const WAIT_FOR_LAST_QUERY_HEIGHT: ArgFlag =
         flag("wait-for-last-query-height");
     pub const WALLET_ALIAS_FORCE: ArgFlag = flag("wallet-alias-force");
+    pub const WASM_CACHE_BUNDLE: ArgOpt<PathBuf> = arg_opt("wasm-cache-bundle");
+    pub const WASM_CACHE_BUNDLE_HASH: ArgOpt<Hash> =
+        arg_opt("wasm-cache-bundle-hash");
     pub const WASM_CHECKSUMS_PATH: Arg<PathBuf> = arg("wasm-checksums-path");
     pub const WASM_DIR: ArgOpt<PathBuf> = arg_opt("wasm-dir");
     pub const WEBSITE_OPT: ArgOpt<String> = arg_opt("website");
@@ -4561,6 +4834,123 @@ pub mod args {
         }
     }
 
+    impl CliToSdkCtxless<RelayerDaemon<SdkTypes>> for RelayerDaemon<CliTypes> {
+        fn to_sdk_ctxless(self) -> RelayerDaemon<SdkTypes> {
+            RelayerDaemon::<SdkTypes> {
+                ledger_address: self.ledger_address,
+                eth_rpc_endpoint: (),
+                eth_addr: self.eth_addr,
+                relayer: self.relayer,
+                state_path: self.state_path,
+                conversion_table: self.conversion_table,
+                gas_price: self.gas_price,
+                max_gas_price: self.max_gas_price,
+                max_gas: self.max_gas,
+                gas: self.gas,
+                confirmations: self.confirmations,
+                resubmit_after_blocks: self.resubmit_after_blocks,
+                retry_dur: self.retry_dur,
+                success_dur: self.success_dur,
+            }
+        }
+    }
+
+    impl Args for RelayerDaemon<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let ledger_address = LEDGER_ADDRESS.parse(matches);
+            let eth_rpc_endpoint = ETH_RPC_ENDPOINT.parse(matches);
+            let eth_addr = ETH_ADDRESS.parse(matches);
+            let relayer = RELAYER.parse(matches);
+            let state_path = RELAYER_STATE_PATH.parse(matches);
+            let conversion_table = CONVERSION_TABLE.parse(matches);
+            let gas_price = ETH_GAS_PRICE.parse(matches);
+            let max_gas_price = ETH_MAX_GAS_PRICE.parse(matches);
+            let max_gas = MAX_ETH_GAS.parse(matches);
+            let gas = ETH_GAS.parse(matches);
+            let confirmations = ETH_CONFIRMATIONS.parse(matches);
+            let resubmit_after_blocks = RESUBMIT_AFTER_BLOCKS.parse(matches);
+            let retry_dur =
+                DAEMON_MODE_RETRY_DUR.parse(matches).map(|dur| dur.0);
+            let success_dur =
+                DAEMON_MODE_SUCCESS_DUR.parse(matches).map(|dur| dur.0);
+            Self {
+                ledger_address,
+                eth_rpc_endpoint,
+                eth_addr,
+                relayer,
+                state_path,
+                conversion_table,
+                gas_price,
+                max_gas_price,
+                max_gas,
+                gas,
+                confirmations,
+                resubmit_after_blocks,
+                retry_dur,
+                success_dur,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
+                .arg(
+                    ETH_RPC_ENDPOINT
+                        .def()
+                        .help(wrap!("The Ethereum RPC endpoint.")),
+                )
+                .arg(ETH_ADDRESS.def().help(wrap!(
+                    "The address of the Ethereum wallet to pay the gas fees. \
+                     The nonces of this account are managed by the daemon."
+                )))
+                .arg(RELAYER.def().help(wrap!(
+                    "The rewards address for relaying Bridge pool proofs."
+                )))
+                .arg(RELAYER_STATE_PATH.def().help(wrap!(
+                    "Path to the file where the daemon persists the state of \
+                     in-flight Ethereum transactions."
+                )))
+                .arg(CONVERSION_TABLE.def().help(wrap!(
+                    "Path to a JSON object containing a mapping between token \
+                     addresses and their conversion rates in gwei. The file \
+                     is read again before every relay, so it can be kept up \
+                     to date by an external process."
+                )))
+                .arg(ETH_GAS_PRICE.def().help(wrap!(
+                    "A fixed price of Ethereum gas, in wei. If unset, the \
+                     price suggested by the Ethereum node is used."
+                )))
+                .arg(ETH_MAX_GAS_PRICE.def().help(wrap!(
+                    "The maximum price of Ethereum gas, in wei, to pay when \
+                     resubmitting stuck transactions."
+                )))
+                .arg(MAX_ETH_GAS.def().help(wrap!(
+                    "The maximum amount Ethereum gas that can be spent \
+                     relaying a Bridge pool batch."
+                )))
+                .arg(ETH_GAS.def().help(wrap!(
+                    "Under ideal conditions, relaying transfers will yield a \
+                     net profit. If that is not possible, setting this \
+                     optional value will result in batch transfers that cost \
+                     as close to the given value as possible without \
+                     exceeding it."
+                )))
+                .arg(ETH_CONFIRMATIONS.def().help(wrap!(
+                    "The number of block confirmations on Ethereum."
+                )))
+                .arg(RESUBMIT_AFTER_BLOCKS.def().help(wrap!(
+                    "The number of Ethereum blocks to wait for before \
+                     resubmitting a transaction with a higher gas price. \
+                     Defaults to 10."
+                )))
+                .arg(DAEMON_MODE_RETRY_DUR.def().help(wrap!(
+                    "The amount of time to sleep between failed relays."
+                )))
+                .arg(DAEMON_MODE_SUCCESS_DUR.def().help(wrap!(
+                    "The amount of time to sleep between successful relays."
+                )))
+        }
+    }
+
     impl CliToSdk<TxCustom<SdkTypes>> for TxCustom<CliTypes> {
         type Error = std::io::Error;
 
@@ -5141,28 +5531,218 @@ pub mod args {
             let threshold = THRESHOLD.parse(matches);
             Self {
                 tx,
-                vp_code_path,
-                public_keys,
-                threshold,
+                vp_code_path,
+                public_keys,
+                threshold,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(CODE_PATH_OPT.def().help(wrap!(
+                    "The path to the validity predicate WASM code to be used \
+                     for the new account. Uses the default user VP if none \
+                     specified."
+                )))
+                .arg(PUBLIC_KEYS.def().help(wrap!(
+                    "A list public keys to be associated with the new account \
+                     in hexadecimal encoding."
+                )))
+                .arg(THRESHOLD.def().help(wrap!(
+                    "The minimum number of signature to be provided for \
+                     authorization. Must be less then the maximum number of \
+                     public keys provided."
+                )))
+        }
+    }
+
+    impl CliToSdk<TxInitVestingAccount<SdkTypes>>
+        for TxInitVestingAccount<CliTypes>
+    {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<TxInitVestingAccount<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(TxInitVestingAccount::<SdkTypes> {
+                tx,
+                vp_code_path: self.vp_code_path,
+                tx_code_path: self.tx_code_path,
+                public_keys: self
+                    .public_keys
+                    .iter()
+                    .map(|pk| chain_ctx.get(pk))
+                    .collect(),
+                threshold: self.threshold,
+                token: chain_ctx.get(&self.token),
+                amount: self.amount,
+                start: self.start,
+                cliff: self.cliff,
+                end: self.end,
+            })
+        }
+    }
+
+    impl Args for TxInitVestingAccount<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let vp_code_path = CODE_PATH_OPT
+                .parse(matches)
+                .unwrap_or_else(|| PathBuf::from(VP_VESTING_WASM));
+            let tx_code_path = PathBuf::from(TX_INIT_VESTING_ACCOUNT_WASM);
+            let public_keys = PUBLIC_KEYS.parse(matches);
+            let threshold = THRESHOLD.parse(matches);
+            let token = TOKEN.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let start = START_EPOCH.parse(matches);
+            let cliff = CLIFF_EPOCH_OPT.parse(matches);
+            let end = END_EPOCH.parse(matches);
+            Self {
+                tx,
+                vp_code_path,
+                tx_code_path,
+                public_keys,
+                threshold,
+                token,
+                amount,
+                start,
+                cliff,
+                end,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(CODE_PATH_OPT.def().help(wrap!(
+                    "The path to the validity predicate WASM code to be used \
+                     for the new account. Uses the default vesting VP if none \
+                     specified."
+                )))
+                .arg(PUBLIC_KEYS.def().help(wrap!(
+                    "A list public keys to be associated with the new account \
+                     in hexadecimal encoding."
+                )))
+                .arg(THRESHOLD.def().help(wrap!(
+                    "The minimum number of signature to be provided for \
+                     authorization. Must be less then the maximum number of \
+                     public keys provided."
+                )))
+                .arg(TOKEN.def().help(wrap!("The vested token.")))
+                .arg(AMOUNT.def().help(wrap!(
+                    "The amount of tokens locked by the vesting schedule."
+                )))
+                .arg(START_EPOCH.def().help(wrap!(
+                    "The epoch from which the tokens start vesting linearly."
+                )))
+                .arg(CLIFF_EPOCH_OPT.def().help(wrap!(
+                    "The first epoch at which any tokens are vested. Defaults \
+                     to the start epoch."
+                )))
+                .arg(END_EPOCH.def().help(wrap!(
+                    "The epoch at which all the tokens are vested."
+                )))
+        }
+    }
+
+    impl CliToSdk<ScheduleTx<SdkTypes>> for ScheduleTx<CliTypes> {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<ScheduleTx<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(ScheduleTx::<SdkTypes> {
+                tx,
+                scheduled_tx: std::fs::read(self.scheduled_tx).map_err(
+                    |e| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::InvalidInput,
+                            format!("Error reading tx file: {}", e),
+                        )
+                    },
+                )?,
+                scheduled_tx_signatures: self
+                    .scheduled_tx_signatures
+                    .iter()
+                    .map(|path| {
+                        std::fs::read(path).map_err(|e| {
+                            std::io::Error::new(
+                                std::io::ErrorKind::InvalidInput,
+                                format!("Error reading signature file: {}", e),
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+                height: self.height,
+                epoch: self.epoch,
+                scheduled_gas_limit: self.scheduled_gas_limit,
+                fee_payer: chain_ctx.get(&self.fee_payer),
+                tx_code_path: self.tx_code_path,
+            })
+        }
+    }
+
+    impl Args for ScheduleTx<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let scheduled_tx = TX_PATH.parse(matches);
+            let scheduled_tx_signatures = SCHEDULED_SIGNATURES.parse(matches);
+            let height = BLOCK_HEIGHT_OPT.parse(matches);
+            let epoch = EPOCH.parse(matches);
+            let scheduled_gas_limit = SCHEDULED_GAS_LIMIT.parse(matches);
+            let fee_payer = SOURCE.parse(matches);
+            let tx_code_path = PathBuf::from(TX_SCHEDULE_WASM);
+            Self {
+                tx,
+                scheduled_tx,
+                scheduled_tx_signatures,
+                height,
+                epoch,
+                scheduled_gas_limit,
+                fee_payer,
                 tx_code_path,
             }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Tx<CliTypes>>()
-                .arg(CODE_PATH_OPT.def().help(wrap!(
-                    "The path to the validity predicate WASM code to be used \
-                     for the new account. Uses the default user VP if none \
-                     specified."
+                .arg(TX_PATH.def().help(wrap!(
+                    "The path to the raw transaction to schedule, typically \
+                     produced with the `--dump-tx` flag."
                 )))
-                .arg(PUBLIC_KEYS.def().help(wrap!(
-                    "A list public keys to be associated with the new account \
-                     in hexadecimal encoding."
+                .arg(SCHEDULED_SIGNATURES.def().help(wrap!(
+                    "List of file paths containing the serialized signatures \
+                     of the scheduled transaction, typically produced by the \
+                     `sign-offline` command."
                 )))
-                .arg(THRESHOLD.def().help(wrap!(
-                    "The minimum number of signature to be provided for \
-                     authorization. Must be less then the maximum number of \
-                     public keys provided."
+                .arg(BLOCK_HEIGHT_OPT.def().help(wrap!(
+                    "The block height at which to execute the transaction."
+                )))
+                .arg(EPOCH.def().help(wrap!(
+                    "The epoch in whose first block to execute the \
+                     transaction."
+                )))
+                .group(
+                    ArgGroup::new("schedule")
+                        .args([BLOCK_HEIGHT_OPT.name, EPOCH.name])
+                        .required(true),
+                )
+                .arg(SCHEDULED_GAS_LIMIT.def().help(wrap!(
+                    "The gas limit of the scheduled transaction. Its fees are \
+                     escrowed in the gas token and at the gas price of this \
+                     transaction."
+                )))
+                .arg(SOURCE.def().help(wrap!(
+                    "The account escrowing the fees of the scheduled \
+                     transaction."
                 )))
         }
     }
@@ -5822,6 +6402,7 @@ pub mod args {
                 proposal_data,
                 is_pgf_stewards: self.is_pgf_stewards,
                 is_pgf_funding: self.is_pgf_funding,
+                is_param_change: self.is_param_change,
                 tx_code_path: self.tx_code_path,
             })
         }
@@ -5833,6 +6414,7 @@ pub mod args {
             let proposal_data = DATA_PATH.parse(matches);
             let is_pgf_stewards = PROPOSAL_PGF_STEWARD.parse(matches);
             let is_pgf_funding = PROPOSAL_PGF_FUNDING.parse(matches);
+            let is_param_change = PROPOSAL_PARAM_CHANGE.parse(matches);
             let tx_code_path = PathBuf::from(TX_INIT_PROPOSAL);
 
             Self {
@@ -5841,6 +6423,7 @@ pub mod args {
                 tx_code_path,
                 is_pgf_stewards,
                 is_pgf_funding,
+                is_param_change,
             }
         }
 
@@ -5856,7 +6439,10 @@ pub mod args {
                             "Flag if the proposal is of type pgf-stewards. \
                              Used to elect/remove stewards."
                         ))
-                        .conflicts_with(PROPOSAL_PGF_FUNDING.name),
+                        .conflicts_with_all([
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_PARAM_CHANGE.name,
+                        ]),
                 )
                 .arg(
                     PROPOSAL_PGF_FUNDING
@@ -5865,7 +6451,23 @@ pub mod args {
                             "Flag if the proposal is of type pgf-funding. \
                              Used to control continuous/retro PGF fundings."
                         ))
-                        .conflicts_with(PROPOSAL_PGF_STEWARD.name),
+                        .conflicts_with_all([
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PARAM_CHANGE.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_PARAM_CHANGE
+                        .def()
+                        .help(wrap!(
+                            "Flag if the proposal is of type param-change. \
+                             Used to change protocol, PoS, governance, PGF \
+                             and IBC parameters."
+                        ))
+                        .conflicts_with_all([
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                        ]),
                 )
         }
     }
@@ -6222,6 +6824,57 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<AutoCompound<SdkTypes>> for AutoCompound<CliTypes> {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<AutoCompound<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_chain_or_exit();
+
+            Ok(AutoCompound::<SdkTypes> {
+                tx,
+                validator: chain_ctx.get(&self.validator),
+                source: self.source.map(|x| chain_ctx.get(&x)),
+                disable: self.disable,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            })
+        }
+    }
+
+    impl Args for AutoCompound<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let validator = VALIDATOR.parse(matches);
+            let source = SOURCE_OPT.parse(matches);
+            let disable = DISABLE.parse(matches);
+            let tx_code_path = PathBuf::from(TX_AUTO_COMPOUND_WASM);
+            Self {
+                tx,
+                validator,
+                source,
+                disable,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(VALIDATOR.def().help(wrap!("Validator address.")))
+                .arg(SOURCE_OPT.def().help(wrap!(
+                    "Source address of the bond. For self-bonds, the \
+                     validator is also the source."
+                )))
+                .arg(DISABLE.def().help(wrap!(
+                    "Disable the auto-compounding of the bond's rewards. The \
+                     rewards then have to be claimed with the `claim-rewards` \
+                     command."
+                )))
+        }
+    }
+
     impl CliToSdk<QueryConversions<SdkTypes>> for QueryConversions<CliTypes> {
         type Error = std::convert::Infallible;
 
@@ -6374,6 +7027,57 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<ShieldedHistory<SdkTypes>> for ShieldedHistory<CliTypes> {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<ShieldedHistory<SdkTypes>, Self::Error> {
+            let query = self.query.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(ShieldedHistory::<SdkTypes> {
+                query,
+                owner: chain_ctx.get_cached(&self.owner),
+                format: self.format,
+                output: self.output,
+            })
+        }
+    }
+
+    impl Args for ShieldedHistory<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = VIEWING_KEY.parse(matches);
+            let format = HISTORY_FORMAT.parse(matches);
+            let output = OUTPUT.parse(matches);
+            Self {
+                query,
+                owner,
+                format,
+                output,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(
+                    VIEWING_KEY.def().help(wrap!(
+                        "The viewing key whose history to export."
+                    )),
+                )
+                .arg(HISTORY_FORMAT.def().help(wrap!(
+                    "The format of the exported history, either \"csv\" \
+                     (default) or \"json\"."
+                )))
+                .arg(OUTPUT.def().help(wrap!(
+                    "Save the history to the given file. When not supplied, \
+                     the history will be printed to stdout instead."
+                )))
+        }
+    }
+
     impl Args for QueryRewardsEstimate<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
@@ -6859,6 +7563,85 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<MultisigCreate<SdkTypes>> for MultisigCreate<CliTypes> {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<MultisigCreate<SdkTypes>, Self::Error> {
+            let query = self.query.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_chain_or_exit();
+
+            Ok(MultisigCreate::<SdkTypes> {
+                query,
+                tx_path: self.tx_path,
+                owner: chain_ctx.get(&self.owner),
+                output_folder: self.output_folder,
+            })
+        }
+    }
+
+    impl Args for MultisigCreate<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let tx_path = TX_PATH.parse(matches);
+            let owner = OWNER.parse(matches);
+            let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
+            Self {
+                query,
+                tx_path,
+                owner,
+                output_folder,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(TX_PATH.def().help(wrap!(
+                    "The path to the raw transaction file, typically produced \
+                     by using the `--dump-tx` flag."
+                )))
+                .arg(OWNER.def().help(wrap!(
+                    "The address of the multisig account that has to sign the \
+                     transaction."
+                )))
+                .arg(OUTPUT_FOLDER_PATH.def().help(wrap!(
+                    "The output folder path where the bundle will be stored."
+                )))
+        }
+    }
+
+    impl CliToSdk<MultisigSubmit<SdkTypes>> for MultisigSubmit<CliTypes> {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<MultisigSubmit<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let bundle = std::fs::read(self.bundle)?;
+
+            Ok(MultisigSubmit::<SdkTypes> { tx, bundle })
+        }
+    }
+
+    impl Args for MultisigSubmit<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let bundle = BUNDLE_PATH.parse(matches);
+            Self { tx, bundle }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>().arg(
+                BUNDLE_PATH
+                    .def()
+                    .help(wrap!("The path to the multisig bundle file.")),
+            )
+        }
+    }
+
     impl Args for ShieldedSync<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let ledger_address = CONFIG_RPC_LEDGER_ADDRESS.parse(matches);
@@ -7422,6 +8205,8 @@ pub mod args {
             Ok(Tx::<SdkTypes> {
                 dry_run: self.dry_run,
                 dry_run_wrapper: self.dry_run_wrapper,
+                dry_run_trace: self.dry_run_trace,
+                dry_run_diff: self.dry_run_diff,
                 dump_tx: self.dump_tx,
                 dump_wrapper_tx: self.dump_wrapper_tx,
                 output_folder: self.output_folder,
@@ -7493,6 +8278,22 @@ pub mod args {
                     ))
                     .conflicts_with(DRY_RUN_TX.name),
             )
+            .arg(DRY_RUN_TRACE.def().help(wrap!(
+                "Trace the host calls, their gas and the storage keys they \
+                 touch in the WASM executions of a dry run. Only applies \
+                 together with `--dry-run` or `--dry-run-wrapper`. The traces \
+                 are written to the `--output-folder`, if any."
+            )))
+            .arg(
+                DRY_RUN_DIFF
+                    .def()
+                    .help(wrap!(
+                        "Show the state changes, the events and the triggered \
+                         VPs of a dry run. Only applies together with \
+                         `--dry-run` or `--dry-run-wrapper`."
+                    ))
+                    .conflicts_with(DRY_RUN_TRACE.name),
+            )
             .arg(
                 DUMP_TX
                     .def()
@@ -7617,6 +8418,8 @@ pub mod args {
         fn parse(matches: &ArgMatches) -> Self {
             let dry_run = DRY_RUN_TX.parse(matches);
             let dry_run_wrapper = DRY_RUN_WRAPPER_TX.parse(matches);
+            let dry_run_trace = DRY_RUN_TRACE.parse(matches);
+            let dry_run_diff = DRY_RUN_DIFF.parse(matches);
             let dump_tx = DUMP_TX.parse(matches);
             let dump_wrapper_tx = DUMP_WRAPPER_TX.parse(matches);
             let force = FORCE.parse(matches);
@@ -7652,6 +8455,8 @@ pub mod args {
             Self {
                 dry_run,
                 dry_run_wrapper,
+                dry_run_trace,
+                dry_run_diff,
                 dump_tx,
                 dump_wrapper_tx,
                 force,
@@ -7812,6 +8617,8 @@ pub mod args {
             let use_device = USE_DEVICE.parse(matches);
             let device_transport = DEVICE_TRANSPORT.parse(matches);
             let ledger_zip32 = LEDGER_ZIP32.parse(matches);
+            let recover_accounts = RECOVER_ACCOUNTS.parse(matches);
+            let with_indexer = WITH_INDEXER.parse(matches);
             Self {
                 scheme,
                 shielded,
@@ -7825,6 +8632,8 @@ pub mod args {
                 use_device,
                 device_transport,
                 birthday,
+                recover_accounts,
+                with_indexer,
             }
         }
 
@@ -7889,13 +8698,41 @@ pub mod args {
             .arg(HD_PROMPT_BIP39_PASSPHRASE.def().help(wrap!(
                 "Use an additional passphrase for HD-key generation."
             )))
+            .arg(LEDGER_ZIP32.def().requires(SHIELDED.name).help(wrap!(
+                "Use the modified ZIP 32 algorithm supported by Ledger \
+                 devices. This flag is necessary if importing the generated \
+                 mnemonic code onto the Ledger device at some future time is \
+                 a requirement."
+            )))
             .arg(
-                LEDGER_ZIP32.def().requires(SHIELDED.name).help(wrap!(
-                    "Use the modified ZIP 32 algorithm supported by Ledger \
-                     devices. This flag is necessary if importing the \
-                     generated mnemonic code onto the Ledger device at some \
-                     future time is a requirement."
-                )),
+                RECOVER_ACCOUNTS
+                    .def()
+                    .requires(SHIELDED.name)
+                    .requires(WITH_INDEXER.name)
+                    .conflicts_with_all([
+                        HD_DERIVATION_PATH.name,
+                        BIRTHDAY.name,
+                        USE_DEVICE.name,
+                    ])
+                    .help(wrap!(
+                        "Recover the given number of shielded accounts from \
+                         the mnemonic code, following the default derivation \
+                         paths m/32'/877'/account'. The birthday of each \
+                         account is set to the block preceding its first \
+                         received note, as found by scanning the data of the \
+                         MASP indexer. The accounts are stored with the \
+                         aliases \"<alias>-<account>\"."
+                    )),
+            )
+            .arg(
+                WITH_INDEXER
+                    .def()
+                    .requires(RECOVER_ACCOUNTS.name)
+                    .help(wrap!(
+                        "Address of a `namada-masp-indexer` live instance \
+                         used to find the birthdays of the recovered shielded \
+                         accounts."
+                    )),
             )
         }
     }
@@ -8252,6 +9089,7 @@ pub mod args {
         pub pre_genesis_path: Option<PathBuf>,
         pub allow_duplicate_ip: bool,
         pub add_persistent_peers: bool,
+        pub wasm_cache_bundle: Option<(PathBuf, Hash)>,
     }
 
     impl Args for JoinNetwork {
@@ -8261,12 +9099,16 @@ pub mod args {
             let pre_genesis_path = PRE_GENESIS_PATH.parse(matches);
             let allow_duplicate_ip = ALLOW_DUPLICATE_IP.parse(matches);
             let add_persistent_peers = ADD_PERSISTENT_PEERS.parse(matches);
+            let wasm_cache_bundle = WASM_CACHE_BUNDLE
+                .parse(matches)
+                .zip(WASM_CACHE_BUNDLE_HASH.parse(matches));
             Self {
                 chain_id,
                 genesis_validator,
                 pre_genesis_path,
                 allow_duplicate_ip,
                 add_persistent_peers,
+                wasm_cache_bundle,
             }
         }
 
@@ -8283,6 +9125,48 @@ pub mod args {
                 "Whether to add persistent peers to the P2P config of CometBFT, \
                  derived from the list of genesis validators.",
             ))
+            .arg(
+                WASM_CACHE_BUNDLE
+                    .def()
+                    .requires(WASM_CACHE_BUNDLE_HASH.name)
+                    .help(wrap!(
+                        "Path to a bundle of precompiled WASM modules, as \
+                         exported by `namada node utils precompile-wasm`, to \
+                         import into the compilation cache of the node."
+                    )),
+            )
+            .arg(
+                WASM_CACHE_BUNDLE_HASH
+                    .def()
+                    .requires(WASM_CACHE_BUNDLE.name)
+                    .help(wrap!(
+                        "The hash of the bundle of precompiled WASM modules, \
+                         as printed when it was exported. The bundle is only \
+                         imported if it matches this hash, which must come \
+                         from a trusted source."
+                    )),
+            )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct PrecompileWasm {
+        pub export_bundle: Option<PathBuf>,
+    }
+
+    impl Args for PrecompileWasm {
+        fn parse(matches: &ArgMatches) -> Self {
+            let export_bundle = EXPORT_BUNDLE.parse(matches);
+            Self { export_bundle }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(EXPORT_BUNDLE.def().help(wrap!(
+                "Export the compiled modules to a bundle at the given path, \
+                 which can be imported with `join-network \
+                 --wasm-cache-bundle` along with the printed hash of the \
+                 bundle."
+            )))
         }
     }
 
@@ -8354,6 +9238,63 @@ pub mod args {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct MultisigSign<C: NamadaTypes = SdkTypes> {
+        pub bundle_path: PathBuf,
+        pub secret_keys: Vec<C::Keypair>,
+        pub signatures: Vec<PathBuf>,
+    }
+
+    impl Args for MultisigSign<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let bundle_path = BUNDLE_PATH.parse(matches);
+            let secret_keys = PRIVATE_KEYS.parse(matches);
+            let signatures = SIGNATURES.parse(matches);
+
+            Self {
+                bundle_path,
+                secret_keys,
+                signatures,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(BUNDLE_PATH.def().help(wrap!(
+                "The path to the multisig bundle file. The bundle is updated \
+                 in place."
+            )))
+            .arg(PRIVATE_KEYS.def().help(wrap!(
+                "The private keys of the multisig account to sign the \
+                 transaction with."
+            )))
+            .arg(SIGNATURES.def().help(wrap!(
+                "List of file paths containing signatures of the transaction, \
+                 typically produced by the `sign-offline` command."
+            )))
+        }
+    }
+
+    impl CliToSdk<MultisigSign<SdkTypes>> for MultisigSign<CliTypes> {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<MultisigSign<SdkTypes>, Self::Error> {
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(MultisigSign::<SdkTypes> {
+                bundle_path: self.bundle_path,
+                secret_keys: self
+                    .secret_keys
+                    .iter()
+                    .map(|key| chain_ctx.get_cached(key))
+                    .collect(),
+                signatures: self.signatures,
+            })
+        }
+    }
+
     impl CliToSdk<SignOffline<SdkTypes>> for SignOffline<CliTypes> {
         type Error = std::io::Error;
 
@@ -8955,6 +9896,7 @@ pub enum NamadaRelayer {
     EthBridgePoolWithCtx(Box<(cmds::EthBridgePoolWithCtx, Context)>),
     EthBridgePoolWithoutCtx(cmds::EthBridgePoolWithoutCtx),
     ValidatorSet(cmds::ValidatorSet),
+    Daemon(cmds::RelayerDaemon),
 }
 
 pub fn namada_relayer_cli() -> Result<NamadaRelayer> {
@@ -8977,6 +9919,9 @@ pub fn namada_relayer_cli() -> Result<NamadaRelayer> {
             cmds::NamadaRelayer::ValidatorSet(sub_cmd) => {
                 Ok(NamadaRelayer::ValidatorSet(sub_cmd))
             }
+            cmds::NamadaRelayer::Daemon(sub_cmd) => {
+                Ok(NamadaRelayer::Daemon(sub_cmd))
+            }
         },
         None => {
             let mut app = app;
@@ -151,6 +151,44 @@ impl CliApi {
                             )
                         }
                     }
+                    Sub::TxInitVestingAccount(TxInitVestingAccount(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        let dry_run =
+                            args.tx.dry_run || args.tx.dry_run_wrapper;
+                        tx::submit_init_vesting_account(&namada, args).await?;
+                        if !dry_run {
+                            namada
+                                .wallet()
+                                .await
+                                .save()
+                                .unwrap_or_else(|err| eprintln!("{}", err));
+                        } else {
+                            namada.io().println(
+                                "Transaction dry run. No addresses have been \
+                                 saved.",
+                            )
+                        }
+                    }
+                    Sub::TxSchedule(TxSchedule(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_schedule_tx(&namada, args).await?;
+                    }
                     Sub::TxBecomeValidator(TxBecomeValidator(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -285,6 +323,18 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::submit_claim_rewards(&namada, args).await?;
                     }
+                    Sub::AutoCompound(AutoCompound(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_auto_compound(&namada, args).await?;
+                    }
                     Sub::Redelegate(Redelegate(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -384,6 +434,30 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::gen_ibc_shielding_transfer(&namada, args).await?;
                     }
+                    Sub::MultisigCreate(MultisigCreate(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        tx::multisig_create(&namada, args).await?;
+                    }
+                    Sub::MultisigSubmit(MultisigSubmit(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_multisig(&namada, args).await?;
+                    }
                     #[cfg(feature = "namada-eth-bridge")]
                     Sub::AddToEthBridgePool(args) => {
                         let args = args.0;
@@ -572,6 +646,18 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_rewards_estimate(&namada, args).await;
                     }
+                    Sub::ShieldedHistory(ShieldedHistory(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_shielded_history(&namada, args).await;
+                    }
                     Sub::QueryBonds(QueryBonds(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -844,6 +930,12 @@ impl CliApi {
                         let args = args.to_sdk(&mut ctx)?;
                         utils::sign_offline(args).await
                     }
+                    ClientUtils::MultisigSign(MultisigSign(args)) => {
+                        let mut ctx = cli::Context::new::<IO>(global_args)
+                            .expect("expected to construct a context");
+                        let args = args.to_sdk(&mut ctx)?;
+                        utils::multisig_sign(args)
+                    }
                     ClientUtils::DefaultBaseDir(DefaultBaseDir(args)) => {
                         utils::default_base_dir(global_args, args)
                     }
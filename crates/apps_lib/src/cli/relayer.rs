@@ -29,7 +29,13 @@ impl CliApi {
     where
         C: CliClient,
     {
-        use namada_sdk::eth_bridge::{bridge_pool, validator_set};
+        use std::sync::Arc;
+
+        use namada_sdk::eth_bridge::ethers::types::U256;
+        use namada_sdk::eth_bridge::relayer::{
+            ConversionTableFile, EthNodeGasPrice, FixedGasPrice,
+        };
+        use namada_sdk::eth_bridge::{bridge_pool, relayer, validator_set};
 
         use crate::cli::args::{CliToSdk, CliToSdkCtxless};
         use crate::cli::cmds::*;
@@ -159,6 +165,39 @@ impl CliApi {
                     .await?;
                 }
             },
+            cli::NamadaRelayer::Daemon(RelayerDaemon(args)) => {
+                let client = client.unwrap_or_else(|| {
+                    C::from_tendermint_address(&args.ledger_address)
+                });
+                client.wait_until_node_is_synced(&io).await?;
+                let eth_client =
+                    get_eth_rpc_client(&args.eth_rpc_endpoint).await;
+                let args = args.to_sdk_ctxless();
+                let rates = ConversionTableFile(args.conversion_table.clone());
+                if let Some(gas_price) = args.gas_price {
+                    let gas_prices = FixedGasPrice(U256::from(gas_price));
+                    relayer::relay_daemon(
+                        eth_client,
+                        &client,
+                        &io,
+                        &gas_prices,
+                        &rates,
+                        args,
+                    )
+                    .await?;
+                } else {
+                    let gas_prices = EthNodeGasPrice(Arc::clone(&eth_client));
+                    relayer::relay_daemon(
+                        eth_client,
+                        &client,
+                        &io,
+                        &gas_prices,
+                        &rates,
+                        args,
+                    )
+                    .await?;
+                }
+            }
         }
         Ok(())
     }
@@ -3,17 +3,22 @@
 use std::fs::File;
 use std::io::{self, Write};
 use std::str::FromStr;
+use std::time::Duration;
 
 use color_eyre::eyre::Result;
 use itertools::sorted;
 use ledger_namada_rs::{BIP44Path, NamadaApp};
+use masp_primitives::sapling::ViewingKey;
 use namada_core::chain::BlockHeight;
 use namada_core::masp::{ExtendedSpendingKey, MaspValue, PaymentAddress};
 use namada_sdk::address::{Address, DecodeError};
 use namada_sdk::borsh::{BorshDeserialize, BorshSerializeExt};
 use namada_sdk::io::{display_line, edisplay_line, Io};
 use namada_sdk::key::*;
-use namada_sdk::masp::find_valid_diversifier;
+use namada_sdk::masp::utils::MaspClient;
+use namada_sdk::masp::{
+    find_key_birthdays, find_valid_diversifier, IndexerMaspClient,
+};
 use namada_sdk::wallet::{
     DecryptionError, DerivationPath, DerivationPathError, FindKeyError, Wallet,
 };
@@ -29,6 +34,13 @@ use crate::wallet::{
     self, read_and_confirm_encryption_password, CliWalletUtils, WalletTransport,
 };
 
+/// Maximum number of concurrent requests made to the MASP indexer when
+/// looking for the birthdays of recovered shielded accounts
+const RECOVERY_MAX_CONCURRENT_FETCHES: usize = 100;
+/// Number of blocks fetched at once from the MASP indexer when looking for
+/// the birthdays of recovered shielded accounts
+const RECOVERY_BLOCK_BATCH_SIZE: u64 = 10_000;
+
 impl CliApi {
     pub async fn handle_wallet_command(
         cmd: cmds::NamadaWallet,
@@ -250,6 +262,139 @@ async fn shielded_key_derive(
     );
 }
 
+/// Recovers the masp spending keys of several accounts from the mnemonic code
+/// in the wallet, setting their birthdays from the data of a MASP indexer.
+async fn shielded_accounts_recover(
+    ctx: Context,
+    io: &impl Io,
+    args::KeyDerive {
+        alias,
+        alias_force,
+        unsafe_dont_encrypt,
+        ledger_zip32,
+        prompt_bip39_passphrase,
+        recover_accounts,
+        with_indexer,
+        ..
+    }: args::KeyDerive,
+) {
+    let (Some(accounts), Some(endpoint)) = (recover_accounts, with_indexer)
+    else {
+        edisplay_line!(
+            io,
+            "Recovering shielded accounts requires a MASP indexer."
+        );
+        cli::safe_exit(1)
+    };
+    let mut wallet = load_wallet(ctx);
+    let alias = alias.to_lowercase();
+    let keys =
+        Wallet::<CliWalletUtils>::derive_shielded_accounts_from_mnemonic_code(
+            ledger_zip32,
+            accounts,
+            None,
+            prompt_bip39_passphrase,
+        )
+        .unwrap_or_else(|| {
+            edisplay_line!(io, "Failed to derive the keys.");
+            display_line!(io, "No changes are persisted. Exiting.");
+            cli::safe_exit(1)
+        });
+
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(60))
+        .build()
+        .unwrap_or_else(|err| {
+            edisplay_line!(io, "Failed to build http client: {}", err);
+            cli::safe_exit(1)
+        });
+    let url = endpoint.as_str().try_into().unwrap_or_else(|err| {
+        edisplay_line!(io, "Failed to parse API endpoint {endpoint:?}: {err}");
+        cli::safe_exit(1)
+    });
+    let indexer = IndexerMaspClient::new(
+        client,
+        url,
+        true,
+        RECOVERY_MAX_CONCURRENT_FETCHES,
+    );
+    let last_height = match indexer.last_block_height().await {
+        Ok(Some(height)) => height,
+        Ok(None) => {
+            edisplay_line!(io, "The MASP indexer has not indexed any block.");
+            cli::safe_exit(1)
+        }
+        Err(err) => {
+            edisplay_line!(io, "Failed to query the MASP indexer: {}", err);
+            cli::safe_exit(1)
+        }
+    };
+    display_line!(
+        io,
+        "Looking for the first activity of {} accounts up to height {}...",
+        accounts,
+        last_height
+    );
+    let viewing_keys = keys
+        .iter()
+        .map(|(_, spend_key)| ViewingKey::from(spend_key.to_viewing_key()))
+        .collect::<Vec<_>>();
+    let birthdays = find_key_birthdays(
+        &indexer,
+        &viewing_keys,
+        BlockHeight::first(),
+        last_height,
+        RECOVERY_BLOCK_BATCH_SIZE,
+    )
+    .await
+    .unwrap_or_else(|err| {
+        edisplay_line!(
+            io,
+            "Failed to fetch data from the MASP indexer: {}",
+            err
+        );
+        display_line!(io, "No changes are persisted. Exiting.");
+        cli::safe_exit(1)
+    });
+
+    let encryption_password =
+        read_and_confirm_encryption_password(unsafe_dont_encrypt);
+    for (account, ((derivation_path, spend_key), birthday)) in
+        keys.into_iter().zip(birthdays).enumerate()
+    {
+        let account_alias = wallet
+            .insert_spending_key(
+                format!("{alias}-{account}"),
+                alias_force,
+                spend_key,
+                Some(birthday),
+                encryption_password.clone(),
+                Some(derivation_path.clone()),
+            )
+            .unwrap_or_else(|| {
+                edisplay_line!(
+                    io,
+                    "Failed to add the key of account {}.",
+                    account
+                );
+                display_line!(io, "No changes are persisted. Exiting.");
+                cli::safe_exit(1)
+            });
+        display_line!(
+            io,
+            "Recovered account {} with alias \"{}\" and birthday {} using HD \
+             derivation path {}",
+            account,
+            account_alias,
+            birthday,
+            derivation_path
+        );
+    }
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
+}
+
 /// Generate a spending key.
 fn shielded_key_gen(
     ctx: Context,
@@ -647,6 +792,8 @@ async fn key_derive(
 ) {
     if !args_key_derive.shielded {
         transparent_key_and_address_derive(ctx, io, args_key_derive).await
+    } else if args_key_derive.recover_accounts.is_some() {
+        shielded_accounts_recover(ctx, io, args_key_derive).await
     } else {
         shielded_key_derive(ctx, io, args_key_derive).await
     }
@@ -7,11 +7,13 @@ use color_eyre::owo_colors::OwoColorize;
 use data_encoding::HEXLOWER;
 use either::Either;
 use masp_primitives::asset_type::AssetType;
+use masp_primitives::memo::Memo;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
 use masp_primitives::transaction::components::I128Sum;
+use masp_primitives::transaction::TransparentAddress;
 use masp_primitives::zip32::ExtendedFullViewingKey;
-use namada_core::masp::{BalanceOwner, MaspEpoch};
+use namada_core::masp::{addr_taddr, BalanceOwner, MaspEpoch};
 use namada_core::token::Amount;
 use namada_sdk::address::{Address, InternalAddress, MASP};
 use namada_sdk::borsh::BorshDeserialize;
@@ -32,7 +34,7 @@ use namada_sdk::hash::Hash;
 use namada_sdk::io::{display, display_line, edisplay_line, Client, Io};
 use namada_sdk::key::*;
 use namada_sdk::masp::shielded_wallet::ShieldedApi;
-use namada_sdk::masp::MaspTokenRewardData;
+use namada_sdk::masp::{MaspTokenRewardData, NoteDirection};
 use namada_sdk::parameters::{
     storage as param_storage, EpochDuration, ProposalBytes,
 };
@@ -391,6 +393,187 @@ pub async fn query_rewards_estimate(
     );
 }
 
+/// A row of an exported shielded history
+#[derive(serde::Serialize)]
+struct ShieldedHistoryRow {
+    height: u64,
+    tx_index: u32,
+    batch_index: Option<u32>,
+    direction: &'static str,
+    token: String,
+    amount: String,
+    memo: String,
+    sources: Vec<String>,
+    targets: Vec<String>,
+}
+
+/// Quote a CSV field if it contains any special characters
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Export the history of the notes of a viewing key
+pub async fn query_shielded_history(
+    context: &impl Namada,
+    args: args::ShieldedHistory,
+) {
+    let mut shielded = context.shielded_mut().await;
+    if let Err(err) = shielded.load().await {
+        edisplay_line!(
+            context.io(),
+            "Failed to load the shielded context: {err}. Run shielded-sync \
+             first."
+        );
+        cli::safe_exit(1)
+    }
+    let vk = args.owner.as_viewing_key();
+    let history = match shielded.shielded_history(&vk) {
+        Ok(history) => history,
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Failed to build the shielded history: {err}"
+            );
+            cli::safe_exit(1)
+        }
+    };
+    let notes_without_history = shielded.notes_without_history(&vk);
+    if notes_without_history > 0 {
+        edisplay_line!(
+            context.io(),
+            "Warning: {notes_without_history} note(s) of this viewing key \
+             were found before the shielded history was tracked and are \
+             missing from it. Remove the shielded context from the wallet \
+             directory and run shielded-sync again to rebuild it."
+        );
+    }
+
+    // Map the transparent addresses of the wallet to their aliases
+    let known_taddrs: HashMap<_, _> = context
+        .wallet()
+        .await
+        .get_addresses()
+        .into_iter()
+        .map(|(alias, addr)| (addr_taddr(addr), alias))
+        .collect();
+    let lookup_taddr = |taddr: &TransparentAddress| {
+        known_taddrs
+            .get(taddr)
+            .cloned()
+            .unwrap_or_else(|| HEXLOWER.encode(&taddr.0))
+    };
+
+    let mut rows = Vec::with_capacity(history.len());
+    for entry in history {
+        let (token, amount) = match shielded
+            .decode_asset_type(context.client(), entry.asset_type)
+            .await
+        {
+            Some(asset_data) => {
+                let amount = DenominatedAmount::new(
+                    Amount::from_masp_denominated(
+                        entry.value,
+                        asset_data.position,
+                    ),
+                    asset_data.denom,
+                );
+                (
+                    lookup_token_alias(context, &asset_data.token, &MASP).await,
+                    amount.to_string(),
+                )
+            }
+            None => (entry.asset_type.to_string(), entry.value.to_string()),
+        };
+        let memo = match Memo::try_from(&entry.memo) {
+            Ok(Memo::Empty) => String::new(),
+            Ok(Memo::Text(text)) => text.to_string(),
+            _ => HEXLOWER.encode(entry.memo.as_slice()),
+        };
+        rows.push(ShieldedHistoryRow {
+            height: entry.indexed_tx.height.0,
+            tx_index: entry.indexed_tx.index.0,
+            batch_index: entry.indexed_tx.batch_index,
+            direction: match entry.direction {
+                NoteDirection::Incoming => "incoming",
+                NoteDirection::Outgoing => "outgoing",
+            },
+            token,
+            amount,
+            memo,
+            sources: entry
+                .counterparts
+                .sources
+                .iter()
+                .map(lookup_taddr)
+                .collect(),
+            targets: entry
+                .counterparts
+                .targets
+                .iter()
+                .map(lookup_taddr)
+                .collect(),
+        });
+    }
+    drop(shielded);
+
+    let output = match args.format {
+        args::HistoryFormat::Json => serde_json::to_string_pretty(&rows)
+            .expect("Serializing the shielded history should not fail"),
+        args::HistoryFormat::Csv => {
+            let mut output = String::from(
+                "height,tx_index,batch_index,direction,token,amount,memo,\
+                 sources,targets\n",
+            );
+            for row in rows {
+                let fields = [
+                    row.height.to_string(),
+                    row.tx_index.to_string(),
+                    row.batch_index
+                        .map(|ix| ix.to_string())
+                        .unwrap_or_default(),
+                    row.direction.to_string(),
+                    row.token,
+                    row.amount,
+                    row.memo,
+                    row.sources.join(";"),
+                    row.targets.join(";"),
+                ];
+                let line = fields
+                    .iter()
+                    .map(|field| csv_field(field))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                output.push_str(&line);
+                output.push('\n');
+            }
+            output
+        }
+    };
+
+    match args.output {
+        Some(path) => {
+            if let Err(err) = std::fs::write(&path, output) {
+                edisplay_line!(
+                    context.io(),
+                    "Failed to write the shielded history to {}: {err}",
+                    path.display()
+                );
+                cli::safe_exit(1)
+            }
+            display_line!(
+                context.io(),
+                "Shielded history written to {}",
+                path.display()
+            );
+        }
+        None => display!(context.io(), "{output}"),
+    }
+}
+
 /// Query token shielded balance(s)
 async fn query_shielded_balance(
     context: &impl Namada,
@@ -16,11 +16,13 @@ use namada_sdk::args::TxBecomeValidator;
 use namada_sdk::borsh::{BorshDeserialize, BorshSerializeExt};
 use namada_sdk::collections::HashMap;
 use namada_sdk::governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, ParameterChangeProposal, PgfFundingProposal,
+    PgfStewardProposal,
 };
 use namada_sdk::ibc::convert_masp_tx_to_ibc_memo;
 use namada_sdk::io::{display_line, edisplay_line, Io};
 use namada_sdk::key::*;
+use namada_sdk::multisig::MultisigTxBundle;
 use namada_sdk::rpc::{InnerTxResult, TxBroadcastData, TxResponse};
 use namada_sdk::state::EPOCH_SWITCH_BLOCKS_DELAY;
 use namada_sdk::tx::data::compute_inner_tx_hash;
@@ -351,6 +353,76 @@ where
     Ok(())
 }
 
+/// Create a bundle to collect the signatures of a multisig account's
+/// transaction
+pub async fn multisig_create(
+    namada: &impl Namada,
+    args: args::MultisigCreate,
+) -> Result<(), error::Error> {
+    let tx_data = std::fs::read(&args.tx_path).map_err(|err| {
+        error::Error::Other(format!(
+            "Couldn't open file at {}: {err}",
+            args.tx_path.display()
+        ))
+    })?;
+    let tx = Tx::try_from_json_bytes(&tx_data).map_err(|err| {
+        error::Error::Encode(error::EncodingError::Serde(err.to_string()))
+    })?;
+    let account =
+        namada_sdk::rpc::get_account_info(namada.client(), &args.owner)
+            .await?
+            .ok_or_else(|| {
+                error::TxSubmitError::InvalidAccount(args.owner.encode())
+            })?;
+    let bundle = MultisigTxBundle::new(tx, account)?;
+
+    let filename = format!(
+        "multisig_bundle_{}.json",
+        bundle.tx.raw_header_hash().to_string().to_lowercase()
+    );
+    let output_path = match &args.output_folder {
+        Some(path) => path.join(filename),
+        None => filename.into(),
+    };
+    let out = File::create(&output_path)
+        .expect("Should be able to create the multisig bundle file.");
+    bundle
+        .to_writer_json(out)
+        .expect("Multisig bundle should be serializable.");
+    display_line!(
+        namada.io(),
+        "Multisig bundle for the account {} serialized to {}. {} signatures \
+         are required.",
+        bundle.owner,
+        output_path.to_string_lossy(),
+        bundle.threshold
+    );
+
+    Ok(())
+}
+
+/// Check a multisig bundle against the on-chain account, wrap it and submit
+/// it
+pub async fn submit_multisig<N: Namada>(
+    namada: &N,
+    args: args::MultisigSubmit,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada_sdk::io::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx || args.tx.dump_wrapper_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx)?;
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_update_account<N: Namada>(
     namada: &N,
     args: args::TxUpdateAccount,
@@ -398,6 +470,54 @@ where
     Ok(None)
 }
 
+pub async fn submit_init_vesting_account<N: Namada>(
+    namada: &N,
+    args: args::TxInitVestingAccount,
+) -> Result<Option<Address>, error::Error>
+where
+    <N::Client as namada_sdk::io::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) =
+        tx::build_init_vesting_account(namada, &args).await?;
+
+    if args.tx.dump_tx || args.tx.dump_wrapper_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx)?;
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        let cmt = tx.first_commitments().unwrap().to_owned();
+        let wrapper_hash = tx.wrapper_hash();
+        let response = namada.submit(tx, &args.tx).await?;
+        if let Some(result) =
+            response.is_applied_and_valid(wrapper_hash.as_ref(), &cmt)
+        {
+            return Ok(result.initialized_accounts.first().cloned());
+        }
+    }
+
+    Ok(None)
+}
+
+pub async fn submit_schedule_tx<N: Namada>(
+    namada: &N,
+    args: args::ScheduleTx,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada_sdk::io::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx || args.tx.dump_wrapper_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx)?;
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_change_consensus_key(
     namada: &impl Namada,
     args: args::ConsensusKeyChange,
@@ -1343,6 +1463,38 @@ where
             tx::build_pgf_stewards_proposal(namada, &args, proposal).await?,
             proposal_author,
         )
+    } else if args.is_param_change {
+        let proposal =
+            ParameterChangeProposal::try_from(args.proposal_data.as_ref())
+                .map_err(|e| {
+                    error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                        e.to_string(),
+                    )
+                })?;
+        let author_balance = namada_sdk::rpc::get_token_balance(
+            namada.client(),
+            &namada.native_token(),
+            &proposal.proposal.author,
+            None,
+        )
+        .await
+        .unwrap();
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| {
+                error::TxSubmitError::InvalidProposal(e.to_string())
+            })?;
+        let proposal_author = proposal.proposal.author.clone();
+
+        (
+            tx::build_param_change_proposal(namada, &args, proposal).await?,
+            proposal_author,
+        )
     } else {
         let proposal = DefaultProposal::try_from(args.proposal_data.as_ref())
             .map_err(|e| {
@@ -1536,6 +1688,26 @@ where
     Ok(())
 }
 
+pub async fn submit_auto_compound<N: Namada>(
+    namada: &N,
+    args: args::AutoCompound,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada_sdk::io::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx || args.tx.dump_wrapper_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx)?;
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_redelegate<N: Namada>(
     namada: &N,
     args: args::Redelegate,
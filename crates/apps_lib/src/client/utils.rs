@@ -14,11 +14,13 @@ use namada_sdk::args::DeviceTransport;
 use namada_sdk::borsh::BorshSerializeExt;
 use namada_sdk::chain::ChainId;
 use namada_sdk::dec::Dec;
+use namada_sdk::hash::Hash;
 use namada_sdk::ibc::trace::ibc_token;
 use namada_sdk::key::*;
+use namada_sdk::multisig::MultisigTxBundle;
 use namada_sdk::string_encoding::StringEncoded;
 use namada_sdk::token;
-use namada_sdk::tx::{Authorization, Tx};
+use namada_sdk::tx::{Authorization, SignatureIndex, Tx};
 use namada_sdk::uint::Uint;
 use namada_sdk::wallet::{alias, LoadStoreError, Wallet};
 use namada_vm::validate_untrusted_wasm;
@@ -62,6 +64,7 @@ pub async fn join_network(
         pre_genesis_path,
         allow_duplicate_ip,
         add_persistent_peers,
+        wasm_cache_bundle,
     }: args::JoinNetwork,
 ) {
     use tokio::fs;
@@ -259,6 +262,16 @@ pub async fn join_network(
     validate_wasm_artifacts_aux(&chain_id, &chain_dir.join(&config.wasm_dir))
         .await;
 
+    // Import the precompiled WASM modules, if any
+    if let Some((bundle_path, bundle_hash)) = wasm_cache_bundle {
+        import_wasm_cache_bundle(
+            &chain_dir,
+            &chain_dir.join(&config.wasm_dir),
+            &bundle_path,
+            &bundle_hash,
+        );
+    }
+
     // Save the config and the wallet
     config.write(&base_dir, &chain_id, true).unwrap();
     crate::wallet::save(&wallet).unwrap();
@@ -271,6 +284,33 @@ async fn validate_wasm_artifacts_aux(chain_id: &ChainId, wasm_dir: &Path) {
     wasm_loader::validate_wasm_artifacts(wasm_dir).await;
 }
 
+fn import_wasm_cache_bundle(
+    chain_dir: &Path,
+    wasm_dir: &Path,
+    bundle_path: &Path,
+    bundle_hash: &Hash,
+) {
+    println!(
+        "Importing precompiled WASM modules from {}...",
+        bundle_path.to_string_lossy()
+    );
+    let imported =
+        wasm_loader::precompiled::precompile_targets(chain_dir, wasm_dir)
+            .and_then(|targets| {
+                wasm_loader::precompiled::WasmCaches::open(chain_dir)
+                    .import_bundle(&targets, bundle_path, bundle_hash)
+            });
+    match imported {
+        Ok(imported) => {
+            println!("Imported {imported} precompiled WASM module(s).")
+        }
+        Err(err) => {
+            eprintln!("Failed to import the precompiled WASM modules: {err}");
+            safe_exit(1)
+        }
+    }
+}
+
 pub fn validate_wasm(args::ValidateWasm { code_path }: args::ValidateWasm) {
     let code = std::fs::read(code_path).unwrap();
     match validate_untrusted_wasm(code) {
@@ -1133,6 +1173,76 @@ pub async fn sign_offline(
     }
 }
 
+/// Offline add signatures to a multisig bundle.
+pub fn multisig_sign(
+    args::MultisigSign {
+        bundle_path,
+        secret_keys,
+        signatures,
+    }: args::MultisigSign,
+) {
+    let bundle_data = fs::read(&bundle_path).unwrap_or_else(|err| {
+        eprintln!("Couldn't open file at {}: {err}", bundle_path.display());
+        safe_exit(1)
+    });
+    let mut bundle = MultisigTxBundle::try_from_json_bytes(&bundle_data)
+        .unwrap_or_else(|err| {
+            eprintln!("Couldn't decode the multisig bundle: {err}");
+            safe_exit(1)
+        });
+
+    if let Err(err) = bundle.sign(&secret_keys) {
+        eprintln!("Couldn't sign the transaction: {err}");
+        safe_exit(1)
+    }
+    for signature_path in signatures {
+        let signature = fs::read(&signature_path)
+            .ok()
+            .and_then(|bytes| SignatureIndex::try_from_json_bytes(&bytes).ok())
+            .unwrap_or_else(|| {
+                eprintln!(
+                    "Couldn't decode the signature at {}",
+                    signature_path.display()
+                );
+                safe_exit(1)
+            });
+        match bundle.add_signature(signature) {
+            Ok(true) => {}
+            Ok(false) => println!(
+                "Replaced an existing signature with the one at {}",
+                signature_path.display()
+            ),
+            Err(err) => {
+                eprintln!(
+                    "Couldn't add the signature at {}: {err}",
+                    signature_path.display()
+                );
+                safe_exit(1)
+            }
+        }
+    }
+
+    let bundle_file = File::create(&bundle_path)
+        .expect("Should be able to create the multisig bundle file.");
+    bundle
+        .to_writer_json(bundle_file)
+        .expect("Multisig bundle should be serializable.");
+
+    println!(
+        "Collected {} out of {} required signatures for the account {}.",
+        bundle.signatures_count(),
+        bundle.threshold,
+        bundle.owner
+    );
+    if !bundle.is_complete() {
+        println!("Missing signatures from the public keys:");
+        for (idx, pk) in bundle.missing_signers() {
+            println!("  {idx}: {pk}");
+        }
+    }
+    println!("Multisig bundle updated at {}", bundle_path.display());
+}
+
 /// Add a spinning wheel to a message for long running commands.
 /// Can be turned off for E2E tests by setting the `REDUCED_CLI_PRINTING`
 /// environment variable.
@@ -1,4 +1,5 @@
 //! Runtime configuration for a validator node.
+use namada_sdk::eth_bridge::oracle::config::{RpcEndpoints, RpcEndpointsError};
 #[allow(unused_imports)]
 use namada_sdk::ethereum_events::EthereumEvent;
 use serde::{Deserialize, Serialize};
@@ -33,6 +34,16 @@ pub struct Config {
     /// The Ethereum JSON-RPC endpoint that the Ethereum event oracle will use
     /// to listen for events from the Ethereum bridge smart contracts
     pub oracle_rpc_endpoint: String,
+    /// Additional Ethereum JSON-RPC endpoints followed by the oracle. When
+    /// set, the oracle only forwards the events that a quorum of all the
+    /// endpoints agree on.
+    #[serde(default)]
+    pub oracle_rpc_additional_endpoints: Vec<String>,
+    /// The number of Ethereum JSON-RPC endpoints that must agree on the
+    /// events of a block, which must be a majority of them. Defaults to the
+    /// smallest majority.
+    #[serde(default)]
+    pub oracle_rpc_quorum: Option<usize>,
     /// The size of bounded channel between the Ethereum oracle and main
     /// ledger subprocesses. This is the number of Ethereum events that
     /// can be held in the channel. The default is 1000.
@@ -44,7 +55,22 @@ impl Default for Config {
         Self {
             mode: Mode::RemoteEndpoint,
             oracle_rpc_endpoint: DEFAULT_ORACLE_RPC_ENDPOINT.to_owned(),
+            oracle_rpc_additional_endpoints: vec![],
+            oracle_rpc_quorum: None,
             channel_buffer_size: ORACLE_CHANNEL_BUFFER_SIZE,
         }
     }
 }
+
+impl Config {
+    /// The Ethereum JSON-RPC endpoints followed by the oracle.
+    pub fn oracle_rpc_endpoints(
+        &self,
+    ) -> Result<RpcEndpoints, RpcEndpointsError> {
+        let urls = std::iter::once(&self.oracle_rpc_endpoint)
+            .chain(&self.oracle_rpc_additional_endpoints)
+            .cloned()
+            .collect();
+        RpcEndpoints::new(urls, self.oracle_rpc_quorum)
+    }
+}
@@ -483,10 +483,14 @@ impl Finalized {
         let templates::IbcParams {
             default_mint_limit,
             default_per_epoch_throughput_limit,
+            default_per_epoch_channel_throughput_limit,
+            default_per_epoch_account_throughput_limit,
         } = self.parameters.ibc_params.clone();
         IbcParameters {
             default_mint_limit,
             default_per_epoch_throughput_limit,
+            default_per_epoch_channel_throughput_limit,
+            default_per_epoch_account_throughput_limit,
         }
     }
 
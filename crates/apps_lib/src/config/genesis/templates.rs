@@ -514,6 +514,12 @@ pub struct IbcParams {
     pub default_mint_limit: token::Amount,
     /// Default per-epoch throughput limit of each token
     pub default_per_epoch_throughput_limit: token::Amount,
+    /// Default per-epoch throughput limit of each token on each channel
+    #[serde(default)]
+    pub default_per_epoch_channel_throughput_limit: Option<token::Amount>,
+    /// Default per-epoch withdraw limit of each token for each sender
+    #[serde(default)]
+    pub default_per_epoch_account_throughput_limit: Option<token::Amount>,
 }
 
 impl TokenBalances {
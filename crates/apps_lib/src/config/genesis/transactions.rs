@@ -79,6 +79,8 @@ fn get_tx_args(use_device: bool) -> TxArgs {
     TxArgs {
         dry_run: false,
         dry_run_wrapper: false,
+        dry_run_trace: false,
+        dry_run_diff: false,
         dump_tx: false,
         dump_wrapper_tx: false,
         output_folder: None,
@@ -7,13 +7,18 @@ pub mod utils;
 
 use std::fs::{create_dir_all, File};
 use std::io::Write;
+use std::net::SocketAddr;
 use std::num::NonZeroU64;
 use std::path::{Path, PathBuf};
 
 use directories::ProjectDirs;
 use namada_sdk::chain::{BlockHeight, ChainId};
 use namada_sdk::collections::HashMap;
+use namada_sdk::state::PruningMode;
 use namada_sdk::time::Rfc3339String;
+use namada_sdk::wallet::remote_signer::{
+    RemoteSignerError, SignerAuthKey, SignerEndpoint,
+};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -33,6 +38,10 @@ pub const FILENAME: &str = "config.toml";
 pub const COMETBFT_DIR: &str = "cometbft";
 /// Chain-specific Namada DB. Nested in chain dirs.
 pub const DB_DIR: &str = "db";
+/// Chain-specific cache of compiled VP WASMs. Nested in chain dirs.
+pub const VP_WASM_CACHE_DIR: &str = "vp_wasm_cache";
+/// Chain-specific cache of compiled tx WASMs. Nested in chain dirs.
+pub const TX_WASM_CACHE_DIR: &str = "tx_wasm_cache";
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
@@ -114,6 +123,9 @@ pub struct Shell {
     /// When set, will limit the how many block heights in the past can the
     /// storage be queried for reading values.
     pub storage_read_past_height_limit: Option<u64>,
+    /// The policy for pruning the history of the state in the DB. Queries
+    /// for pruned heights fail.
+    pub pruning_mode: PruningMode,
     /// Use the [`Ledger::db_dir()`] method to read the value.
     db_dir: PathBuf,
     /// Use the [`Ledger::cometbft_dir()`] method to read the value.
@@ -127,6 +139,22 @@ pub struct Shell {
     pub blocks_between_snapshots: Option<NonZeroU64>,
     /// Number of snapshots to keep
     pub snapshots_to_keep: Option<NonZeroU64>,
+    /// When set, the node serves its Prometheus metrics on the `/metrics`
+    /// path of this address
+    pub metrics_address: Option<SocketAddr>,
+    /// The remote signers of the validator's protocol key, given as
+    /// `tcp://<host>:<port>` or `unix://<path>`. When set, the node signs
+    /// its protocol messages with them instead of the key in its wallet.
+    #[serde(default)]
+    pub remote_signers: Vec<String>,
+    /// The number of remote signers that must sign a message, which must be
+    /// a majority of them. Defaults to the smallest majority.
+    #[serde(default)]
+    pub remote_signer_threshold: Option<usize>,
+    /// The file of the hex encoded key shared with the remote signers over
+    /// TCP, which authenticates the requests sent to them
+    #[serde(default)]
+    pub remote_signer_auth_key: Option<PathBuf>,
 }
 
 impl Ledger {
@@ -151,12 +179,17 @@ impl Ledger {
                 tx_wasm_compilation_cache_bytes: None,
                 // Default corresponds to 1 hour of past blocks at 1 block/sec
                 storage_read_past_height_limit: Some(3600),
+                pruning_mode: PruningMode::Archive,
                 db_dir: DB_DIR.into(),
                 cometbft_dir: COMETBFT_DIR.into(),
                 action_at_height: None,
                 tendermint_mode: mode,
                 blocks_between_snapshots: None,
                 snapshots_to_keep: None,
+                metrics_address: None,
+                remote_signers: vec![],
+                remote_signer_threshold: None,
+                remote_signer_auth_key: None,
             },
             cometbft: tendermint_config,
             ethereum_bridge: ethereum_bridge::ledger::Config::default(),
@@ -180,6 +213,26 @@ impl Ledger {
 }
 
 impl Shell {
+    /// The addresses of the remote signers of the validator's protocol key
+    pub fn remote_signer_endpoints(
+        &self,
+    ) -> std::result::Result<Vec<SignerEndpoint>, RemoteSignerError> {
+        self.remote_signers
+            .iter()
+            .map(|endpoint| endpoint.parse())
+            .collect()
+    }
+
+    /// The key authenticating the requests to the remote signers over TCP
+    pub fn remote_signer_auth_key(
+        &self,
+    ) -> std::result::Result<Option<SignerAuthKey>, RemoteSignerError> {
+        self.remote_signer_auth_key
+            .as_ref()
+            .map(SignerAuthKey::read)
+            .transpose()
+    }
+
     /// Get the directory path to the DB
     pub fn db_dir(&self, chain_id: &ChainId) -> PathBuf {
         self.base_dir.join(chain_id.as_str()).join(&self.db_dir)
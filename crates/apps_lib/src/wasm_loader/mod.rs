@@ -1,4 +1,7 @@
 //! A module for loading WASM files and downloading pre-built WASMs.
+
+pub mod precompiled;
+
 use std::fs;
 use std::path::Path;
 
@@ -0,0 +1,517 @@
+//! Ahead-of-time compilation of WASMs into the compilation caches of a node,
+//! and portable bundles of the compiled modules that can be shipped alongside
+//! releases.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use data_encoding::HEXLOWER;
+use eyre::{eyre, WrapErr};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use namada_sdk::hash::Hash;
+use namada_vm::wasm::{cache_version, TxCache, VpCache};
+use namada_vm::WasmCacheRwAccess;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::Checksums;
+use crate::config::{self, genesis};
+
+/// The name of the manifest file of a bundle
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// The kind of a WASM, which determines the cache of its compiled module
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum WasmKind {
+    Tx,
+    Vp,
+}
+
+impl WasmKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tx => "tx",
+            Self::Vp => "vp",
+        }
+    }
+}
+
+/// A WASM to compile ahead of time
+#[derive(Debug, Clone)]
+pub struct PrecompileTarget {
+    /// The kind of the WASM
+    pub kind: WasmKind,
+    /// The file name of the WASM in the wasm directory
+    pub name: String,
+    /// The hash of the WASM code
+    pub hash: Hash,
+    /// The WASM code
+    pub code: Vec<u8>,
+}
+
+/// A compiled module in a bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledModule {
+    /// The kind of the compiled WASM
+    pub kind: WasmKind,
+    /// The hash of the compiled WASM code
+    pub hash: Hash,
+    /// The SHA-256 of the compiled module, hex encoded
+    pub module_sha256: String,
+}
+
+/// The manifest of a bundle of compiled modules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// The version of the compilation cache the modules were compiled for
+    pub cache_version: String,
+    /// The compiled modules contained in the bundle
+    pub modules: Vec<BundledModule>,
+}
+
+/// The compilation caches of the WASMs of a chain
+pub struct WasmCaches {
+    vp: VpCache<WasmCacheRwAccess>,
+    tx: TxCache<WasmCacheRwAccess>,
+}
+
+impl WasmCaches {
+    /// Open the compilation caches in the given chain directory
+    pub fn open(chain_dir: impl AsRef<Path>) -> Self {
+        // NB: the in-memory caches are not used, but they must be non-empty
+        Self {
+            vp: VpCache::new(
+                chain_dir.as_ref().join(config::VP_WASM_CACHE_DIR),
+                1,
+            ),
+            tx: TxCache::new(
+                chain_dir.as_ref().join(config::TX_WASM_CACHE_DIR),
+                1,
+            ),
+        }
+    }
+
+    /// Compile the given WASMs into the file caches, skipping the ones that
+    /// were already compiled. Returns the number of compiled modules.
+    pub fn precompile(
+        &self,
+        targets: &[PrecompileTarget],
+    ) -> eyre::Result<usize> {
+        let mut compiled = 0_usize;
+        for target in targets {
+            let is_compiled = match target.kind {
+                WasmKind::Tx => self.tx.compile_to_file(&target.code),
+                WasmKind::Vp => self.vp.compile_to_file(&target.code),
+            }
+            .map_err(|err| eyre!("Failed to compile {}: {err}", target.name))?;
+            if is_compiled {
+                println!("Compiled {}", target.name);
+                compiled = compiled.saturating_add(1);
+            }
+        }
+        Ok(compiled)
+    }
+
+    /// Write a bundle of the compiled modules of the given WASMs to a gzipped
+    /// tarball. Returns the hash of the bundle, which can be published to
+    /// let users check its integrity.
+    pub fn export_bundle(
+        &self,
+        targets: &[PrecompileTarget],
+        bundle_path: impl AsRef<Path>,
+    ) -> eyre::Result<Hash> {
+        let mut bundle = tar::Builder::new(Vec::new());
+        let mut modules = Vec::with_capacity(targets.len());
+        for target in targets {
+            let module_file = match target.kind {
+                WasmKind::Tx => self.tx.module_file(&target.hash),
+                WasmKind::Vp => self.vp.module_file(&target.hash),
+            }
+            .ok_or_else(|| {
+                eyre!("The module of {} has not been compiled", target.name)
+            })?;
+            let module = fs::read(&module_file).wrap_err_with(|| {
+                format!("Failed to read {}", module_file.to_string_lossy())
+            })?;
+            append_file(
+                &mut bundle,
+                &module_path(target.kind, &target.hash),
+                &module,
+            )?;
+            modules.push(BundledModule {
+                kind: target.kind,
+                hash: target.hash,
+                module_sha256: sha256_hex(&module),
+            });
+        }
+        let manifest = BundleManifest {
+            cache_version: cache_version(),
+            modules,
+        };
+        append_file(
+            &mut bundle,
+            MANIFEST_FILE,
+            &serde_json::to_vec_pretty(&manifest)?,
+        )?;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&bundle.into_inner()?)?;
+        let bundle = encoder.finish()?;
+        fs::write(&bundle_path, &bundle).wrap_err_with(|| {
+            format!(
+                "Failed to write the bundle to {}",
+                bundle_path.as_ref().to_string_lossy()
+            )
+        })?;
+        Ok(Hash::sha256(&bundle))
+    }
+
+    /// Import the compiled modules of the given WASMs from a bundle. The
+    /// bundle must match the given trusted hash, as returned by
+    /// [`WasmCaches::export_bundle`], since its manifest can't vouch for the
+    /// modules on its own. It must also have been built for the same cache
+    /// version and every module must match the checksum of the manifest,
+    /// otherwise nothing is imported. Modules of other WASMs are ignored.
+    /// Returns the number of imported modules.
+    pub fn import_bundle(
+        &self,
+        targets: &[PrecompileTarget],
+        bundle_path: impl AsRef<Path>,
+        bundle_hash: &Hash,
+    ) -> eyre::Result<usize> {
+        let bundle = fs::read(&bundle_path).wrap_err_with(|| {
+            format!(
+                "Failed to read the bundle at {}",
+                bundle_path.as_ref().to_string_lossy()
+            )
+        })?;
+        let actual_hash = Hash::sha256(&bundle);
+        if actual_hash != *bundle_hash {
+            return Err(eyre!(
+                "The bundle has hash {actual_hash}, but {bundle_hash} was \
+                 expected"
+            ));
+        }
+        let mut files = BTreeMap::new();
+        let mut archive = tar::Archive::new(GzDecoder::new(&bundle[..]));
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.to_string_lossy().into_owned();
+            let mut contents = vec![];
+            entry.read_to_end(&mut contents)?;
+            files.insert(path, contents);
+        }
+
+        let manifest: BundleManifest = serde_json::from_slice(
+            files
+                .get(MANIFEST_FILE)
+                .ok_or_else(|| eyre!("The bundle is missing its manifest"))?,
+        )?;
+        let expected_version = cache_version();
+        if manifest.cache_version != expected_version {
+            return Err(eyre!(
+                "The bundle was compiled for version {}, but this node \
+                 requires version {expected_version}",
+                manifest.cache_version
+            ));
+        }
+
+        // Verify all the modules before importing any of them
+        let known: BTreeSet<_> = targets
+            .iter()
+            .map(|target| (target.kind, target.hash))
+            .collect();
+        let mut modules = Vec::with_capacity(manifest.modules.len());
+        for BundledModule {
+            kind,
+            hash,
+            module_sha256,
+        } in manifest.modules
+        {
+            if !known.contains(&(kind, hash)) {
+                println!(
+                    "Skipping the bundled {} module {hash}, which is not used \
+                     by this chain",
+                    kind.as_str()
+                );
+                continue;
+            }
+            let path = module_path(kind, &hash);
+            let module = files
+                .remove(&path)
+                .ok_or_else(|| eyre!("The bundle is missing {path}"))?;
+            if sha256_hex(&module) != module_sha256 {
+                return Err(eyre!("Checksum mismatch of the bundled {path}"));
+            }
+            modules.push((kind, hash, module));
+        }
+
+        let imported = modules.len();
+        for (kind, hash, module) in modules {
+            match kind {
+                WasmKind::Tx => self.tx.import_module_file(&hash, &module),
+                WasmKind::Vp => self.vp.import_module_file(&hash, &module),
+            }?;
+        }
+        Ok(imported)
+    }
+}
+
+/// Find the WASMs of a chain to compile ahead of time, i.e. all the WASMs
+/// listed in the checksums of the wasm directory. WASMs from the allowlists
+/// set at genesis are compiled into the cache of their list, while the others
+/// are classified by the prefix of their name.
+pub fn precompile_targets(
+    chain_dir: impl AsRef<Path>,
+    wasm_dir: impl AsRef<Path>,
+) -> eyre::Result<Vec<PrecompileTarget>> {
+    let genesis =
+        genesis::chain::Finalized::read_toml_files(chain_dir.as_ref())?;
+    let parameters = &genesis.parameters.parameters;
+    let parse_allowlist = |allowlist: &Option<Vec<String>>| {
+        allowlist
+            .iter()
+            .flatten()
+            .map(|hash| hash.to_lowercase())
+            .collect::<BTreeSet<_>>()
+    };
+    let mut vp_allowlist = parse_allowlist(&parameters.vp_allowlist);
+    let mut tx_allowlist = parse_allowlist(&parameters.tx_allowlist);
+
+    let checksums = Checksums::read_checksums(&wasm_dir)?;
+    let mut targets = Vec::with_capacity(checksums.0.len());
+    for (name, full_name) in checksums.0 {
+        let code = fs::read(wasm_dir.as_ref().join(&full_name))
+            .wrap_err_with(|| format!("Failed to read {full_name}"))?;
+        let hash = Hash::sha256(&code);
+        let hash_hex = HEXLOWER.encode(&hash.0);
+        let kind = if vp_allowlist.remove(&hash_hex) {
+            WasmKind::Vp
+        } else if tx_allowlist.remove(&hash_hex) {
+            WasmKind::Tx
+        } else if name.starts_with("vp_") {
+            WasmKind::Vp
+        } else {
+            WasmKind::Tx
+        };
+        targets.push(PrecompileTarget {
+            kind,
+            name,
+            hash,
+            code,
+        });
+    }
+    for hash in vp_allowlist.iter().chain(&tx_allowlist) {
+        println!(
+            "Skipping the allowlisted WASM {hash}, which is missing from the \
+             wasm directory"
+        );
+    }
+    // Keep a stable order for reproducible bundles
+    targets.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(targets)
+}
+
+fn module_path(kind: WasmKind, hash: &Hash) -> String {
+    format!("{}/{}.bin", kind.as_str(), HEXLOWER.encode(&hash.0))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    HEXLOWER.encode(&Sha256::digest(bytes))
+}
+
+fn append_file(
+    bundle: &mut tar::Builder<Vec<u8>>,
+    path: &str,
+    contents: &[u8],
+) -> eyre::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    bundle.append_data(&mut header, path, contents)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write a bundle of the given manifest and module, bypassing the
+    /// checks of the export
+    fn write_bundle(
+        path: &Path,
+        manifest: &BundleManifest,
+        target: &PrecompileTarget,
+        module: &[u8],
+    ) -> Hash {
+        let mut bundle = tar::Builder::new(Vec::new());
+        append_file(
+            &mut bundle,
+            MANIFEST_FILE,
+            &serde_json::to_vec(manifest).unwrap(),
+        )
+        .unwrap();
+        append_file(
+            &mut bundle,
+            &module_path(target.kind, &target.hash),
+            module,
+        )
+        .unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&bundle.into_inner().unwrap()).unwrap();
+        let bundle = encoder.finish().unwrap();
+        fs::write(path, &bundle).unwrap();
+        Hash::sha256(&bundle)
+    }
+
+    /// Test that a bundle can only be imported if it's unaltered
+    #[test]
+    fn test_bundle_export_import() {
+        let code = b"tx code".to_vec();
+        let target = PrecompileTarget {
+            kind: WasmKind::Tx,
+            name: "tx_test.wasm".to_string(),
+            hash: Hash::sha256(&code),
+            code,
+        };
+        let module = b"compiled module".to_vec();
+        let targets = vec![target.clone()];
+
+        let source_dir = tempfile::tempdir().unwrap();
+        let source = WasmCaches::open(source_dir.path());
+        let bundle_path = source_dir.path().join("bundle.tar.gz");
+        // The module must be compiled before exporting it
+        assert!(source.export_bundle(&targets, &bundle_path).is_err());
+        source.tx.import_module_file(&target.hash, &module).unwrap();
+        let bundle_hash = source.export_bundle(&targets, &bundle_path).unwrap();
+        assert_eq!(bundle_hash, Hash::sha256(fs::read(&bundle_path).unwrap()));
+
+        // Import the bundle into a fresh cache
+        let target_dir = tempfile::tempdir().unwrap();
+        let imported = WasmCaches::open(target_dir.path());
+        assert_eq!(
+            imported
+                .import_bundle(&targets, &bundle_path, &bundle_hash)
+                .unwrap(),
+            1
+        );
+        let module_file = imported.tx.module_file(&target.hash).unwrap();
+        assert_eq!(fs::read(module_file).unwrap(), module);
+        assert!(imported.vp.module_file(&target.hash).is_none());
+
+        // Modules of unknown WASMs are skipped
+        let other_dir = tempfile::tempdir().unwrap();
+        let other = WasmCaches::open(other_dir.path());
+        assert_eq!(
+            other
+                .import_bundle(&[], &bundle_path, &bundle_hash)
+                .unwrap(),
+            0
+        );
+
+        // The bundle must match the trusted hash
+        assert!(
+            other
+                .import_bundle(&targets, &bundle_path, &Hash::sha256(b"other"))
+                .is_err()
+        );
+        assert!(other.tx.module_file(&target.hash).is_none());
+
+        // Modules not matching the manifest are rejected
+        let tampered_path = source_dir.path().join("tampered.tar.gz");
+        let manifest = BundleManifest {
+            cache_version: cache_version(),
+            modules: vec![BundledModule {
+                kind: target.kind,
+                hash: target.hash,
+                module_sha256: sha256_hex(&module),
+            }],
+        };
+        let tampered_hash = write_bundle(
+            &tampered_path,
+            &manifest,
+            &target,
+            b"malicious module",
+        );
+        assert!(
+            other
+                .import_bundle(&targets, &tampered_path, &tampered_hash)
+                .is_err()
+        );
+        assert!(other.tx.module_file(&target.hash).is_none());
+    }
+
+    /// Test that a forged bundle is rejected even though its manifest
+    /// matches its modules
+    #[test]
+    fn test_forged_bundle_rejected() {
+        let code = b"tx code".to_vec();
+        let target = PrecompileTarget {
+            kind: WasmKind::Tx,
+            name: "tx_test.wasm".to_string(),
+            hash: Hash::sha256(&code),
+            code,
+        };
+        let targets = vec![target.clone()];
+
+        let source_dir = tempfile::tempdir().unwrap();
+        let source = WasmCaches::open(source_dir.path());
+        let bundle_path = source_dir.path().join("bundle.tar.gz");
+        source
+            .tx
+            .import_module_file(&target.hash, b"compiled module")
+            .unwrap();
+        let trusted_hash =
+            source.export_bundle(&targets, &bundle_path).unwrap();
+
+        // Replace the bundle with a forged one, whose manifest has the
+        // checksum of the malicious module
+        let malicious = b"malicious module";
+        let manifest = BundleManifest {
+            cache_version: cache_version(),
+            modules: vec![BundledModule {
+                kind: target.kind,
+                hash: target.hash,
+                module_sha256: sha256_hex(malicious),
+            }],
+        };
+        let forged_hash =
+            write_bundle(&bundle_path, &manifest, &target, malicious);
+
+        // The forged bundle is consistent, so it would be imported if its own
+        // hash was trusted
+        let target_dir = tempfile::tempdir().unwrap();
+        let imported = WasmCaches::open(target_dir.path());
+        assert_eq!(
+            imported
+                .import_bundle(&targets, &bundle_path, &forged_hash)
+                .unwrap(),
+            1
+        );
+        // But it doesn't match the hash of the exported bundle
+        let forged_dir = tempfile::tempdir().unwrap();
+        let forged = WasmCaches::open(forged_dir.path());
+        let err = forged
+            .import_bundle(&targets, &bundle_path, &trusted_hash)
+            .unwrap_err();
+        assert!(err.to_string().contains("was expected"));
+        assert!(forged.tx.module_file(&target.hash).is_none());
+    }
+}
@@ -36,7 +36,8 @@ use namada_apps_lib::ibc::core::host::types::identifiers::{
 };
 use namada_apps_lib::ibc::primitives::ToProto;
 use namada_apps_lib::ibc::{
-    IbcActions, NftTransferModule, TransferModule, COMMITMENT_PREFIX,
+    IbcActions, InterchainAccountModule, NftTransferModule, TransferModule,
+    COMMITMENT_PREFIX,
 };
 use namada_apps_lib::masp_primitives::merkle_tree::CommitmentTree;
 use namada_apps_lib::masp_primitives::transaction::Transaction;
@@ -1727,7 +1728,9 @@ fn ibc_vp_validate_action(c: &mut Criterion) {
 
         let module = TransferModule::new(ctx.clone(), verifiers);
         actions.add_transfer_module(module);
-        let module = NftTransferModule::<_, token::Store<()>>::new(ctx);
+        let module = NftTransferModule::<_, token::Store<()>>::new(ctx.clone());
+        actions.add_transfer_module(module);
+        let module = InterchainAccountModule::<_, token::Store<()>>::new(ctx);
         actions.add_transfer_module(module);
 
         group.bench_function(bench_name, |b| {
@@ -1788,7 +1791,9 @@ fn ibc_vp_execute_action(c: &mut Criterion) {
 
         let module = TransferModule::new(ctx.clone(), verifiers);
         actions.add_transfer_module(module);
-        let module = NftTransferModule::<_, token::Store<()>>::new(ctx);
+        let module = NftTransferModule::<_, token::Store<()>>::new(ctx.clone());
+        actions.add_transfer_module(module);
+        let module = InterchainAccountModule::<_, token::Store<()>>::new(ctx);
         actions.add_transfer_module(module);
 
         group.bench_function(bench_name, |b| {
@@ -67,6 +67,9 @@ pub const MASP: Address = Address::Internal(InternalAddress::Masp);
 pub const MULTITOKEN: Address = Address::Internal(InternalAddress::Multitoken);
 /// Internal Eth bridge address
 pub const ETH_BRIDGE: Address = Address::Internal(InternalAddress::EthBridge);
+/// Internal address of the scheduled transactions queue
+pub const TX_SCHEDULER: Address =
+    Address::Internal(InternalAddress::TxScheduler);
 /// Address with temporary storage is used to pass data from txs to VPs which is
 /// never committed to DB
 pub const TEMP_STORAGE: Address =
@@ -147,6 +150,12 @@ impl From<raw::Address<'_, raw::Validated>> for Address {
             raw::Discriminant::ReplayProtection => {
                 Address::Internal(InternalAddress::ReplayProtection)
             }
+            raw::Discriminant::TxScheduler => {
+                Address::Internal(InternalAddress::TxScheduler)
+            }
+            raw::Discriminant::IcaHost => Address::Internal(
+                InternalAddress::IcaHost(IbcTokenHash(*raw_addr.data())),
+            ),
         }
     }
 }
@@ -253,6 +262,17 @@ impl<'addr> From<&'addr Address> for raw::Address<'addr, raw::Validated> {
                 .validate()
                 .expect("This raw address is valid")
             }
+            Address::Internal(InternalAddress::TxScheduler) => {
+                raw::Address::from_discriminant(raw::Discriminant::TxScheduler)
+                    .validate()
+                    .expect("This raw address is valid")
+            }
+            Address::Internal(InternalAddress::IcaHost(IbcTokenHash(hash))) => {
+                raw::Address::from_discriminant(raw::Discriminant::IcaHost)
+                    .with_data_array_ref(hash)
+                    .validate()
+                    .expect("This raw address is valid")
+            }
         }
     }
 }
@@ -573,6 +593,9 @@ pub enum InternalAddress {
     Ibc,
     /// IBC-related token
     IbcToken(IbcTokenHash),
+    /// IBC interchain account controlled by another chain. The hash is
+    /// derived from the connection and the controller port.
+    IcaHost(IbcTokenHash),
     /// Governance address
     Governance,
     /// Bridge to Ethereum
@@ -591,6 +614,8 @@ pub enum InternalAddress {
     Masp,
     /// Replay protection
     ReplayProtection,
+    /// Scheduled transactions queue
+    TxScheduler,
     /// Address with temporary storage is used to pass data from txs to VPs
     /// which is never committed to DB
     TempStorage,
@@ -608,6 +633,7 @@ impl Display for InternalAddress {
                 Self::Governance => "Governance".to_string(),
                 Self::Ibc => "IBC".to_string(),
                 Self::IbcToken(hash) => format!("IbcToken: {}", hash),
+                Self::IcaHost(hash) => format!("IcaHost: {}", hash),
                 Self::EthBridge => "EthBridge".to_string(),
                 Self::EthBridgePool => "EthBridgePool".to_string(),
                 Self::Erc20(eth_addr) => format!("Erc20: {}", eth_addr),
@@ -616,6 +642,7 @@ impl Display for InternalAddress {
                 Self::Pgf => "PGF".to_string(),
                 Self::Masp => "MASP".to_string(),
                 Self::ReplayProtection => "ReplayProtection".to_string(),
+                Self::TxScheduler => "TxScheduler".to_string(),
                 Self::TempStorage => "TempStorage".to_string(),
             }
         )
@@ -633,6 +660,7 @@ impl InternalAddress {
             "governance" => Some(InternalAddress::Governance),
             "masp" => Some(InternalAddress::Masp),
             "replayprotection" => Some(InternalAddress::ReplayProtection),
+            "txscheduler" => Some(InternalAddress::TxScheduler),
             "pgf" => Some(InternalAddress::Pgf),
             _ => None,
         }
@@ -839,6 +867,7 @@ pub mod testing {
             InternalAddress::Parameters => {}
             InternalAddress::Ibc => {}
             InternalAddress::IbcToken(_) => {}
+            InternalAddress::IcaHost(_) => {}
             InternalAddress::EthBridge => {}
             InternalAddress::EthBridgePool => {}
             InternalAddress::Erc20(_) => {}
@@ -847,6 +876,7 @@ pub mod testing {
             InternalAddress::Masp => {}
             InternalAddress::Multitoken => {}
             InternalAddress::ReplayProtection => {}
+            InternalAddress::TxScheduler => {}
             InternalAddress::TempStorage => {} /* Add new addresses in the
                                                 * `prop_oneof` below. */
         };
@@ -856,6 +886,7 @@ pub mod testing {
             Just(InternalAddress::Ibc),
             Just(InternalAddress::Parameters),
             arb_ibc_token(),
+            arb_ica_host(),
             Just(InternalAddress::Governance),
             Just(InternalAddress::EthBridge),
             Just(InternalAddress::EthBridgePool),
@@ -865,6 +896,7 @@ pub mod testing {
             Just(InternalAddress::Pgf),
             Just(InternalAddress::Masp),
             Just(InternalAddress::ReplayProtection),
+            Just(InternalAddress::TxScheduler),
             Just(InternalAddress::TempStorage),
         ]
     }
@@ -890,6 +922,11 @@ pub mod testing {
         })
     }
 
+    fn arb_ica_host() -> impl Strategy<Value = InternalAddress> {
+        proptest::array::uniform20(proptest::num::u8::ANY)
+            .prop_map(|hash| InternalAddress::IcaHost(IbcTokenHash(hash)))
+    }
+
     fn arb_erc20() -> impl Strategy<Value = InternalAddress> {
         proptest::array::uniform20(proptest::num::u8::ANY).prop_map(|addr| {
             InternalAddress::Erc20(crate::ethereum_events::EthAddress(addr))
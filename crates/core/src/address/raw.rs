@@ -69,6 +69,10 @@ pub enum Discriminant {
     TempStorage = 15,
     /// Replay protection
     ReplayProtection = 16,
+    /// Scheduled transactions queue
+    TxScheduler = 17,
+    /// IBC interchain account raw address.
+    IcaHost = 18,
 }
 
 /// Raw address representation.
@@ -118,7 +122,8 @@ impl<S> Address<'_, S> {
                 | Discriminant::Established
                 | Discriminant::Erc20
                 | Discriminant::Nut
-                | Discriminant::IbcToken,
+                | Discriminant::IbcToken
+                | Discriminant::IcaHost,
         )
     }
 }
@@ -1,8 +1,10 @@
 //! Configuration for an oracle.
-use std::num::NonZeroU64;
+use std::collections::BTreeSet;
+use std::num::{NonZeroU64, NonZeroUsize};
 
 use namada_core::ethereum_events::EthAddress;
 use namada_core::ethereum_structs;
+use thiserror::Error;
 
 /// Configuration for an oracle.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -31,3 +33,121 @@ impl std::default::Default for Config {
         }
     }
 }
+
+/// Errors in the configuration of the Ethereum RPC endpoints of an oracle.
+#[allow(missing_docs)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RpcEndpointsError {
+    #[error("At least one Ethereum RPC endpoint must be configured")]
+    NoEndpoints,
+    #[error("The Ethereum RPC endpoint {0} is configured more than once")]
+    DuplicateEndpoint(String),
+    #[error(
+        "The quorum must be a majority of the {endpoints} Ethereum RPC \
+         endpoints, got {quorum}"
+    )]
+    InvalidQuorum { quorum: usize, endpoints: usize },
+}
+
+/// The Ethereum RPC endpoints followed by an oracle. The events of a block
+/// are only forwarded to the ledger once `quorum` of the endpoints agree on
+/// them.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct RpcEndpoints {
+    urls: Vec<String>,
+    quorum: NonZeroUsize,
+}
+
+impl RpcEndpoints {
+    /// Configure the given endpoints. The quorum must be a strict majority
+    /// of the endpoints, such that two sets of endpoints can never reach a
+    /// quorum on conflicting events. When no quorum is given, the smallest
+    /// majority is used.
+    pub fn new(
+        urls: Vec<String>,
+        quorum: Option<usize>,
+    ) -> Result<Self, RpcEndpointsError> {
+        let endpoints = urls.len();
+        if endpoints == 0 {
+            return Err(RpcEndpointsError::NoEndpoints);
+        }
+        let mut unique = BTreeSet::new();
+        if let Some(url) = urls.iter().find(|url| !unique.insert(*url)) {
+            return Err(RpcEndpointsError::DuplicateEndpoint(url.clone()));
+        }
+        let majority = (endpoints / 2).saturating_add(1);
+        let quorum = quorum.unwrap_or(majority);
+        if quorum < majority || quorum > endpoints {
+            return Err(RpcEndpointsError::InvalidQuorum { quorum, endpoints });
+        }
+        Ok(Self {
+            urls,
+            quorum: NonZeroUsize::new(quorum)
+                .expect("The quorum is at least a majority"),
+        })
+    }
+
+    /// Follow a single endpoint.
+    pub fn single(url: impl Into<String>) -> Self {
+        Self {
+            urls: vec![url.into()],
+            quorum: NonZeroUsize::MIN,
+        }
+    }
+
+    /// The URLs of the endpoints.
+    pub fn urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    /// The number of endpoints that must agree on the events of a block.
+    pub fn quorum(&self) -> NonZeroUsize {
+        self.quorum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that the quorum of the RPC endpoints must be a majority.
+    #[test]
+    fn test_rpc_endpoints_quorum() {
+        let urls = |n: usize| -> Vec<String> {
+            (0..n).map(|i| format!("http://127.0.0.1:{i}")).collect()
+        };
+
+        assert_eq!(
+            RpcEndpoints::new(vec![], None),
+            Err(RpcEndpointsError::NoEndpoints)
+        );
+        assert_eq!(
+            RpcEndpoints::new(vec!["a".to_string(), "a".to_string()], None),
+            Err(RpcEndpointsError::DuplicateEndpoint("a".to_string()))
+        );
+        assert_eq!(
+            RpcEndpoints::new(urls(1), None).unwrap(),
+            RpcEndpoints::single("http://127.0.0.1:0")
+        );
+        assert_eq!(RpcEndpoints::new(urls(3), None).unwrap().quorum().get(), 2);
+        assert_eq!(RpcEndpoints::new(urls(4), None).unwrap().quorum().get(), 3);
+        assert_eq!(
+            RpcEndpoints::new(urls(5), Some(5)).unwrap().quorum().get(),
+            5
+        );
+        assert_eq!(
+            RpcEndpoints::new(urls(4), Some(2)),
+            Err(RpcEndpointsError::InvalidQuorum {
+                quorum: 2,
+                endpoints: 4
+            })
+        );
+        assert_eq!(
+            RpcEndpoints::new(urls(3), Some(4)),
+            Err(RpcEndpointsError::InvalidQuorum {
+                quorum: 4,
+                endpoints: 3
+            })
+        );
+    }
+}
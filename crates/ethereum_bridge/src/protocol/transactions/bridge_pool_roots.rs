@@ -4,8 +4,9 @@ use eyre::Result;
 use namada_core::address::Address;
 use namada_core::chain::BlockHeight;
 use namada_core::collections::{HashMap, HashSet};
+use namada_core::hash::Hash;
 use namada_core::keccak::keccak_hash;
-use namada_core::key::{common, SignableEthMessage};
+use namada_core::key::{common, SigScheme, SignableEthMessage};
 use namada_core::token::Amount;
 use namada_state::{DBIter, StorageHasher, WlState, DB};
 use namada_storage::{StorageRead, StorageWrite};
@@ -31,6 +32,24 @@ pub fn sign_bridge_pool_root<D, H>(
     eth_hot_key: &common::SecretKey,
     protocol_key: &common::SecretKey,
 ) -> Option<bridge_pool_roots::SignedVext>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    sign_bridge_pool_root_with(state, validator_addr, eth_hot_key, |hash| {
+        Some(common::SigScheme::sign(protocol_key, hash))
+    })
+}
+
+/// Sign the latest Bridge pool root with a function signing the hash of the
+/// vote extension with the protocol key, and return the associated vote
+/// extension protocol transaction. Returns `None` if the signing fails.
+pub fn sign_bridge_pool_root_with<D, H>(
+    state: &WlState<D, H>,
+    validator_addr: &Address,
+    eth_hot_key: &common::SecretKey,
+    sign: impl FnOnce(&Hash) -> Option<common::Signature>,
+) -> Option<bridge_pool_roots::SignedVext>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
@@ -47,7 +66,11 @@ where
         validator_addr: validator_addr.clone(),
         sig: signed.sig,
     };
-    Some(ext.sign(protocol_key))
+    Signed::<bridge_pool_roots::Vext>::try_new_with(ext, |hash| {
+        sign(hash).ok_or(())
+    })
+    .ok()
+    .map(bridge_pool_roots::SignedVext)
 }
 
 /// Applies a tally of signatures on over the Ethereum
@@ -12,7 +12,8 @@ use namada_core::address::Address;
 use namada_core::chain::{BlockHeight, Epoch};
 use namada_core::collections::{HashMap, HashSet};
 use namada_core::ethereum_events::EthereumEvent;
-use namada_core::key::common;
+use namada_core::hash::Hash;
+use namada_core::key::{common, SigScheme};
 use namada_core::storage::Key;
 use namada_core::token::Amount;
 use namada_proof_of_stake::storage::read_owned_pos_params;
@@ -20,6 +21,7 @@ use namada_state::tx_queue::ExpiredTx;
 use namada_state::{DBIter, StorageHasher, WlState, DB};
 use namada_systems::governance;
 use namada_tx::data::BatchedTxResult;
+use namada_tx::Signed;
 use namada_vote_ext::ethereum_events::{MultiSignedEthEvent, SignedVext, Vext};
 
 use super::ChangedKeys;
@@ -51,6 +53,27 @@ pub fn sign_ethereum_events<D, H>(
     protocol_key: &common::SecretKey,
     ethereum_events: Vec<EthereumEvent>,
 ) -> Option<SignedVext>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    sign_ethereum_events_with(state, validator_addr, ethereum_events, |hash| {
+        Some(common::SigScheme::sign(protocol_key, hash))
+    })
+}
+
+/// Sign the given Ethereum events with a function signing the hash of the
+/// vote extension with the protocol key, and return the associated vote
+/// extension protocol transaction. Returns `None` if the signing fails.
+///
+/// __INVARIANT__: Assume `ethereum_events` are sorted in ascending
+/// order.
+pub fn sign_ethereum_events_with<D, H>(
+    state: &WlState<D, H>,
+    validator_addr: &Address,
+    ethereum_events: Vec<EthereumEvent>,
+    sign: impl FnOnce(&Hash) -> Option<common::Signature>,
+) -> Option<SignedVext>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
@@ -73,7 +96,9 @@ where
         tracing::debug!("New Ethereum events - {:#?}", ext.ethereum_events);
     }
 
-    Some(ext.sign(protocol_key).into())
+    Signed::<Vext>::try_new_with(ext, |hash| sign(hash).ok_or(()))
+        .ok()
+        .map(Into::into)
 }
 
 /// Applies derived state changes to storage, based on Ethereum `events` which
@@ -149,6 +149,18 @@ impl Display for EventLevel {
     }
 }
 
+impl FromStr for EventLevel {
+    type Err = EventError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "block" => Ok(EventLevel::Block),
+            "tx" => Ok(EventLevel::Tx),
+            _ => Err(EventError::InvalidEventLevel(s.to_owned())),
+        }
+    }
+}
+
 /// ABCI event type.
 ///
 /// It is comprised of an event domain and sub-domain, plus any other
@@ -319,6 +331,9 @@ pub enum EventError {
     /// Error when parsing an event type
     #[error("Invalid event type")]
     InvalidEventType,
+    /// Error when parsing an event level
+    #[error("Invalid event level: {0}")]
+    InvalidEventLevel(String),
     /// Error when parsing attributes from an event JSON.
     #[error("Json missing `attributes` field")]
     MissingAttributes,
@@ -529,3 +544,48 @@ impl From<Event> for namada_core::tendermint::abci::Event {
         }
     }
 }
+
+impl TryFrom<namada_core::tendermint::abci::Event> for Event {
+    type Error = EventError;
+
+    fn try_from(
+        event: namada_core::tendermint::abci::Event,
+    ) -> Result<Self, Self::Error> {
+        use extend::{Domain, RawReadFromEventAttributes};
+
+        let mut level = None;
+        let mut attributes = BTreeMap::new();
+
+        for attr in event.attributes {
+            let key = attr.key_str().map_err(|err| {
+                EventError::AttributeEncoding(err.to_string())
+            })?;
+            let value = attr.value_str().map_err(|err| {
+                EventError::AttributeEncoding(err.to_string())
+            })?;
+            if key == "event-level" {
+                level = Some(value.parse()?);
+            } else {
+                attributes.insert(key.to_owned(), value.to_owned());
+            }
+        }
+
+        let level = level.ok_or(EventError::MissingAttribute("event-level"))?;
+        // NB: events carrying their domain in the attributes only have
+        // their sub-domain set as the kind of the ABCI event
+        let domain =
+            Domain::<Event>::raw_read_opt_from_event_attributes(&attributes);
+        let event_type = match domain {
+            Some(domain) => EventTypeBuilder::new_with_type(domain)
+                .with_segment(&event.kind)
+                .build(),
+            None => event.kind.parse()?,
+        };
+
+        Ok(Self {
+            level,
+            event_type,
+            attributes,
+        })
+    }
+}
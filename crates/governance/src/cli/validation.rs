@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use namada_core::address::Address;
 use namada_core::arith::{self, checked};
@@ -7,6 +7,7 @@ use namada_core::token;
 use thiserror::Error;
 
 use super::onchain::{PgfFunding, StewardsUpdate};
+use crate::storage::param_change::{ParameterChange, MAX_PARAMETER_CHANGES};
 
 /// This enum represents proposal data
 #[derive(Debug, Error)]
@@ -64,6 +65,15 @@ pub enum ProposalValidation {
     /// The PGF funding data is not valid
     #[error("invalid proposal extra data: cannot be empty.")]
     InvalidPgfFundingExtraData,
+    /// The parameter change data is not valid
+    #[error(
+        "Invalid proposal extra data: must contain at least one and less than \
+         {0} changes, each to a different parameter."
+    )]
+    InvalidParameterChangeExtraData(usize),
+    /// A parameter change is not valid
+    #[error("Invalid parameter change: {0}")]
+    InvalidParameterChange(String),
     #[error("Arithmetic {0}.")]
     Arith(#[from] arith::Error),
 }
@@ -230,3 +240,26 @@ pub fn is_valid_pgf_funding_data(
         Err(ProposalValidation::InvalidPgfFundingExtraData)
     }
 }
+
+pub fn is_valid_parameter_change_data(
+    data: &[ParameterChange],
+) -> Result<(), ProposalValidation> {
+    let parameters = data
+        .iter()
+        .map(ParameterChange::parameter)
+        .collect::<BTreeSet<_>>();
+    if data.is_empty()
+        || data.len() >= MAX_PARAMETER_CHANGES
+        || parameters.len() != data.len()
+    {
+        return Err(ProposalValidation::InvalidParameterChangeExtraData(
+            MAX_PARAMETER_CHANGES,
+        ));
+    }
+    for change in data {
+        change.validate().map_err(|err| {
+            ProposalValidation::InvalidParameterChange(err.to_string())
+        })?;
+    }
+    Ok(())
+}
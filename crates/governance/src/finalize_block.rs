@@ -10,7 +10,10 @@ use namada_core::encode;
 use namada_core::ibc::PGFIbcTarget;
 use namada_events::extend::{ComposeEvent, Height};
 use namada_events::{EmitEvents, EventLevel};
-use namada_state::{Key, Result, State, StateRead, StorageRead, StorageWrite};
+use namada_state::{
+    Key, Result, State, StateRead, StorageRead, StorageWrite, TxWlState,
+    TxWrites,
+};
 use namada_systems::{proof_of_stake, trans_token as token};
 use namada_tx::data::TxType;
 use namada_tx::{Code, Data, Tx};
@@ -19,6 +22,9 @@ use crate::event::GovernanceEvent;
 use crate::pgf::storage::keys as pgf_keys;
 use crate::pgf::storage::steward::StewardDetail;
 use crate::pgf::{storage as pgf_storage, ADDRESS as PGF_ADDRESS};
+use crate::storage::param_change::{
+    is_valid_governance_params_update, ParameterChange,
+};
 use crate::storage::proposal::{
     AddRemove, PGFAction, PGFTarget, ProposalType, StoragePgfFunding,
 };
@@ -30,21 +36,29 @@ use crate::{storage, ProposalVote, ADDRESS as GOV_ADDRESS};
 
 /// Apply governance updates for a block. On a new epoch, this will look for
 /// proposals to tally completed proposals and execute accepted proposals.
+///
+/// The changes to the parameters that are not owned by governance (protocol,
+/// PoS and IBC parameters) are applied with `apply_param_change`.
 #[allow(clippy::too_many_arguments)]
-pub fn finalize_block<S, Token, PoS, FnTx, FnIbcTransfer>(
+pub fn finalize_block<S, Token, PoS, FnTx, FnIbcTransfer, FnParamChange>(
     state: &mut S,
     events: &mut impl EmitEvents,
     current_epoch: Epoch,
     is_new_epoch: bool,
     dispatch_tx: FnTx,
     transfer_over_ibc: FnIbcTransfer,
+    apply_param_change: FnParamChange,
 ) -> Result<()>
 where
-    S: StateRead + State,
+    S: StateRead + State + TxWrites,
     Token: token::Read<S> + token::Write<S> + token::Events<S>,
     PoS: proof_of_stake::Read<S>,
     FnTx: FnMut(&Tx, &mut S) -> Result<bool>,
     FnIbcTransfer: Fn(&mut S, &Address, &Address, &PGFIbcTarget) -> Result<()>,
+    FnParamChange: for<'s> FnMut(
+        &mut TxWlState<'s, S::D, S::H>,
+        &ParameterChange,
+    ) -> Result<()>,
 {
     if is_new_epoch {
         load_and_execute_governance_proposals::<
@@ -53,49 +67,89 @@ where
             PoS,
             FnTx,
             FnIbcTransfer,
-        >(state, events, current_epoch, dispatch_tx, transfer_over_ibc)?;
+            FnParamChange,
+        >(
+            state,
+            events,
+            current_epoch,
+            dispatch_tx,
+            transfer_over_ibc,
+            apply_param_change,
+        )?;
     }
     Ok(())
 }
 
-fn load_and_execute_governance_proposals<S, Token, PoS, FnTx, FnIbcTransfer>(
+fn load_and_execute_governance_proposals<
+    S,
+    Token,
+    PoS,
+    FnTx,
+    FnIbcTransfer,
+    FnParamChange,
+>(
     state: &mut S,
     events: &mut impl EmitEvents,
     current_epoch: Epoch,
     dispatch_tx: FnTx,
     transfer_over_ibc: FnIbcTransfer,
+    apply_param_change: FnParamChange,
 ) -> Result<()>
 where
-    S: StateRead + State,
+    S: StateRead + State + TxWrites,
     Token: token::Read<S> + token::Write<S> + token::Events<S>,
     PoS: proof_of_stake::Read<S>,
     FnTx: FnMut(&Tx, &mut S) -> Result<bool>,
     FnIbcTransfer: Fn(&mut S, &Address, &Address, &PGFIbcTarget) -> Result<()>,
+    FnParamChange: for<'s> FnMut(
+        &mut TxWlState<'s, S::D, S::H>,
+        &ParameterChange,
+    ) -> Result<()>,
 {
     let proposal_ids = load_proposals(state, current_epoch)?;
 
-    execute_governance_proposals::<S, Token, PoS, FnTx, FnIbcTransfer>(
+    execute_governance_proposals::<
+        S,
+        Token,
+        PoS,
+        FnTx,
+        FnIbcTransfer,
+        FnParamChange,
+    >(
         state,
         events,
         proposal_ids,
         dispatch_tx,
         transfer_over_ibc,
+        apply_param_change,
     )
 }
 
-fn execute_governance_proposals<S, Token, PoS, FnTx, FnIbcTransfer>(
+fn execute_governance_proposals<
+    S,
+    Token,
+    PoS,
+    FnTx,
+    FnIbcTransfer,
+    FnParamChange,
+>(
     state: &mut S,
     events: &mut impl EmitEvents,
     proposal_ids: BTreeSet<u64>,
     mut dispatch_tx: FnTx,
     mut transfer_over_ibc: FnIbcTransfer,
+    mut apply_param_change: FnParamChange,
 ) -> Result<()>
 where
-    S: StateRead + State,
+    S: StateRead + State + TxWrites,
     Token: token::Read<S> + token::Write<S> + token::Events<S>,
     PoS: proof_of_stake::Read<S>,
     FnTx: FnMut(&Tx, &mut S) -> Result<bool>,
     FnIbcTransfer: Fn(&mut S, &Address, &Address, &PGFIbcTarget) -> Result<()>,
+    FnParamChange: for<'s> FnMut(
+        &mut TxWlState<'s, S::D, S::H>,
+        &ParameterChange,
+    ) -> Result<()>,
 {
     for id in proposal_ids {
         let proposal_funds_key = keys::get_funds_key(id);
@@ -189,6 +243,26 @@ where
                             id
                         );
 
+                        GovernanceEvent::passed_proposal(id, false, false)
+                    }
+                    ProposalType::ParameterChange(changes) => {
+                        let result = execute_parameter_change_proposal(
+                            state,
+                            changes,
+                            &mut apply_param_change,
+                        )?;
+                        tracing::info!(
+                            "Governance proposal #{} for parameter changes \
+                             has been executed. {}.",
+                            id,
+                            if result {
+                                "State changes have been applied successfully"
+                            } else {
+                                "FAILURE trying to apply the state changes - \
+                                 no state change occurred"
+                            }
+                        );
+
                         GovernanceEvent::passed_proposal(id, false, false)
                     }
                 };
@@ -433,6 +507,52 @@ where
     Ok(true)
 }
 
+fn execute_parameter_change_proposal<S, FnParamChange>(
+    state: &mut S,
+    changes: BTreeSet<ParameterChange>,
+    apply_param_change: &mut FnParamChange,
+) -> Result<bool>
+where
+    S: State + TxWrites,
+    FnParamChange: for<'s> FnMut(
+        &mut TxWlState<'s, S::D, S::H>,
+        &ParameterChange,
+    ) -> Result<()>,
+{
+    // The changes have been validated when the proposal was submitted, but
+    // the governance parameters might have been changed by another proposal
+    // since then
+    if changes.iter().any(|change| change.validate().is_err())
+        || !is_valid_governance_params_update(state, &changes)?
+    {
+        return Ok(false);
+    }
+
+    // The changes are written at the tx level of the write log, so that
+    // either all of them or none are applied
+    let result = changes.iter().try_for_each(|change| {
+        let mut state = state.with_tx_writes();
+        match change {
+            ParameterChange::Governance(change) => change.apply(&mut state),
+            ParameterChange::Pgf(change) => change.apply(&mut state),
+            ParameterChange::Protocol(_)
+            | ParameterChange::Pos(_)
+            | ParameterChange::Ibc(_) => apply_param_change(&mut state, change),
+        }
+    });
+    match result {
+        Ok(()) => {
+            state.write_log_mut().commit_batch_and_current_tx();
+            Ok(true)
+        }
+        Err(err) => {
+            tracing::warn!("Failed to apply the parameter changes: {err}");
+            state.write_log_mut().drop_batch();
+            Ok(false)
+        }
+    }
+}
+
 fn execute_pgf_funding_proposal<S, Token, FnIbcTransfer>(
     storage: &mut S,
     token: &Address,
@@ -2,6 +2,8 @@
 
 /// Governance proposal keys
 pub mod keys;
+/// Parameter change structures
+pub mod param_change;
 /// Proposal structures
 pub mod proposal;
 /// Vote structures
@@ -0,0 +1,765 @@
+//! Typed parameter changes that a governance proposal can apply natively,
+//! without having to execute any wasm code.
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::address::Address;
+use namada_core::dec::Dec;
+use namada_core::parameters::ProposalBytes;
+use namada_core::token;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_state::{Key, Result, StorageRead, StorageWrite};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::pgf::storage::keys as pgf_keys;
+use crate::storage::keys as gov_keys;
+
+/// The maximum number of parameter changes in a single proposal
+pub const MAX_PARAMETER_CHANGES: usize = 20;
+
+#[allow(missing_docs)]
+#[derive(Debug, Error)]
+pub enum ParameterChangeError {
+    #[error("The value of {0} must be between 0 and 1, got {1}")]
+    RateOutOfRange(String, Dec),
+    #[error("The value of {0} must not be negative, got {1}")]
+    NegativeValue(String, Dec),
+    #[error("The value of {0} must not be zero")]
+    ZeroValue(String),
+    #[error("The value of max_proposal_bytes is out of range, got {0}")]
+    InvalidProposalBytes(u64),
+    #[error("Invalid IBC channel ID {0}")]
+    InvalidChannelId(String),
+}
+
+/// A change to one of the protocol parameters
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSchema,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterChange {
+    /// Change of a protocol parameter
+    Protocol(ProtocolParamChange),
+    /// Change of a PoS parameter
+    Pos(PosParamChange),
+    /// Change of a governance parameter
+    Governance(GovernanceParamChange),
+    /// Change of a PGF parameter
+    Pgf(PgfParamChange),
+    /// Change of an IBC parameter
+    Ibc(IbcParamChange),
+}
+
+/// A change to a protocol parameter. The parameters that are read-only once
+/// the chain has started, like the epoch duration, cannot be changed.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSchema,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolParamChange {
+    /// Max payload size, in bytes, for a mempool tx
+    MaxTxBytes(u32),
+    /// Max payload size, in bytes, for a tx batch proposal
+    MaxProposalBytes(u64),
+    /// Max gas for block
+    MaxBlockGas(u64),
+    /// The gas limit for a masp transaction paying fees
+    MaspFeePaymentGasLimit(u64),
+    /// Gas scale
+    GasScale(u64),
+    /// Set the cost per gas unit of a token allowed for fee payment, or
+    /// disallow the token for fee payment if the amount is `None`
+    MinimumGasPrice {
+        /// The fee token
+        token: Address,
+        /// The cost per gas unit
+        amount: Option<token::Amount>,
+    },
+    /// Enable the native token transfer
+    IsNativeTokenTransferable(bool),
+}
+
+/// A change to a PoS parameter. The parameters that would require a
+/// migration of the epoched PoS data, like the pipeline and unbonding
+/// lengths, the max number of validator slots or the liveness window, cannot
+/// be changed.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSchema,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PosParamChange {
+    /// Amount of tokens rewarded to a validator for proposing a block
+    BlockProposerReward(Dec),
+    /// Amount of tokens rewarded to each validator that voted on a block
+    /// proposal
+    BlockVoteReward(Dec),
+    /// Maximum staking rewards rate per annum
+    MaxInflationRate(Dec),
+    /// Target ratio of staked NAM tokens to total NAM tokens
+    TargetStakedRatio(Dec),
+    /// Fraction of validator's stake that should be slashed on a duplicate
+    /// vote
+    DuplicateVoteMinSlashRate(Dec),
+    /// Fraction of validator's stake that should be slashed on a light client
+    /// attack
+    LightClientAttackMinSlashRate(Dec),
+    /// The minimum amount of bonded tokens that a validator needs to be in
+    /// either the `consensus` or `below_capacity` validator sets
+    ValidatorStakeThreshold(token::Amount),
+    /// The minimum required activity of consesus validators, in percentage,
+    /// over the liveness window
+    LivenessThreshold(Dec),
+    /// PoS gain p
+    RewardsGainP(Dec),
+    /// PoS gain d
+    RewardsGainD(Dec),
+}
+
+/// A change to a governance parameter
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSchema,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum GovernanceParamChange {
+    /// Minimum amount of locked funds
+    MinProposalFund(token::Amount),
+    /// Maximum length for proposal code in bytes
+    MaxProposalCodeSize(u64),
+    /// Minimum number of epochs between the proposal end epoch and start
+    /// epoch
+    MinProposalVotingPeriod(u64),
+    /// Maximum number of epochs between the proposal start epoch and
+    /// activation epoch
+    MaxProposalPeriod(u64),
+    /// Maximum number of characters for proposal content
+    MaxProposalContentSize(u64),
+    /// Minimum number of epochs between the end and activation epochs
+    MinProposalGraceEpochs(u64),
+    /// Maximum number of epochs between current epoch and start epoch
+    MaxProposalLatency(u64),
+}
+
+/// A change to a PGF parameter. The stewards set is updated with steward
+/// proposals instead.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSchema,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PgfParamChange {
+    /// The pgf funding inflation rate
+    PgfInflationRate(Dec),
+    /// The pgf stewards inflation rate
+    StewardsInflationRate(Dec),
+    /// The maximum number of pgf stewards at once
+    MaximumNumberOfStewards(u64),
+}
+
+/// A change to an IBC parameter
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSchema,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum IbcParamChange {
+    /// Default supply limit of each token
+    DefaultMintLimit(token::Amount),
+    /// Default per-epoch throughput limit of each token
+    DefaultPerEpochThroughputLimit(token::Amount),
+    /// Default per-epoch throughput limit of each token on each channel, or
+    /// no limit if the amount is `None`
+    DefaultPerEpochChannelThroughputLimit(Option<token::Amount>),
+    /// Default per-epoch withdraw limit of each token for each sender, or no
+    /// limit if the amount is `None`
+    DefaultPerEpochAccountThroughputLimit(Option<token::Amount>),
+    /// Freeze or unfreeze a channel. No new transfer can be sent or received
+    /// through a frozen channel.
+    FrozenChannel {
+        /// The channel ID, e.g. `channel-0`
+        channel_id: String,
+        /// Whether the channel is frozen
+        frozen: bool,
+    },
+}
+
+impl ParameterChange {
+    /// The name of the changed parameter. Two changes with the same name
+    /// target the same parameter.
+    pub fn parameter(&self) -> String {
+        match self {
+            ParameterChange::Protocol(change) => {
+                let name = match change {
+                    ProtocolParamChange::MaxTxBytes(_) => "max_tx_bytes",
+                    ProtocolParamChange::MaxProposalBytes(_) => {
+                        "max_proposal_bytes"
+                    }
+                    ProtocolParamChange::MaxBlockGas(_) => "max_block_gas",
+                    ProtocolParamChange::MaspFeePaymentGasLimit(_) => {
+                        "masp_fee_payment_gas_limit"
+                    }
+                    ProtocolParamChange::GasScale(_) => "gas_scale",
+                    ProtocolParamChange::MinimumGasPrice { token, .. } => {
+                        return format!("protocol/minimum_gas_price/{token}");
+                    }
+                    ProtocolParamChange::IsNativeTokenTransferable(_) => {
+                        "is_native_token_transferable"
+                    }
+                };
+                format!("protocol/{name}")
+            }
+            ParameterChange::Pos(change) => {
+                let name = match change {
+                    PosParamChange::BlockProposerReward(_) => {
+                        "block_proposer_reward"
+                    }
+                    PosParamChange::BlockVoteReward(_) => "block_vote_reward",
+                    PosParamChange::MaxInflationRate(_) => "max_inflation_rate",
+                    PosParamChange::TargetStakedRatio(_) => {
+                        "target_staked_ratio"
+                    }
+                    PosParamChange::DuplicateVoteMinSlashRate(_) => {
+                        "duplicate_vote_min_slash_rate"
+                    }
+                    PosParamChange::LightClientAttackMinSlashRate(_) => {
+                        "light_client_attack_min_slash_rate"
+                    }
+                    PosParamChange::ValidatorStakeThreshold(_) => {
+                        "validator_stake_threshold"
+                    }
+                    PosParamChange::LivenessThreshold(_) => {
+                        "liveness_threshold"
+                    }
+                    PosParamChange::RewardsGainP(_) => "rewards_gain_p",
+                    PosParamChange::RewardsGainD(_) => "rewards_gain_d",
+                };
+                format!("pos/{name}")
+            }
+            ParameterChange::Governance(change) => {
+                let name = match change {
+                    GovernanceParamChange::MinProposalFund(_) => {
+                        "min_proposal_fund"
+                    }
+                    GovernanceParamChange::MaxProposalCodeSize(_) => {
+                        "max_proposal_code_size"
+                    }
+                    GovernanceParamChange::MinProposalVotingPeriod(_) => {
+                        "min_proposal_voting_period"
+                    }
+                    GovernanceParamChange::MaxProposalPeriod(_) => {
+                        "max_proposal_period"
+                    }
+                    GovernanceParamChange::MaxProposalContentSize(_) => {
+                        "max_proposal_content_size"
+                    }
+                    GovernanceParamChange::MinProposalGraceEpochs(_) => {
+                        "min_proposal_grace_epochs"
+                    }
+                    GovernanceParamChange::MaxProposalLatency(_) => {
+                        "max_proposal_latency"
+                    }
+                };
+                format!("governance/{name}")
+            }
+            ParameterChange::Pgf(change) => {
+                let name = match change {
+                    PgfParamChange::PgfInflationRate(_) => "pgf_inflation_rate",
+                    PgfParamChange::StewardsInflationRate(_) => {
+                        "stewards_inflation_rate"
+                    }
+                    PgfParamChange::MaximumNumberOfStewards(_) => {
+                        "maximum_number_of_stewards"
+                    }
+                };
+                format!("pgf/{name}")
+            }
+            ParameterChange::Ibc(change) => {
+                let name = match change {
+                    IbcParamChange::DefaultMintLimit(_) => "default_mint_limit",
+                    IbcParamChange::DefaultPerEpochThroughputLimit(_) => {
+                        "default_per_epoch_throughput_limit"
+                    }
+                    IbcParamChange::DefaultPerEpochChannelThroughputLimit(
+                        _,
+                    ) => "default_per_epoch_channel_throughput_limit",
+                    IbcParamChange::DefaultPerEpochAccountThroughputLimit(
+                        _,
+                    ) => "default_per_epoch_account_throughput_limit",
+                    IbcParamChange::FrozenChannel { channel_id, .. } => {
+                        return format!("ibc/frozen_channel/{channel_id}");
+                    }
+                };
+                format!("ibc/{name}")
+            }
+        }
+    }
+
+    /// The new value of the changed parameter
+    pub fn value(&self) -> String {
+        match self {
+            ParameterChange::Protocol(change) => match change {
+                ProtocolParamChange::MaxTxBytes(value) => value.to_string(),
+                ProtocolParamChange::MaxProposalBytes(value)
+                | ProtocolParamChange::MaxBlockGas(value)
+                | ProtocolParamChange::MaspFeePaymentGasLimit(value)
+                | ProtocolParamChange::GasScale(value) => value.to_string(),
+                ProtocolParamChange::MinimumGasPrice { amount, .. } => amount
+                    .map(|amount| amount.to_string())
+                    .unwrap_or_else(|| "none".to_string()),
+                ProtocolParamChange::IsNativeTokenTransferable(value) => {
+                    value.to_string()
+                }
+            },
+            ParameterChange::Pos(change) => match change {
+                PosParamChange::BlockProposerReward(value)
+                | PosParamChange::BlockVoteReward(value)
+                | PosParamChange::MaxInflationRate(value)
+                | PosParamChange::TargetStakedRatio(value)
+                | PosParamChange::DuplicateVoteMinSlashRate(value)
+                | PosParamChange::LightClientAttackMinSlashRate(value)
+                | PosParamChange::LivenessThreshold(value)
+                | PosParamChange::RewardsGainP(value)
+                | PosParamChange::RewardsGainD(value) => value.to_string(),
+                PosParamChange::ValidatorStakeThreshold(value) => {
+                    value.to_string_native()
+                }
+            },
+            ParameterChange::Governance(change) => match change {
+                GovernanceParamChange::MinProposalFund(value) => {
+                    value.to_string_native()
+                }
+                GovernanceParamChange::MaxProposalCodeSize(value)
+                | GovernanceParamChange::MinProposalVotingPeriod(value)
+                | GovernanceParamChange::MaxProposalPeriod(value)
+                | GovernanceParamChange::MaxProposalContentSize(value)
+                | GovernanceParamChange::MinProposalGraceEpochs(value)
+                | GovernanceParamChange::MaxProposalLatency(value) => {
+                    value.to_string()
+                }
+            },
+            ParameterChange::Pgf(change) => match change {
+                PgfParamChange::PgfInflationRate(value)
+                | PgfParamChange::StewardsInflationRate(value) => {
+                    value.to_string()
+                }
+                PgfParamChange::MaximumNumberOfStewards(value) => {
+                    value.to_string()
+                }
+            },
+            ParameterChange::Ibc(change) => match change {
+                IbcParamChange::DefaultMintLimit(value)
+                | IbcParamChange::DefaultPerEpochThroughputLimit(value) => {
+                    value.to_string()
+                }
+                IbcParamChange::DefaultPerEpochChannelThroughputLimit(
+                    value,
+                )
+                | IbcParamChange::DefaultPerEpochAccountThroughputLimit(
+                    value,
+                ) => value
+                    .map(|value| value.to_string())
+                    .unwrap_or_else(|| "none".to_string()),
+                IbcParamChange::FrozenChannel { frozen, .. } => {
+                    frozen.to_string()
+                }
+            },
+        }
+    }
+
+    /// Check that the new value of the parameter is within its valid range
+    pub fn validate(&self) -> std::result::Result<(), ParameterChangeError> {
+        let is_rate = |value: &Dec| {
+            if value.is_negative() || *value > Dec::one() {
+                Err(ParameterChangeError::RateOutOfRange(
+                    self.parameter(),
+                    *value,
+                ))
+            } else {
+                Ok(())
+            }
+        };
+        let is_non_zero = |value: u64| {
+            if value == 0 {
+                Err(ParameterChangeError::ZeroValue(self.parameter()))
+            } else {
+                Ok(())
+            }
+        };
+        match self {
+            ParameterChange::Protocol(change) => match change {
+                ProtocolParamChange::MaxTxBytes(value) => {
+                    is_non_zero(u64::from(*value))
+                }
+                ProtocolParamChange::MaxProposalBytes(value) => {
+                    ProposalBytes::new(*value).map(|_| ()).ok_or(
+                        ParameterChangeError::InvalidProposalBytes(*value),
+                    )
+                }
+                ProtocolParamChange::MaxBlockGas(value)
+                | ProtocolParamChange::GasScale(value) => is_non_zero(*value),
+                ProtocolParamChange::MaspFeePaymentGasLimit(_)
+                | ProtocolParamChange::MinimumGasPrice { .. }
+                | ProtocolParamChange::IsNativeTokenTransferable(_) => Ok(()),
+            },
+            ParameterChange::Pos(change) => match change {
+                PosParamChange::BlockProposerReward(value)
+                | PosParamChange::BlockVoteReward(value)
+                | PosParamChange::MaxInflationRate(value)
+                | PosParamChange::TargetStakedRatio(value)
+                | PosParamChange::DuplicateVoteMinSlashRate(value)
+                | PosParamChange::LightClientAttackMinSlashRate(value)
+                | PosParamChange::LivenessThreshold(value) => is_rate(value),
+                PosParamChange::RewardsGainP(value)
+                | PosParamChange::RewardsGainD(value) => {
+                    if value.is_negative() {
+                        Err(ParameterChangeError::NegativeValue(
+                            self.parameter(),
+                            *value,
+                        ))
+                    } else {
+                        Ok(())
+                    }
+                }
+                PosParamChange::ValidatorStakeThreshold(_) => Ok(()),
+            },
+            ParameterChange::Governance(change) => match change {
+                GovernanceParamChange::MaxProposalPeriod(value)
+                | GovernanceParamChange::MaxProposalContentSize(value) => {
+                    is_non_zero(*value)
+                }
+                GovernanceParamChange::MinProposalFund(_)
+                | GovernanceParamChange::MaxProposalCodeSize(_)
+                | GovernanceParamChange::MinProposalVotingPeriod(_)
+                | GovernanceParamChange::MinProposalGraceEpochs(_)
+                | GovernanceParamChange::MaxProposalLatency(_) => Ok(()),
+            },
+            ParameterChange::Pgf(change) => match change {
+                PgfParamChange::PgfInflationRate(value)
+                | PgfParamChange::StewardsInflationRate(value) => {
+                    is_rate(value)
+                }
+                PgfParamChange::MaximumNumberOfStewards(_) => Ok(()),
+            },
+            ParameterChange::Ibc(change) => match change {
+                IbcParamChange::FrozenChannel { channel_id, .. } => {
+                    // The channel IDs of a chain are assigned as
+                    // `channel-{counter}`
+                    channel_id
+                        .strip_prefix("channel-")
+                        .and_then(|counter| counter.parse::<u64>().ok())
+                        .map(|_| ())
+                        .ok_or_else(|| {
+                            ParameterChangeError::InvalidChannelId(
+                                channel_id.clone(),
+                            )
+                        })
+                }
+                IbcParamChange::DefaultMintLimit(_)
+                | IbcParamChange::DefaultPerEpochThroughputLimit(_)
+                | IbcParamChange::DefaultPerEpochChannelThroughputLimit(_)
+                | IbcParamChange::DefaultPerEpochAccountThroughputLimit(_) => {
+                    Ok(())
+                }
+            },
+        }
+    }
+}
+
+impl Display for ParameterChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.parameter(), self.value())
+    }
+}
+
+impl GovernanceParamChange {
+    /// Write the new value of the governance parameter to storage
+    pub fn apply<S>(&self, storage: &mut S) -> Result<()>
+    where
+        S: StorageWrite,
+    {
+        match self {
+            GovernanceParamChange::MinProposalFund(value) => {
+                storage.write(&gov_keys::get_min_proposal_fund_key(), value)
+            }
+            GovernanceParamChange::MaxProposalCodeSize(value) => storage
+                .write(&gov_keys::get_max_proposal_code_size_key(), value),
+            GovernanceParamChange::MinProposalVotingPeriod(value) => storage
+                .write(&gov_keys::get_min_proposal_voting_period_key(), value),
+            GovernanceParamChange::MaxProposalPeriod(value) => {
+                storage.write(&gov_keys::get_max_proposal_period_key(), value)
+            }
+            GovernanceParamChange::MaxProposalContentSize(value) => {
+                storage.write(&gov_keys::get_max_proposal_content_key(), value)
+            }
+            GovernanceParamChange::MinProposalGraceEpochs(value) => storage
+                .write(&gov_keys::get_min_proposal_grace_epochs_key(), value),
+            GovernanceParamChange::MaxProposalLatency(value) => {
+                storage.write(&gov_keys::get_max_proposal_latency_key(), value)
+            }
+        }
+    }
+}
+
+impl PgfParamChange {
+    /// Write the new value of the PGF parameter to storage
+    pub fn apply<S>(&self, storage: &mut S) -> Result<()>
+    where
+        S: StorageWrite,
+    {
+        match self {
+            PgfParamChange::PgfInflationRate(value) => {
+                storage.write(&pgf_keys::get_pgf_inflation_rate_key(), value)
+            }
+            PgfParamChange::StewardsInflationRate(value) => storage
+                .write(&pgf_keys::get_steward_inflation_rate_key(), value),
+            PgfParamChange::MaximumNumberOfStewards(value) => storage.write(
+                &pgf_keys::get_maximum_number_of_pgf_steward_key(),
+                value,
+            ),
+        }
+    }
+}
+
+/// Check that the governance parameters remain consistent once the given
+/// changes are applied on top of the current parameters, i.e. that the
+/// minimum voting period and grace epochs of a proposal still fit in the
+/// maximum proposal period.
+pub fn is_valid_governance_params_update<S>(
+    storage: &S,
+    changes: &BTreeSet<ParameterChange>,
+) -> Result<bool>
+where
+    S: StorageRead,
+{
+    let read = |key: Key| -> Result<u64> {
+        Ok(storage.read(&key)?.unwrap_or_default())
+    };
+    let mut min_voting_period =
+        read(gov_keys::get_min_proposal_voting_period_key())?;
+    let mut max_period = read(gov_keys::get_max_proposal_period_key())?;
+    let mut min_grace_epochs =
+        read(gov_keys::get_min_proposal_grace_epochs_key())?;
+
+    for change in changes {
+        match change {
+            ParameterChange::Governance(
+                GovernanceParamChange::MinProposalVotingPeriod(value),
+            ) => min_voting_period = *value,
+            ParameterChange::Governance(
+                GovernanceParamChange::MaxProposalPeriod(value),
+            ) => max_period = *value,
+            ParameterChange::Governance(
+                GovernanceParamChange::MinProposalGraceEpochs(value),
+            ) => min_grace_epochs = *value,
+            _ => {}
+        }
+    }
+
+    Ok(min_voting_period
+        .checked_add(min_grace_epochs)
+        .is_some_and(|min_period| min_period <= max_period))
+}
+
+#[cfg(any(test, feature = "testing"))]
+/// Testing helpers and strategies for parameter changes
+pub mod testing {
+    use namada_core::address::testing::arb_non_internal_address;
+    use namada_core::dec::testing::arb_dec;
+    use namada_core::token::testing::arb_amount;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Generate an arbitrary parameter change
+    pub fn arb_parameter_change() -> impl Strategy<Value = ParameterChange> {
+        prop_oneof![
+            any::<u64>().prop_map(|value| ParameterChange::Protocol(
+                ProtocolParamChange::MaxBlockGas(value)
+            )),
+            (
+                arb_non_internal_address(),
+                proptest::option::of(arb_amount())
+            )
+                .prop_map(|(token, amount)| {
+                    ParameterChange::Protocol(
+                        ProtocolParamChange::MinimumGasPrice { token, amount },
+                    )
+                }),
+            arb_dec().prop_map(|value| ParameterChange::Pos(
+                PosParamChange::MaxInflationRate(value)
+            )),
+            arb_amount().prop_map(|value| ParameterChange::Governance(
+                GovernanceParamChange::MinProposalFund(value)
+            )),
+            any::<u64>().prop_map(|value| ParameterChange::Pgf(
+                PgfParamChange::MaximumNumberOfStewards(value)
+            )),
+            arb_amount().prop_map(|value| ParameterChange::Ibc(
+                IbcParamChange::DefaultMintLimit(value)
+            )),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use namada_state::testing::TestState;
+
+    use super::*;
+    use crate::parameters::GovernanceParameters;
+
+    #[test]
+    fn test_validate_parameter_change() {
+        let rate = |value: Dec| {
+            ParameterChange::Pos(PosParamChange::MaxInflationRate(value))
+        };
+        assert!(rate(Dec::zero()).validate().is_ok());
+        assert!(rate(Dec::one()).validate().is_ok());
+        assert!(rate(Dec::new(101, 2).unwrap()).validate().is_err());
+        assert!(rate(Dec::new(-1, 2).unwrap()).validate().is_err());
+
+        let change =
+            ParameterChange::Protocol(ProtocolParamChange::MaxBlockGas(0));
+        assert!(change.validate().is_err());
+        let change =
+            ParameterChange::Protocol(ProtocolParamChange::MaxProposalBytes(0));
+        assert!(change.validate().is_err());
+        let change = ParameterChange::Protocol(
+            ProtocolParamChange::MaxProposalBytes(1024),
+        );
+        assert!(change.validate().is_ok());
+
+        let freeze = |channel_id: &str| {
+            ParameterChange::Ibc(IbcParamChange::FrozenChannel {
+                channel_id: channel_id.to_string(),
+                frozen: true,
+            })
+        };
+        assert!(freeze("channel-0").validate().is_ok());
+        assert!(freeze("channel-x").validate().is_err());
+        assert!(freeze("connection-0").validate().is_err());
+    }
+
+    #[test]
+    fn test_governance_params_update() {
+        let mut state = TestState::default();
+        let params = GovernanceParameters::default();
+        params.init_storage(&mut state).unwrap();
+
+        // Shrinking the max proposal period below the min voting period and
+        // grace epochs is invalid
+        let too_short = params
+            .min_proposal_voting_period
+            .checked_add(params.min_proposal_grace_epochs)
+            .and_then(|period| period.checked_sub(1))
+            .unwrap();
+        let changes = BTreeSet::from([ParameterChange::Governance(
+            GovernanceParamChange::MaxProposalPeriod(too_short),
+        )]);
+        assert!(!is_valid_governance_params_update(&state, &changes).unwrap());
+
+        // Unless the other parameters are changed too
+        let changes = BTreeSet::from([
+            ParameterChange::Governance(
+                GovernanceParamChange::MaxProposalPeriod(too_short),
+            ),
+            ParameterChange::Governance(
+                GovernanceParamChange::MinProposalGraceEpochs(
+                    params.min_proposal_grace_epochs.checked_sub(1).unwrap(),
+                ),
+            ),
+        ]);
+        assert!(is_valid_governance_params_update(&state, &changes).unwrap());
+
+        for change in &changes {
+            let ParameterChange::Governance(change) = change else {
+                unreachable!()
+            };
+            change.apply(&mut state).unwrap();
+        }
+        let max_proposal_period: Option<u64> = state
+            .read(&gov_keys::get_max_proposal_period_key())
+            .unwrap();
+        assert_eq!(max_proposal_period, Some(too_short));
+    }
+}
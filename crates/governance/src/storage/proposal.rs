@@ -14,10 +14,12 @@ use namada_migrations::*;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use super::param_change::ParameterChange;
 use super::vote::ProposalVote;
 use crate::cli::onchain::{
-    DefaultProposal, PgfAction, PgfContinuous, PgfFundingProposal, PgfRetro,
-    PgfSteward, PgfStewardProposal, StewardsUpdate,
+    DefaultProposal, ParameterChangeProposal, PgfAction, PgfContinuous,
+    PgfFundingProposal, PgfRetro, PgfSteward, PgfStewardProposal,
+    StewardsUpdate,
 };
 use crate::utils::{ProposalStatus, TallyType};
 
@@ -169,6 +171,23 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
     }
 }
 
+impl TryFrom<ParameterChangeProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: ParameterChangeProposal) -> Result<Self, Self::Error> {
+        let changes = value.data.into_iter().collect::<BTreeSet<_>>();
+
+        Ok(InitProposalData {
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::ParameterChange(changes),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            activation_epoch: value.proposal.activation_epoch,
+        })
+    }
+}
+
 /// Storage struture for pgf fundings
 #[derive(
     Debug,
@@ -222,6 +241,8 @@ pub enum ProposalType {
     PGFSteward(BTreeSet<AddRemove<Address>>),
     /// PGF funding proposal
     PGFPayment(BTreeSet<PGFAction>),
+    /// Protocol parameters change proposal
+    ParameterChange(BTreeSet<ParameterChange>),
 }
 
 /// An add or remove action for PGF
@@ -391,6 +412,13 @@ impl ProposalType {
                     .map(|action| format!("\n  {}", &action))
                     .join("")
             ),
+            ProposalType::ParameterChange(changes) => format!(
+                "Changes:{}",
+                changes
+                    .iter()
+                    .map(|change| format!("\n  {}", &change))
+                    .join("")
+            ),
         }
     }
 }
@@ -402,6 +430,7 @@ impl Display for ProposalType {
             ProposalType::DefaultWithWasm(_) => write!(f, "Default with Wasm"),
             ProposalType::PGFSteward(_) => write!(f, "PGF steward"),
             ProposalType::PGFPayment(_) => write!(f, "PGF funding"),
+            ProposalType::ParameterChange(_) => write!(f, "Parameter change"),
         }
     }
 }
@@ -590,6 +619,7 @@ pub mod testing {
     use proptest::{collection, prop_compose};
 
     use super::*;
+    use crate::storage::param_change::testing::arb_parameter_change;
     use crate::storage::vote::testing::arb_proposal_vote;
 
     /// Generate an arbitrary add or removal of what's generated by the supplied
@@ -680,6 +710,8 @@ pub mod testing {
             .prop_map(ProposalType::PGFSteward),
             collection::btree_set(arb_pgf_action(), 0..10)
                 .prop_map(ProposalType::PGFPayment),
+            collection::btree_set(arb_parameter_change(), 0..10)
+                .prop_map(ProposalType::ParameterChange),
         ]
     }
 
@@ -94,6 +94,7 @@ impl TallyType {
             (ProposalType::PGFPayment(_), false) => {
                 TallyType::OneHalfOverOneThird
             }
+            (ProposalType::ParameterChange(_), _) => TallyType::TwoFifths,
         }
     }
 }
@@ -19,6 +19,9 @@ use thiserror::Error;
 
 use self::utils::ReadType;
 use crate::address::{Address, InternalAddress};
+use crate::storage::param_change::{
+    is_valid_governance_params_update, ParameterChange, MAX_PARAMETER_CHANGES,
+};
 use crate::storage::proposal::{AddRemove, PGFAction, ProposalType};
 use crate::storage::{is_proposal_accepted, keys as gov_storage};
 use crate::utils::is_valid_validator_voting_period;
@@ -539,6 +542,41 @@ where
                     )
                 })
             }
+            ProposalType::ParameterChange(changes) => {
+                if changes.is_empty() || changes.len() >= MAX_PARAMETER_CHANGES
+                {
+                    return Err(Error::new_alloc(format!(
+                        "Invalid number of parameter changes ({}), it must be \
+                         non-zero and less than {MAX_PARAMETER_CHANGES}",
+                        changes.len()
+                    )));
+                }
+
+                // each change must target a different parameter
+                let changed_parameters = changes
+                    .iter()
+                    .map(ParameterChange::parameter)
+                    .collect::<BTreeSet<String>>();
+                if changed_parameters.len() != changes.len() {
+                    return Err(Error::new_const(
+                        "The same parameter is changed more than once",
+                    ));
+                }
+
+                for change in &changes {
+                    change
+                        .validate()
+                        .map_err(|err| Error::new_alloc(err.to_string()))?;
+                }
+
+                is_valid_governance_params_update(&ctx.pre(), &changes)?
+                    .ok_or_else(|| {
+                        Error::new_const(
+                            "The minimum voting period and grace epochs of a \
+                             proposal must fit in the maximum proposal period",
+                        )
+                    })
+            }
             // Default proposal condition are checked already for all other
             // proposals.
             // default_with_wasm proposal needs to check only for valid code
@@ -30,6 +30,7 @@ use prost::Message;
 
 use super::client::{AnyClientState, AnyConsensusState};
 use super::storage::IbcStorageContext;
+use crate::forward::InFlightForward;
 use crate::{storage, trace, NftClass, NftMetadata};
 
 /// Result of IBC common function call
@@ -762,6 +763,177 @@ pub trait IbcCommonContext: IbcStorageContext {
             .write(&key, amount)
             .map_err(ContextError::from)
     }
+
+    /// Read the per-epoch deposit of the given token on the channel
+    fn channel_deposit(
+        &self,
+        channel_id: &ChannelId,
+        token: &Address,
+    ) -> Result<Amount> {
+        let key = storage::channel_deposit_key(channel_id, token);
+        Ok(self.storage().read::<Amount>(&key)?.unwrap_or_default())
+    }
+
+    /// Write the per-epoch deposit of the given token on the channel
+    fn store_channel_deposit(
+        &mut self,
+        channel_id: &ChannelId,
+        token: &Address,
+        amount: Amount,
+    ) -> Result<()> {
+        let key = storage::channel_deposit_key(channel_id, token);
+        self.storage_mut()
+            .write(&key, amount)
+            .map_err(ContextError::from)
+    }
+
+    /// Read the per-epoch withdraw of the given token on the channel
+    fn channel_withdraw(
+        &self,
+        channel_id: &ChannelId,
+        token: &Address,
+    ) -> Result<Amount> {
+        let key = storage::channel_withdraw_key(channel_id, token);
+        Ok(self.storage().read::<Amount>(&key)?.unwrap_or_default())
+    }
+
+    /// Write the per-epoch withdraw of the given token on the channel
+    fn store_channel_withdraw(
+        &mut self,
+        channel_id: &ChannelId,
+        token: &Address,
+        amount: Amount,
+    ) -> Result<()> {
+        let key = storage::channel_withdraw_key(channel_id, token);
+        self.storage_mut()
+            .write(&key, amount)
+            .map_err(ContextError::from)
+    }
+
+    /// Read the per-epoch withdraw of the given token by the sender
+    fn account_withdraw(
+        &self,
+        account: &Address,
+        token: &Address,
+    ) -> Result<Amount> {
+        let key = storage::account_withdraw_key(account, token);
+        Ok(self.storage().read::<Amount>(&key)?.unwrap_or_default())
+    }
+
+    /// Write the per-epoch withdraw of the given token by the sender
+    fn store_account_withdraw(
+        &mut self,
+        account: &Address,
+        token: &Address,
+        amount: Amount,
+    ) -> Result<()> {
+        let key = storage::account_withdraw_key(account, token);
+        self.storage_mut()
+            .write(&key, amount)
+            .map_err(ContextError::from)
+    }
+
+    /// Read the in-flight forward of the packet
+    fn in_flight_forward(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<InFlightForward>> {
+        let key = storage::in_flight_forward_key(port_id, channel_id, sequence);
+        self.storage().read(&key).map_err(ContextError::from)
+    }
+
+    /// Write the in-flight forward of the packet
+    fn store_in_flight_forward(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        forward: InFlightForward,
+    ) -> Result<()> {
+        let key = storage::in_flight_forward_key(port_id, channel_id, sequence);
+        self.storage_mut()
+            .write(&key, forward)
+            .map_err(ContextError::from)
+    }
+
+    /// Delete the in-flight forward of the packet
+    fn delete_in_flight_forward(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<()> {
+        let key = storage::in_flight_forward_key(port_id, channel_id, sequence);
+        self.storage_mut().delete(&key).map_err(ContextError::from)
+    }
+
+    /// Write the forward of the packet whose refund couldn't be sent. The
+    /// refunded tokens remain in the IBC account.
+    fn store_stuck_forward(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        forward: InFlightForward,
+    ) -> Result<()> {
+        let key = storage::stuck_forward_key(port_id, channel_id, sequence);
+        self.storage_mut()
+            .write(&key, forward)
+            .map_err(ContextError::from)
+    }
+
+    /// Read the active channel of the interchain account
+    fn ica_active_channel(
+        &self,
+        controller_port_id: &PortId,
+        connection_id: &ConnectionId,
+    ) -> Result<Option<ChannelId>> {
+        storage::ica_active_channel(
+            self.storage(),
+            controller_port_id,
+            connection_id,
+        )
+        .map_err(ContextError::from)
+    }
+
+    /// Write the active channel of the interchain account
+    fn store_ica_active_channel(
+        &mut self,
+        controller_port_id: &PortId,
+        connection_id: &ConnectionId,
+        channel_id: &ChannelId,
+    ) -> Result<()> {
+        let key =
+            storage::ica_active_channel_key(controller_port_id, connection_id);
+        self.storage_mut()
+            .write(&key, channel_id.to_string())
+            .map_err(ContextError::from)
+    }
+
+    /// Read the address of the interchain account
+    fn ica_account(
+        &self,
+        controller_port_id: &PortId,
+        connection_id: &ConnectionId,
+    ) -> Result<Option<String>> {
+        let key = storage::ica_account_key(controller_port_id, connection_id);
+        self.storage().read(&key).map_err(ContextError::from)
+    }
+
+    /// Write the address of the interchain account
+    fn store_ica_account(
+        &mut self,
+        controller_port_id: &PortId,
+        connection_id: &ConnectionId,
+        address: String,
+    ) -> Result<()> {
+        let key = storage::ica_account_key(controller_port_id, connection_id);
+        self.storage_mut()
+            .write(&key, address)
+            .map_err(ContextError::from)
+    }
 }
 
 /// Read and decode the IBC sequence
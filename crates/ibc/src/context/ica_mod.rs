@@ -0,0 +1,645 @@
+//! IBC module for interchain accounts
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use data_encoding::BASE64;
+use ibc::apps::transfer::types::Amount as IbcAmount;
+use ibc::core::channel::types::acknowledgement::{
+    Acknowledgement, AcknowledgementStatus, StatusValue,
+};
+use ibc::core::channel::types::channel::{Counterparty, Order};
+use ibc::core::channel::types::error::{ChannelError, PacketError};
+use ibc::core::channel::types::packet::Packet;
+use ibc::core::channel::types::Version;
+use ibc::core::host::types::identifiers::{ChannelId, ConnectionId, PortId};
+use ibc::core::router::module::Module;
+use ibc::core::router::types::module::{ModuleExtras, ModuleId};
+use ibc::primitives::proto::Any;
+use ibc::primitives::Signer;
+use namada_core::address::Address;
+use namada_core::token::Amount;
+use namada_state::StorageRead;
+use namada_systems::trans_token;
+use prost::Message;
+
+use super::common::IbcCommonContext;
+use super::transfer_mod::ModuleWrapper;
+use crate::ica::{
+    controller_owner, host_account, host_port_id, is_controller_port,
+    InterchainAccountPacketData, Metadata, MsgSend, TxMsgData,
+    HOST_PORT_ID_STR, MODULE_ID_STR, MSG_SEND_RESPONSE_TYPE_URL,
+};
+use crate::trace::convert_to_address;
+
+/// A token transfer from the interchain account
+struct IcaTransfer {
+    target: Address,
+    token: Address,
+    amount: Amount,
+}
+
+/// IBC module for interchain accounts
+#[derive(Debug)]
+pub struct InterchainAccountModule<C, Token>
+where
+    C: IbcCommonContext,
+{
+    /// IBC context
+    pub ctx: Rc<RefCell<C>>,
+    _marker: PhantomData<Token>,
+}
+
+impl<C, Token> InterchainAccountModule<C, Token>
+where
+    C: IbcCommonContext,
+    Token: trans_token::Keys,
+{
+    /// Make a new module
+    pub fn new(ctx: Rc<RefCell<C>>) -> Self {
+        Self {
+            ctx,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Check the channel to be opened on a controller port and return the
+    /// channel version
+    fn validate_chan_open_init(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<Version, ChannelError> {
+        if controller_owner(port_id).is_none() {
+            return Err(ica_error(format!(
+                "The port isn't a controller port: {port_id}"
+            )));
+        }
+        if counterparty.port_id.as_str() != HOST_PORT_ID_STR {
+            return Err(ica_error(format!(
+                "The counterparty port isn't the host port: {}",
+                counterparty.port_id
+            )));
+        }
+        let connection_id = validate_channel(order, connection_hops)?;
+        let metadata = if version.as_str().is_empty() {
+            let connection = self
+                .ctx
+                .borrow()
+                .connection_end(connection_id)
+                .map_err(ica_error)?;
+            let host_connection_id =
+                connection.counterparty().connection_id().ok_or_else(|| {
+                    ica_error(format!(
+                        "The counterparty connection of {connection_id} \
+                         doesn't exist"
+                    ))
+                })?;
+            Metadata::new(connection_id, host_connection_id)
+        } else {
+            let metadata =
+                Metadata::from_version(version).map_err(ica_error)?;
+            if metadata.controller_connection_id != connection_id.as_str() {
+                return Err(ica_error(format!(
+                    "The controller connection {} doesn't match the channel \
+                     connection {connection_id}",
+                    metadata.controller_connection_id
+                )));
+            }
+            metadata
+        };
+        self.check_no_active_channel(port_id, connection_id, port_id)?;
+        Ok(metadata.to_version())
+    }
+
+    /// Check the channel to be opened on the host port and return the channel
+    /// version with the address of the interchain account
+    fn validate_chan_open_try(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<Version, ChannelError> {
+        if port_id.as_str() != HOST_PORT_ID_STR {
+            return Err(ica_error(format!(
+                "The port isn't the host port: {port_id}"
+            )));
+        }
+        if !is_controller_port(&counterparty.port_id) {
+            return Err(ica_error(format!(
+                "The counterparty port isn't a controller port: {}",
+                counterparty.port_id
+            )));
+        }
+        let connection_id = validate_channel(order, connection_hops)?;
+        let mut metadata =
+            Metadata::from_version(counterparty_version).map_err(ica_error)?;
+        if metadata.host_connection_id != connection_id.as_str() {
+            return Err(ica_error(format!(
+                "The host connection {} doesn't match the channel connection \
+                 {connection_id}",
+                metadata.host_connection_id
+            )));
+        }
+        self.check_no_active_channel(
+            &counterparty.port_id,
+            connection_id,
+            port_id,
+        )?;
+        metadata.address =
+            host_account(connection_id, &counterparty.port_id).to_string();
+        Ok(metadata.to_version())
+    }
+
+    /// Check the version of the host and return the metadata
+    fn validate_chan_open_ack(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<(ConnectionId, Metadata), ChannelError> {
+        let metadata =
+            Metadata::from_version(counterparty_version).map_err(ica_error)?;
+        if metadata.address.is_empty() {
+            return Err(ica_error(
+                "The interchain account address isn't set by the host",
+            ));
+        }
+        let channel = self
+            .ctx
+            .borrow()
+            .channel_end(port_id, channel_id)
+            .map_err(ica_error)?;
+        let connection_id =
+            channel.connection_hops.first().cloned().ok_or_else(|| {
+                ica_error(format!("No connection of the channel {channel_id}"))
+            })?;
+        if metadata.controller_connection_id != connection_id.as_str() {
+            return Err(ica_error(format!(
+                "The controller connection {} doesn't match the channel \
+                 connection {connection_id}",
+                metadata.controller_connection_id
+            )));
+        }
+        Ok((connection_id, metadata))
+    }
+
+    /// Check that no channel is open for the interchain account. A new
+    /// channel can be opened after the previous one has been closed, e.g. by
+    /// a timeout.
+    fn check_no_active_channel(
+        &self,
+        controller_port_id: &PortId,
+        connection_id: &ConnectionId,
+        port_id: &PortId,
+    ) -> Result<(), ChannelError> {
+        let ctx = self.ctx.borrow();
+        let active_channel = ctx
+            .ica_active_channel(controller_port_id, connection_id)
+            .map_err(ica_error)?;
+        if let Some(channel_id) = active_channel {
+            let channel =
+                ctx.channel_end(port_id, &channel_id).map_err(ica_error)?;
+            if channel.is_open() {
+                return Err(ica_error(format!(
+                    "The interchain account already has the open channel \
+                     {channel_id}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Execute the messages of the packet received on the host port and
+    /// return the encoded results
+    fn execute_packet(&mut self, packet: &Packet) -> Result<Vec<u8>, String> {
+        if packet.port_id_on_b.as_str() != HOST_PORT_ID_STR {
+            return Err(format!(
+                "The packet can't be received on the port {}",
+                packet.port_id_on_b
+            ));
+        }
+        let channel = self
+            .ctx
+            .borrow()
+            .channel_end(&packet.port_id_on_b, &packet.chan_id_on_b)
+            .map_err(|e| e.to_string())?;
+        let connection_id = channel
+            .connection_hops
+            .first()
+            .cloned()
+            .ok_or_else(|| "No connection of the channel".to_string())?;
+        let active_channel = self
+            .ctx
+            .borrow()
+            .ica_active_channel(&packet.port_id_on_a, &connection_id)
+            .map_err(|e| e.to_string())?;
+        if active_channel.as_ref() != Some(&packet.chan_id_on_b) {
+            return Err(format!(
+                "The channel {} isn't the active channel of the interchain \
+                 account",
+                packet.chan_id_on_b
+            ));
+        }
+
+        let packet_data = InterchainAccountPacketData::from_json(&packet.data)?;
+        let messages = packet_data.messages()?;
+        if messages.is_empty() {
+            return Err("No message to be executed".to_string());
+        }
+        let account = host_account(&connection_id, &packet.port_id_on_a);
+        // Decode all the messages and check the balances before executing
+        // them not to apply them partially
+        let mut transfers = vec![];
+        let mut msg_responses = vec![];
+        for message in messages {
+            transfers.extend(decode_transfers(&account, message)?);
+            msg_responses.push(Any {
+                type_url: MSG_SEND_RESPONSE_TYPE_URL.to_string(),
+                value: vec![],
+            });
+        }
+        self.check_balances(&account, &transfers)?;
+
+        let mut ctx = self.ctx.borrow_mut();
+        ctx.insert_verifier(&account).map_err(|e| e.to_string())?;
+        for IcaTransfer {
+            target,
+            token,
+            amount,
+        } in transfers
+        {
+            ctx.transfer_token(&account, &target, &token, amount)
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(TxMsgData {
+            data: vec![],
+            msg_responses,
+        }
+        .encode_to_vec())
+    }
+
+    /// Check if the interchain account has enough balances for the transfers
+    fn check_balances(
+        &self,
+        account: &Address,
+        transfers: &[IcaTransfer],
+    ) -> Result<(), String> {
+        let mut debits: BTreeMap<&Address, Amount> = BTreeMap::new();
+        for transfer in transfers {
+            let debit = debits.entry(&transfer.token).or_default();
+            *debit = debit
+                .checked_add(transfer.amount)
+                .ok_or_else(|| "Overflowed the amount".to_string())?;
+        }
+        let ctx = self.ctx.borrow();
+        for (token, debit) in debits {
+            let balance_key = Token::balance_key(token, account);
+            let balance = ctx
+                .storage()
+                .read::<Amount>(&balance_key)
+                .map_err(|e| e.to_string())?
+                .unwrap_or_default();
+            if balance < debit {
+                return Err(format!(
+                    "The interchain account doesn't have enough balance of \
+                     {token}"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<C, Token> ModuleWrapper for InterchainAccountModule<C, Token>
+where
+    C: IbcCommonContext + Debug,
+    Token: trans_token::Keys + Debug,
+{
+    fn as_module(&self) -> &dyn Module {
+        self
+    }
+
+    fn as_module_mut(&mut self) -> &mut dyn Module {
+        self
+    }
+
+    fn module_id(&self) -> ModuleId {
+        ModuleId::new(MODULE_ID_STR.to_string())
+    }
+
+    fn port_id(&self) -> PortId {
+        host_port_id()
+    }
+
+    fn is_bound_port(&self, port_id: &PortId) -> bool {
+        *port_id == self.port_id() || is_controller_port(port_id)
+    }
+}
+
+impl<C, Token> Module for InterchainAccountModule<C, Token>
+where
+    C: IbcCommonContext + Debug,
+    Token: trans_token::Keys + Debug,
+{
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_init_validate(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<Version, ChannelError> {
+        self.validate_chan_open_init(
+            order,
+            connection_hops,
+            port_id,
+            counterparty,
+            version,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_init_execute(
+        &mut self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        let version = self.validate_chan_open_init(
+            order,
+            connection_hops,
+            port_id,
+            counterparty,
+            version,
+        )?;
+        Ok((ModuleExtras::empty(), version))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_try_validate(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<Version, ChannelError> {
+        self.validate_chan_open_try(
+            order,
+            connection_hops,
+            port_id,
+            counterparty,
+            counterparty_version,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_try_execute(
+        &mut self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        let version = self.validate_chan_open_try(
+            order,
+            connection_hops,
+            port_id,
+            counterparty,
+            counterparty_version,
+        )?;
+        let connection_id = &connection_hops[0];
+        let account = host_account(connection_id, &counterparty.port_id);
+        let mut ctx = self.ctx.borrow_mut();
+        ctx.store_ica_active_channel(
+            &counterparty.port_id,
+            connection_id,
+            channel_id,
+        )
+        .map_err(ica_error)?;
+        ctx.store_ica_account(
+            &counterparty.port_id,
+            connection_id,
+            account.to_string(),
+        )
+        .map_err(ica_error)?;
+        Ok((ModuleExtras::empty(), version))
+    }
+
+    fn on_chan_open_ack_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<(), ChannelError> {
+        self.validate_chan_open_ack(port_id, channel_id, counterparty_version)
+            .map(|_| ())
+    }
+
+    fn on_chan_open_ack_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<ModuleExtras, ChannelError> {
+        let (connection_id, metadata) = self.validate_chan_open_ack(
+            port_id,
+            channel_id,
+            counterparty_version,
+        )?;
+        let mut ctx = self.ctx.borrow_mut();
+        ctx.store_ica_active_channel(port_id, &connection_id, channel_id)
+            .map_err(ica_error)?;
+        ctx.store_ica_account(port_id, &connection_id, metadata.address)
+            .map_err(ica_error)?;
+        Ok(ModuleExtras::empty())
+    }
+
+    fn on_chan_open_confirm_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    fn on_chan_open_confirm_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Ok(ModuleExtras::empty())
+    }
+
+    fn on_chan_close_init_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Err(ica_error(
+            "The channel of an interchain account can't be closed",
+        ))
+    }
+
+    fn on_chan_close_init_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Err(ica_error(
+            "The channel of an interchain account can't be closed",
+        ))
+    }
+
+    fn on_chan_close_confirm_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    fn on_chan_close_confirm_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Ok(ModuleExtras::empty())
+    }
+
+    fn on_recv_packet_execute(
+        &mut self,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Acknowledgement) {
+        let status = match self.execute_packet(packet) {
+            Ok(result) => AcknowledgementStatus::success(
+                StatusValue::new(BASE64.encode(&result))
+                    .expect("The result shouldn't be empty"),
+            ),
+            Err(e) => {
+                self.ctx.borrow().log_string(format!(
+                    "Executing the interchain account packet failed: {e}"
+                ));
+                AcknowledgementStatus::error(
+                    StatusValue::new(format!(
+                        "Executing the interchain account packet failed: {e}"
+                    ))
+                    .expect("The error message shouldn't be empty"),
+                )
+            }
+        };
+        (ModuleExtras::empty(), status.into())
+    }
+
+    fn on_acknowledgement_packet_validate(
+        &self,
+        _packet: &Packet,
+        _acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        // The result is only used by the owner
+        Ok(())
+    }
+
+    fn on_acknowledgement_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        (ModuleExtras::empty(), Ok(()))
+    }
+
+    fn on_timeout_packet_validate(
+        &self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        // The ordered channel is closed by the timeout. A new channel can be
+        // opened for the interchain account.
+        Ok(())
+    }
+
+    fn on_timeout_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        (ModuleExtras::empty(), Ok(()))
+    }
+}
+
+/// Check the channel ordering and return the connection of the channel
+fn validate_channel(
+    order: Order,
+    connection_hops: &[ConnectionId],
+) -> Result<&ConnectionId, ChannelError> {
+    if order != Order::Ordered {
+        return Err(ica_error(format!(
+            "The channel of an interchain account should be ordered: {order}"
+        )));
+    }
+    connection_hops
+        .first()
+        .ok_or_else(|| ica_error("No connection of the channel"))
+}
+
+/// Decode the transfers of the message from the interchain account
+fn decode_transfers(
+    account: &Address,
+    message: Any,
+) -> Result<Vec<IcaTransfer>, String> {
+    let type_url = message.type_url.clone();
+    let msg = MsgSend::try_from(message)
+        .map_err(|_| format!("Unsupported message: {type_url}"))?;
+    if msg.from_address != account.to_string() {
+        return Err(format!(
+            "The sender {} isn't the interchain account",
+            msg.from_address
+        ));
+    }
+    let target = Address::decode(&msg.to_address).map_err(|e| {
+        format!("Invalid target address {}: {e}", msg.to_address)
+    })?;
+    msg.amount
+        .into_iter()
+        .map(|coin| {
+            let token =
+                convert_to_address(&coin.denom).map_err(|e| e.to_string())?;
+            let amount = IbcAmount::from_str(&coin.amount)
+                .ok()
+                .and_then(|amount| Amount::try_from(amount).ok())
+                .ok_or_else(|| format!("Invalid amount: {}", coin.amount))?;
+            Ok(IcaTransfer {
+                target: target.clone(),
+                token,
+                amount,
+            })
+        })
+        .collect()
+}
+
+fn ica_error(error: impl ToString) -> ChannelError {
+    ChannelError::AppModule {
+        description: error.to_string(),
+    }
+}
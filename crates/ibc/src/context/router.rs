@@ -33,6 +33,15 @@ impl<'a> IbcRouter<'a> {
         self.modules.insert(module_id.clone(), Rc::new(module));
         self.ports.insert(port_id, module_id);
     }
+
+    /// Get the mutable module bound to the port
+    pub fn module_mut(
+        &mut self,
+        port_id: &PortId,
+    ) -> Option<&mut (dyn ModuleWrapper + 'a)> {
+        let module_id = self.lookup_module(port_id)?;
+        self.modules.get_mut(&module_id).and_then(Rc::get_mut)
+    }
 }
 
 impl<'a> Router for IbcRouter<'a> {
@@ -51,6 +60,11 @@ impl<'a> Router for IbcRouter<'a> {
     }
 
     fn lookup_module(&self, port_id: &PortId) -> Option<ModuleId> {
-        self.ports.get(port_id).cloned()
+        self.ports.get(port_id).cloned().or_else(|| {
+            self.modules
+                .iter()
+                .find(|(_, module)| module.is_bound_port(port_id))
+                .map(|(module_id, _)| module_id.clone())
+        })
     }
 }
@@ -3,6 +3,7 @@
 use std::cell::RefCell;
 use std::collections::BTreeSet;
 use std::rc::Rc;
+use std::str::FromStr;
 
 use ibc::apps::transfer::context::{
     TokenTransferExecutionContext, TokenTransferValidationContext,
@@ -110,42 +111,98 @@ where
             .map_err(TokenTransferError::from)
     }
 
-    /// Add the amount to the per-epoch withdraw of the token
+    /// Add the amount to the per-epoch deposit of the token and to the
+    /// per-epoch deposit of the token on the channel
     fn add_deposit(
         &self,
+        channel_id: &ChannelId,
         token: &Address,
         amount: Amount,
     ) -> Result<(), TokenTransferError> {
-        let deposit = self.inner.borrow().deposit(token)?;
-        let added_deposit = deposit.checked_add(amount).ok_or_else(|| {
+        let overflow = || {
             TokenTransferError::Other(
                 "The per-epoch deposit overflowed".to_string(),
             )
-        })?;
+        };
+        let deposit = self.inner.borrow().deposit(token)?;
+        let added_deposit = deposit.checked_add(amount).ok_or_else(overflow)?;
         self.inner
             .borrow_mut()
-            .store_deposit(token, added_deposit)
+            .store_deposit(token, added_deposit)?;
+
+        let deposit = self.inner.borrow().channel_deposit(channel_id, token)?;
+        let added_deposit = deposit.checked_add(amount).ok_or_else(overflow)?;
+        self.inner
+            .borrow_mut()
+            .store_channel_deposit(channel_id, token, added_deposit)
             .map_err(TokenTransferError::from)
     }
 
-    /// Add the amount to the per-epoch withdraw of the token
+    /// Add the amount to the per-epoch withdraw of the token, to the
+    /// per-epoch withdraw of the token on the channel and to the per-epoch
+    /// withdraw of the token by the sender. The tokens forwarded or refunded
+    /// by the IBC account are only limited per channel.
     fn add_withdraw(
         &self,
+        channel_id: &ChannelId,
+        sender: &Address,
         token: &Address,
         amount: Amount,
     ) -> Result<(), TokenTransferError> {
-        let withdraw = self.inner.borrow().withdraw(token)?;
-        let added_withdraw = withdraw.checked_add(amount).ok_or_else(|| {
+        let overflow = || {
             TokenTransferError::Other(
                 "The per-epoch withdraw overflowed".to_string(),
             )
-        })?;
+        };
+        let withdraw = self.inner.borrow().withdraw(token)?;
+        let added_withdraw =
+            withdraw.checked_add(amount).ok_or_else(overflow)?;
         self.inner
             .borrow_mut()
-            .store_withdraw(token, added_withdraw)
+            .store_withdraw(token, added_withdraw)?;
+
+        let withdraw =
+            self.inner.borrow().channel_withdraw(channel_id, token)?;
+        let added_withdraw =
+            withdraw.checked_add(amount).ok_or_else(overflow)?;
+        self.inner.borrow_mut().store_channel_withdraw(
+            channel_id,
+            token,
+            added_withdraw,
+        )?;
+
+        if *sender == IBC_ESCROW_ADDRESS {
+            return Ok(());
+        }
+        let withdraw = self.inner.borrow().account_withdraw(sender, token)?;
+        let added_withdraw =
+            withdraw.checked_add(amount).ok_or_else(overflow)?;
+        self.inner
+            .borrow_mut()
+            .store_account_withdraw(sender, token, added_withdraw)
             .map_err(TokenTransferError::from)
     }
 
+    /// Get the channel of our side from the outermost prefix of the trace
+    /// path. The trace path of a minted or burned token always starts with
+    /// the port and the channel on this chain.
+    fn get_channel_id(
+        &self,
+        coin: &PrefixedCoin,
+    ) -> Result<ChannelId, TokenTransferError> {
+        let trace_path = coin.denom.trace_path.to_string();
+        trace_path
+            .split('/')
+            .nth(1)
+            .and_then(|channel_id| ChannelId::from_str(channel_id).ok())
+            .ok_or_else(|| {
+                TokenTransferError::Other(format!(
+                    "No channel in the trace path: Denom {}",
+                    coin.denom
+                ))
+            })
+    }
+
     fn maybe_store_ibc_denom(
         &self,
         owner: &Address,
@@ -246,13 +303,13 @@ where
         &mut self,
         from_account: &Self::AccountId,
         _port_id: &PortId,
-        _channel_id: &ChannelId,
+        channel_id: &ChannelId,
         coin: &PrefixedCoin,
         _memo: &Memo,
     ) -> Result<(), TokenTransferError> {
         let (ibc_token, amount) = self.get_token_amount(coin)?;
 
-        self.add_withdraw(&ibc_token, amount)?;
+        self.add_withdraw(channel_id, from_account, &ibc_token, amount)?;
 
         // A transfer of NUT tokens must be verified by their VP
         if ibc_token.is_internal()
@@ -282,12 +339,12 @@ where
         &mut self,
         to_account: &Self::AccountId,
         _port_id: &PortId,
-        _channel_id: &ChannelId,
+        channel_id: &ChannelId,
         coin: &PrefixedCoin,
     ) -> Result<(), TokenTransferError> {
         let (ibc_token, amount) = self.get_token_amount(coin)?;
 
-        self.add_deposit(&ibc_token, amount)?;
+        self.add_deposit(channel_id, &ibc_token, amount)?;
 
         self.inner
             .borrow_mut()
@@ -303,8 +360,10 @@ where
         // The trace path of the denom is already updated if receiving the token
         let (ibc_token, amount) = self.get_token_amount(coin)?;
 
+        let channel_id = self.get_channel_id(coin)?;
+
         self.update_mint_amount(&ibc_token, amount, true)?;
-        self.add_deposit(&ibc_token, amount)?;
+        self.add_deposit(&channel_id, &ibc_token, amount)?;
 
         // A transfer of NUT tokens must be verified by their VP
         if ibc_token.is_internal()
@@ -330,9 +389,10 @@ where
         _memo: &Memo,
     ) -> Result<(), TokenTransferError> {
         let (ibc_token, amount) = self.get_token_amount(coin)?;
+        let channel_id = self.get_channel_id(coin)?;
 
         self.update_mint_amount(&ibc_token, amount, false)?;
-        self.add_withdraw(&ibc_token, amount)?;
+        self.add_withdraw(&channel_id, account, &ibc_token, amount)?;
 
         // A transfer of NUT tokens must be verified by their VP
         if ibc_token.is_internal()
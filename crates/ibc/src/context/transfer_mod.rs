@@ -19,7 +19,9 @@ use ibc::apps::transfer::module::{
 };
 use ibc::apps::transfer::types::error::TokenTransferError;
 use ibc::apps::transfer::types::MODULE_ID_STR;
-use ibc::core::channel::types::acknowledgement::Acknowledgement;
+use ibc::core::channel::types::acknowledgement::{
+    Acknowledgement, AcknowledgementStatus, StatusValue,
+};
 use ibc::core::channel::types::channel::{Counterparty, Order};
 use ibc::core::channel::types::error::{ChannelError, PacketError};
 use ibc::core::channel::types::packet::Packet;
@@ -32,6 +34,8 @@ use namada_core::address::Address;
 
 use super::common::IbcCommonContext;
 use super::token_transfer::TokenTransferContext;
+use crate::forward::forward_metadata;
+use crate::IBC_ESCROW_ADDRESS;
 
 /// IBC module wrapper for getting the reference of the module
 pub trait ModuleWrapper: Module {
@@ -46,6 +50,17 @@ pub trait ModuleWrapper: Module {
 
     /// Get the port ID
     fn port_id(&self) -> PortId;
+
+    /// Check if the port is bound to the module. A module can be bound to
+    /// ports with a prefix, e.g. the interchain account controller ports.
+    fn is_bound_port(&self, port_id: &PortId) -> bool {
+        *port_id == self.port_id()
+    }
+
+    /// Reject the forward of the next received packet, so that the packet is
+    /// acknowledged with the error instead of being received. Only the
+    /// transfer module forwards packets.
+    fn reject_forward(&mut self, _error: String) {}
 }
 
 /// IBC module for token transfer
@@ -56,6 +71,8 @@ where
 {
     /// IBC actions
     pub ctx: TokenTransferContext<C>,
+    /// The error of the forward of the next received packet
+    forward_error: Option<String>,
 }
 
 impl<C> TransferModule<C>
@@ -69,6 +86,7 @@ where
     ) -> Self {
         Self {
             ctx: TokenTransferContext::new(ctx, verifiers),
+            forward_error: None,
         }
     }
 }
@@ -92,6 +110,10 @@ where
     fn port_id(&self) -> PortId {
         self.ctx.get_port().expect("The port ID should be set")
     }
+
+    fn reject_forward(&mut self, error: String) {
+        self.forward_error = Some(error);
+    }
 }
 
 impl<C> Module for TransferModule<C>
@@ -277,7 +299,29 @@ where
         packet: &Packet,
         _relayer: &Signer,
     ) -> (ModuleExtras, Acknowledgement) {
-        on_recv_packet_execute(&mut self.ctx, packet)
+        match forward_metadata(packet) {
+            Some((mut packet_data, _)) => {
+                if let Some(error) = self.forward_error.take() {
+                    // The tokens cannot be forwarded, so they aren't received
+                    // and the sender is refunded on the acknowledgement
+                    let status = AcknowledgementStatus::error(
+                        StatusValue::new(format!(
+                            "Forwarding the packet failed: {error}"
+                        ))
+                        .expect("The error message shouldn't be empty"),
+                    );
+                    return (ModuleExtras::empty(), status.into());
+                }
+                // The tokens to be forwarded are received by the IBC account
+                // and then sent to the next chain by `IbcActions`
+                packet_data.receiver = IBC_ESCROW_ADDRESS.to_string().into();
+                let mut packet = packet.clone();
+                packet.data = serde_json::to_vec(&packet_data)
+                    .expect("Encoding the packet data shouldn't fail");
+                on_recv_packet_execute(&mut self.ctx, &packet)
+            }
+            None => on_recv_packet_execute(&mut self.ctx, packet),
+        }
     }
 
     fn on_acknowledgement_packet_validate(
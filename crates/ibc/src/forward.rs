@@ -0,0 +1,273 @@
+//! IBC packet forwarding
+//!
+//! A received ICS-20 packet can carry a forward instruction in its memo, e.g.
+//! `{"forward": {"receiver": "cosmos1...", "port": "transfer", "channel":
+//! "channel-1"}}`. The tokens of such a packet aren't credited to the receiver
+//! of the packet on this chain. They are received by the IBC account and are
+//! immediately sent on the given channel to the given receiver, so that this
+//! chain can be used as a hop between two other chains.
+//!
+//! The received packet is acknowledged as soon as the forwarded packet has
+//! been sent. When the forwarded packet cannot be sent, the received packet is
+//! acknowledged with an error and the tokens aren't received. When the
+//! forwarded packet fails on the next chain or times out, the refunded tokens
+//! are sent back to the original sender through the channel they came from.
+//! The forwards waiting for an acknowledgement are kept in storage until then.
+//! If the refund cannot be sent either, the tokens remain in the IBC account
+//! and the forward is kept in storage as stuck.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::apps::transfer::types::packet::PacketData;
+use crate::apps::transfer::types::{
+    Amount as IbcAmount, PrefixedCoin, PrefixedDenom, TracePrefix,
+    PORT_ID_STR as FT_PORT_ID_STR,
+};
+use crate::core::channel::types::packet::Packet;
+use crate::core::host::types::identifiers::{ChannelId, PortId};
+
+/// The timeout of a forwarded packet when the instruction doesn't set it
+pub const DEFAULT_FORWARD_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// The memo of a packet to be forwarded, as it is encoded in JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PacketMetadata {
+    forward: RawForwardMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RawForwardMetadata {
+    receiver: String,
+    port: String,
+    channel: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timeout: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    next: Option<serde_json::Value>,
+}
+
+/// An instruction to forward the tokens of a received packet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForwardMetadata {
+    /// The receiver on the next chain
+    pub receiver: String,
+    /// The port on this chain to send the tokens through
+    pub port_id: PortId,
+    /// The channel on this chain to send the tokens through
+    pub channel_id: ChannelId,
+    /// The timeout of the forwarded packet from the time it is sent
+    pub timeout: Duration,
+    /// The memo of the forwarded packet, which can hold the instruction for
+    /// the next hop
+    pub next: Option<String>,
+}
+
+impl ForwardMetadata {
+    /// Parse the forward instruction from the memo of a packet. Returns
+    /// `None` if the memo doesn't hold a valid instruction.
+    pub fn from_memo(memo: impl AsRef<str>) -> Option<Self> {
+        let metadata: PacketMetadata =
+            serde_json::from_str(memo.as_ref()).ok()?;
+        let RawForwardMetadata {
+            receiver,
+            port,
+            channel,
+            timeout,
+            next,
+        } = metadata.forward;
+        let timeout = match timeout {
+            Some(timeout) => parse_timeout(&timeout)?,
+            None => DEFAULT_FORWARD_TIMEOUT,
+        };
+        let next = match next {
+            // The next memo can be given as a JSON string or as a JSON object
+            Some(serde_json::Value::String(next)) => Some(next),
+            Some(next) => Some(next.to_string()),
+            None => None,
+        };
+        Some(Self {
+            receiver,
+            port_id: PortId::from_str(&port).ok()?,
+            channel_id: ChannelId::from_str(&channel).ok()?,
+            timeout,
+            next,
+        })
+    }
+
+    /// Encode the forward instruction as a packet memo
+    pub fn to_memo(&self) -> String {
+        let next = self.next.as_ref().map(|next| {
+            serde_json::from_str(next)
+                .unwrap_or_else(|_| serde_json::Value::String(next.clone()))
+        });
+        let metadata = PacketMetadata {
+            forward: RawForwardMetadata {
+                receiver: self.receiver.clone(),
+                port: self.port_id.to_string(),
+                channel: self.channel_id.to_string(),
+                timeout: Some(serde_json::Value::String(format!(
+                    "{}s",
+                    self.timeout.as_secs()
+                ))),
+                next,
+            },
+        };
+        serde_json::to_string(&metadata)
+            .expect("Encoding the packet metadata shouldn't fail")
+    }
+}
+
+/// Parse the timeout given either as a number of nanoseconds or as a string
+/// with a unit, e.g. `"10m"`
+fn parse_timeout(timeout: &serde_json::Value) -> Option<Duration> {
+    match timeout {
+        serde_json::Value::Number(nanos) => {
+            nanos.as_u64().map(Duration::from_nanos)
+        }
+        serde_json::Value::String(timeout) => {
+            let split = timeout.find(|c: char| !c.is_ascii_digit())?;
+            let (value, unit) = timeout.split_at(split);
+            let value: u64 = value.parse().ok()?;
+            let secs = match unit {
+                "ns" => return Some(Duration::from_nanos(value)),
+                "ms" => return Some(Duration::from_millis(value)),
+                "s" => value,
+                "m" => value.checked_mul(60)?,
+                "h" => value.checked_mul(3600)?,
+                _ => return None,
+            };
+            Some(Duration::from_secs(secs))
+        }
+        _ => None,
+    }
+}
+
+/// Get the packet data and the forward instruction of the received packet if
+/// it should be forwarded
+pub fn forward_metadata(
+    packet: &Packet,
+) -> Option<(PacketData, ForwardMetadata)> {
+    if packet.port_id_on_b.as_str() != FT_PORT_ID_STR {
+        return None;
+    }
+    let packet_data =
+        serde_json::from_slice::<PacketData>(&packet.data).ok()?;
+    let forward = ForwardMetadata::from_memo(packet_data.memo.as_ref())?;
+    Some((packet_data, forward))
+}
+
+/// Get the coin on this chain of the tokens received with the packet
+pub fn received_coin(
+    packet: &Packet,
+    packet_data: &PacketData,
+) -> PrefixedCoin {
+    let mut denom = packet_data.token.denom.clone();
+    let source_prefix = TracePrefix::new(
+        packet.port_id_on_a.clone(),
+        packet.chan_id_on_a.clone(),
+    );
+    if denom.trace_path.starts_with(&source_prefix) {
+        // The token returns to this chain
+        denom.remove_trace_prefix(&source_prefix);
+    } else {
+        denom.add_trace_prefix(TracePrefix::new(
+            packet.port_id_on_b.clone(),
+            packet.chan_id_on_b.clone(),
+        ));
+    }
+    PrefixedCoin {
+        denom,
+        amount: packet_data.token.amount,
+    }
+}
+
+/// A forwarded packet waiting for its acknowledgement. It holds what is
+/// needed to send the tokens back to the original sender on failure.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct InFlightForward {
+    /// The port on this chain the tokens were received through
+    pub src_port_id: String,
+    /// The channel on this chain the tokens were received through
+    pub src_channel_id: String,
+    /// The original sender on the source chain
+    pub sender: String,
+    /// The denom of the tokens on this chain
+    pub denom: String,
+    /// The amount of the tokens
+    pub amount: String,
+}
+
+impl InFlightForward {
+    /// Make a new in-flight forward of the received packet
+    pub fn new(
+        packet: &Packet,
+        packet_data: &PacketData,
+        coin: &PrefixedCoin,
+    ) -> Self {
+        Self {
+            src_port_id: packet.port_id_on_b.to_string(),
+            src_channel_id: packet.chan_id_on_b.to_string(),
+            sender: packet_data.sender.to_string(),
+            denom: coin.denom.to_string(),
+            amount: coin.amount.to_string(),
+        }
+    }
+
+    /// The port and the channel to send the refund through
+    pub fn refund_channel(&self) -> Option<(PortId, ChannelId)> {
+        Some((
+            PortId::from_str(&self.src_port_id).ok()?,
+            ChannelId::from_str(&self.src_channel_id).ok()?,
+        ))
+    }
+
+    /// The coin to be refunded
+    pub fn refund_coin(&self) -> Option<PrefixedCoin> {
+        Some(PrefixedCoin {
+            denom: PrefixedDenom::from_str(&self.denom).ok()?,
+            amount: IbcAmount::from_str(&self.amount).ok()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forward_memo() {
+        let memo = r#"{"forward":{"receiver":"cosmos1receiver","port":"transfer","channel":"channel-1"}}"#;
+        let forward = ForwardMetadata::from_memo(memo).unwrap();
+        assert_eq!(forward.receiver, "cosmos1receiver");
+        assert_eq!(forward.port_id, PortId::transfer());
+        assert_eq!(forward.channel_id, ChannelId::new(1));
+        assert_eq!(forward.timeout, DEFAULT_FORWARD_TIMEOUT);
+        assert_eq!(forward.next, None);
+        assert_eq!(
+            ForwardMetadata::from_memo(forward.to_memo()),
+            Some(forward)
+        );
+
+        // The timeout can be given in nanoseconds or with a unit and the
+        // next hop is kept as the memo of the forwarded packet
+        let memo = r#"{"forward":{"receiver":"a","port":"transfer","channel":"channel-1","timeout":"1h","next":{"forward":{"receiver":"b","port":"transfer","channel":"channel-2","timeout":1000}}}}"#;
+        let forward = ForwardMetadata::from_memo(memo).unwrap();
+        assert_eq!(forward.timeout, Duration::from_secs(3600));
+        let next = ForwardMetadata::from_memo(forward.next.unwrap()).unwrap();
+        assert_eq!(next.receiver, "b");
+        assert_eq!(next.channel_id, ChannelId::new(2));
+        assert_eq!(next.timeout, Duration::from_nanos(1000));
+
+        // Other memos aren't forward instructions
+        assert_eq!(ForwardMetadata::from_memo(""), None);
+        assert_eq!(ForwardMetadata::from_memo("memo"), None);
+        let memo = r#"{"forward":{"receiver":"a","port":"transfer","channel":"invalid channel"}}"#;
+        assert_eq!(ForwardMetadata::from_memo(memo), None);
+        let memo = r#"{"forward":{"receiver":"a","port":"transfer","channel":"channel-1","timeout":"1y"}}"#;
+        assert_eq!(ForwardMetadata::from_memo(memo), None);
+    }
+}
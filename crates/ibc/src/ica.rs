@@ -0,0 +1,437 @@
+//! IBC interchain accounts (ICS-27)
+//!
+//! A Namada account can control an account on another chain through the
+//! controller port `icacontroller-<owner>`. The channel is opened with
+//! [`MsgRegisterInterchainAccount`] and the transactions to be executed on the
+//! other chain are sent with [`MsgSendTx`]. Both have to be authorized by the
+//! owner.
+//!
+//! An account on another chain can control an interchain account hosted on
+//! Namada through the host port `icahost`. The hosted account is an internal
+//! address derived from the connection and the controller port. It can only
+//! be changed by the messages in the packets received on its channel, which
+//! are validated by the IBC VP.
+
+use std::str::FromStr;
+
+use data_encoding::BASE64;
+use namada_core::address::{Address, InternalAddress};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::core::channel::types::Version;
+use crate::core::host::types::identifiers::{ConnectionId, PortId};
+use crate::primitives::proto::Any;
+use crate::trace::calc_ibc_token_hash;
+
+/// Module ID of interchain accounts
+pub const MODULE_ID_STR: &str = "interchainaccounts";
+/// Port ID of the host
+pub const HOST_PORT_ID_STR: &str = "icahost";
+/// Prefix of the controller port IDs
+pub const CONTROLLER_PORT_PREFIX: &str = "icacontroller-";
+/// Version of interchain accounts
+pub const VERSION: &str = "ics27-1";
+/// The encoding of the messages in a packet
+pub const ENCODING_PROTO3: &str = "proto3";
+/// The type of the transactions in a packet
+pub const TX_TYPE_SDK_MULTI_MSG: &str = "sdk_multi_msg";
+/// The packet data type to execute a transaction
+pub const TYPE_EXECUTE_TX: i32 = 1;
+
+/// Type URL of [`MsgRegisterInterchainAccount`]
+pub const MSG_REGISTER_TYPE_URL: &str = "/ibc.applications.\
+                                         interchain_accounts.controller.v1.\
+                                         MsgRegisterInterchainAccount";
+/// Type URL of [`MsgSendTx`]
+pub const MSG_SEND_TX_TYPE_URL: &str =
+    "/ibc.applications.interchain_accounts.controller.v1.MsgSendTx";
+/// Type URL of [`MsgSend`]
+pub const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+/// Type URL of the response of [`MsgSend`]
+pub const MSG_SEND_RESPONSE_TYPE_URL: &str =
+    "/cosmos.bank.v1beta1.MsgSendResponse";
+
+/// The channel version of interchain accounts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    /// Version of interchain accounts
+    pub version: String,
+    /// Connection ID on the controller chain
+    pub controller_connection_id: String,
+    /// Connection ID on the host chain
+    pub host_connection_id: String,
+    /// Address of the interchain account on the host chain. It is set by the
+    /// host chain.
+    #[serde(default)]
+    pub address: String,
+    /// Encoding of the messages
+    pub encoding: String,
+    /// Type of the transactions
+    pub tx_type: String,
+}
+
+impl Metadata {
+    /// Make a new metadata for the given connections
+    pub fn new(
+        controller_connection_id: &ConnectionId,
+        host_connection_id: &ConnectionId,
+    ) -> Self {
+        Self {
+            version: VERSION.to_string(),
+            controller_connection_id: controller_connection_id.to_string(),
+            host_connection_id: host_connection_id.to_string(),
+            address: String::new(),
+            encoding: ENCODING_PROTO3.to_string(),
+            tx_type: TX_TYPE_SDK_MULTI_MSG.to_string(),
+        }
+    }
+
+    /// Parse the metadata from the channel version
+    pub fn from_version(version: &Version) -> Result<Self, String> {
+        let metadata: Self = serde_json::from_str(version.as_str())
+            .map_err(|e| format!("Invalid ICS-27 metadata: {e}"))?;
+        if metadata.version != VERSION {
+            return Err(format!(
+                "Unsupported ICS-27 version: {}",
+                metadata.version
+            ));
+        }
+        if metadata.encoding != ENCODING_PROTO3 {
+            return Err(format!(
+                "Unsupported ICS-27 encoding: {}",
+                metadata.encoding
+            ));
+        }
+        if metadata.tx_type != TX_TYPE_SDK_MULTI_MSG {
+            return Err(format!(
+                "Unsupported ICS-27 transaction type: {}",
+                metadata.tx_type
+            ));
+        }
+        Ok(metadata)
+    }
+
+    /// Encode the metadata as the channel version
+    pub fn to_version(&self) -> Version {
+        Version::new(
+            serde_json::to_string(self)
+                .expect("Encoding the metadata shouldn't fail"),
+        )
+    }
+}
+
+/// The packet data of interchain accounts
+#[derive(Clone, PartialEq, Message)]
+pub struct InterchainAccountPacketData {
+    /// The type of the packet data
+    #[prost(int32, tag = "1")]
+    pub r#type: i32,
+    /// The encoded [`CosmosTx`]
+    #[prost(bytes = "vec", tag = "2")]
+    pub data: Vec<u8>,
+    /// Memo
+    #[prost(string, tag = "3")]
+    pub memo: String,
+}
+
+/// The packet data as it is encoded in JSON in a packet
+#[derive(Serialize, Deserialize)]
+struct JsonPacketData {
+    #[serde(rename = "type")]
+    packet_type: String,
+    #[serde(default)]
+    data: String,
+    #[serde(default)]
+    memo: String,
+}
+
+impl InterchainAccountPacketData {
+    /// Make the packet data to execute the given messages
+    pub fn execute_tx(messages: Vec<Any>, memo: impl Into<String>) -> Self {
+        Self {
+            r#type: TYPE_EXECUTE_TX,
+            data: CosmosTx { messages }.encode_to_vec(),
+            memo: memo.into(),
+        }
+    }
+
+    /// Decode the messages to be executed
+    pub fn messages(&self) -> Result<Vec<Any>, String> {
+        if self.r#type != TYPE_EXECUTE_TX {
+            return Err(format!(
+                "Unsupported ICS-27 packet data type: {}",
+                self.r#type
+            ));
+        }
+        CosmosTx::decode(self.data.as_slice())
+            .map(|tx| tx.messages)
+            .map_err(|e| format!("Invalid ICS-27 transaction: {e}"))
+    }
+
+    /// Encode the packet data in JSON to be sent in a packet
+    pub fn to_json(&self) -> Vec<u8> {
+        let packet_type = match self.r#type {
+            TYPE_EXECUTE_TX => "TYPE_EXECUTE_TX".to_string(),
+            _ => "TYPE_UNSPECIFIED".to_string(),
+        };
+        let data = JsonPacketData {
+            packet_type,
+            data: BASE64.encode(&self.data),
+            memo: self.memo.clone(),
+        };
+        serde_json::to_vec(&data)
+            .expect("Encoding the packet data shouldn't fail")
+    }
+
+    /// Decode the packet data in JSON of a packet
+    pub fn from_json(bytes: &[u8]) -> Result<Self, String> {
+        let data: JsonPacketData = serde_json::from_slice(bytes)
+            .map_err(|e| format!("Invalid ICS-27 packet data: {e}"))?;
+        let r#type = match data.packet_type.as_str() {
+            "TYPE_EXECUTE_TX" => TYPE_EXECUTE_TX,
+            _ => 0,
+        };
+        let bytes = BASE64
+            .decode(data.data.as_bytes())
+            .map_err(|e| format!("Invalid ICS-27 packet data: {e}"))?;
+        Ok(Self {
+            r#type,
+            data: bytes,
+            memo: data.memo,
+        })
+    }
+}
+
+/// The messages to be executed on the host chain
+#[derive(Clone, PartialEq, Message)]
+pub struct CosmosTx {
+    /// Messages
+    #[prost(message, repeated, tag = "1")]
+    pub messages: Vec<Any>,
+}
+
+/// The results of the executed messages, which are set to the
+/// acknowledgement
+#[derive(Clone, PartialEq, Message)]
+pub struct TxMsgData {
+    /// Deprecated message results
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub data: Vec<Vec<u8>>,
+    /// The responses of the messages
+    #[prost(message, repeated, tag = "2")]
+    pub msg_responses: Vec<Any>,
+}
+
+/// Message to open a channel for an interchain account on the controller
+/// chain
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgRegisterInterchainAccount {
+    /// The owner of the interchain account
+    #[prost(string, tag = "1")]
+    pub owner: String,
+    /// The connection to the host chain
+    #[prost(string, tag = "2")]
+    pub connection_id: String,
+    /// The channel version. The default metadata is used when it is empty.
+    #[prost(string, tag = "3")]
+    pub version: String,
+    /// The channel ordering. Only ordered channels are supported.
+    #[prost(int32, tag = "4")]
+    pub ordering: i32,
+}
+
+/// Message to send a transaction to be executed by the interchain account on
+/// the host chain
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgSendTx {
+    /// The owner of the interchain account
+    #[prost(string, tag = "1")]
+    pub owner: String,
+    /// The connection to the host chain
+    #[prost(string, tag = "2")]
+    pub connection_id: String,
+    /// The packet data
+    #[prost(message, optional, tag = "3")]
+    pub packet_data: Option<InterchainAccountPacketData>,
+    /// The timeout in nanoseconds from the current block time
+    #[prost(uint64, tag = "4")]
+    pub relative_timeout: u64,
+}
+
+impl MsgSendTx {
+    /// Get the controller port and the connection of the interchain account
+    pub fn controller(&self) -> Option<(PortId, ConnectionId)> {
+        let owner = Address::decode(&self.owner).ok()?;
+        let connection_id = ConnectionId::from_str(&self.connection_id).ok()?;
+        Some((controller_port_id(&owner), connection_id))
+    }
+}
+
+/// Bank message to transfer tokens from the interchain account on the host
+/// chain
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgSend {
+    /// The source address
+    #[prost(string, tag = "1")]
+    pub from_address: String,
+    /// The target address
+    #[prost(string, tag = "2")]
+    pub to_address: String,
+    /// The tokens to be transferred
+    #[prost(message, repeated, tag = "3")]
+    pub amount: Vec<Coin>,
+}
+
+/// Coin of a bank message
+#[derive(Clone, PartialEq, Message)]
+pub struct Coin {
+    /// The denomination, which is a token address or an IBC denom on Namada
+    #[prost(string, tag = "1")]
+    pub denom: String,
+    /// The amount
+    #[prost(string, tag = "2")]
+    pub amount: String,
+}
+
+macro_rules! impl_any_conversion {
+    ($msg:ty, $type_url:expr) => {
+        impl From<$msg> for Any {
+            fn from(msg: $msg) -> Self {
+                Any {
+                    type_url: $type_url.to_string(),
+                    value: msg.encode_to_vec(),
+                }
+            }
+        }
+
+        impl TryFrom<Any> for $msg {
+            type Error = String;
+
+            fn try_from(any: Any) -> Result<Self, Self::Error> {
+                if any.type_url != $type_url {
+                    return Err(format!(
+                        "Unexpected type URL: {}",
+                        any.type_url
+                    ));
+                }
+                <$msg>::decode(any.value.as_slice()).map_err(|e| e.to_string())
+            }
+        }
+    };
+}
+
+impl_any_conversion!(MsgRegisterInterchainAccount, MSG_REGISTER_TYPE_URL);
+impl_any_conversion!(MsgSendTx, MSG_SEND_TX_TYPE_URL);
+impl_any_conversion!(MsgSend, MSG_SEND_TYPE_URL);
+
+/// Get the host port ID
+pub fn host_port_id() -> PortId {
+    PortId::from_str(HOST_PORT_ID_STR).expect("The port ID should be valid")
+}
+
+/// Get the controller port ID of the owner
+pub fn controller_port_id(owner: &Address) -> PortId {
+    PortId::from_str(&format!("{CONTROLLER_PORT_PREFIX}{owner}"))
+        .expect("The port ID should be valid")
+}
+
+/// Get the owner of the controller port. Returns `None` if the port isn't a
+/// controller port.
+pub fn controller_owner(port_id: &PortId) -> Option<Address> {
+    let owner = port_id.as_str().strip_prefix(CONTROLLER_PORT_PREFIX)?;
+    Address::decode(owner).ok()
+}
+
+/// Check if the port is a controller port
+pub fn is_controller_port(port_id: &PortId) -> bool {
+    port_id.as_str().starts_with(CONTROLLER_PORT_PREFIX)
+}
+
+/// Get the address of the interchain account hosted on this chain, which is
+/// controlled through the given connection and controller port
+pub fn host_account(
+    connection_id: &ConnectionId,
+    controller_port_id: &PortId,
+) -> Address {
+    let hash = calc_ibc_token_hash(format!(
+        "{HOST_PORT_ID_STR}/{connection_id}/{controller_port_id}"
+    ));
+    Address::Internal(InternalAddress::IcaHost(hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address::testing::established_address_1;
+
+    use super::*;
+
+    #[test]
+    fn test_ica_metadata() {
+        let conn_a = ConnectionId::new(0);
+        let conn_b = ConnectionId::new(1);
+        let metadata = Metadata::new(&conn_a, &conn_b);
+        let version = metadata.to_version();
+        assert_eq!(Metadata::from_version(&version).unwrap(), metadata);
+
+        let version = Version::new(
+            r#"{"version":"ics27-1","controller_connection_id":"connection-0","host_connection_id":"connection-1","address":"cosmos1abc","encoding":"proto3","tx_type":"sdk_multi_msg"}"#.to_string(),
+        );
+        let metadata = Metadata::from_version(&version).unwrap();
+        assert_eq!(metadata.address, "cosmos1abc");
+
+        let version = Version::new(
+            r#"{"version":"ics27-1","controller_connection_id":"connection-0","host_connection_id":"connection-1","encoding":"proto3json","tx_type":"sdk_multi_msg"}"#.to_string(),
+        );
+        assert!(Metadata::from_version(&version).is_err());
+        assert!(
+            Metadata::from_version(&Version::new("ics20-1".to_string()))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_ica_packet_data() {
+        let msg = MsgSend {
+            from_address: "from".to_string(),
+            to_address: "to".to_string(),
+            amount: vec![Coin {
+                denom: "uatom".to_string(),
+                amount: "100".to_string(),
+            }],
+        };
+        let packet_data = InterchainAccountPacketData::execute_tx(
+            vec![msg.clone().into()],
+            "memo",
+        );
+        let json = packet_data.to_json();
+        let decoded = InterchainAccountPacketData::from_json(&json).unwrap();
+        assert_eq!(decoded, packet_data);
+        let messages = decoded.messages().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(MsgSend::try_from(messages[0].clone()).unwrap(), msg);
+        assert!(MsgSendTx::try_from(messages[0].clone()).is_err());
+
+        let json = br#"{"type":"TYPE_UNSPECIFIED","data":"","memo":""}"#;
+        let packet_data = InterchainAccountPacketData::from_json(json).unwrap();
+        assert!(packet_data.messages().is_err());
+    }
+
+    #[test]
+    fn test_ica_ports() {
+        let owner = established_address_1();
+        let port_id = controller_port_id(&owner);
+        assert!(is_controller_port(&port_id));
+        assert_eq!(controller_owner(&port_id), Some(owner));
+        assert_eq!(controller_owner(&host_port_id()), None);
+
+        let conn = ConnectionId::new(0);
+        let account = host_account(&conn, &port_id);
+        assert!(matches!(
+            account,
+            Address::Internal(InternalAddress::IcaHost(_))
+        ));
+        assert_ne!(account, host_account(&ConnectionId::new(1), &port_id));
+        assert_eq!(Address::decode(account.encode()).unwrap(), account);
+    }
+}
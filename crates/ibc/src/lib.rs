@@ -20,6 +20,8 @@
 mod actions;
 pub mod context;
 pub mod event;
+pub mod forward;
+pub mod ica;
 mod msg;
 mod nft;
 pub mod parameters;
@@ -33,12 +35,14 @@ use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::rc::Rc;
 use std::str::FromStr;
+use std::time::Duration;
 
 pub use actions::transfer_over_ibc;
 use apps::transfer::types::packet::PacketData;
 use apps::transfer::types::PORT_ID_STR;
 use borsh::BorshDeserialize;
 pub use context::common::IbcCommonContext;
+pub use context::ica_mod::InterchainAccountModule;
 pub use context::nft_transfer::NftTransferContext;
 pub use context::nft_transfer_mod::NftTransferModule;
 use context::router::IbcRouter;
@@ -47,6 +51,7 @@ pub use context::token_transfer::TokenTransferContext;
 pub use context::transfer_mod::{ModuleWrapper, TransferModule};
 use context::IbcContext;
 pub use context::ValidationParams;
+use forward::{ForwardMetadata, InFlightForward};
 use ibc::apps::nft_transfer::handler::{
     send_nft_transfer_execute, send_nft_transfer_validate,
 };
@@ -63,23 +68,32 @@ use ibc::apps::transfer::handler::{
 use ibc::apps::transfer::types::error::TokenTransferError;
 use ibc::apps::transfer::types::msgs::transfer::MsgTransfer as IbcMsgTransfer;
 use ibc::apps::transfer::types::{
-    is_receiver_chain_source, TracePrefix, PORT_ID_STR as FT_PORT_ID_STR,
+    is_receiver_chain_source, PrefixedCoin, TracePrefix,
+    PORT_ID_STR as FT_PORT_ID_STR,
 };
+use ibc::core::channel::handler::{send_packet_execute, send_packet_validate};
 use ibc::core::channel::types::acknowledgement::AcknowledgementStatus;
+use ibc::core::channel::types::channel::Order;
 use ibc::core::channel::types::commitment::compute_ack_commitment;
 use ibc::core::channel::types::msgs::{
-    MsgRecvPacket as IbcMsgRecvPacket, PacketMsg,
+    ChannelMsg, MsgChannelOpenInit, MsgRecvPacket as IbcMsgRecvPacket,
+    PacketMsg,
 };
+use ibc::core::channel::types::packet::Packet;
 use ibc::core::channel::types::timeout::{TimeoutHeight, TimeoutTimestamp};
+use ibc::core::channel::types::Version;
 use ibc::core::entrypoint::{execute, validate};
 use ibc::core::handler::types::error::ContextError;
 use ibc::core::handler::types::events::Error as RawIbcEventError;
 use ibc::core::handler::types::msgs::MsgEnvelope;
 use ibc::core::host::types::error::IdentifierError;
-use ibc::core::host::types::identifiers::{ChannelId, PortId, Sequence};
+use ibc::core::host::types::identifiers::{
+    ChannelId, ConnectionId, PortId, Sequence,
+};
 use ibc::core::router::types::error::RouterError;
 use ibc::primitives::proto::Any;
 pub use ibc::*;
+use ica::{MsgRegisterInterchainAccount, MsgSendTx};
 use masp_primitives::transaction::Transaction as MaspTransaction;
 pub use msg::*;
 use namada_core::address::{self, Address};
@@ -109,7 +123,8 @@ use trace::{
 };
 
 use crate::storage::{
-    channel_counter_key, client_counter_key, connection_counter_key,
+    account_withdraw_prefix, channel_counter_key, channel_deposit_prefix,
+    channel_withdraw_prefix, client_counter_key, connection_counter_key,
     deposit_prefix, nft_class_key, nft_metadata_key, withdraw_prefix,
 };
 
@@ -260,7 +275,10 @@ where
             .into_storage_result()
             .ok();
         match msg {
-            None => {}
+            // Interchain account messages don't transfer tokens
+            None
+            | Some(IbcMessage::IcaRegister(_))
+            | Some(IbcMessage::IcaSendTx(_)) => {}
             // This event is emitted on the sender
             Some(IbcMessage::Transfer(msg)) => {
                 // Get the packet commitment from post-storage that corresponds
@@ -315,7 +333,9 @@ where
                             amount,
                             keys_changed,
                         )?;
-                    } else {
+                    } else if msg.packet.port_id_on_b.as_str()
+                        == NFT_PORT_ID_STR
+                    {
                         let packet_data =
                             serde_json::from_slice::<NftPacketData>(
                                 &msg.packet.data,
@@ -684,9 +704,13 @@ where
                     );
                     self.insert_verifiers()?;
                 }
+                let forward = self.check_forward(&envelope)?;
                 execute(&mut self.ctx, &mut self.router, *envelope.clone())
                     .map_err(|e| Error::Context(Box::new(e)))?;
 
+                // Forward the received tokens or refund a failed forward
+                self.forward_packet(&envelope, forward)?;
+
                 // Extract MASP tx from the memo in the packet if needed
                 let masp_tx = match &*envelope {
                     MsgEnvelope::Packet(PacketMsg::Recv(msg))
@@ -705,9 +729,375 @@ where
                 };
                 Ok((None, masp_tx))
             }
+            IbcMessage::IcaRegister(msg) => {
+                let (owner, envelope) = ica_register_envelope(&msg)?;
+                // Add the owner to the set of verifiers
+                self.verifiers.borrow_mut().insert(owner);
+                self.insert_verifiers()?;
+                execute(&mut self.ctx, &mut self.router, envelope)
+                    .map_err(|e| Error::Context(Box::new(e)))?;
+                Ok((None, None))
+            }
+            IbcMessage::IcaSendTx(msg) => {
+                let (owner, packet) = self.ica_packet(&msg)?;
+                // Add the owner to the set of verifiers
+                self.verifiers.borrow_mut().insert(owner);
+                self.insert_verifiers()?;
+                send_packet_execute(&mut self.ctx, packet)
+                    .map_err(|e| Error::Context(Box::new(e)))?;
+                Ok((None, None))
+            }
+        }
+    }
+
+    /// Make the packet to send the transaction to be executed by the
+    /// interchain account on the host chain
+    fn ica_packet(&self, msg: &MsgSendTx) -> Result<(Address, Packet), Error> {
+        let owner = ica_owner(&msg.owner)?;
+        let (port_id, connection_id) = msg.controller().ok_or_else(|| {
+            Error::Other(format!("Invalid connection: {}", msg.connection_id))
+        })?;
+        let packet_data = msg.packet_data.as_ref().ok_or_else(|| {
+            Error::Other("No packet data of the interchain account".to_string())
+        })?;
+        if msg.relative_timeout == 0 {
+            return Err(Error::Other(
+                "The relative timeout should be positive".to_string(),
+            ));
+        }
+
+        let ctx = self.ctx.inner.borrow();
+        let chan_id_on_a = ctx
+            .ica_active_channel(&port_id, &connection_id)
+            .map_err(|e| Error::Context(Box::new(e)))?
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "No active channel of the interchain account: Port \
+                     {port_id}, Connection {connection_id}"
+                ))
+            })?;
+        let channel = ctx
+            .channel_end(&port_id, &chan_id_on_a)
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        let chan_id_on_b = channel
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "No counterparty channel of {chan_id_on_a}"
+                ))
+            })?;
+        let seq_on_a = ctx
+            .get_next_sequence_send(&port_id, &chan_id_on_a)
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        let now = ctx
+            .host_timestamp()
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        let timeout_timestamp = (now
+            + Duration::from_nanos(msg.relative_timeout))
+        .map_err(|e| Error::Other(e.to_string()))?;
+        let packet = Packet {
+            seq_on_a,
+            port_id_on_a: port_id,
+            chan_id_on_a,
+            port_id_on_b: channel.counterparty().port_id().clone(),
+            chan_id_on_b,
+            data: packet_data.to_json(),
+            timeout_height_on_b: TimeoutHeight::Never,
+            timeout_timestamp_on_b: TimeoutTimestamp::At(timeout_timestamp),
+        };
+        Ok((owner, packet))
+    }
+
+    /// Check that the tokens of a newly received packet with a forward
+    /// instruction can be sent to the next chain, and return the instruction.
+    /// When they cannot, the transfer module acknowledges the packet with an
+    /// error instead of receiving the tokens, so that the sender is refunded.
+    fn check_forward(
+        &mut self,
+        envelope: &MsgEnvelope,
+    ) -> Result<Option<(PacketData, ForwardMetadata)>, Error> {
+        let MsgEnvelope::Packet(PacketMsg::Recv(msg)) = envelope else {
+            return Ok(None);
+        };
+        let Some((packet_data, forward)) =
+            forward::forward_metadata(&msg.packet)
+        else {
+            return Ok(None);
+        };
+        // The tokens of a packet that has already been received aren't
+        // forwarded again
+        let ack_key = storage::ack_key(
+            &msg.packet.port_id_on_b,
+            &msg.packet.chan_id_on_b,
+            msg.packet.seq_on_a,
+        );
+        let is_received =
+            self.ctx
+                .inner
+                .borrow()
+                .storage()
+                .has_key(&ack_key)
+                .map_err(|e| Error::Context(Box::new(ContextError::from(e))))?;
+        if is_received {
+            return Ok(None);
+        }
+
+        let coin = forward::received_coin(&msg.packet, &packet_data);
+        let validation = self
+            .forward_message(
+                forward.port_id.clone(),
+                forward.channel_id.clone(),
+                coin,
+                forward.receiver.clone(),
+                forward.next.clone().unwrap_or_default(),
+                forward.timeout,
+            )
+            .and_then(|message| self.validate_send(message));
+        match validation {
+            Ok(()) => Ok(Some((packet_data, forward))),
+            Err(e) => {
+                self.ctx.inner.borrow().log_string(format!(
+                    "The received packet cannot be forwarded: {e}"
+                ));
+                if let Some(module) =
+                    self.router.module_mut(&msg.packet.port_id_on_b)
+                {
+                    module.reject_forward(e.to_string());
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// Send the tokens of a received packet with a forward instruction to the
+    /// next chain. When a forwarded packet is acknowledged with an error or
+    /// times out, send the refunded tokens back to the original sender.
+    fn forward_packet(
+        &mut self,
+        envelope: &MsgEnvelope,
+        forward: Option<(PacketData, ForwardMetadata)>,
+    ) -> Result<(), Error> {
+        match envelope {
+            MsgEnvelope::Packet(PacketMsg::Recv(msg)) => {
+                let Some((packet_data, forward)) = forward else {
+                    return Ok(());
+                };
+                if !self.is_receiving_success(msg)? {
+                    return Ok(());
+                }
+                let coin = forward::received_coin(&msg.packet, &packet_data);
+                let in_flight =
+                    InFlightForward::new(&msg.packet, &packet_data, &coin);
+                let sequence = self.send_from_ibc_account(
+                    forward.port_id.clone(),
+                    forward.channel_id.clone(),
+                    coin,
+                    forward.receiver,
+                    forward.next.unwrap_or_default(),
+                    forward.timeout,
+                )?;
+                self.ctx
+                    .inner
+                    .borrow_mut()
+                    .store_in_flight_forward(
+                        &forward.port_id,
+                        &forward.channel_id,
+                        sequence,
+                        in_flight,
+                    )
+                    .map_err(|e| Error::Context(Box::new(e)))
+            }
+            MsgEnvelope::Packet(PacketMsg::Ack(msg)) => {
+                let is_success =
+                    serde_json::from_slice::<AcknowledgementStatus>(
+                        msg.acknowledgement.as_ref(),
+                    )
+                    .map(|ack| ack.is_successful())
+                    .unwrap_or(false);
+                self.complete_forward(&msg.packet, is_success)
+            }
+            MsgEnvelope::Packet(PacketMsg::Timeout(msg)) => {
+                self.complete_forward(&msg.packet, false)
+            }
+            MsgEnvelope::Packet(PacketMsg::TimeoutOnClose(msg)) => {
+                self.complete_forward(&msg.packet, false)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Remove the in-flight forward of the packet, and send the refunded
+    /// tokens back to the original sender if the forward failed. When the
+    /// refund cannot be sent, the tokens remain in the IBC account and the
+    /// forward is recorded as stuck.
+    fn complete_forward(
+        &mut self,
+        packet: &Packet,
+        is_success: bool,
+    ) -> Result<(), Error> {
+        let Some(in_flight) = self
+            .ctx
+            .inner
+            .borrow()
+            .in_flight_forward(
+                &packet.port_id_on_a,
+                &packet.chan_id_on_a,
+                packet.seq_on_a,
+            )
+            .map_err(|e| Error::Context(Box::new(e)))?
+        else {
+            return Ok(());
+        };
+        self.ctx
+            .inner
+            .borrow_mut()
+            .delete_in_flight_forward(
+                &packet.port_id_on_a,
+                &packet.chan_id_on_a,
+                packet.seq_on_a,
+            )
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        if is_success {
+            return Ok(());
+        }
+
+        // The refund isn't tracked
+        let refund = in_flight
+            .refund_channel()
+            .zip(in_flight.refund_coin())
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "Invalid in-flight forward: {in_flight:?}"
+                ))
+            })
+            .and_then(|((port_id, channel_id), coin)| {
+                let message = self.forward_message(
+                    port_id,
+                    channel_id,
+                    coin,
+                    in_flight.sender.clone(),
+                    String::new(),
+                    forward::DEFAULT_FORWARD_TIMEOUT,
+                )?;
+                self.validate_send(message.clone())?;
+                Ok(message)
+            });
+        match refund {
+            Ok(message) => self.execute_send(message).map(|_| ()),
+            Err(e) => {
+                self.ctx.inner.borrow().log_string(format!(
+                    "Refunding the failed forward failed, the tokens remain \
+                     in the IBC account: {in_flight:?}, {e}"
+                ));
+                self.ctx
+                    .inner
+                    .borrow_mut()
+                    .store_stuck_forward(
+                        &packet.port_id_on_a,
+                        &packet.chan_id_on_a,
+                        packet.seq_on_a,
+                        in_flight,
+                    )
+                    .map_err(|e| Error::Context(Box::new(e)))
+            }
         }
     }
 
+    /// Send the tokens held by the IBC account and return the sequence of the
+    /// sent packet
+    fn send_from_ibc_account(
+        &mut self,
+        port_id: PortId,
+        channel_id: ChannelId,
+        coin: PrefixedCoin,
+        receiver: String,
+        memo: String,
+        timeout: Duration,
+    ) -> Result<Sequence, Error> {
+        let message = self.forward_message(
+            port_id, channel_id, coin, receiver, memo, timeout,
+        )?;
+        self.validate_send(message.clone())?;
+        self.execute_send(message)
+    }
+
+    /// Make the message to send the tokens held by the IBC account. The
+    /// tokens cannot be sent through a channel frozen by governance.
+    fn forward_message(
+        &self,
+        port_id: PortId,
+        channel_id: ChannelId,
+        coin: PrefixedCoin,
+        receiver: String,
+        memo: String,
+        timeout: Duration,
+    ) -> Result<IbcMsgTransfer, Error> {
+        let ctx = self.ctx.inner.borrow();
+        let is_frozen = storage::is_channel_frozen(ctx.storage(), &channel_id)
+            .map_err(|e| Error::Context(Box::new(ContextError::from(e))))?;
+        if is_frozen {
+            return Err(Error::Other(format!(
+                "Transfer through a channel frozen by governance is not \
+                 allowed: Channel {channel_id}"
+            )));
+        }
+        let now = ctx
+            .host_timestamp()
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        let timeout_timestamp =
+            (now + timeout).map_err(|e| Error::Other(e.to_string()))?;
+        Ok(IbcMsgTransfer {
+            port_id_on_a: port_id,
+            chan_id_on_a: channel_id,
+            packet_data: PacketData {
+                token: coin,
+                sender: IBC_ESCROW_ADDRESS.to_string().into(),
+                receiver: receiver.into(),
+                memo: memo.into(),
+            },
+            timeout_height_on_b: TimeoutHeight::Never,
+            timeout_timestamp_on_b: TimeoutTimestamp::At(timeout_timestamp),
+        })
+    }
+
+    /// Validate the message to send the tokens held by the IBC account. The
+    /// sent packet isn't validated by the IBC VP with the received message,
+    /// so it has to be validated here.
+    fn validate_send(&self, message: IbcMsgTransfer) -> Result<(), Error> {
+        let token_transfer_ctx = TokenTransferContext::new(
+            self.ctx.inner.clone(),
+            self.verifiers.clone(),
+        );
+        send_transfer_validate(&self.ctx, &token_transfer_ctx, message)
+            .map_err(Error::TokenTransfer)
+    }
+
+    /// Send the tokens held by the IBC account with the validated message and
+    /// return the sequence of the sent packet
+    fn execute_send(
+        &mut self,
+        message: IbcMsgTransfer,
+    ) -> Result<Sequence, Error> {
+        let sequence = self
+            .ctx
+            .inner
+            .borrow()
+            .get_next_sequence_send(
+                &message.port_id_on_a,
+                &message.chan_id_on_a,
+            )
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        let mut token_transfer_ctx = TokenTransferContext::new(
+            self.ctx.inner.clone(),
+            self.verifiers.clone(),
+        );
+        send_transfer_execute(&mut self.ctx, &mut token_transfer_ctx, message)
+            .map_err(Error::TokenTransfer)?;
+        Ok(sequence)
+    }
+
     /// Check the result of receiving the packet by checking the packet
     /// acknowledgement
     pub fn is_receiving_success(
@@ -774,6 +1164,16 @@ where
                 validate(&self.ctx, &self.router, *envelope)
                     .map_err(|e| Error::Context(Box::new(e)))
             }
+            IbcMessage::IcaRegister(msg) => {
+                let (_, envelope) = ica_register_envelope(&msg)?;
+                validate(&self.ctx, &self.router, envelope)
+                    .map_err(|e| Error::Context(Box::new(e)))
+            }
+            IbcMessage::IcaSendTx(msg) => {
+                let (_, packet) = self.ica_packet(&msg)?;
+                send_packet_validate(&self.ctx, &packet)
+                    .map_err(|e| Error::Context(Box::new(e)))
+            }
         }
     }
 
@@ -786,6 +1186,42 @@ where
     }
 }
 
+/// Parse the owner of an interchain account
+fn ica_owner(owner: &str) -> Result<Address, Error> {
+    Address::decode(owner).map_err(|_| {
+        Error::Other(format!("Cannot convert the owner address {owner}"))
+    })
+}
+
+/// Make the message to open the channel for the interchain account
+fn ica_register_envelope(
+    msg: &MsgRegisterInterchainAccount,
+) -> Result<(Address, MsgEnvelope), Error> {
+    let owner = ica_owner(&msg.owner)?;
+    let connection_id =
+        ConnectionId::from_str(&msg.connection_id).map_err(|_| {
+            Error::Other(format!("Invalid connection: {}", msg.connection_id))
+        })?;
+    // Only ordered channels are supported
+    if msg.ordering != Order::None as i32
+        && msg.ordering != Order::Ordered as i32
+    {
+        return Err(Error::Other(format!(
+            "Unsupported channel ordering: {}",
+            msg.ordering
+        )));
+    }
+    let msg = MsgChannelOpenInit {
+        port_id_on_a: ica::controller_port_id(&owner),
+        connection_hops_on_a: vec![connection_id],
+        port_id_on_b: ica::host_port_id(),
+        ordering: Order::Ordered,
+        signer: owner.to_string().into(),
+        version_proposal: Version::new(msg.version.clone()),
+    };
+    Ok((owner, MsgEnvelope::Channel(ChannelMsg::OpenInit(msg))))
+}
+
 // Extract the involved namada address from the packet (either sender or
 // receiver) to trigger its vp. Returns None if an address could not be found
 fn get_envelope_verifier(
@@ -797,6 +1233,12 @@ fn get_envelope_verifier(
                 FT_PORT_ID_STR => {
                     serde_json::from_slice::<PacketData>(&msg.packet.data)
                         .ok()
+                        // The tokens to be forwarded are received by the IBC
+                        // account instead of the receiver
+                        .filter(|packet_data| {
+                            ForwardMetadata::from_memo(&packet_data.memo)
+                                .is_none()
+                        })
                         .map(|packet_data| packet_data.receiver)
                 }
                 NFT_PORT_ID_STR => {
@@ -846,6 +1288,13 @@ fn get_envelope_verifier(
                 _ => None,
             }
         }
+        // The owner of the interchain account authorizes the registration
+        MsgEnvelope::Channel(ChannelMsg::OpenInit(msg))
+            if ica::is_controller_port(&msg.port_id_on_a) =>
+        {
+            ica::controller_owner(&msg.port_id_on_a)
+                .map(|owner| owner.to_string().into())
+        }
         _ => None,
     }
 }
@@ -866,13 +1315,20 @@ pub fn decode_message<Transfer: BorshDeserialize>(
             };
             return Ok(IbcMessage::Transfer(Box::new(msg)));
         }
-        if let Ok(message) = IbcMsgNftTransfer::try_from(any_msg) {
+        if let Ok(message) = IbcMsgNftTransfer::try_from(any_msg.clone()) {
             let msg = MsgNftTransfer {
                 message,
                 transfer: None,
             };
             return Ok(IbcMessage::NftTransfer(msg));
         }
+        if let Ok(msg) = MsgRegisterInterchainAccount::try_from(any_msg.clone())
+        {
+            return Ok(IbcMessage::IcaRegister(msg));
+        }
+        if let Ok(msg) = MsgSendTx::try_from(any_msg) {
+            return Ok(IbcMessage::IcaSendTx(msg));
+        }
     }
 
     // Transfer message with `ShieldingTransfer`
@@ -1080,7 +1536,12 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
-    for prefix in [deposit_prefix(), withdraw_prefix()] {
+    for prefix in [
+        deposit_prefix(),
+        withdraw_prefix(),
+        channel_deposit_prefix(),
+        channel_withdraw_prefix(),
+    ] {
         let keys: Vec<Key> = state
             .iter_prefix(&prefix)?
             .map(|(key, _, _)| {
@@ -1092,6 +1553,15 @@ where
         }
     }
 
+    // The number of senders isn't bounded, so their keys are deleted instead
+    let keys: Vec<Key> = state
+        .iter_prefix(&account_withdraw_prefix())?
+        .map(|(key, _, _)| Key::parse(key).expect("The key should be parsable"))
+        .collect();
+    for key in keys {
+        state.delete(&key)?;
+    }
+
     Ok(())
 }
 
@@ -17,6 +17,8 @@ use ibc::primitives::proto::Protobuf;
 use masp_primitives::transaction::Transaction as MaspTransaction;
 use namada_core::borsh::BorshSerializeExt;
 
+use crate::ica::{MsgRegisterInterchainAccount, MsgSendTx};
+
 /// The different variants of an Ibc message
 #[derive(Debug, Clone)]
 pub enum IbcMessage<Transfer> {
@@ -26,6 +28,10 @@ pub enum IbcMessage<Transfer> {
     Transfer(Box<MsgTransfer<Transfer>>),
     /// NFT transfer
     NftTransfer(MsgNftTransfer<Transfer>),
+    /// Register an interchain account controlled by a Namada account
+    IcaRegister(MsgRegisterInterchainAccount),
+    /// Send a transaction to be executed by an interchain account
+    IcaSendTx(MsgSendTx),
 }
 
 /// IBC transfer message with `Transfer`
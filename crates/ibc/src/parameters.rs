@@ -2,15 +2,35 @@
 
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::token::Amount;
-use namada_state::{Result, StorageWrite};
+use namada_state::{OptionExt, Result, StorageRead, StorageWrite};
 
-#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+use crate::storage::{
+    default_account_throughput_limit_key, default_channel_throughput_limit_key,
+    params_key,
+};
+
+#[derive(Clone, Debug)]
 /// Governance parameter structure
 pub struct IbcParameters {
     /// Default supply limit of each token
     pub default_mint_limit: Amount,
     /// Default per-epoch throughput limit of each token
     pub default_per_epoch_throughput_limit: Amount,
+    /// Default per-epoch throughput limit of each token on each channel.
+    /// `None` means that the channels aren't limited individually.
+    pub default_per_epoch_channel_throughput_limit: Option<Amount>,
+    /// Default per-epoch withdraw limit of each token for each sender.
+    /// `None` means that the senders aren't limited individually.
+    pub default_per_epoch_account_throughput_limit: Option<Amount>,
+}
+
+/// The IBC parameters stored under the parameters key. The limits that were
+/// added after the launch are stored under their own keys to keep the layout
+/// of the stored parameters.
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+struct StoredIbcParameters {
+    default_mint_limit: Amount,
+    default_per_epoch_throughput_limit: Amount,
 }
 
 impl Default for IbcParameters {
@@ -18,6 +38,8 @@ impl Default for IbcParameters {
         Self {
             default_mint_limit: Amount::zero(),
             default_per_epoch_throughput_limit: Amount::zero(),
+            default_per_epoch_channel_throughput_limit: None,
+            default_per_epoch_account_throughput_limit: None,
         }
     }
 }
@@ -28,7 +50,57 @@ impl IbcParameters {
     where
         S: StorageWrite,
     {
-        let key = crate::storage::params_key();
-        storage.write(&key, self)
+        let stored = StoredIbcParameters {
+            default_mint_limit: self.default_mint_limit,
+            default_per_epoch_throughput_limit: self
+                .default_per_epoch_throughput_limit,
+        };
+        storage.write(&params_key(), stored)?;
+        write_optional_limit(
+            storage,
+            &default_channel_throughput_limit_key(),
+            self.default_per_epoch_channel_throughput_limit,
+        )?;
+        write_optional_limit(
+            storage,
+            &default_account_throughput_limit_key(),
+            self.default_per_epoch_account_throughput_limit,
+        )
+    }
+
+    /// Read IBC parameters from storage
+    pub fn read<S>(storage: &S) -> Result<Self>
+    where
+        S: StorageRead,
+    {
+        let StoredIbcParameters {
+            default_mint_limit,
+            default_per_epoch_throughput_limit,
+        } = storage
+            .read(&params_key())?
+            .ok_or_err_msg("IBC parameters should be stored")?;
+        Ok(Self {
+            default_mint_limit,
+            default_per_epoch_throughput_limit,
+            default_per_epoch_channel_throughput_limit: storage
+                .read(&default_channel_throughput_limit_key())?,
+            default_per_epoch_account_throughput_limit: storage
+                .read(&default_account_throughput_limit_key())?,
+        })
+    }
+}
+
+/// Write an optional limit, a missing limit being removed from storage
+fn write_optional_limit<S>(
+    storage: &mut S,
+    key: &namada_core::storage::Key,
+    limit: Option<Amount>,
+) -> Result<()>
+where
+    S: StorageWrite,
+{
+    match limit {
+        Some(limit) => storage.write(key, limit),
+        None => storage.delete(key),
     }
 }
@@ -38,6 +38,20 @@ const MINT: &str = "mint";
 const THROUGHPUT_LIMIT: &str = "throughput_limit";
 const DEPOSIT: &str = "deposit";
 const WITHDRAW: &str = "withdraw";
+const CHANNEL_THROUGHPUT_LIMIT: &str = "channel_throughput_limit";
+const ACCOUNT_THROUGHPUT_LIMIT: &str = "account_throughput_limit";
+const CHANNEL_DEPOSIT: &str = "channel_deposit";
+const CHANNEL_WITHDRAW: &str = "channel_withdraw";
+const ACCOUNT_WITHDRAW: &str = "account_withdraw";
+const FROZEN_CHANNEL: &str = "frozen_channel";
+const DEFAULT_CHANNEL_THROUGHPUT_LIMIT: &str =
+    "default_channel_throughput_limit";
+const DEFAULT_ACCOUNT_THROUGHPUT_LIMIT: &str =
+    "default_account_throughput_limit";
+const FORWARD: &str = "forward";
+const STUCK_FORWARD: &str = "stuck_forward";
+const ICA_ACTIVE_CHANNEL: &str = "ica_active_channel";
+const ICA_ACCOUNT: &str = "ica_account";
 
 /// Mint IBC tokens. This function doesn't emit event (see
 /// `mint_tokens_and_emit_event` below)
@@ -586,9 +600,7 @@ pub fn get_limits<S: StorageRead>(
     Ok(match (mint_limit, throughput_limit) {
         (Some(ml), Some(tl)) => (ml, tl),
         _ => {
-            let params: IbcParameters = storage
-                .read(&params_key())?
-                .expect("Parameters should be stored");
+            let params = IbcParameters::read(storage)?;
             (
                 mint_limit.unwrap_or(params.default_mint_limit),
                 throughput_limit
@@ -647,3 +659,291 @@ pub fn withdraw_key(token: &Address) -> Key {
         .push(&token.to_string().to_db_key())
         .expect("Cannot obtain a storage key")
 }
+
+/// Returns a key of the per-epoch throughput limit for the token on the
+/// channel. Channel IDs are unique on a chain, so the port isn't included.
+pub fn channel_throughput_limit_key(
+    channel_id: &ChannelId,
+    token: &Address,
+) -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&CHANNEL_THROUGHPUT_LIMIT.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&channel_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        // Set as String to avoid checking the token address
+        .push(&token.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the per-epoch withdraw limit of each sender for the token
+pub fn account_throughput_limit_key(token: &Address) -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&ACCOUNT_THROUGHPUT_LIMIT.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        // Set as String to avoid checking the token address
+        .push(&token.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the default per-epoch throughput limit of each token on
+/// each channel. This IBC parameter isn't stored with the others.
+pub fn default_channel_throughput_limit_key() -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&DEFAULT_CHANNEL_THROUGHPUT_LIMIT.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the default per-epoch withdraw limit of each token for
+/// each sender. This IBC parameter isn't stored with the others.
+pub fn default_account_throughput_limit_key() -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&DEFAULT_ACCOUNT_THROUGHPUT_LIMIT.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the per-epoch throughput limit for the token on the channel. If it
+/// doesn't exist in the storage, the default limit is loaded from IBC
+/// parameters. `None` means that the channel isn't limited.
+pub fn get_channel_throughput_limit<S: StorageRead>(
+    storage: &S,
+    channel_id: &ChannelId,
+    token: &Address,
+) -> Result<Option<Amount>> {
+    let key = channel_throughput_limit_key(channel_id, token);
+    match storage.read(&key)? {
+        Some(limit) => Ok(Some(limit)),
+        None => storage.read(&default_channel_throughput_limit_key()),
+    }
+}
+
+/// Get the per-epoch withdraw limit of each sender for the token. If it
+/// doesn't exist in the storage, the default limit is loaded from IBC
+/// parameters. `None` means that the senders aren't limited.
+pub fn get_account_throughput_limit<S: StorageRead>(
+    storage: &S,
+    token: &Address,
+) -> Result<Option<Amount>> {
+    let key = account_throughput_limit_key(token);
+    match storage.read(&key)? {
+        Some(limit) => Ok(Some(limit)),
+        None => storage.read(&default_account_throughput_limit_key()),
+    }
+}
+
+/// Returns a prefix of the per-epoch deposit of each channel
+pub fn channel_deposit_prefix() -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&CHANNEL_DEPOSIT.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the per-epoch deposit for the token on the channel
+pub fn channel_deposit_key(channel_id: &ChannelId, token: &Address) -> Key {
+    channel_deposit_prefix()
+        .push(&channel_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        // Set as String to avoid checking the token address
+        .push(&token.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a prefix of the per-epoch withdraw of each channel
+pub fn channel_withdraw_prefix() -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&CHANNEL_WITHDRAW.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the per-epoch withdraw for the token on the channel
+pub fn channel_withdraw_key(channel_id: &ChannelId, token: &Address) -> Key {
+    channel_withdraw_prefix()
+        .push(&channel_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        // Set as String to avoid checking the token address
+        .push(&token.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a prefix of the per-epoch withdraw of each sender
+pub fn account_withdraw_prefix() -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&ACCOUNT_WITHDRAW.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the per-epoch withdraw for the token by the sender
+pub fn account_withdraw_key(account: &Address, token: &Address) -> Key {
+    account_withdraw_prefix()
+        .push(&account.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        // Set as String to avoid checking the token address
+        .push(&token.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns the channel ID and the token if the given key is a key of the
+/// per-epoch deposit or withdraw on a channel
+pub fn is_channel_throughput_key(key: &Key) -> Option<(ChannelId, Address)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(channel_id),
+            DbKeySeg::StringSeg(token),
+        ] if addr == &Address::Internal(InternalAddress::Ibc)
+            && (prefix == CHANNEL_DEPOSIT || prefix == CHANNEL_WITHDRAW) =>
+        {
+            let channel_id = ChannelId::from_str(channel_id).ok()?;
+            let token = Address::decode(token).ok()?;
+            Some((channel_id, token))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the sender and the token if the given key is a key of the
+/// per-epoch withdraw by a sender
+pub fn is_account_withdraw_key(key: &Key) -> Option<(Address, Address)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(account),
+            DbKeySeg::StringSeg(token),
+        ] if addr == &Address::Internal(InternalAddress::Ibc)
+            && prefix == ACCOUNT_WITHDRAW =>
+        {
+            let account = Address::decode(account).ok()?;
+            let token = Address::decode(token).ok()?;
+            Some((account, token))
+        }
+        _ => None,
+    }
+}
+
+/// Returns a key of the emergency freeze flag of the channel
+pub fn frozen_channel_key(channel_id: &ChannelId) -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&FROZEN_CHANNEL.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&channel_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns true if the channel has been frozen by governance
+pub fn is_channel_frozen<S: StorageRead>(
+    storage: &S,
+    channel_id: &ChannelId,
+) -> Result<bool> {
+    let key = frozen_channel_key(channel_id);
+    Ok(storage.read(&key)?.unwrap_or(false))
+}
+
+/// Freeze the channel. No new transfer can be sent or received through a
+/// frozen channel, but the pending packets can still be acknowledged or
+/// timed out to refund the senders. This is meant to be called by the code
+/// of a governance proposal.
+pub fn freeze_channel<S: StorageWrite>(
+    storage: &mut S,
+    channel_id: &ChannelId,
+) -> Result<()> {
+    storage.write(&frozen_channel_key(channel_id), true)
+}
+
+/// Unfreeze the channel. This is meant to be called by the code of a
+/// governance proposal.
+pub fn unfreeze_channel<S: StorageWrite>(
+    storage: &mut S,
+    channel_id: &ChannelId,
+) -> Result<()> {
+    storage.delete(&frozen_channel_key(channel_id))
+}
+
+/// Returns a key of the in-flight forward of the packet sent with the
+/// sequence on the channel
+pub fn in_flight_forward_key(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&FORWARD.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&port_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&channel_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&sequence.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the forward of the packet sent with the sequence on the
+/// channel, whose refund couldn't be sent
+pub fn stuck_forward_key(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&STUCK_FORWARD.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&port_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&channel_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&sequence.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns a key of the active channel of the interchain account controlled
+/// through the controller port and the connection on this chain
+pub fn ica_active_channel_key(
+    controller_port_id: &PortId,
+    connection_id: &ConnectionId,
+) -> Key {
+    ica_key(ICA_ACTIVE_CHANNEL, controller_port_id, connection_id)
+}
+
+/// Returns a key of the address of the interchain account controlled through
+/// the controller port and the connection on this chain
+pub fn ica_account_key(
+    controller_port_id: &PortId,
+    connection_id: &ConnectionId,
+) -> Key {
+    ica_key(ICA_ACCOUNT, controller_port_id, connection_id)
+}
+
+/// Read the active channel of the interchain account controlled through the
+/// controller port and the connection
+pub fn ica_active_channel<S: StorageRead>(
+    storage: &S,
+    controller_port_id: &PortId,
+    connection_id: &ConnectionId,
+) -> Result<Option<ChannelId>> {
+    let key = ica_active_channel_key(controller_port_id, connection_id);
+    storage
+        .read::<String>(&key)?
+        .map(|channel_id| {
+            ChannelId::from_str(&channel_id).map_err(|e| {
+                Error::new_alloc(format!(
+                    "Decoding the channel ID failed: Key {key}, Error {e}"
+                ))
+            })
+        })
+        .transpose()
+}
+
+fn ica_key(
+    prefix: &str,
+    controller_port_id: &PortId,
+    connection_id: &ConnectionId,
+) -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&prefix.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&controller_port_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&connection_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
@@ -13,7 +13,7 @@ use borsh::BorshDeserialize;
 use context::{
     PseudoExecutionContext, PseudoExecutionStorage, VpValidationContext,
 };
-use namada_core::address::Address;
+use namada_core::address::{Address, InternalAddress};
 use namada_core::arith::checked;
 use namada_core::collections::HashSet;
 use namada_core::storage::Key;
@@ -27,16 +27,22 @@ use namada_vp::native_vp::{Ctx, CtxPreStorageRead, NativeVp, VpEvaluator};
 use namada_vp::VpEnv;
 use thiserror::Error;
 
-use crate::core::host::types::identifiers::ChainId as IbcChainId;
+use crate::core::channel::types::msgs::PacketMsg;
+use crate::core::handler::types::msgs::MsgEnvelope;
+use crate::core::host::types::identifiers::{ChainId as IbcChainId, ChannelId};
 use crate::core::host::types::path::UPGRADED_IBC_STATE;
 use crate::event::IbcEvent;
 use crate::storage::{
-    deposit_key, get_limits, is_ibc_key, is_ibc_trace_key, mint_amount_key,
+    account_withdraw_key, channel_deposit_key, channel_withdraw_key,
+    deposit_key, get_account_throughput_limit, get_channel_throughput_limit,
+    get_limits, ica_active_channel, is_account_withdraw_key, is_channel_frozen,
+    is_channel_throughput_key, is_ibc_key, is_ibc_trace_key, mint_amount_key,
     withdraw_key,
 };
 use crate::trace::calc_hash;
 use crate::{
-    Error as ActionError, IbcActions, NftTransferModule, TransferModule,
+    decode_message, Error as ActionError, IbcActions, IbcMessage,
+    InterchainAccountModule, NftTransferModule, TransferModule,
     ValidationParams, COMMITMENT_PREFIX,
 };
 
@@ -55,6 +61,8 @@ pub enum VpError {
     IbcEvent(String),
     #[error("IBC rate limit: {0}")]
     RateLimit(String),
+    #[error("IBC frozen channel: {0}")]
+    FrozenChannel(String),
 }
 
 /// IBC functions result
@@ -162,6 +170,9 @@ where
             .data(batched_tx.cmt)
             .ok_or(VpError::NoTxData)?;
 
+        // Reject a transfer through a channel frozen by governance
+        self.check_frozen_channel(&tx_data)?;
+
         // Pseudo execution and compare them
         self.validate_state(&tx_data, keys_changed)?;
 
@@ -250,6 +261,8 @@ where
         actions.add_transfer_module(module);
         let module = NftTransferModule::<_, Token>::new(ctx.clone());
         actions.add_transfer_module(module);
+        let module = InterchainAccountModule::<_, Token>::new(ctx.clone());
+        actions.add_transfer_module(module);
         // Charge gas for the expensive execution
         self.ctx.charge_gas(IBC_ACTION_EXECUTE_GAS.into())?;
         actions.execute::<Transfer>(tx_data)?;
@@ -270,6 +283,36 @@ where
             match_value(key, actual, ctx.borrow().get_changed_value(key))?;
         }
 
+        // An interchain account can be debited only by the received packets
+        // executed on the host side
+        for key in keys_changed {
+            let Some([_, owner]) = Token::is_any_token_balance_key(key) else {
+                continue;
+            };
+            if !matches!(owner, Address::Internal(InternalAddress::IcaHost(_)))
+            {
+                continue;
+            }
+            let pre: Amount = self.ctx.read_pre(key)?.unwrap_or_default();
+            let post: Amount = self.ctx.read_post(key)?.unwrap_or_default();
+            let expected = match ctx.borrow().get_changed_value(key) {
+                Some(StorageModification::Write { value }) => {
+                    Amount::try_from_slice(value).map_err(VpError::Decoding)?
+                }
+                Some(_) => Amount::zero(),
+                None => pre,
+            };
+            if post < expected {
+                return Err(VpError::StateChange(format!(
+                    "The interchain account was debited without a received \
+                     packet: Key {key}, actual {}, expected at least {}",
+                    post.to_string_native(),
+                    expected.to_string_native(),
+                ))
+                .into());
+            }
+        }
+
         // check the events
         let actual: BTreeSet<_> = self
             .ctx
@@ -303,7 +346,9 @@ where
 
         let module = TransferModule::new(ctx.clone(), verifiers);
         actions.add_transfer_module(module);
-        let module = NftTransferModule::<_, Token>::new(ctx);
+        let module = NftTransferModule::<_, Token>::new(ctx.clone());
+        actions.add_transfer_module(module);
+        let module = InterchainAccountModule::<_, Token>::new(ctx);
         actions.add_transfer_module(module);
         // Charge gas for the expensive validation
         self.ctx.charge_gas(IBC_ACTION_VALIDATE_GAS.into())?;
@@ -366,6 +411,47 @@ where
         Ok(())
     }
 
+    fn check_frozen_channel(&self, tx_data: &[u8]) -> Result<()> {
+        let channel_id = match decode_message::<Transfer>(tx_data) {
+            Ok(IbcMessage::Transfer(msg)) => msg.message.chan_id_on_a,
+            Ok(IbcMessage::NftTransfer(msg)) => msg.message.chan_id_on_a,
+            Ok(IbcMessage::IcaSendTx(msg)) => {
+                let channel_id = match msg.controller() {
+                    Some((port_id, connection_id)) => ica_active_channel(
+                        &self.ctx.pre(),
+                        &port_id,
+                        &connection_id,
+                    )?,
+                    None => None,
+                };
+                match channel_id {
+                    Some(channel_id) => channel_id,
+                    None => return Ok(()),
+                }
+            }
+            // A new channel is opened for an interchain account
+            Ok(IbcMessage::IcaRegister(_)) => return Ok(()),
+            Ok(IbcMessage::Envelope(envelope)) => match *envelope {
+                MsgEnvelope::Packet(PacketMsg::Recv(msg)) => {
+                    msg.packet.chan_id_on_b
+                }
+                // Acknowledgements and timeouts are still allowed to refund
+                // the senders of the pending packets
+                _ => return Ok(()),
+            },
+            // An invalid message is rejected by the other checks
+            Err(_) => return Ok(()),
+        };
+        if is_channel_frozen(&self.ctx.pre(), &channel_id)? {
+            return Err(VpError::FrozenChannel(format!(
+                "Transfer through a channel frozen by governance is not \
+                 allowed: Channel {channel_id}"
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
     fn check_limits(&self, keys_changed: &BTreeSet<Key>) -> Result<bool> {
         let tokens: BTreeSet<&Address> = keys_changed
             .iter()
@@ -390,7 +476,8 @@ where
             }
 
             // Check the rate limit
-            let throughput = self.calc_throughput(token)?;
+            let throughput = self
+                .calc_throughput(&deposit_key(token), &withdraw_key(token))?;
             if throughput_limit < throughput {
                 return Err(VpError::RateLimit(format!(
                     "Transfer exceeding the per-epoch throughput limit is not \
@@ -400,16 +487,73 @@ where
                 .into());
             }
         }
+
+        // Check the rate limit of each channel
+        let channels: BTreeSet<(ChannelId, Address)> = keys_changed
+            .iter()
+            .filter_map(is_channel_throughput_key)
+            .collect();
+        for (channel_id, token) in channels {
+            let Some(throughput_limit) = get_channel_throughput_limit(
+                &self.ctx.pre(),
+                &channel_id,
+                &token,
+            )?
+            else {
+                continue;
+            };
+            let throughput = self.calc_throughput(
+                &channel_deposit_key(&channel_id, &token),
+                &channel_withdraw_key(&channel_id, &token),
+            )?;
+            if throughput_limit < throughput {
+                return Err(VpError::RateLimit(format!(
+                    "Transfer exceeding the per-epoch throughput limit of the \
+                     channel is not allowed: Channel {channel_id}, token \
+                     {token}, per-epoch throughput limit {throughput_limit}, \
+                     actual throughput {throughput}"
+                ))
+                .into());
+            }
+        }
+
+        // Check the withdraw limit of each sender
+        let accounts: BTreeSet<(Address, Address)> = keys_changed
+            .iter()
+            .filter_map(is_account_withdraw_key)
+            .collect();
+        for (account, token) in accounts {
+            let Some(withdraw_limit) =
+                get_account_throughput_limit(&self.ctx.pre(), &token)?
+            else {
+                continue;
+            };
+            let withdraw: Amount = self
+                .ctx
+                .read_post(&account_withdraw_key(&account, &token))?
+                .unwrap_or_default();
+            if withdraw_limit < withdraw {
+                return Err(VpError::RateLimit(format!(
+                    "Transfer exceeding the per-epoch withdraw limit of the \
+                     sender is not allowed: Sender {account}, token {token}, \
+                     per-epoch withdraw limit {withdraw_limit}, actual \
+                     withdraw {withdraw}"
+                ))
+                .into());
+            }
+        }
         Ok(true)
     }
 
-    fn calc_throughput(&self, token: &Address) -> Result<Amount> {
-        let deposit_key = deposit_key(token);
+    fn calc_throughput(
+        &self,
+        deposit_key: &Key,
+        withdraw_key: &Key,
+    ) -> Result<Amount> {
         let deposit: Amount =
-            self.ctx.read_post(&deposit_key)?.unwrap_or_default();
-        let withdraw_key = withdraw_key(token);
+            self.ctx.read_post(deposit_key)?.unwrap_or_default();
         let withdraw: Amount =
-            self.ctx.read_post(&withdraw_key)?.unwrap_or_default();
+            self.ctx.read_post(withdraw_key)?.unwrap_or_default();
         let throughput = if deposit < withdraw {
             withdraw
                 .checked_sub(deposit)
@@ -563,12 +707,12 @@ mod tests {
     use crate::primitives::{Timestamp, ToProto};
     use crate::storage::{
         self, ack_key, channel_counter_key, channel_key,
-        client_connections_key, client_counter_key, client_state_key,
-        client_update_height_key, client_update_timestamp_key, commitment_key,
-        connection_counter_key, connection_key, consensus_state_key,
-        ibc_trace_key, mint_amount_key, next_sequence_ack_key,
-        next_sequence_recv_key, next_sequence_send_key, nft_class_key,
-        nft_metadata_key, receipt_key,
+        channel_throughput_limit_key, client_connections_key,
+        client_counter_key, client_state_key, client_update_height_key,
+        client_update_timestamp_key, commitment_key, connection_counter_key,
+        connection_key, consensus_state_key, ibc_trace_key, mint_amount_key,
+        next_sequence_ack_key, next_sequence_recv_key, next_sequence_send_key,
+        nft_class_key, nft_metadata_key, receipt_key,
     };
     use crate::trace::{calc_hash, ibc_token};
     use crate::{
@@ -627,6 +771,7 @@ mod tests {
         let ibc_params = IbcParameters {
             default_mint_limit: Amount::native_whole(100),
             default_per_epoch_throughput_limit: Amount::native_whole(100),
+            ..Default::default()
         };
         ibc_params.init_storage(&mut state).unwrap();
         namada_proof_of_stake::test_utils::test_init_genesis::<
@@ -2234,8 +2379,7 @@ mod tests {
     // skip test_close_init_channel() and test_close_confirm_channel() since it
     // is not allowed to close the transfer channel
 
-    #[test]
-    fn test_send_packet() {
+    fn validate_send_packet(init: impl FnOnce(&mut TestState)) -> Result<()> {
         let mut keys_changed = BTreeSet::new();
         let mut state = init_storage();
         insert_init_client(&mut state);
@@ -2264,6 +2408,7 @@ mod tests {
             .write_log_mut()
             .write(&balance_key, amount.serialize_to_vec())
             .expect("write failed");
+        init(&mut state);
         state.write_log_mut().commit_batch_and_current_tx();
         state.commit_block().expect("commit failed");
         // for next block
@@ -2318,6 +2463,21 @@ mod tests {
             .write(&withdraw_key, bytes)
             .expect("write failed");
         keys_changed.insert(withdraw_key);
+        let channel_withdraw_key =
+            channel_withdraw_key(&get_channel_id(), &nam());
+        let bytes = amount.serialize_to_vec();
+        let _ = state
+            .write_log_mut()
+            .write(&channel_withdraw_key, bytes)
+            .expect("write failed");
+        keys_changed.insert(channel_withdraw_key);
+        let account_withdraw_key = account_withdraw_key(&sender, &nam());
+        let bytes = amount.serialize_to_vec();
+        let _ = state
+            .write_log_mut()
+            .write(&account_withdraw_key, bytes)
+            .expect("write failed");
+        keys_changed.insert(account_withdraw_key);
         // event
         let transfer_event = TransferEvent {
             sender: msg.packet_data.sender.clone(),
@@ -2381,10 +2541,74 @@ mod tests {
             vp_wasm_cache,
         );
         let ibc = Ibc::new(ctx);
-        assert_matches!(
-            ibc.validate_tx(&batched_tx, &keys_changed, &verifiers),
-            Ok(_)
-        );
+        ibc.validate_tx(&batched_tx, &keys_changed, &verifiers)
+    }
+
+    #[test]
+    fn test_send_packet() {
+        assert_matches!(validate_send_packet(|_| {}), Ok(_));
+    }
+
+    #[test]
+    fn test_send_packet_exceeding_channel_limit() {
+        // 100 NAM are sent through the channel
+        let result = validate_send_packet(|state| {
+            let limit_key =
+                channel_throughput_limit_key(&get_channel_id(), &nam());
+            let _ = state
+                .write_log_mut()
+                .write(&limit_key, Amount::native_whole(99).serialize_to_vec())
+                .expect("write failed");
+        });
+        let err = result.expect_err("The channel limit should be exceeded");
+        assert!(err.to_string().contains("throughput limit of the channel"));
+
+        // Other channels aren't limited by the channel-specific limit
+        let result = validate_send_packet(|state| {
+            let other_channel = ChannelId::new(1);
+            let limit_key =
+                channel_throughput_limit_key(&other_channel, &nam());
+            let _ = state
+                .write_log_mut()
+                .write(&limit_key, Amount::native_whole(99).serialize_to_vec())
+                .expect("write failed");
+        });
+        assert_matches!(result, Ok(_));
+    }
+
+    #[test]
+    fn test_send_packet_exceeding_account_limit() {
+        let result = validate_send_packet(|state| {
+            let params = IbcParameters {
+                default_mint_limit: Amount::native_whole(100),
+                default_per_epoch_throughput_limit: Amount::native_whole(100),
+                default_per_epoch_channel_throughput_limit: Some(
+                    Amount::native_whole(100),
+                ),
+                default_per_epoch_account_throughput_limit: Some(
+                    Amount::native_whole(99),
+                ),
+            };
+            params.init_storage(state).unwrap();
+        });
+        let err = result.expect_err("The sender limit should be exceeded");
+        assert!(err.to_string().contains("withdraw limit of the sender"));
+    }
+
+    #[test]
+    fn test_send_packet_on_frozen_channel() {
+        let result = validate_send_packet(|state| {
+            storage::freeze_channel(state, &get_channel_id()).unwrap();
+        });
+        let err = result.expect_err("The channel should be frozen");
+        assert!(err.to_string().contains("frozen by governance"));
+
+        // The transfer is allowed again once the channel is unfrozen
+        let result = validate_send_packet(|state| {
+            storage::freeze_channel(state, &get_channel_id()).unwrap();
+            storage::unfreeze_channel(state, &get_channel_id()).unwrap();
+        });
+        assert_matches!(result, Ok(_));
     }
 
     #[test]
@@ -2499,6 +2723,16 @@ mod tests {
             .write(&deposit_key, bytes)
             .expect("write failed");
         keys_changed.insert(deposit_key);
+        let channel_deposit_key =
+            channel_deposit_key(&packet.chan_id_on_b, &ibc_token);
+        let bytes = Amount::from_str(coin.amount.to_string(), 0)
+            .unwrap()
+            .serialize_to_vec();
+        let _ = state
+            .write_log_mut()
+            .write(&channel_deposit_key, bytes)
+            .expect("write failed");
+        keys_changed.insert(channel_deposit_key);
         // denom
         let trace_hash = calc_hash(coin.denom.to_string());
         let trace_key = ibc_trace_key(receiver.to_string(), &trace_hash);
@@ -2852,6 +3086,14 @@ mod tests {
             .write(&deposit_key, bytes)
             .expect("write failed");
         keys_changed.insert(deposit_key);
+        let channel_deposit_key =
+            channel_deposit_key(&packet.chan_id_on_a, &nam());
+        let bytes = amount.serialize_to_vec();
+        let _ = state
+            .write_log_mut()
+            .write(&channel_deposit_key, bytes)
+            .expect("write failed");
+        keys_changed.insert(channel_deposit_key);
         // event
         let timeout_event = TimeoutEvent {
             refund_receiver: data.sender,
@@ -3010,6 +3252,14 @@ mod tests {
             .write(&deposit_key, bytes)
             .expect("write failed");
         keys_changed.insert(deposit_key);
+        let channel_deposit_key =
+            channel_deposit_key(&packet.chan_id_on_a, &nam());
+        let bytes = amount.serialize_to_vec();
+        let _ = state
+            .write_log_mut()
+            .write(&channel_deposit_key, bytes)
+            .expect("write failed");
+        keys_changed.insert(channel_deposit_key);
         // event
         let timeout_event = TimeoutEvent {
             refund_receiver: data.sender,
@@ -12,7 +12,9 @@
 //!
 //! - [`transaction`]: contains functions to construct all the transactions
 //!   currently supported by the protocol
-//! - [`reading`]: exposes queries to retrieve data from a Namada node
+//! - [`reading`]: exposes queries to retrieve data from a Namada node,
+//!   optionally verifying the storage proofs of the results against a trusted
+//!   app hash
 //! - [`writing`]: exposes functions to send data to a Namada node
 //!
 //! Both the [`reading`] and [`writing`] modules are further divided into a
@@ -16,6 +16,7 @@ pub mod governance;
 pub mod pgf;
 pub mod pos;
 pub mod tx;
+pub mod verified;
 
 /// Query the address of the native token
 pub async fn query_native_token(
@@ -0,0 +1,94 @@
+use borsh::BorshDeserialize;
+use namada_sdk::chain::Epoch;
+use namada_sdk::error::QueryError;
+use namada_sdk::governance::storage::keys as governance_storage;
+use namada_sdk::governance::utils::ProposalResult;
+use namada_sdk::proof_of_stake::storage::bond_handle;
+use namada_sdk::storage::Key;
+use namada_sdk::token::storage_key::balance_key;
+
+use super::*;
+use crate::reading::proof::{verify_storage_proof, TrustedAppHash};
+
+/// Query the value of a storage key together with its storage proof, and
+/// verify the proof against the trusted app hash. The query is made at the
+/// height of the state committed by the app hash. Note that the node can only
+/// prove the absence of keys in the IBC store, so querying any other key that
+/// is not in storage fails.
+pub async fn query_storage_value_verified<T>(
+    tendermint_addr: &str,
+    trusted: &TrustedAppHash,
+    key: &Key,
+) -> Result<Option<T>, Error>
+where
+    T: BorshDeserialize,
+{
+    let client = HttpClient::new(
+        TendermintAddress::from_str(tendermint_addr)
+            .map_err(|e| Error::Other(e.to_string()))?,
+    )
+    .map_err(|e| Error::Other(e.to_string()))?;
+    let (value, proof) = rpc::query_storage_value_bytes(
+        &client,
+        key,
+        Some(trusted.height),
+        true,
+    )
+    .await?;
+    let proof = proof.ok_or_else(|| {
+        Error::from(QueryError::InvalidProof(format!(
+            "The node didn't return a proof for the key {key}"
+        )))
+    })?;
+    verify_storage_proof(trusted, key, value.as_deref(), &proof)?;
+    value
+        .map(|bytes| {
+            T::try_from_slice(&bytes).map_err(|err| {
+                Error::Encode(EncodingError::Decoding(err.to_string()))
+            })
+        })
+        .transpose()
+}
+
+/// Query the token balance of an owner, verified against the trusted app hash
+pub async fn get_token_balance_verified(
+    tendermint_addr: &str,
+    trusted: &TrustedAppHash,
+    token: &Address,
+    owner: &Address,
+) -> Result<token::Amount, Error> {
+    let key = balance_key(token, owner);
+    query_storage_value_verified(tendermint_addr, trusted, &key)
+        .await?
+        .ok_or_else(|| Error::from(QueryError::NoSuchKey(key.to_string())))
+}
+
+/// Query the amount of a bond that started in the given epoch, verified
+/// against the trusted app hash
+pub async fn get_bond_amount_verified(
+    tendermint_addr: &str,
+    trusted: &TrustedAppHash,
+    source: &Address,
+    validator: &Address,
+    start_epoch: Epoch,
+) -> Result<token::Amount, Error> {
+    let key = bond_handle(source, validator)
+        .get_data_handler()
+        .get_data_key(&start_epoch);
+    query_storage_value_verified(tendermint_addr, trusted, &key)
+        .await?
+        .ok_or_else(|| Error::from(QueryError::NoSuchKey(key.to_string())))
+}
+
+/// Query the result of a governance proposal that has ended, verified against
+/// the trusted app hash
+pub async fn query_proposal_result_verified(
+    tendermint_addr: &str,
+    trusted: &TrustedAppHash,
+    proposal_id: u64,
+) -> Result<ProposalResult, Error> {
+    let key = governance_storage::get_proposal_result_key(proposal_id);
+    query_storage_value_verified(tendermint_addr, trusted, &key)
+        .await?
+        .ok_or_else(|| Error::from(QueryError::NoSuchKey(key.to_string())))
+}
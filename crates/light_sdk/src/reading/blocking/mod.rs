@@ -17,6 +17,7 @@ pub mod governance;
 pub mod pgf;
 pub mod pos;
 pub mod tx;
+pub mod verified;
 
 /// Query the address of the native token
 pub fn query_native_token(tendermint_addr: &str) -> Result<Address, Error> {
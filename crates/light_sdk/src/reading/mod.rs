@@ -2,3 +2,4 @@
 pub mod asynchronous;
 #[cfg(feature = "blocking")]
 pub mod blocking;
+pub mod proof;
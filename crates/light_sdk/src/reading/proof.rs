@@ -0,0 +1,251 @@
+//! Verification of the storage proofs returned by a Namada node.
+//!
+//! A node can attach ICS23 proofs to the value of a storage key. A proof
+//! consists of two commitment proofs: the first one proves the key-value pair
+//! against the root of the sub-tree of the key's store, and the second one
+//! proves this sub-tree root against the root of the base tree, which is the
+//! app hash committed by CometBFT. Verifying a proof against an app hash taken
+//! from a header checked by a light client removes the need to trust the full
+//! node that served the query.
+//!
+//! Note that the app hash in the header at height `h + 1` commits to the
+//! state after the block at height `h`, so queries have to be made at the
+//! height of the [`TrustedAppHash`] and not at the height of the header.
+
+use ics23::commitment_proof::Proof as Ics23Proof;
+use ics23::{CommitmentProof, HostFunctionsManager};
+use namada_sdk::chain::BlockHeight;
+use namada_sdk::error::{Error, QueryError};
+use namada_sdk::state::ics23_specs::{ibc_proof_specs, proof_specs};
+use namada_sdk::state::{Sha256Hasher, StoreType};
+use namada_sdk::storage::Key;
+use namada_sdk::tendermint::block::Header;
+use namada_sdk::tendermint::merkle::proof::ProofOps;
+use prost::Message;
+
+/// The root of the state at a given height, taken from a trusted header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedAppHash {
+    /// The height of the state committed by the app hash
+    pub height: BlockHeight,
+    /// The app hash, i.e. the root of the base Merkle tree
+    pub app_hash: Vec<u8>,
+}
+
+impl TrustedAppHash {
+    /// Take the app hash from a CometBFT header that was verified by a light
+    /// client. The app hash of a header commits to the state of the previous
+    /// block, so the header of the first block cannot be used.
+    pub fn from_verified_header(header: &Header) -> Result<Self, Error> {
+        let height = header
+            .height
+            .value()
+            .checked_sub(1)
+            .filter(|height| *height > 0)
+            .ok_or_else(|| {
+                invalid_proof("The first block doesn't commit to any state")
+            })?;
+        Ok(Self {
+            height: BlockHeight(height),
+            app_hash: header.app_hash.as_bytes().to_vec(),
+        })
+    }
+}
+
+/// Verify the proof of the value of a storage key against a trusted app hash.
+/// When `value` is `None`, the proof must be a proof of the key's absence,
+/// which the node can only produce for keys in the IBC store.
+pub fn verify_storage_proof(
+    trusted: &TrustedAppHash,
+    key: &Key,
+    value: Option<&[u8]>,
+    proof: &ProofOps,
+) -> Result<(), Error> {
+    let (store_type, sub_key) = StoreType::sub_key(key)
+        .map_err(|err| invalid_proof(err.to_string()))?;
+    let specs = match store_type {
+        StoreType::Ibc => ibc_proof_specs::<Sha256Hasher>(),
+        StoreType::Account | StoreType::PoS | StoreType::NoDiff => {
+            proof_specs::<Sha256Hasher>()
+        }
+        StoreType::Base | StoreType::BridgePool | StoreType::CommitData => {
+            return Err(invalid_proof(format!(
+                "Keys of the {store_type} store cannot be proven with ICS23"
+            )));
+        }
+    };
+
+    let [sub_op, base_op] = proof.ops.as_slice() else {
+        return Err(invalid_proof(format!(
+            "Expected 2 proof operations, got {}",
+            proof.ops.len()
+        )));
+    };
+    let key_str = key.to_string();
+    if sub_op.key != key_str.as_bytes() || base_op.key != key_str.as_bytes() {
+        return Err(invalid_proof(format!(
+            "The proof is not for the key {key}"
+        )));
+    }
+    let sub_proof = decode_commitment_proof(&sub_op.data)?;
+    let base_proof = decode_commitment_proof(&base_op.data)?;
+
+    // First, verify the sub proof and compute the root of the sub-tree
+    let sub_key = sub_key.to_string();
+    let sub_root = match value {
+        Some(value) => {
+            let Some(Ics23Proof::Exist(existence_proof)) = &sub_proof.proof
+            else {
+                return Err(invalid_proof(format!(
+                    "Expected an existence proof for the key {key}"
+                )));
+            };
+            let sub_root = ics23::calculate_existence_root::<
+                HostFunctionsManager,
+            >(existence_proof)
+            .map_err(|err| invalid_proof(err.to_string()))?;
+            if !ics23::verify_membership::<HostFunctionsManager>(
+                &sub_proof,
+                &specs[0],
+                &sub_root,
+                sub_key.as_bytes(),
+                value,
+            ) {
+                return Err(invalid_proof(format!(
+                    "The value of the key {key} is not in the sub-tree"
+                )));
+            }
+            sub_root
+        }
+        None => {
+            let Some(Ics23Proof::Nonexist(non_existence_proof)) =
+                &sub_proof.proof
+            else {
+                return Err(invalid_proof(format!(
+                    "Expected a non-existence proof for the key {key}"
+                )));
+            };
+            let neighbor = non_existence_proof
+                .left
+                .as_ref()
+                .or(non_existence_proof.right.as_ref())
+                .ok_or_else(|| {
+                    invalid_proof("The non-existence proof has no neighbor")
+                })?;
+            let sub_root = ics23::calculate_existence_root::<
+                HostFunctionsManager,
+            >(neighbor)
+            .map_err(|err| invalid_proof(err.to_string()))?;
+            if !ics23::verify_non_membership::<HostFunctionsManager>(
+                &sub_proof,
+                &specs[0],
+                &sub_root,
+                sub_key.as_bytes(),
+            ) {
+                return Err(invalid_proof(format!(
+                    "The absence of the key {key} is not proven"
+                )));
+            }
+            sub_root
+        }
+    };
+
+    // Then, verify that the sub-tree root is committed by the app hash
+    if !ics23::verify_membership::<HostFunctionsManager>(
+        &base_proof,
+        &specs[1],
+        &trusted.app_hash,
+        store_type.to_string().as_bytes(),
+        &sub_root,
+    ) {
+        return Err(invalid_proof(format!(
+            "The {store_type} sub-tree is not committed by the trusted app \
+             hash at height {}",
+            trusted.height
+        )));
+    }
+    Ok(())
+}
+
+fn decode_commitment_proof(data: &[u8]) -> Result<CommitmentProof, Error> {
+    CommitmentProof::decode(data).map_err(|err| {
+        invalid_proof(format!("Failed to decode the commitment proof: {err}"))
+    })
+}
+
+fn invalid_proof(msg: impl ToString) -> Error {
+    Error::Query(QueryError::InvalidProof(msg.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use namada_sdk::address::{Address, InternalAddress};
+    use namada_sdk::state::{MembershipProof, MerkleTree};
+    use namada_sdk::storage::KeySeg;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_storage_proof() {
+        let mut tree = MerkleTree::<Sha256Hasher>::default();
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::PoS).to_db_key().into();
+        let pos_key = key_prefix.push(&"test".to_string()).unwrap();
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::Ibc).to_db_key().into();
+        let ibc_key = key_prefix.push(&"test".to_string()).unwrap();
+        let ibc_non_key = key_prefix.push(&"test2".to_string()).unwrap();
+        let pos_val = [1u8; 8].to_vec();
+        let ibc_val = [2u8; 8].to_vec();
+        tree.update(&pos_key, pos_val.clone()).unwrap();
+        tree.update(&ibc_key, ibc_val.clone()).unwrap();
+
+        let trusted = TrustedAppHash {
+            height: BlockHeight(1),
+            app_hash: tree.root().0.to_vec(),
+        };
+
+        let proof = match tree
+            .get_sub_tree_existence_proof(
+                std::array::from_ref(&pos_key),
+                vec![&pos_val],
+            )
+            .unwrap()
+        {
+            MembershipProof::ICS23(proof) => proof,
+            _ => panic!("Test failed"),
+        };
+        let proof: ProofOps =
+            tree.get_sub_tree_proof(&pos_key, proof).unwrap().into();
+
+        verify_storage_proof(&trusted, &pos_key, Some(&pos_val), &proof)
+            .unwrap();
+        // A different value must be rejected
+        assert!(
+            verify_storage_proof(&trusted, &pos_key, Some(&[0u8; 8]), &proof)
+                .is_err()
+        );
+        // A proof of another key must be rejected
+        assert!(
+            verify_storage_proof(&trusted, &ibc_key, Some(&pos_val), &proof)
+                .is_err()
+        );
+        // A different app hash must be rejected
+        let untrusted = TrustedAppHash {
+            height: BlockHeight(1),
+            app_hash: vec![0u8; 32],
+        };
+        assert!(
+            verify_storage_proof(&untrusted, &pos_key, Some(&pos_val), &proof)
+                .is_err()
+        );
+
+        // The absence of a key in the IBC store can be proven
+        let proof: ProofOps =
+            tree.get_non_existence_proof(&ibc_non_key).unwrap().into();
+        verify_storage_proof(&trusted, &ibc_non_key, None, &proof).unwrap();
+        assert!(
+            verify_storage_proof(&trusted, &ibc_key, None, &proof).is_err()
+        );
+    }
+}
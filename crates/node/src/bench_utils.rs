@@ -278,6 +278,7 @@ impl BenchShellInner {
             batched_tx.cmt,
             &mut self.inner.vp_wasm_cache,
             &mut self.inner.tx_wasm_cache,
+            None,
         )
         .unwrap()
     }
@@ -883,7 +884,10 @@ impl Client for BenchShell {
 
         let shell = self.read();
 
-        if request.path == RPC.shell().dry_run_tx_path() {
+        if request.path == RPC.shell().dry_run_tx_path()
+            || request.path == RPC.shell().dry_run_tx_with_trace_path()
+            || request.path == RPC.shell().dry_run_tx_with_diff_path()
+        {
             dry_run_tx(
                 // This is safe because nothing else is using `self.state`
                 // concurrently and the `TempWlState` will be dropped right
@@ -1,14 +1,18 @@
 //! The ledger modules
 
 use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
 
 use namada_sdk::borsh::BorshSerializeExt;
 use namada_sdk::gas::{GasMetering, TxGasMeter};
 use namada_sdk::parameters;
-use namada_sdk::queries::{EncodedResponseQuery, RequestQuery};
+use namada_sdk::queries::{EncodedResponseQuery, RequestQuery, RPC};
+use namada_sdk::state::write_log::StorageModification;
 use namada_sdk::state::{
-    DBIter, Result, ResultExt, StorageHasher, TxIndex, DB,
+    DBIter, Result, ResultExt, StateRead, StorageHasher, TxIndex, DB,
 };
+use namada_sdk::tx::data::diff::{DiffedDryRunResult, KeyDiff};
+use namada_sdk::tx::data::trace::TracedDryRunResult;
 use namada_sdk::tx::data::{DryRunResult, GasLimit, TxResult, TxType};
 use namada_sdk::tx::Tx;
 use namada_vm::wasm::{TxCache, VpCache};
@@ -17,7 +21,11 @@ use namada_vm::WasmCacheAccess;
 use crate::protocol;
 use crate::protocol::ShellParams;
 
-/// Dry run a transaction
+/// Dry run a transaction. When requested via the `dry_run_tx_with_trace` path,
+/// the host calls of the tx and of the WASM VPs are traced and returned
+/// together with the result. When requested via the `dry_run_tx_with_diff`
+/// path, the storage changes, the events and the triggered VPs of the tx are
+/// returned together with the result.
 pub fn dry_run_tx<D, H, CA>(
     mut state: namada_sdk::state::TempWlState<'static, D, H>,
     mut vp_wasm_cache: VpCache<CA>,
@@ -33,6 +41,8 @@ where
     tx.validate_tx().into_storage_result()?;
 
     let gas_scale = parameters::get_gas_scale(&state)?;
+    let mut traces = (request.path == RPC.shell().dry_run_tx_with_trace_path())
+        .then(BTreeMap::new);
 
     // Wrapper dry run to allow estimating the entire gas cost of a transaction
     let (wrapper_hash, extended_tx_result, tx_gas_meter) =
@@ -87,6 +97,7 @@ where
         &mut state,
         &mut vp_wasm_cache,
         &mut tx_wasm_cache,
+        traces.as_mut(),
     )
     .map_err(|err| err.error)
     .into_storage_result()?;
@@ -99,14 +110,84 @@ where
             .get_whole_gas_units(gas_scale),
     );
 
+    let data = if let Some(traces) = traces {
+        TracedDryRunResult {
+            result: dry_run_result,
+            traces,
+        }
+        .serialize_to_vec()
+    } else if request.path == RPC.shell().dry_run_tx_with_diff_path() {
+        let mut events = BTreeSet::new();
+        let mut triggered_vps = BTreeSet::new();
+        for result in dry_run_result
+            .0
+            .iter()
+            .filter_map(|(_, res)| res.as_ref().ok())
+        {
+            // Only the events of the accepted txs would be emitted
+            if result.is_accepted() {
+                events.extend(result.events.iter().cloned());
+            }
+            triggered_vps.extend(
+                result
+                    .vps_result
+                    .accepted_vps
+                    .iter()
+                    .chain(&result.vps_result.rejected_vps)
+                    .cloned(),
+            );
+        }
+        DiffedDryRunResult {
+            diff: state_diff(&state)?,
+            result: dry_run_result,
+            events,
+            triggered_vps,
+        }
+        .serialize_to_vec()
+    } else {
+        dry_run_result.serialize_to_vec()
+    };
+
     Ok(EncodedResponseQuery {
-        data: dry_run_result.serialize_to_vec(),
+        data,
         proof: None,
         info: Default::default(),
         height: state.in_mem().get_last_block_height(),
     })
 }
 
+/// Compute the changes of the wrapper tx, which is committed to the block
+/// write log, and of the inner txs committed to the batch against the last
+/// committed state
+fn state_diff<D, H>(
+    state: &namada_sdk::state::TempWlState<'static, D, H>,
+) -> Result<Vec<KeyDiff>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    state
+        .write_log()
+        .get_block_and_batch_modifications()
+        .into_iter()
+        .map(|(key, modification)| {
+            let (pre, _gas) = state.db_read(key)?;
+            let post = match modification {
+                StorageModification::Write { value } => Some(value.clone()),
+                StorageModification::Delete => None,
+                StorageModification::InitAccount { vp_code_hash } => {
+                    Some(vp_code_hash.to_vec())
+                }
+            };
+            Ok(KeyDiff {
+                key: key.clone(),
+                pre,
+                post,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use namada_sdk::borsh::{BorshDeserialize, BorshSerializeExt};
@@ -124,6 +205,7 @@ mod test {
     use namada_sdk::tx::data::TxType;
     use namada_sdk::tx::{Code, Data, Tx};
     use namada_sdk::{address, token};
+    use namada_test_utils::tx_data::TxWriteData;
     use namada_test_utils::TestWasms;
     use namada_vm::wasm::{TxCache, VpCache};
     use namada_vm::{wasm, WasmCacheRoAccess};
@@ -222,7 +304,10 @@ mod test {
             // TODO(namada#3240): this is a hack to propagate errors to the
             // caller, we should really permit error types other
             // than [`std::io::Error`]
-            if request.path == RPC.shell().dry_run_tx_path() {
+            if request.path == RPC.shell().dry_run_tx_path()
+                || request.path == RPC.shell().dry_run_tx_with_trace_path()
+                || request.path == RPC.shell().dry_run_tx_with_diff_path()
+            {
                 dry_run_tx(
                     // This is safe because nothing else is using `self.state`
                     // concurrently and the `TempWlState` will be dropped right
@@ -371,4 +456,145 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_dry_run_tx_with_trace() {
+        // Initialize the `TestClient`
+        let mut client = TestClient::new(RPC);
+        // store the wasm code
+        let tx_read = TestWasms::TxReadStorageKey.read_bytes();
+        let tx_hash = Hash::sha256(&tx_read);
+        let key = Key::wasm_code(&tx_hash);
+        let len_key = Key::wasm_code_len(&tx_hash);
+        client
+            .state
+            .db_write(&key, tx_read.serialize_to_vec())
+            .unwrap();
+        client
+            .state
+            .db_write(&len_key, (tx_read.len() as u64).serialize_to_vec())
+            .unwrap();
+        // store the value read by the tx
+        let read_key = Key::parse("some_key").unwrap();
+        client
+            .state
+            .db_write(&read_key, vec![42_u8].serialize_to_vec())
+            .unwrap();
+
+        let mut outer_tx = Tx::from_type(TxType::Raw);
+        outer_tx.header.chain_id = client.state.in_mem().chain_id.clone();
+        outer_tx.set_code(Code::from_hash(tx_hash, None));
+        outer_tx.set_data(Data::new(read_key.serialize_to_vec()));
+        let cmt = outer_tx.first_commitments().unwrap();
+        let tx_bytes = outer_tx.to_bytes();
+
+        // The result is the same as without tracing
+        let result = RPC
+            .shell()
+            .dry_run_tx_with_trace(&client, Some(tx_bytes), None, false)
+            .await
+            .unwrap()
+            .data;
+        assert!(
+            result
+                .result
+                .0
+                .get_inner_tx_result(None, either::Right(cmt))
+                .unwrap()
+                .as_ref()
+                .unwrap()
+                .is_accepted()
+        );
+
+        // The trace is indexed by the same inner tx hash as the result
+        let inner_tx_hash = namada_sdk::tx::data::compute_inner_tx_hash(
+            None,
+            either::Right(cmt),
+        );
+        let trace = &result.traces[&inner_tx_hash];
+        assert!(trace.vps.is_empty());
+        let read_call = trace
+            .tx
+            .host_calls
+            .iter()
+            .find(|call| call.name == "tx_read")
+            .expect("The storage read must be traced");
+        assert_eq!(read_call.keys, vec![read_key]);
+        assert!(u64::from(read_call.gas.clone()) > 0);
+        // The whole execution costs more than its host calls
+        let host_calls_gas: u64 = trace
+            .tx
+            .host_calls
+            .iter()
+            .map(|call| u64::from(call.gas.clone()))
+            .sum();
+        assert!(u64::from(trace.tx.gas.clone()) > host_calls_gas);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_tx_with_diff() {
+        // Initialize the `TestClient`
+        let mut client = TestClient::new(RPC);
+        // store the wasm code
+        let tx_write = TestWasms::TxWriteStorageKey.read_bytes();
+        let tx_hash = Hash::sha256(&tx_write);
+        let key = Key::wasm_code(&tx_hash);
+        let len_key = Key::wasm_code_len(&tx_hash);
+        client
+            .state
+            .db_write(&key, tx_write.serialize_to_vec())
+            .unwrap();
+        client
+            .state
+            .db_write(&len_key, (tx_write.len() as u64).serialize_to_vec())
+            .unwrap();
+        // store the value overwritten by the tx
+        let write_key = Key::parse("some_key").unwrap();
+        let pre = "pre".to_string().serialize_to_vec();
+        client.state.db_write(&write_key, &pre).unwrap();
+
+        let post = "post".to_string().serialize_to_vec();
+        let mut outer_tx = Tx::from_type(TxType::Raw);
+        outer_tx.header.chain_id = client.state.in_mem().chain_id.clone();
+        outer_tx.set_code(Code::from_hash(tx_hash, None));
+        outer_tx.set_data(Data::new(
+            TxWriteData {
+                key: write_key.clone(),
+                value: post.clone(),
+            }
+            .serialize_to_vec(),
+        ));
+        let cmt = outer_tx.first_commitments().unwrap();
+        let tx_bytes = outer_tx.to_bytes();
+
+        let result = RPC
+            .shell()
+            .dry_run_tx_with_diff(&client, Some(tx_bytes), None, false)
+            .await
+            .unwrap()
+            .data;
+        assert!(
+            result
+                .result
+                .0
+                .get_inner_tx_result(None, either::Right(cmt))
+                .unwrap()
+                .as_ref()
+                .unwrap()
+                .is_accepted()
+        );
+        assert_eq!(
+            result.diff,
+            vec![KeyDiff {
+                key: write_key.clone(),
+                pre: Some(pre.clone()),
+                post: Some(post),
+            }]
+        );
+        assert!(result.events.is_empty());
+        assert!(result.triggered_vps.is_empty());
+
+        // The dry run must not have changed the state
+        assert_eq!(client.state.db_read(&write_key).unwrap().0, Some(pre));
+    }
 }
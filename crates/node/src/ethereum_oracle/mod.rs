@@ -1,5 +1,6 @@
 pub mod control;
 pub mod events;
+pub mod quorum;
 pub mod test_tools;
 
 use std::ops::ControlFlow;
@@ -10,7 +11,7 @@ use ethbridge_events::{event_codecs, EventKind};
 use itertools::Either;
 use namada_sdk::control_flow::time::{Constant, Duration, Instant, Sleep};
 use namada_sdk::eth_bridge::ethers::providers::{Http, Middleware, Provider};
-use namada_sdk::eth_bridge::oracle::config::Config;
+use namada_sdk::eth_bridge::oracle::config::{Config, RpcEndpoints};
 use namada_sdk::eth_bridge::{eth_syncing_status_timeout, ethers, SyncStatus};
 use namada_sdk::ethereum_events::EthereumEvent;
 use namada_sdk::{ethereum_structs, hints};
@@ -21,6 +22,7 @@ use tokio::sync::mpsc::Sender as BoundedSender;
 use tokio::task::LocalSet;
 
 use self::events::PendingEvent;
+use self::quorum::QuorumClient;
 use super::abortable::AbortableSpawner;
 use crate::oracle::control::Command;
 
@@ -44,6 +46,11 @@ pub enum Error {
     MoreConfirmations,
     #[error("The Ethereum oracle timed out")]
     Timeout,
+    #[error(
+        "No quorum of {0} Ethereum RPC endpoints was reached ({1} of {2} \
+         responded)"
+    )]
+    NoQuorum(usize, usize, usize),
 }
 
 /// Convert values to [`ethabi`] Ethereum event logs.
@@ -276,38 +283,56 @@ async fn await_initial_configuration(
 }
 
 /// Set up an Oracle and run the process where the Oracle
-/// processes and forwards Ethereum events to the ledger
+/// processes and forwards Ethereum events to the ledger. When several
+/// endpoints are given, only the events that a quorum of them agree on
+/// are forwarded.
 pub fn run_oracle<C: RpcClient>(
-    url: impl AsRef<str>,
+    endpoints: RpcEndpoints,
     sender: BoundedSender<EthereumEvent>,
     control: control::Receiver,
     last_processed_block: last_processed_block::Sender,
     spawner: &mut AbortableSpawner,
 ) {
-    let url = url.as_ref().to_owned();
     spawner
         .abortable("Ethereum Oracle", move |aborter| {
             let rt = tokio::runtime::Handle::current();
             rt.block_on(async move {
                 LocalSet::new()
                     .run_until(async move {
+                        let urls = endpoints.urls();
+                        let quorum = endpoints.quorum();
                         tracing::info!(
-                            ?url,
+                            ?urls,
+                            %quorum,
                             "Ethereum event oracle is starting"
                         );
 
-                        let oracle = Oracle::<C>::new(
-                            Either::Right(&url),
-                            sender,
-                            last_processed_block,
-                            DEFAULT_BACKOFF,
-                            DEFAULT_CEILING,
-                            control,
-                        );
-                        run_oracle_aux(oracle).await;
+                        if let [url] = urls {
+                            let oracle = Oracle::<C>::new(
+                                Either::Right(url),
+                                sender,
+                                last_processed_block,
+                                DEFAULT_BACKOFF,
+                                DEFAULT_CEILING,
+                                control,
+                            );
+                            run_oracle_aux(oracle).await;
+                        } else {
+                            let oracle = Oracle::new(
+                                Either::Left(QuorumClient::<C>::new(
+                                    &endpoints,
+                                )),
+                                sender,
+                                last_processed_block,
+                                DEFAULT_BACKOFF,
+                                DEFAULT_CEILING,
+                                control,
+                            );
+                            run_oracle_aux(oracle).await;
+                        }
 
                         tracing::info!(
-                            ?url,
+                            ?urls,
                             "Ethereum event oracle is no longer running"
                         );
                     })
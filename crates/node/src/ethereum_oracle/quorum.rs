@@ -0,0 +1,417 @@
+//! An [`RpcClient`] that follows several Ethereum RPC endpoints, and only
+//! reports the block heights and events that a quorum of them agree on.
+//!
+//! Following a single endpoint means trusting it: an endpoint that lies
+//! about the logs of a block can make a validator vote on bad
+//! [`EthereumEvent`](namada_sdk::ethereum_events::EthereumEvent)s. With a
+//! quorum of endpoints, a minority of faulty endpoints can neither forge nor
+//! hide events, and a minority of stalled endpoints doesn't stop the oracle.
+
+use std::cell::RefCell;
+use std::num::NonZeroUsize;
+
+use async_trait::async_trait;
+use ethabi::Address;
+use futures::future::join_all;
+use namada_sdk::control_flow::time::{Duration, Instant};
+use namada_sdk::eth_bridge::oracle::config::RpcEndpoints;
+use namada_sdk::eth_bridge::SyncStatus;
+use namada_sdk::ethereum_structs;
+
+use super::{Error, IntoEthAbiLog, RpcClient};
+
+/// The number of consecutive failed or dissenting responses after which an
+/// endpoint is considered to be unhealthy.
+const UNHEALTHY_THRESHOLD: u64 = 3;
+
+/// The health of an Ethereum RPC endpoint, as observed by the oracle.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EndpointHealth {
+    /// The number of consecutive failed or dissenting responses
+    pub consecutive_failures: u64,
+    /// The total number of failed requests
+    pub failures: u64,
+    /// The total number of responses that disagreed with the quorum
+    pub disagreements: u64,
+    /// The last error returned by the endpoint
+    pub last_error: Option<String>,
+}
+
+impl EndpointHealth {
+    /// Check if the endpoint has recently been responding in agreement with
+    /// the quorum.
+    pub fn is_healthy(&self) -> bool {
+        self.consecutive_failures < UNHEALTHY_THRESHOLD
+    }
+}
+
+/// An Ethereum RPC endpoint followed by a [`QuorumClient`].
+struct Endpoint<C> {
+    url: String,
+    client: C,
+    health: RefCell<EndpointHealth>,
+}
+
+impl<C> Endpoint<C> {
+    /// Record a response in agreement with the quorum.
+    fn record_agreement(&self) {
+        let mut health = self.health.borrow_mut();
+        if !health.is_healthy() {
+            tracing::info!(url = %self.url, "Ethereum RPC endpoint recovered");
+        }
+        health.consecutive_failures = 0;
+    }
+
+    /// Record a response in disagreement with the quorum.
+    fn record_disagreement(&self) {
+        let mut health = self.health.borrow_mut();
+        health.disagreements = health.disagreements.saturating_add(1);
+        self.record_consecutive_failure(&mut health);
+    }
+
+    /// Record a failed request.
+    fn record_failure(&self, error: &Error) {
+        let mut health = self.health.borrow_mut();
+        health.failures = health.failures.saturating_add(1);
+        health.last_error = Some(error.to_string());
+        self.record_consecutive_failure(&mut health);
+    }
+
+    fn record_consecutive_failure(&self, health: &mut EndpointHealth) {
+        let was_healthy = health.is_healthy();
+        health.consecutive_failures =
+            health.consecutive_failures.saturating_add(1);
+        if was_healthy && !health.is_healthy() {
+            tracing::warn!(
+                url = %self.url,
+                failures = health.failures,
+                disagreements = health.disagreements,
+                last_error = ?health.last_error,
+                "Ethereum RPC endpoint is unhealthy"
+            );
+        }
+    }
+}
+
+/// A client that follows several Ethereum RPC endpoints, and only reports
+/// the block heights and events that a quorum of them agree on.
+pub struct QuorumClient<C> {
+    endpoints: Vec<Endpoint<C>>,
+    quorum: NonZeroUsize,
+}
+
+impl<C: RpcClient> QuorumClient<C> {
+    /// Connect to the given endpoints.
+    pub fn new(endpoints: &RpcEndpoints) -> Self {
+        let clients = endpoints
+            .urls()
+            .iter()
+            .map(|url| C::new_client(url))
+            .collect();
+        Self::with_clients(endpoints, clients)
+    }
+
+    /// Follow the given endpoints with already instantiated clients, one
+    /// per URL.
+    pub fn with_clients(endpoints: &RpcEndpoints, clients: Vec<C>) -> Self {
+        assert_eq!(
+            endpoints.urls().len(),
+            clients.len(),
+            "There must be exactly one client per Ethereum RPC endpoint"
+        );
+        Self {
+            endpoints: endpoints
+                .urls()
+                .iter()
+                .cloned()
+                .zip(clients)
+                .map(|(url, client)| Endpoint {
+                    url,
+                    client,
+                    health: Default::default(),
+                })
+                .collect(),
+            quorum: endpoints.quorum(),
+        }
+    }
+
+    /// The health of each endpoint, by URL.
+    pub fn health(&self) -> Vec<(String, EndpointHealth)> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| {
+                (endpoint.url.clone(), endpoint.health.borrow().clone())
+            })
+            .collect()
+    }
+
+    /// Find the response that a quorum of the endpoints agree on, and
+    /// update the health of the endpoints accordingly.
+    fn agree<T>(&self, responses: Vec<Result<T, Error>>) -> Result<T, Error>
+    where
+        T: Clone + PartialEq,
+    {
+        let mut tally: Vec<(&T, usize)> = vec![];
+        for response in responses.iter().filter_map(|r| r.as_ref().ok()) {
+            match tally.iter_mut().find(|(other, _)| *other == response) {
+                Some((_, votes)) => *votes = votes.saturating_add(1),
+                None => tally.push((response, 1)),
+            }
+        }
+        // The quorum is a majority, so at most one response can reach it
+        let agreed = tally
+            .into_iter()
+            .find(|(_, votes)| *votes >= self.quorum.get())
+            .map(|(response, _)| response.clone());
+
+        for (endpoint, response) in self.endpoints.iter().zip(&responses) {
+            match (response, &agreed) {
+                (Err(error), _) => endpoint.record_failure(error),
+                (Ok(response), Some(agreed)) if response == agreed => {
+                    endpoint.record_agreement()
+                }
+                (Ok(_), Some(_)) => endpoint.record_disagreement(),
+                // Without a quorum, we can't tell which endpoints are wrong
+                (Ok(_), None) => {}
+            }
+        }
+
+        agreed.ok_or_else(|| {
+            Error::NoQuorum(
+                self.quorum.get(),
+                responses.iter().filter(|r| r.is_ok()).count(),
+                self.endpoints.len(),
+            )
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: RpcClient> RpcClient for QuorumClient<C> {
+    type Log = ethabi::RawLog;
+
+    #[inline]
+    fn new_client(rpc_url: &str) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(&RpcEndpoints::single(rpc_url))
+    }
+
+    async fn check_events_in_block(
+        &self,
+        block: ethereum_structs::BlockHeight,
+        address: Address,
+        abi_signature: &str,
+    ) -> Result<Vec<Self::Log>, Error> {
+        let responses = join_all(self.endpoints.iter().map(|endpoint| {
+            let block = block.clone();
+            async move {
+                let logs = endpoint
+                    .client
+                    .check_events_in_block(block, address, abi_signature)
+                    .await?;
+                Ok::<_, Error>(
+                    logs.into_iter()
+                        .map(IntoEthAbiLog::into_ethabi_log)
+                        .collect::<Vec<_>>(),
+                )
+            }
+        }))
+        .await;
+        self.agree(responses)
+    }
+
+    async fn syncing(
+        &self,
+        last_processed_block: Option<&ethereum_structs::BlockHeight>,
+        backoff: Duration,
+        deadline: Instant,
+    ) -> Result<SyncStatus, Error> {
+        let responses = join_all(self.endpoints.iter().map(|endpoint| {
+            endpoint
+                .client
+                .syncing(last_processed_block, backoff, deadline)
+        }))
+        .await;
+
+        let mut heights = vec![];
+        let mut behind = 0_usize;
+        for (endpoint, response) in self.endpoints.iter().zip(responses) {
+            match response {
+                Ok(SyncStatus::AtHeight(height)) => {
+                    endpoint.record_agreement();
+                    heights.push(height);
+                }
+                // An endpoint without new blocks isn't necessarily faulty,
+                // new blocks are only produced every few seconds
+                Ok(SyncStatus::Syncing) | Err(Error::FallenBehind) => {
+                    behind = behind.saturating_add(1);
+                }
+                Err(error) => endpoint.record_failure(&error),
+            }
+        }
+
+        // Use the highest block that a quorum of the endpoints have reached
+        let quorum = self.quorum.get();
+        heights.sort_unstable_by(|a, b| b.cmp(a));
+        heights.truncate(quorum);
+        if heights.len() == quorum {
+            let height = heights.pop().expect("The quorum is not zero");
+            return Ok(SyncStatus::AtHeight(height));
+        }
+        let responded = heights.len().saturating_add(behind);
+        if responded >= quorum {
+            Err(Error::FallenBehind)
+        } else {
+            Err(Error::NoQuorum(quorum, responded, self.endpoints.len()))
+        }
+    }
+
+    #[inline(always)]
+    fn may_recover(&self, error: &Error) -> bool {
+        // Faulty or stalled endpoints may recover, and a quorum be reached
+        // again, as long as the ledger is listening
+        !matches!(error, Error::Channel(_, _))
+    }
+}
+
+#[cfg(test)]
+mod test_quorum {
+    use ethbridge_bridge_events::TransferToChainFilter;
+    use num256::Uint256;
+    use tokio::sync::oneshot::{channel, Receiver};
+
+    use super::*;
+    use crate::ethereum_oracle::test_tools::event_log::GetLog;
+    use crate::ethereum_oracle::test_tools::mock_web3_client::{
+        event_signature, TestCmd, Web3Client, Web3Controller,
+    };
+
+    /// Set up a quorum client following `n` mock endpoints.
+    fn setup(n: usize) -> (QuorumClient<Web3Client>, Vec<Web3Controller>) {
+        let urls = (0..n).map(|i| format!("http://127.0.0.1:{i}")).collect();
+        let endpoints = RpcEndpoints::new(urls, None).unwrap();
+        let clients: Vec<_> = (0..n).map(|_| Web3Client::setup().1).collect();
+        let controllers = clients.iter().map(Web3Client::controller).collect();
+        (QuorumClient::with_clients(&endpoints, clients), controllers)
+    }
+
+    /// Add a new bridge event at the given height to a mock endpoint.
+    fn new_event(controller: &Web3Controller, nonce: u64) -> Receiver<()> {
+        let (seen, seen_recv) = channel();
+        controller.apply_cmd(TestCmd::NewEvent {
+            event_type: event_signature::<TransferToChainFilter>(),
+            log: TransferToChainFilter {
+                nonce: nonce.into(),
+                transfers: vec![],
+                confirmations: 100.into(),
+            }
+            .get_log(),
+            height: 1,
+            seen,
+        });
+        seen_recv
+    }
+
+    async fn check_events(
+        client: &QuorumClient<Web3Client>,
+    ) -> Result<Vec<ethabi::RawLog>, Error> {
+        client
+            .check_events_in_block(
+                1u64.into(),
+                Address::zero(),
+                &event_signature::<TransferToChainFilter>(),
+            )
+            .await
+    }
+
+    /// Test that the events that a quorum of the endpoints agree on are
+    /// reported, and that the dissenting endpoint is tracked.
+    #[tokio::test]
+    async fn test_events_with_quorum() {
+        let (client, controllers) = setup(3);
+        let _seen = [
+            new_event(&controllers[0], 0),
+            new_event(&controllers[1], 0),
+            new_event(&controllers[2], 1),
+        ];
+
+        let logs = check_events(&client).await.unwrap();
+        assert_eq!(
+            logs,
+            vec![
+                TransferToChainFilter {
+                    nonce: 0.into(),
+                    transfers: vec![],
+                    confirmations: 100.into(),
+                }
+                .get_log()
+            ]
+        );
+        let health = client.health();
+        assert_eq!(health[0].1, EndpointHealth::default());
+        assert_eq!(health[1].1, EndpointHealth::default());
+        assert_eq!(health[2].1.disagreements, 1);
+        assert_eq!(health[2].1.consecutive_failures, 1);
+    }
+
+    /// Test that events reported by a minority of the endpoints are not
+    /// reported, and that the absence of events is not assumed either.
+    #[tokio::test]
+    async fn test_events_without_quorum() {
+        let (client, controllers) = setup(3);
+        let _seen = new_event(&controllers[0], 0);
+        controllers[1].apply_cmd(TestCmd::Unresponsive);
+
+        let result = check_events(&client).await;
+        assert!(matches!(result, Err(Error::NoQuorum(2, 2, 3))));
+        assert!(client.may_recover(&result.unwrap_err()));
+        let health = client.health();
+        assert_eq!(health[0].1.disagreements, 0);
+        assert_eq!(health[1].1.failures, 1);
+        assert_eq!(health[2].1.disagreements, 0);
+    }
+
+    /// Test that an endpoint that keeps failing is marked as unhealthy,
+    /// without preventing the quorum from being reached, and that it
+    /// recovers once it responds again.
+    #[tokio::test]
+    async fn test_unhealthy_endpoint() {
+        let (client, controllers) = setup(3);
+        controllers[2].apply_cmd(TestCmd::Unresponsive);
+
+        for _ in 0..UNHEALTHY_THRESHOLD {
+            assert_eq!(check_events(&client).await.unwrap(), vec![]);
+        }
+        let health = client.health();
+        assert!(health[0].1.is_healthy());
+        assert!(health[1].1.is_healthy());
+        assert!(!health[2].1.is_healthy());
+        assert_eq!(health[2].1.failures, UNHEALTHY_THRESHOLD);
+        assert!(health[2].1.last_error.is_some());
+
+        controllers[2].apply_cmd(TestCmd::Normal);
+        assert_eq!(check_events(&client).await.unwrap(), vec![]);
+        assert!(client.health()[2].1.is_healthy());
+    }
+
+    /// Test that the reported height is the highest height reached by a
+    /// quorum of the endpoints.
+    #[tokio::test]
+    async fn test_syncing_with_quorum() {
+        let (client, controllers) = setup(3);
+        for (controller, height) in controllers.iter().zip([10u32, 30, 20]) {
+            controller.apply_cmd(TestCmd::NewHeight(Uint256::from(height)));
+        }
+
+        let status = client
+            .syncing(None, Duration::from_secs(1), Instant::now())
+            .await
+            .unwrap();
+        assert!(matches!(
+            status,
+            SyncStatus::AtHeight(height) if height == Uint256::from(20u32)
+        ));
+    }
+}
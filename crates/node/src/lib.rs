@@ -19,6 +19,7 @@ pub mod bench_utils;
 mod broadcaster;
 mod dry_run_tx;
 pub mod ethereum_oracle;
+pub mod metrics;
 pub mod protocol;
 pub mod shell;
 pub mod shims;
@@ -61,7 +62,7 @@ use self::shims::abcipp_shim::AbciService;
 use crate::broadcaster::Broadcaster;
 use crate::config::{ethereum_bridge, TendermintMode};
 use crate::ethereum_oracle as oracle;
-use crate::shell::{Error, MempoolTxType, Shell};
+use crate::shell::{Error, MempoolTxType, ResultCode, Shell};
 use crate::shims::abcipp_shim::AbcippShim;
 use crate::shims::abcipp_shim_types::shim::{Request, Response};
 use crate::tendermint::abci::response;
@@ -113,6 +114,9 @@ impl Shell {
             Request::Query(query) => Ok(Response::Query(self.query(query))),
             Request::PrepareProposal(block) => {
                 tracing::debug!("Request PrepareProposal");
+                let _timer = self
+                    .metrics
+                    .start_abci_phase_timer(metrics::PHASE_PREPARE_PROPOSAL);
                 // TODO: use TM domain type in the handler
                 Ok(Response::PrepareProposal(
                     self.prepare_proposal(block.into()),
@@ -123,6 +127,9 @@ impl Shell {
             }
             Request::ProcessProposal(block) => {
                 tracing::debug!("Request ProcessProposal");
+                let _timer = self
+                    .metrics
+                    .start_abci_phase_timer(metrics::PHASE_PROCESS_PROPOSAL);
                 // TODO: use TM domain type in the handler
                 // NOTE: make sure to put any checks inside process_proposal
                 // since that function is called in other places to rerun the
@@ -158,13 +165,23 @@ impl Shell {
             }
             Request::FinalizeBlock(finalize) => {
                 tracing::debug!("Request FinalizeBlock");
+                let _timer = self
+                    .metrics
+                    .start_abci_phase_timer(metrics::PHASE_FINALIZE_BLOCK);
 
                 self.try_recheck_process_proposal(&finalize)?;
                 self.finalize_block(finalize).map(Response::FinalizeBlock)
             }
             Request::Commit => {
                 tracing::debug!("Request Commit");
-                Ok(self.commit())
+                let timer =
+                    self.metrics.start_abci_phase_timer(metrics::PHASE_COMMIT);
+                let response = self.commit();
+                drop(timer);
+                for (cf, size) in self.state.db().column_family_sizes() {
+                    self.metrics.set_db_size(cf, size);
+                }
+                Ok(response)
             }
             Request::Flush => Ok(Response::Flush),
             Request::Echo(msg) => Ok(Response::Echo(response::Echo {
@@ -176,7 +193,16 @@ impl Shell {
                     CheckTxKind::Recheck => MempoolTxType::RecheckTransaction,
                 };
                 let r#type = mempool_tx_type;
-                Ok(Response::CheckTx(self.mempool_validate(&tx.tx, r#type)))
+                let _timer = self
+                    .metrics
+                    .start_abci_phase_timer(metrics::PHASE_CHECK_TX);
+                let response = self.mempool_validate(&tx.tx, r#type);
+                if let Some(code) = ResultCode::from_u32(response.code.value())
+                    .filter(|code| *code != ResultCode::Ok)
+                {
+                    self.metrics.inc_mempool_rejected_txs(code);
+                }
+                Ok(Response::CheckTx(response))
             }
             Request::ListSnapshots => {
                 Ok(Response::ListSnapshots(self.list_snapshots()))
@@ -633,6 +659,7 @@ fn start_abci_broadcaster_shell(
 
     // Construct our ABCI application.
     let tendermint_mode = config.shell.tendermint_mode.clone();
+    let metrics_address = config.shell.metrics_address;
     let proxy_app_address =
         convert_tm_addr_to_socket_addr(&config.cometbft.proxy_app);
 
@@ -647,6 +674,28 @@ fn start_abci_broadcaster_shell(
         tx_wasm_compilation_cache,
     );
 
+    // Start the metrics server
+    if let Some(metrics_address) = metrics_address {
+        let metrics = shell.metrics();
+        let (metrics_abort_send, metrics_abort_recv) =
+            tokio::sync::oneshot::channel::<()>();
+
+        spawner
+            .abortable("Metrics", move |aborter| async move {
+                metrics::serve(metrics, metrics_address, metrics_abort_recv)
+                    .await;
+                tracing::info!("Metrics server is no longer running.");
+
+                drop(aborter);
+
+                Ok(())
+            })
+            .with_cleanup(async move {
+                let _ = metrics_abort_send.send(());
+            })
+            .spawn();
+    }
+
     // Channel for signalling shut down to ABCI server
     let (abci_abort_send, abci_abort_recv) = tokio::sync::oneshot::channel();
 
@@ -824,8 +873,14 @@ async fn maybe_start_ethereum_oracle(
 
     match config.ethereum_bridge.mode {
         ethereum_bridge::ledger::Mode::RemoteEndpoint => {
+            let endpoints = config
+                .ethereum_bridge
+                .oracle_rpc_endpoints()
+                .unwrap_or_else(|err| {
+                    panic!("Invalid Ethereum oracle RPC endpoints: {err}")
+                });
             oracle::run_oracle::<Provider<Http>>(
-                ethereum_url,
+                endpoints,
                 eth_sender,
                 control_receiver,
                 last_processed_block_sender,
@@ -0,0 +1,285 @@
+//! Prometheus metrics of the ledger node.
+//!
+//! The metrics are updated by the shell and, when a listen address is set in
+//! the node's configuration, they are served in the Prometheus text format on
+//! the `/metrics` path of an HTTP endpoint.
+
+use std::fmt;
+use std::net::SocketAddr;
+
+use namada_sdk::tx::data::ResultCode;
+use prometheus::core::Collector;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramTimer, HistogramVec, IntCounterVec,
+    IntGauge, IntGaugeVec, Opts, Registry, TextEncoder, TEXT_FORMAT,
+};
+use tokio::sync::oneshot::Receiver;
+use warp::Filter;
+
+/// The namespace of all the node's metrics
+const NAMESPACE: &str = "namada";
+
+/// The path on which the metrics are served
+const METRICS_PATH: &str = "metrics";
+
+/// ABCI phase label of the `PrepareProposal` request
+pub const PHASE_PREPARE_PROPOSAL: &str = "prepare_proposal";
+/// ABCI phase label of the `ProcessProposal` request
+pub const PHASE_PROCESS_PROPOSAL: &str = "process_proposal";
+/// ABCI phase label of the `FinalizeBlock` request
+pub const PHASE_FINALIZE_BLOCK: &str = "finalize_block";
+/// ABCI phase label of the `Commit` request
+pub const PHASE_COMMIT: &str = "commit";
+/// ABCI phase label of the `CheckTx` request
+pub const PHASE_CHECK_TX: &str = "check_tx";
+
+/// Cache label of the VP WASM compilation cache
+pub const CACHE_VP: &str = "vp";
+/// Cache label of the tx WASM compilation cache
+pub const CACHE_TX: &str = "tx";
+
+/// The metrics of the ledger node. Cloning it is cheap and all the clones
+/// update the same underlying metrics.
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    /// The height of the last committed block
+    block_height: IntGauge,
+    /// The processing time of the ABCI requests, by phase
+    abci_phase_duration: HistogramVec,
+    /// The number of txs applied in blocks, by result
+    txs: IntCounterVec,
+    /// The gas used by the txs of the last finalized block
+    block_gas_used: IntGauge,
+    /// The number of entries in the WASM compilation caches, by cache
+    wasm_cache_entries: IntGaugeVec,
+    /// The size of the WASM compilation caches, by cache
+    wasm_cache_bytes: IntGaugeVec,
+    /// The number of txs rejected from the mempool, by result code
+    mempool_rejected_txs: IntCounterVec,
+    /// The size of the DB SST files, by column family
+    db_size_bytes: IntGaugeVec,
+}
+
+impl fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Metrics").finish_non_exhaustive()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    /// Create the node's metrics in a new registry
+    pub fn new() -> Self {
+        let registry = Registry::new_custom(Some(NAMESPACE.to_string()), None)
+            .expect("The metrics namespace must be valid");
+
+        let block_height = IntGauge::new(
+            "block_height",
+            "The height of the last committed block",
+        )
+        .expect("The metric must be valid");
+        let abci_phase_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "abci_phase_duration_seconds",
+                "The processing time of the ABCI requests",
+            ),
+            &["phase"],
+        )
+        .expect("The metric must be valid");
+        let txs = IntCounterVec::new(
+            Opts::new("txs_total", "The number of txs applied in blocks"),
+            &["result"],
+        )
+        .expect("The metric must be valid");
+        let block_gas_used = IntGauge::new(
+            "block_gas_used",
+            "The gas used by the txs of the last finalized block",
+        )
+        .expect("The metric must be valid");
+        let wasm_cache_entries = IntGaugeVec::new(
+            Opts::new(
+                "wasm_cache_entries",
+                "The number of entries in the WASM compilation caches",
+            ),
+            &["cache"],
+        )
+        .expect("The metric must be valid");
+        let wasm_cache_bytes = IntGaugeVec::new(
+            Opts::new(
+                "wasm_cache_bytes",
+                "The size of the WASM compilation caches in bytes",
+            ),
+            &["cache"],
+        )
+        .expect("The metric must be valid");
+        let mempool_rejected_txs = IntCounterVec::new(
+            Opts::new(
+                "mempool_rejected_txs_total",
+                "The number of txs rejected from the mempool",
+            ),
+            &["code"],
+        )
+        .expect("The metric must be valid");
+        let db_size_bytes = IntGaugeVec::new(
+            Opts::new("db_size_bytes", "The size of the DB SST files in bytes"),
+            &["column_family"],
+        )
+        .expect("The metric must be valid");
+
+        let collectors: [Box<dyn Collector>; 8] = [
+            Box::new(block_height.clone()),
+            Box::new(abci_phase_duration.clone()),
+            Box::new(txs.clone()),
+            Box::new(block_gas_used.clone()),
+            Box::new(wasm_cache_entries.clone()),
+            Box::new(wasm_cache_bytes.clone()),
+            Box::new(mempool_rejected_txs.clone()),
+            Box::new(db_size_bytes.clone()),
+        ];
+        for collector in collectors {
+            registry
+                .register(collector)
+                .expect("The metrics must not be registered twice");
+        }
+
+        Self {
+            registry,
+            block_height,
+            abci_phase_duration,
+            txs,
+            block_gas_used,
+            wasm_cache_entries,
+            wasm_cache_bytes,
+            mempool_rejected_txs,
+            db_size_bytes,
+        }
+    }
+
+    /// Start timing an ABCI phase. The duration is recorded when the returned
+    /// timer is dropped.
+    pub fn start_abci_phase_timer(&self, phase: &str) -> HistogramTimer {
+        self.abci_phase_duration
+            .with_label_values(&[phase])
+            .start_timer()
+    }
+
+    /// Set the height of the last committed block
+    pub fn set_block_height(&self, height: u64) {
+        self.block_height.set(saturating_i64(height));
+    }
+
+    /// Add to the number of txs applied with the given result
+    pub fn inc_txs(&self, result: &str, count: u64) {
+        self.txs.with_label_values(&[result]).inc_by(count);
+    }
+
+    /// Set the gas used by the last finalized block
+    pub fn set_block_gas_used(&self, gas: u64) {
+        self.block_gas_used.set(saturating_i64(gas));
+    }
+
+    /// Set the size of a WASM compilation cache
+    pub fn set_wasm_cache_size(
+        &self,
+        cache: &str,
+        entries: usize,
+        bytes: usize,
+    ) {
+        self.wasm_cache_entries
+            .with_label_values(&[cache])
+            .set(saturating_i64(entries));
+        self.wasm_cache_bytes
+            .with_label_values(&[cache])
+            .set(saturating_i64(bytes));
+    }
+
+    /// Count a tx rejected from the mempool with the given result code
+    pub fn inc_mempool_rejected_txs(&self, code: ResultCode) {
+        self.mempool_rejected_txs
+            .with_label_values(&[format!("{code:?}").as_str()])
+            .inc();
+    }
+
+    /// Set the size of a DB column family
+    pub fn set_db_size(&self, column_family: &str, bytes: u64) {
+        self.db_size_bytes
+            .with_label_values(&[column_family])
+            .set(saturating_i64(bytes));
+    }
+
+    /// Encode the current value of the metrics in the Prometheus text format
+    pub fn encode(&self) -> String {
+        let mut buffer = vec![];
+        if let Err(err) =
+            TextEncoder::new().encode(&self.registry.gather(), &mut buffer)
+        {
+            tracing::error!("Failed to encode the metrics: {err}");
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+fn saturating_i64<T: TryInto<i64>>(value: T) -> i64 {
+    value.try_into().unwrap_or(i64::MAX)
+}
+
+/// Serve the metrics on the `/metrics` path of the given address until a
+/// signal is sent on the `abort_recv` channel.
+pub async fn serve(
+    metrics: Metrics,
+    listen_addr: SocketAddr,
+    abort_recv: Receiver<()>,
+) {
+    let route = warp::get()
+        .and(warp::path(METRICS_PATH))
+        .and(warp::path::end())
+        .map(move || {
+            warp::reply::with_header(
+                metrics.encode(),
+                "content-type",
+                TEXT_FORMAT,
+            )
+        });
+
+    let (_, future) = warp::serve(route).bind_with_graceful_shutdown(
+        listen_addr,
+        async move {
+            tracing::info!(?listen_addr, "Serving the node metrics");
+            _ = abort_recv.await;
+            tracing::info!("Shutting down the metrics server...");
+        },
+    );
+    future.await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_encoding() {
+        let metrics = Metrics::new();
+        metrics.set_block_height(42);
+        metrics.inc_txs("successful", 3);
+        metrics.inc_mempool_rejected_txs(ResultCode::InvalidSig);
+        metrics.set_wasm_cache_size(CACHE_VP, 2, 1024);
+        drop(metrics.start_abci_phase_timer(PHASE_COMMIT));
+
+        let encoded = metrics.encode();
+        assert!(encoded.contains("namada_block_height 42"));
+        assert!(encoded.contains("namada_txs_total{result=\"successful\"} 3"));
+        assert!(encoded.contains(
+            "namada_mempool_rejected_txs_total{code=\"InvalidSig\"} 1"
+        ));
+        assert!(encoded.contains("namada_wasm_cache_bytes{cache=\"vp\"} 1024"));
+        assert!(encoded.contains(
+            "namada_abci_phase_duration_seconds_count{phase=\"commit\"} 1"
+        ));
+    }
+}
@@ -1,7 +1,8 @@
 //! The ledger's protocol
 use std::cell::RefCell;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Debug, Display};
+use std::sync::Mutex;
 
 use either::Either;
 use eyre::{eyre, WrapErr};
@@ -24,14 +25,15 @@ use namada_sdk::token::utils::is_masp_transfer;
 use namada_sdk::token::Amount;
 use namada_sdk::tx::action::{self, Read};
 use namada_sdk::tx::data::protocol::{ProtocolTx, ProtocolTxType};
+use namada_sdk::tx::data::trace::{BatchedTxTrace, WasmTrace};
 use namada_sdk::tx::data::{
-    BatchedTxResult, ExtendedTxResult, TxResult, VpStatusFlags, VpsResult,
-    WrapperTx,
+    compute_inner_tx_hash, BatchedTxResult, ExtendedTxResult, TxResult,
+    VpStatusFlags, VpsResult, WrapperTx,
 };
 use namada_sdk::tx::{BatchedTxRef, Tx, TxCommitments};
 use namada_sdk::validation::{
     EthBridgeNutVp, EthBridgePoolVp, EthBridgeVp, GovernanceVp, IbcVp, MaspVp,
-    MultitokenVp, NativeVpCtx, ParametersVp, PgfVp, PosVp,
+    MultitokenVp, NativeVpCtx, ParametersVp, PgfVp, PosVp, TxSchedulerVp,
 };
 use namada_sdk::{governance, parameters, state, storage, token};
 use namada_vm::wasm::{TxCache, VpCache};
@@ -240,6 +242,7 @@ where
                     state,
                     vp_wasm_cache,
                     tx_wasm_cache,
+                    None,
                 )
             } else {
                 // Governance proposal. We don't allow tx batches in this case,
@@ -255,6 +258,7 @@ where
                         vp_wasm_cache,
                         tx_wasm_cache,
                     },
+                    None,
                 )?;
 
                 Ok({
@@ -329,6 +333,9 @@ pub(crate) fn get_batch_txs_to_execute<'a>(
     batch_iter
 }
 
+/// Apply the inner transactions of a batch. If traces are given, the WASM
+/// executions of every inner transaction are traced and recorded by the inner
+/// tx hash.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn dispatch_inner_txs<'a, S, D, H, CA>(
     tx: &Tx,
@@ -339,6 +346,7 @@ pub(crate) fn dispatch_inner_txs<'a, S, D, H, CA>(
     state: &'a mut S,
     vp_wasm_cache: &'a mut VpCache<CA>,
     tx_wasm_cache: &'a mut TxCache<CA>,
+    mut traces: Option<&mut BTreeMap<Hash, BatchedTxTrace>>,
 ) -> std::result::Result<ExtendedTxResult<Error>, DispatchError>
 where
     S: 'static
@@ -351,6 +359,11 @@ where
     CA: 'static + WasmCacheAccess + Sync,
 {
     for cmt in get_batch_txs_to_execute(tx, &extended_tx_result.masp_tx_refs) {
+        let trace = traces.as_deref_mut().map(|traces| {
+            traces
+                .entry(compute_inner_tx_hash(wrapper_hash, either::Right(cmt)))
+                .or_default()
+        });
         match apply_wasm_tx(
             &tx.batch_ref_tx(cmt),
             &tx_index,
@@ -360,6 +373,7 @@ where
                 vp_wasm_cache,
                 tx_wasm_cache,
             },
+            trace,
         ) {
             Err(Error::GasError(ref msg)) => {
                 // Gas error aborts the execution of the entire batch
@@ -727,6 +741,7 @@ where
                 vp_wasm_cache,
                 tx_wasm_cache,
             },
+            None,
         ) {
             Ok(result) => {
                 // NOTE: do not commit yet cause this could be exploited to get
@@ -919,11 +934,13 @@ where
 }
 
 // Apply a transaction going via the wasm environment. Gas will be metered and
-// validity predicates will be triggered in the normal way.
+// validity predicates will be triggered in the normal way. If a trace is given,
+// the executions of the tx and of the WASM VPs are recorded in it.
 fn apply_wasm_tx<S, D, H, CA>(
     batched_tx: &BatchedTxRef<'_>,
     tx_index: &TxIndex,
     shell_params: ShellParams<'_, S, D, H, CA>,
+    mut trace: Option<&mut BatchedTxTrace>,
 ) -> Result<BatchedTxResult>
 where
     S: 'static + State<D = D, H = H> + ReadConversionState + Sync,
@@ -945,8 +962,10 @@ where
         tx_gas_meter,
         vp_wasm_cache,
         tx_wasm_cache,
+        trace.as_deref_mut().map(|trace| &mut trace.tx),
     )?;
 
+    let vp_traces = trace.is_some().then(Mutex::default);
     let vps_result = check_vps(CheckVps {
         batched_tx,
         tx_index,
@@ -954,7 +973,14 @@ where
         tx_gas_meter: &mut tx_gas_meter.borrow_mut(),
         verifiers_from_tx: &verifiers,
         vp_wasm_cache,
-    })?;
+        vp_traces: vp_traces.as_ref(),
+    });
+    if let (Some(trace), Some(vp_traces)) = (trace, vp_traces) {
+        trace.vps = vp_traces
+            .into_inner()
+            .expect("The VP traces lock must not be poisoned");
+    }
+    let vps_result = vps_result?;
 
     let initialized_accounts = state.write_log().get_initialized_accounts();
     let changed_keys = state.write_log().get_keys();
@@ -1061,6 +1087,7 @@ fn execute_tx<S, D, H, CA>(
     tx_gas_meter: &RefCell<TxGasMeter>,
     vp_wasm_cache: &mut VpCache<CA>,
     tx_wasm_cache: &mut TxCache<CA>,
+    mut trace: Option<&mut WasmTrace>,
 ) -> Result<BTreeSet<Address>>
 where
     S: State<D = D, H = H>,
@@ -1068,7 +1095,8 @@ where
     H: 'static + StorageHasher + Sync,
     CA: 'static + WasmCacheAccess + Sync,
 {
-    wasm::run::tx(
+    let gas_before = tx_gas_meter.borrow().get_tx_consumed_gas();
+    let result = wasm::run::tx(
         state,
         tx_gas_meter,
         tx_index,
@@ -1076,8 +1104,16 @@ where
         batched_tx.cmt,
         vp_wasm_cache,
         tx_wasm_cache,
-    )
-    .map_err(|err| match err {
+        trace.as_deref_mut(),
+    );
+    if let Some(trace) = trace {
+        trace.gas = tx_gas_meter
+            .borrow()
+            .get_tx_consumed_gas()
+            .checked_sub(gas_before)
+            .unwrap_or_default();
+    }
+    result.map_err(|err| match err {
         wasm::run::Error::GasError(msg) => Error::GasError(msg),
         wasm::run::Error::MissingSection(msg) => Error::MissingSection(msg),
         _ => Error::TxRunnerError(err),
@@ -1096,6 +1132,7 @@ where
     tx_gas_meter: &'a mut TxGasMeter,
     verifiers_from_tx: &'a BTreeSet<Address>,
     vp_wasm_cache: &'a mut VpCache<CA>,
+    vp_traces: Option<&'a Mutex<BTreeMap<Address, WasmTrace>>>,
 }
 
 /// Check the acceptance of a transaction by validity predicates
@@ -1107,6 +1144,7 @@ fn check_vps<S, CA>(
         tx_gas_meter,
         verifiers_from_tx,
         vp_wasm_cache,
+        vp_traces,
     }: CheckVps<'_, S, CA>,
 ) -> Result<VpsResult>
 where
@@ -1125,6 +1163,7 @@ where
         state,
         tx_gas_meter,
         vp_wasm_cache,
+        vp_traces,
     )?;
     tracing::debug!("Total VPs gas cost {:?}", vps_gas);
 
@@ -1135,7 +1174,8 @@ where
     Ok(vps_result)
 }
 
-/// Execute verifiers' validity predicates
+/// Execute verifiers' validity predicates. If the VP traces are given, the
+/// executions of the WASM VPs are traced and recorded by their address.
 #[allow(clippy::too_many_arguments)]
 fn execute_vps<S, CA>(
     verifiers: BTreeSet<Address>,
@@ -1145,6 +1185,7 @@ fn execute_vps<S, CA>(
     state: &S,
     tx_gas_meter: &TxGasMeter,
     vp_wasm_cache: &mut VpCache<CA>,
+    vp_traces: Option<&Mutex<BTreeMap<Address, WasmTrace>>>,
 ) -> Result<(VpsResult, namada_sdk::gas::Gas)>
 where
     S: 'static + ReadConversionState + State + Sync,
@@ -1170,7 +1211,8 @@ where
                             return Err(Error::MissingAddress(addr.clone()));
                         };
 
-                        wasm::run::vp(
+                        let mut trace = vp_traces.map(|_| WasmTrace::default());
+                        let result = wasm::run::vp(
                             vp_code_hash,
                             batched_tx,
                             tx_index,
@@ -1180,8 +1222,21 @@ where
                             &keys_changed,
                             &verifiers,
                             vp_wasm_cache.clone(),
-                        )
-                        .map_err(|err| match err {
+                            trace.as_mut(),
+                        );
+                        if let (Some(vp_traces), Some(mut trace)) =
+                            (vp_traces, trace)
+                        {
+                            trace.gas =
+                                gas_meter.borrow().get_vp_consumed_gas();
+                            vp_traces
+                                .lock()
+                                .expect(
+                                    "The VP traces lock must not be poisoned",
+                                )
+                                .insert(addr.clone(), trace);
+                        }
+                        result.map_err(|err| match err {
                             wasm::run::Error::GasError(msg) => {
                                 Error::GasError(msg)
                             }
@@ -1294,6 +1349,31 @@ where
                                 )
                                 .map_err(Error::NativeVpError)
                             }
+                            InternalAddress::TxScheduler => {
+                                TxSchedulerVp::validate_tx(
+                                    &ctx,
+                                    batched_tx,
+                                    &keys_changed,
+                                    &verifiers,
+                                )
+                                .map_err(Error::NativeVpError)
+                            }
+                            InternalAddress::IcaHost(_) => {
+                                // An interchain account is controlled only
+                                // through the received IBC packets. The IBC VP
+                                // checks that every debit of the account
+                                // matches the host-side execution of the
+                                // packet.
+                                verifiers
+                                    .contains(&Address::Internal(
+                                        InternalAddress::Ibc,
+                                    ))
+                                    .ok_or_else(|| {
+                                        Error::AccessForbidden(
+                                            internal_addr.clone(),
+                                        )
+                                    })
+                            }
                             internal_addr @ (InternalAddress::IbcToken(_)
                             | InternalAddress::Erc20(_)) => {
                                 // The address should be a part of a multitoken
@@ -1635,6 +1715,7 @@ mod tests {
             &state,
             &gas_meter,
             &mut vp_cache,
+            None,
         );
         assert!(matches!(result.unwrap_err(), Error::GasError(_)));
     }
@@ -1684,6 +1765,7 @@ mod tests {
                             &Default::default(),
                             &Default::default(),
                             vp_cache.clone(),
+                            None,
                         )
                         .is_ok()
                     );
@@ -1710,6 +1792,7 @@ mod tests {
                         &Default::default(),
                         &Default::default(),
                         vp_cache.clone(),
+                        None,
                     )
                     .is_err()
                 );
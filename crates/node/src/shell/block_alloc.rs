@@ -20,9 +20,20 @@
 //!   1/2 of the total block space unless there is extra room due to a lack of
 //!   user txs.
 //! - Next, we allot space for user submitted txs until the block is filled.
+//!   These are handed to the allocator by decreasing fee per gas unit, with
+//!   fees paid in non-native tokens converted to the native token using the
+//!   ratios of the minimum gas prices set by governance. Txs paying the same
+//!   fee keep the order in which they were received from the mempool.
 //! - If we cannot fill the block with normal txs, we try to fill it with
 //!   protocol txs that were not allocated in the initial phase.
 //!
+//! # Oversized transactions
+//!
+//! Since protocol txs may take up to half of the block space, user submitted
+//! txs are only guaranteed the other half. Wrapper txs larger than that are
+//! rejected by `CheckTx`, which also evicts them from the mempool when it is
+//! rechecked after a parameter change, so that they cannot linger there
+//! forever. The largest admissible size can be queried from the shell.
 //!
 //! # How gas is allocated
 //!
@@ -32,13 +43,6 @@
 
 pub mod states;
 
-// TODO(namada#3250): what if a tx has a size greater than the threshold
-// for its bin? how do we handle this? if we keep it in the mempool
-// forever, it'll be a DoS vec, as we can make nodes run out of
-// memory! maybe we should allow block decisions for txs that are
-// too big to fit in their respective bin? in these special block
-// decisions, we would only decide proposals with "large" txs??
-
 use std::marker::PhantomData;
 
 use namada_sdk::parameters;
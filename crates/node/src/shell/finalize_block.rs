@@ -1,29 +1,41 @@
 //! Implementation of the `FinalizeBlock` ABCI++ method for the Shell
 
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
 use data_encoding::HEXUPPER;
 use masp_primitives::merkle_tree::CommitmentTree;
 use masp_primitives::sapling::Node;
+use namada_sdk::address::TX_SCHEDULER;
 use namada_sdk::events::extend::{
     ComposeEvent, Height, IndexedMaspData, Info, MaspDataRefs, TxHash,
+    UserAccount,
 };
-use namada_sdk::events::{EmitEvents, Event};
+use namada_sdk::events::{EmitEvents, Event, EventLevel};
 use namada_sdk::gas::event::GasUsed;
 use namada_sdk::gas::GasMetering;
 use namada_sdk::governance::pgf::inflation as pgf_inflation;
+use namada_sdk::governance::storage::param_change::{
+    IbcParamChange, ParameterChange, PosParamChange, ProtocolParamChange,
+};
 use namada_sdk::hash::Hash;
-use namada_sdk::parameters::get_gas_scale;
+use namada_sdk::ibc::core::host::types::identifiers::ChannelId;
+use namada_sdk::parameters::{get_gas_scale, ProposalBytes};
 use namada_sdk::proof_of_stake::storage::{
     find_validator_by_raw_hash, write_last_block_proposer_address,
 };
 use namada_sdk::state::write_log::StorageModification;
 use namada_sdk::state::{
-    Result, ResultExt, StorageWrite, EPOCH_SWITCH_BLOCKS_DELAY,
+    OptionExt, Result, ResultExt, StorageWrite, TxWrites,
+    EPOCH_SWITCH_BLOCKS_DELAY,
 };
 use namada_sdk::storage::{BlockHeader, BlockResults, Epoch};
+use namada_sdk::token::event::{TokenEvent, TokenOperation};
 use namada_sdk::tx::data::protocol::ProtocolTxType;
-use namada_sdk::tx::data::VpStatusFlags;
+use namada_sdk::tx::data::{TxResult, VpStatusFlags};
 use namada_sdk::tx::event::{Batch, Code};
-use namada_sdk::tx::new_tx_event;
+use namada_sdk::tx::{new_scheduled_tx_event, new_tx_event};
+use namada_sdk::tx_scheduler::{self, ScheduledTx, TxSchedule};
 use namada_sdk::{ibc, proof_of_stake};
 use namada_vote_ext::ethereum_events::MultiSignedEthEvent;
 use namada_vote_ext::ethereum_tx_data_variants;
@@ -118,6 +130,10 @@ where
             self.apply_inflation(current_epoch, emit_events)?;
         }
 
+        // Re-bond the rewards of the next bonds with auto-compounding, now
+        // that the rewards products of the last epoch are up-to-date
+        pos_compound_rewards(&mut self.state, emit_events, current_epoch)?;
+
         let mut stats = InternalStats::default();
 
         let native_block_proposer_address = {
@@ -158,6 +174,21 @@ where
             },
         );
 
+        // Execute the scheduled transactions that are due, indexed after the
+        // transactions of the block
+        self.execute_scheduled_txs(
+            &native_block_proposer_address,
+            new_epoch,
+            req.txs.len(),
+            ExecutionArgs {
+                response: &mut response,
+                changed_keys: &mut changed_keys,
+                stats: &mut stats,
+                height,
+            },
+        )?;
+        self.register_scheduled_txs(&mut response, &changed_keys)?;
+
         stats.set_tx_cache_size(
             self.tx_wasm_cache.get_size(),
             self.tx_wasm_cache.get_cache_size(),
@@ -169,6 +200,7 @@ where
 
         tracing::info!("{}", stats);
         tracing::info!("{}", stats.format_tx_executed());
+        stats.record_metrics(&self.metrics);
 
         // Update the MASP commitment tree anchor if the tree was updated
         let tree_key = token::storage_key::masp_commitment_tree_key();
@@ -779,6 +811,7 @@ where
             let consumed_gas = tx_gas_meter.get_tx_consumed_gas();
 
             // save the gas cost
+            stats.set_tx_gas(tx_hash, consumed_gas.clone());
             self.update_tx_gas(tx_hash, consumed_gas);
 
             if let Some(wrapper_cache) = self.evaluate_tx_result(
@@ -852,6 +885,7 @@ where
             let consumed_gas = tx_gas_meter.get_tx_consumed_gas();
 
             // update the gas cost of the corresponding wrapper
+            stats.set_tx_gas(tx_hash, consumed_gas.clone());
             self.update_tx_gas(tx_hash, consumed_gas);
 
             self.evaluate_tx_result(
@@ -876,6 +910,267 @@ where
     }
 }
 
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    // Execute the inner transactions scheduled for the current block height
+    // and, in the first block of an epoch, for the current epoch. The escrowed
+    // fees of each tx are paid to the block proposer
+    fn execute_scheduled_txs(
+        &mut self,
+        native_block_proposer_address: &Address,
+        new_epoch: bool,
+        first_tx_index: usize,
+        ExecutionArgs {
+            response,
+            changed_keys,
+            stats,
+            height,
+        }: ExecutionArgs<'_>,
+    ) -> Result<()> {
+        let mut due_txs = tx_scheduler::read_scheduled_txs(
+            &self.state,
+            &TxSchedule::Height(height),
+        )?;
+        if new_epoch {
+            let (current_epoch, _gas) = self.state.in_mem().get_current_epoch();
+            due_txs.extend(tx_scheduler::read_scheduled_txs(
+                &self.state,
+                &TxSchedule::Epoch(current_epoch),
+            )?);
+        }
+
+        let block_time = self.state.in_mem().header.as_ref().map(|h| h.time);
+
+        for (tx_index, (key, scheduled)) in (first_tx_index..).zip(due_txs) {
+            self.state.delete(&key)?;
+
+            let tx = match Tx::try_from_bytes(&scheduled.tx) {
+                Ok(tx) => tx,
+                Err(err) => {
+                    // This shouldn't happen as it's prevented by the VP
+                    tracing::error!(
+                        "Failed to decode the scheduled tx {}: {err}. \
+                         Refunding its fees.",
+                        scheduled.tx_hash
+                    );
+                    self.transfer_scheduled_tx_fee(
+                        response,
+                        &scheduled,
+                        &scheduled.fee_payer,
+                        SCHEDULED_TX_FEE_REFUND,
+                    )?;
+                    continue;
+                }
+            };
+            // The tx may have expired since it was scheduled
+            if let (Some(exp), Some(block_time)) =
+                (tx.header.expiration, block_time)
+            {
+                if block_time > exp {
+                    tracing::info!(
+                        "The scheduled tx {} expired at {exp}. Refunding its \
+                         fees.",
+                        scheduled.tx_hash
+                    );
+                    self.transfer_scheduled_tx_fee(
+                        response,
+                        &scheduled,
+                        &scheduled.fee_payer,
+                        SCHEDULED_TX_FEE_REFUND,
+                    )?;
+                    continue;
+                }
+            }
+            if self
+                .state
+                .write_log()
+                .has_replay_protection_entry(&scheduled.tx_hash)
+            {
+                tracing::info!(
+                    "The scheduled tx {} was already applied in this block. \
+                     Refunding its fees.",
+                    scheduled.tx_hash
+                );
+                self.transfer_scheduled_tx_fee(
+                    response,
+                    &scheduled,
+                    &scheduled.fee_payer,
+                    SCHEDULED_TX_FEE_REFUND,
+                )?;
+                continue;
+            }
+
+            let tx_event = new_scheduled_tx_event(&tx, height.0);
+            let is_atomic_batch = tx.header.atomic;
+            let commitments_len = tx.commitments().len() as u64;
+            let gas_scale = get_gas_scale(&self.state)?;
+            // Charge the gas of the tx bytes like for wrapper txs
+            let mut tx_gas_meter = TxGasMeter::new(
+                Gas::from_whole_units(scheduled.gas_limit.into(), gas_scale)
+                    .unwrap_or_else(|| u64::MAX.into()),
+            );
+            let tx_gas_meter = match tx_gas_meter.add_wrapper_gas(&scheduled.tx)
+            {
+                Ok(()) => Ok(tx_gas_meter),
+                Err(err) => Err((tx_gas_meter, err)),
+            };
+
+            let (dispatch_result, tx_gas_meter) = match tx_gas_meter {
+                Ok(tx_gas_meter) => {
+                    let tx_gas_meter = RefCell::new(tx_gas_meter);
+                    let dispatch_result = protocol::dispatch_tx(
+                        &tx,
+                        DispatchArgs::Raw {
+                            wrapper_hash: None,
+                            tx_index: TxIndex::must_from_usize(tx_index),
+                            wrapper_tx_result: Some(
+                                TxResult::default().to_extended_result(None),
+                            ),
+                            vp_wasm_cache: &mut self.vp_wasm_cache,
+                            tx_wasm_cache: &mut self.tx_wasm_cache,
+                        },
+                        &tx_gas_meter,
+                        &mut self.state,
+                    );
+                    (dispatch_result, tx_gas_meter.into_inner())
+                }
+                Err((tx_gas_meter, err)) => (
+                    Err(DispatchError {
+                        error: protocol::Error::GasError(err.to_string()),
+                        tx_result: None,
+                    }),
+                    tx_gas_meter,
+                ),
+            };
+            let consumed_gas = tx_gas_meter.get_tx_consumed_gas();
+            stats.set_tx_gas(scheduled.tx_hash, consumed_gas.clone());
+            self.update_tx_gas(scheduled.tx_hash, consumed_gas);
+
+            // The replay protection entry of the tx has been written when it
+            // was scheduled
+            self.evaluate_tx_result(
+                response,
+                dispatch_result,
+                TxData {
+                    is_atomic_batch,
+                    tx: &tx,
+                    commitments_len,
+                    tx_index,
+                    replay_protection_hashes: None,
+                    tx_gas_meter,
+                    height,
+                },
+                TxLogs {
+                    tx_event,
+                    stats,
+                    changed_keys,
+                },
+            );
+
+            self.transfer_scheduled_tx_fee(
+                response,
+                &scheduled,
+                native_block_proposer_address,
+                SCHEDULED_TX_FEE_PAYMENT,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Write the replay protection entries of the txs scheduled in this block.
+    // This prevents anyone from front-running a scheduled tx by wrapping it
+    // before its scheduled time. If the tx has already been applied (or
+    // scheduled twice), drop the new entry and refund its escrowed fees.
+    fn register_scheduled_txs(
+        &mut self,
+        response: &mut shim::response::FinalizeBlock,
+        changed_keys: &BTreeSet<Key>,
+    ) -> Result<()> {
+        for key in changed_keys {
+            if tx_scheduler::is_scheduled_tx_key(key).is_none() {
+                continue;
+            }
+            let Some(scheduled) = self.state.read::<ScheduledTx>(key)? else {
+                // The entry has already been executed and removed
+                continue;
+            };
+            let tx_hash = scheduled.tx_hash;
+            if self.state.write_log().has_replay_protection_entry(&tx_hash)
+                || self.state.has_replay_protection_entry(&tx_hash)?
+            {
+                tracing::info!(
+                    "The scheduled tx {tx_hash} has already been applied or \
+                     scheduled. Refunding its fees."
+                );
+                self.state.delete(key)?;
+                self.transfer_scheduled_tx_fee(
+                    response,
+                    &scheduled,
+                    &scheduled.fee_payer,
+                    SCHEDULED_TX_FEE_REFUND,
+                )?;
+                continue;
+            }
+            self.state
+                .write_tx_hash(tx_hash)
+                .expect("Error while writing tx hash to storage");
+        }
+        Ok(())
+    }
+
+    // Transfer the escrowed fees of a scheduled tx to the given target and
+    // emit the corresponding token event
+    fn transfer_scheduled_tx_fee(
+        &mut self,
+        response: &mut shim::response::FinalizeBlock,
+        scheduled: &ScheduledTx,
+        target: &Address,
+        descriptor: &'static str,
+    ) -> Result<()> {
+        token::transfer(
+            &mut self.state,
+            &scheduled.fee_token,
+            &TX_SCHEDULER,
+            target,
+            scheduled.fee,
+        )?;
+        let source_post_balance = token::read_balance(
+            &self.state,
+            &scheduled.fee_token,
+            &TX_SCHEDULER,
+        )?;
+        let target_post_balance =
+            token::read_balance(&self.state, &scheduled.fee_token, target)?;
+        let height = self.state.in_mem().get_last_block_height().next_height();
+        response.events.emit(
+            TokenEvent {
+                descriptor: descriptor.into(),
+                level: EventLevel::Tx,
+                operation: TokenOperation::transfer(
+                    UserAccount::Internal(TX_SCHEDULER),
+                    UserAccount::Internal(target.clone()),
+                    scheduled.fee_token.clone(),
+                    scheduled.fee.into(),
+                    source_post_balance.into(),
+                    Some(target_post_balance.into()),
+                ),
+            }
+            .with(Height(height))
+            .with(TxHash(scheduled.tx_hash)),
+        );
+        Ok(())
+    }
+}
+
+/// Descriptor of the payment of a scheduled tx's fees to the block proposer
+const SCHEDULED_TX_FEE_PAYMENT: &str = "scheduled-tx-fee-payment";
+/// Descriptor of the refund of a scheduled tx's fees to its fee payer
+const SCHEDULED_TX_FEE_REFUND: &str = "scheduled-tx-fee-refund";
+
 struct ExecutionArgs<'finalize> {
     response: &'finalize mut shim::response::FinalizeBlock,
     changed_keys: &'finalize mut BTreeSet<Key>,
@@ -1130,6 +1425,7 @@ where
         proof_of_stake::Store<_>,
         _,
         _,
+        _,
     >(
         &mut shell.state,
         emit_events,
@@ -1196,9 +1492,139 @@ where
                 token::Transfer,
             >(state, token, source, target)
         },
+        |state, change| apply_param_change(state, change),
     )
 }
 
+/// Apply a parameter change of an accepted governance proposal to the
+/// parameters that are not owned by governance
+fn apply_param_change<S>(
+    storage: &mut S,
+    change: &ParameterChange,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    match change {
+        ParameterChange::Protocol(change) => match change {
+            ProtocolParamChange::MaxTxBytes(value) => storage
+                .write(&parameters::storage::get_max_tx_bytes_key(), value),
+            ProtocolParamChange::MaxProposalBytes(value) => {
+                let value = ProposalBytes::new(*value)
+                    .ok_or_err_msg("Invalid max proposal bytes")?;
+                storage.write(
+                    &parameters::storage::get_max_proposal_bytes_key(),
+                    value,
+                )
+            }
+            ProtocolParamChange::MaxBlockGas(value) => storage
+                .write(&parameters::storage::get_max_block_gas_key(), value),
+            ProtocolParamChange::MaspFeePaymentGasLimit(value) => storage
+                .write(
+                    &parameters::storage::get_masp_fee_payment_gas_limit_key(),
+                    value,
+                ),
+            ProtocolParamChange::GasScale(value) => {
+                storage.write(&parameters::storage::get_gas_scale_key(), value)
+            }
+            ProtocolParamChange::MinimumGasPrice { token, amount } => {
+                let key = parameters::storage::get_gas_cost_key();
+                let mut minimum_gas_price: BTreeMap<Address, token::Amount> =
+                    storage
+                        .read(&key)?
+                        .ok_or_err_msg("Minimum gas prices should be stored")?;
+                match amount {
+                    Some(amount) => {
+                        minimum_gas_price.insert(token.clone(), *amount);
+                    }
+                    None => {
+                        minimum_gas_price.remove(token);
+                    }
+                }
+                storage.write(&key, minimum_gas_price)
+            }
+            ProtocolParamChange::IsNativeTokenTransferable(value) => storage
+                .write(
+                    &parameters::storage::get_native_token_transferable_key(),
+                    value,
+                ),
+        },
+        ParameterChange::Pos(change) => {
+            let mut params =
+                proof_of_stake::storage::read_owned_pos_params(storage)?;
+            match change {
+                PosParamChange::BlockProposerReward(value) => {
+                    params.block_proposer_reward = *value
+                }
+                PosParamChange::BlockVoteReward(value) => {
+                    params.block_vote_reward = *value
+                }
+                PosParamChange::MaxInflationRate(value) => {
+                    params.max_inflation_rate = *value
+                }
+                PosParamChange::TargetStakedRatio(value) => {
+                    params.target_staked_ratio = *value
+                }
+                PosParamChange::DuplicateVoteMinSlashRate(value) => {
+                    params.duplicate_vote_min_slash_rate = *value
+                }
+                PosParamChange::LightClientAttackMinSlashRate(value) => {
+                    params.light_client_attack_min_slash_rate = *value
+                }
+                PosParamChange::ValidatorStakeThreshold(value) => {
+                    params.validator_stake_threshold = *value
+                }
+                PosParamChange::LivenessThreshold(value) => {
+                    params.liveness_threshold = *value
+                }
+                PosParamChange::RewardsGainP(value) => {
+                    params.rewards_gain_p = *value
+                }
+                PosParamChange::RewardsGainD(value) => {
+                    params.rewards_gain_d = *value
+                }
+            }
+            proof_of_stake::storage::write_pos_params(storage, &params)
+        }
+        ParameterChange::Ibc(change) => {
+            let mut params = ibc::parameters::IbcParameters::read(storage)?;
+            match change {
+                IbcParamChange::DefaultMintLimit(value) => {
+                    params.default_mint_limit = *value
+                }
+                IbcParamChange::DefaultPerEpochThroughputLimit(value) => {
+                    params.default_per_epoch_throughput_limit = *value
+                }
+                IbcParamChange::DefaultPerEpochChannelThroughputLimit(
+                    value,
+                ) => params.default_per_epoch_channel_throughput_limit = *value,
+                IbcParamChange::DefaultPerEpochAccountThroughputLimit(
+                    value,
+                ) => params.default_per_epoch_account_throughput_limit = *value,
+                IbcParamChange::FrozenChannel { channel_id, frozen } => {
+                    let channel_id =
+                        ChannelId::from_str(channel_id).map_err(|err| {
+                            namada_sdk::state::Error::new_alloc(format!(
+                                "Invalid channel ID {channel_id}: {err}"
+                            ))
+                        })?;
+                    return if *frozen {
+                        ibc::storage::freeze_channel(storage, &channel_id)
+                    } else {
+                        ibc::storage::unfreeze_channel(storage, &channel_id)
+                    };
+                }
+            }
+            params.init_storage(storage)
+        }
+        ParameterChange::Governance(_) | ParameterChange::Pgf(_) => {
+            Err(namada_sdk::state::Error::new_const(
+                "Governance applies the changes of its own parameters",
+            ))
+        }
+    }
+}
+
 /// Dependency-injection indirection for token system
 fn token_finalize_block<S>(
     storage: &mut S,
@@ -1237,6 +1663,46 @@ where
     )
 }
 
+/// Dependency-injection indirection for PoS auto-compounding. Only a bounded
+/// number of bonds is compounded in each block. Every bond is compounded at
+/// the tx level of the write log, so that a failing bond is skipped instead of
+/// aborting the block.
+fn pos_compound_rewards<S>(
+    state: &mut S,
+    events: &mut impl EmitEvents,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    S: State + TxWrites,
+{
+    let bond_ids = proof_of_stake::next_auto_compound_bonds(
+        state,
+        current_epoch,
+        proof_of_stake::MAX_AUTO_COMPOUND_BONDS_PER_BLOCK,
+    )?;
+    for bond_id in bond_ids {
+        let result =
+            proof_of_stake::compound_bond_rewards::<
+                _,
+                governance::Store<_>,
+                token::Store<_>,
+            >(
+                &mut state.with_tx_writes(), events, &bond_id, current_epoch
+            );
+        match result {
+            Ok(()) => state.write_log_mut().commit_batch_and_current_tx(),
+            Err(err) => {
+                tracing::error!(
+                    "Failed to auto-compound the rewards of bond {bond_id}: \
+                     {err}"
+                );
+                state.write_log_mut().drop_batch();
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Dependency-injection indirection for PGF inflation
 fn pgf_apply_inflation<S>(storage: &mut S) -> Result<()>
 where
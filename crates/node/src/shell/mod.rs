@@ -61,6 +61,7 @@ use namada_sdk::time::DateTimeUtc;
 pub use namada_sdk::tx::data::ResultCode;
 use namada_sdk::tx::data::{TxType, WrapperTx};
 use namada_sdk::tx::{Section, Tx};
+use namada_sdk::wallet::remote_signer::{RemoteSigner, SignStep};
 use namada_sdk::{
     eth_bridge, governance, hints, migrations, parameters, proof_of_stake,
     token,
@@ -73,6 +74,7 @@ use tokio::sync::mpsc::{Receiver, UnboundedSender};
 
 use super::ethereum_oracle::{self as oracle, last_processed_block};
 use crate::config::{self, genesis, TendermintMode, ValidatorLocalConfig};
+use crate::metrics::Metrics;
 use crate::protocol::ShellParams;
 use crate::shims::abcipp_shim_types::shim;
 use crate::shims::abcipp_shim_types::shim::response::TxResult;
@@ -182,6 +184,9 @@ pub(super) enum ShellMode {
         eth_oracle: Option<EthereumOracleChannels>,
         validator_local_config: Option<ValidatorLocalConfig>,
         local_config: Option<NodeLocalConfig>,
+        /// The remote signers of the protocol key, if the protocol messages
+        /// aren't signed with the key of the wallet
+        remote_signer: Option<RemoteSigner>,
     },
     Full {
         local_config: Option<NodeLocalConfig>,
@@ -384,6 +389,8 @@ where
     /// Data for a node downloading and apply snapshots as part of
     /// the fast sync protocol.
     pub syncing: Option<SnapshotSync>,
+    /// The node's metrics
+    pub metrics: Metrics,
 }
 
 /// Storage key filter to store the diffs into the storage. Return `false` for
@@ -486,6 +493,16 @@ where
         let mode = config.shell.tendermint_mode;
         let storage_read_past_height_limit =
             config.shell.storage_read_past_height_limit;
+        #[cfg(not(any(test, fuzzing)))]
+        let remote_signers = config
+            .shell
+            .remote_signer_endpoints()
+            .expect("Invalid remote signers configuration");
+        #[cfg(not(any(test, fuzzing)))]
+        let remote_signer_auth_key = config
+            .shell
+            .remote_signer_auth_key()
+            .expect("Failed to read the remote signers authentication key");
         if !Path::new(&base_dir).is_dir() {
             std::fs::create_dir(&base_dir)
                 .expect("Creating directory for Namada should not fail");
@@ -515,7 +532,7 @@ where
         };
 
         // load last state from storage
-        let state = FullAccessState::open(
+        let mut state = FullAccessState::open(
             db_path,
             db_cache,
             chain_id.clone(),
@@ -523,10 +540,13 @@ where
             config.shell.storage_read_past_height_limit,
             is_key_diff_storable,
         );
-        let vp_wasm_cache_dir =
-            base_dir.join(chain_id.as_str()).join("vp_wasm_cache");
-        let tx_wasm_cache_dir =
-            base_dir.join(chain_id.as_str()).join("tx_wasm_cache");
+        state.in_mem_mut().pruning_mode = config.shell.pruning_mode;
+        let vp_wasm_cache_dir = base_dir
+            .join(chain_id.as_str())
+            .join(config::VP_WASM_CACHE_DIR);
+        let tx_wasm_cache_dir = base_dir
+            .join(chain_id.as_str())
+            .join(config::TX_WASM_CACHE_DIR);
         // load in keys and address from wallet if mode is set to `Validator`
         let mode = match mode {
             TendermintMode::Validator => {
@@ -569,19 +589,27 @@ where
                             None
                         };
 
-                    wallet
-                        .take_validator_data()
-                        .map(|data| ShellMode::Validator {
-                            data,
-                            broadcast_sender,
-                            eth_oracle,
-                            validator_local_config,
-                            local_config,
-                        })
-                        .expect(
-                            "Validator data should have been stored in the \
-                             wallet",
-                        )
+                    let data = wallet.take_validator_data().expect(
+                        "Validator data should have been stored in the wallet",
+                    );
+                    let remote_signer =
+                        (!remote_signers.is_empty()).then(|| {
+                            RemoteSigner::new(
+                                remote_signers,
+                                config.shell.remote_signer_threshold,
+                                data.keys.protocol_keypair.to_public(),
+                                remote_signer_auth_key,
+                            )
+                            .expect("Invalid remote signers configuration")
+                        });
+                    ShellMode::Validator {
+                        data,
+                        broadcast_sender,
+                        eth_oracle,
+                        validator_local_config,
+                        local_config,
+                        remote_signer,
+                    }
                 }
                 #[cfg(any(test, fuzzing))]
                 {
@@ -599,6 +627,7 @@ where
                         eth_oracle,
                         validator_local_config: None,
                         local_config: None,
+                        remote_signer: None,
                     }
                 }
             }
@@ -667,6 +696,7 @@ where
             scheduled_migration,
             blocks_between_snapshots: config.shell.blocks_between_snapshots,
             syncing: None,
+            metrics: Metrics::default(),
         };
         shell.update_eth_oracle(&Default::default());
         shell
@@ -795,6 +825,7 @@ where
         tracing::info!(
             "Committed block hash: {merkle_root}, height: {committed_height}",
         );
+        self.metrics.set_block_height(committed_height.0);
 
         self.broadcast_queued_txs();
         let take_snapshot = self.check_snapshot_required();
@@ -871,15 +902,8 @@ where
 
         let ext = self.craft_extension();
 
-        let protocol_key = self
-            .mode
-            .get_protocol_key()
-            .expect("Validators should have protocol keys");
-
-        let protocol_txs = iter_protocol_txs(ext).map(|protocol_tx| {
-            protocol_tx
-                .sign(protocol_key, self.chain_id.clone())
-                .to_bytes()
+        let protocol_txs = iter_protocol_txs(ext).filter_map(|protocol_tx| {
+            self.sign_protocol_tx(&protocol_tx).map(|tx| tx.to_bytes())
         });
 
         for tx in protocol_txs {
@@ -907,19 +931,37 @@ where
             // Ethereum events to retransmit
             return;
         }
-        if let Some(vote_extension) = self.sign_ethereum_events(eth_events) {
-            let protocol_key = self
-                .mode
-                .get_protocol_key()
-                .expect("Validators should have protocol keys");
-
-            let signed_tx = EthereumTxData::EthEventsVext(
-                namada_vote_ext::ethereum_events::SignedVext(vote_extension),
+        // The retransmissions have their own steps, as the vote extension of
+        // the new Ethereum events is also signed at this height
+        let signed_tx = self
+            .sign_ethereum_events(
+                eth_events.clone(),
+                SignStep::EthEventsRetransmitVext,
             )
-            .sign(protocol_key, self.chain_id.clone())
-            .to_bytes();
-
-            self.mode.broadcast(signed_tx);
+            .and_then(|vote_extension| {
+                let protocol_tx = EthereumTxData::EthEventsVext(
+                    namada_vote_ext::ethereum_events::SignedVext(
+                        vote_extension,
+                    ),
+                );
+                self.sign_protocol_tx_at_step(
+                    &protocol_tx,
+                    SignStep::EthEventsRetransmitTx,
+                )
+            });
+        if let Some(signed_tx) = signed_tx {
+            self.mode.broadcast(signed_tx.to_bytes());
+        } else if self.state.ethbridge_queries().is_bridge_active() {
+            // The events couldn't be signed, retry at the next height
+            tracing::warn!(
+                "Failed to sign the retransmission of {} expired Ethereum \
+                 events",
+                eth_events.len()
+            );
+            let queue = &mut self.state.in_mem_mut().expired_txs_queue;
+            for event in eth_events {
+                queue.push(ExpiredTx::EthereumEvent(event));
+            }
         }
     }
 
@@ -1214,6 +1256,25 @@ where
                 }
             },
             TxType::Wrapper(wrapper) => {
+                // Tx size. Wrapper txs larger than the block space that
+                // cannot be taken up by protocol txs might never be
+                // included in a block. Checking this on rechecks too
+                // evicts the txs made oversized by a parameter change.
+                let max_wrapper_tx_bytes = parameters::max_wrapper_tx_bytes(
+                    &self.state,
+                )
+                .expect("Failed to get max wrapper tx bytes from storage");
+                if tx_bytes.len() as u64 > max_wrapper_tx_bytes {
+                    response.code = ResultCode::ExceedsBlockSpace.into();
+                    response.log = format!(
+                        "{INVALID_MSG}: Wrapper transaction of {} bytes \
+                         exceeds the maximum admissible size of {} bytes",
+                        tx_bytes.len(),
+                        max_wrapper_tx_bytes
+                    );
+                    return response;
+                }
+
                 // Get the gas scale first
                 let gas_scale = match get_gas_scale(&self.state) {
                     Ok(scale) => scale,
@@ -2171,6 +2232,144 @@ mod shell_tests {
         );
     }
 
+    /// Check that expired Ethereum events are retransmitted with a remote
+    /// signer of the protocol key, along with the protocol txs of the vote
+    /// extensions signed at the same height, and that they are queued again
+    /// if they can't be signed.
+    #[test]
+    fn test_commit_broadcasts_expired_eth_events_with_remote_signer() {
+        use std::net::TcpListener;
+
+        use namada_sdk::wallet::remote_signer::{
+            SignRequest, SignerAuthKey, SignerEndpoint, ValidatorSigner,
+            DEFAULT_MAX_HEIGHT_JUMP,
+        };
+        use tokio::sync::mpsc::UnboundedReceiver;
+
+        use crate::shell::test_utils::TestShell;
+
+        if !is_bridge_comptime_enabled() {
+            // NOTE: this test doesn't work if the ethereum bridge
+            // is disabled at compile time.
+            return;
+        }
+
+        let (mut shell, mut broadcaster_rx, _, _) =
+            test_utils::setup_at_height(5);
+        let height = shell.state.in_mem().get_last_block_height();
+        let protocol_key =
+            shell.mode.get_protocol_key().expect("Test failed").clone();
+        let auth_key: SignerAuthKey = "ab".repeat(32).parse().unwrap();
+        let expired_events = vec![
+            EthereumEvent::TransfersToNamada {
+                nonce: 0u64.into(),
+                transfers: vec![],
+            },
+            EthereumEvent::TransfersToNamada {
+                nonce: 1u64.into(),
+                transfers: vec![],
+            },
+        ];
+        let push_expired_events = |shell: &mut TestShell| {
+            for event in &expired_events {
+                shell
+                    .state
+                    .in_mem_mut()
+                    .expired_txs_queue
+                    .push(ExpiredTx::EthereumEvent(event.clone()));
+            }
+        };
+
+        // A stand-in signer of the protocol key, serving requests until the
+        // end of the test
+        let state_dir = tempdir().unwrap();
+        let use_remote_signer =
+            |shell: &mut TestShell, name: &str, conflict: bool| {
+                let mut signer = ValidatorSigner::open(
+                    protocol_key.clone(),
+                    state_dir.path().join(name),
+                    DEFAULT_MAX_HEIGHT_JUMP,
+                )
+                .unwrap();
+                if conflict {
+                    signer
+                        .sign(&SignRequest {
+                            height,
+                            step: SignStep::EthEventsRetransmitVext,
+                            hash: Hash::sha256(b"conflict"),
+                        })
+                        .unwrap();
+                }
+                let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+                let endpoint = SignerEndpoint::Tcp(
+                    listener.local_addr().unwrap().to_string(),
+                );
+                let signer_auth_key = auth_key.clone();
+                std::thread::spawn(move || {
+                    for stream in listener.incoming() {
+                        let _ = signer.serve_authenticated_connection(
+                            &mut stream.unwrap(),
+                            &signer_auth_key,
+                        );
+                    }
+                });
+                if let ShellMode::Validator { remote_signer, .. } =
+                    &mut shell.mode
+                {
+                    *remote_signer = Some(
+                        RemoteSigner::new(
+                            vec![endpoint],
+                            None,
+                            protocol_key.to_public(),
+                            Some(auth_key.clone()),
+                        )
+                        .unwrap(),
+                    );
+                }
+            };
+        let broadcast_eth_events =
+            |broadcaster_rx: &mut UnboundedReceiver<Vec<u8>>| {
+                std::iter::from_fn(|| broadcaster_rx.try_recv().ok())
+                    .filter_map(|serialized_tx| {
+                        let tx = Tx::try_from_bytes(&serialized_tx[..]).ok()?;
+                        ethereum_tx_data_variants::EthEventsVext::try_from(&tx)
+                            .ok()
+                            .map(|ext| ext.data.ethereum_events)
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+        // The expired events are retransmitted in their own tx, signed at
+        // the same height as the vote extension of the new events
+        use_remote_signer(&mut shell, "clean.toml", false);
+        push_expired_events(&mut shell);
+        shell.broadcast_queued_txs();
+        let broadcast = broadcast_eth_events(&mut broadcaster_rx);
+        assert_eq!(broadcast.len(), 2);
+        assert!(broadcast.contains(&expired_events));
+        assert_eq!(
+            shell.state.in_mem_mut().expired_txs_queue.drain().count(),
+            0
+        );
+
+        // If the retransmission can't be signed, the events are queued again
+        use_remote_signer(&mut shell, "conflict.toml", true);
+        push_expired_events(&mut shell);
+        shell.broadcast_queued_txs();
+        assert!(
+            !broadcast_eth_events(&mut broadcaster_rx)
+                .contains(&expired_events)
+        );
+        let queued: Vec<_> = shell
+            .state
+            .in_mem_mut()
+            .expired_txs_queue
+            .drain()
+            .map(|ExpiredTx::EthereumEvent(event)| event)
+            .collect();
+        assert_eq!(queued, expired_events);
+    }
+
     /// Test that Ethereum events with outdated nonces are
     /// not validated by `CheckTx`.
     #[test]
@@ -2932,6 +3131,60 @@ mod shell_tests {
         assert_eq!(result.code, ResultCode::TooLarge.into());
     }
 
+    /// Test that wrappers not fitting in the block space guaranteed to them
+    /// are rejected, also when rechecking the mempool
+    #[test]
+    fn test_max_wrapper_tx_bytes_check_tx() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+
+        let new_tx = |size: usize| {
+            let keypair = super::test_utils::gen_keypair();
+            let mut wrapper =
+                Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                    Fee {
+                        amount_per_gas_unit: DenominatedAmount::native(
+                            100.into(),
+                        ),
+                        token: shell.state.in_mem().native_token.clone(),
+                    },
+                    keypair.ref_to(),
+                    GAS_LIMIT.into(),
+                ))));
+            wrapper.header.chain_id = shell.chain_id.clone();
+            wrapper
+                .set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+            wrapper.set_data(Data::new(vec![0; size]));
+            wrapper.sign_wrapper(keypair);
+
+            wrapper.to_bytes()
+        };
+        let tx = new_tx(50_000);
+
+        let result = shell.mempool_validate(&tx, MempoolTxType::NewTransaction);
+        assert!(result.code != ResultCode::ExceedsBlockSpace.into());
+
+        // Shrink the proposal space such that the tx cannot fit in the half
+        // of it guaranteed to wrappers
+        let proposal_bytes_key =
+            parameters::storage::get_max_proposal_bytes_key();
+        let max_proposal_bytes =
+            parameters::ProposalBytes::new(tx.len() as u64 * 2 - 2).unwrap();
+        shell
+            .state
+            .write(&proposal_bytes_key, max_proposal_bytes)
+            .unwrap();
+        assert_eq!(
+            parameters::max_wrapper_tx_bytes(&shell.state).unwrap(),
+            tx.len() as u64 - 1
+        );
+
+        let result = shell.mempool_validate(&tx, MempoolTxType::NewTransaction);
+        assert_eq!(result.code, ResultCode::ExceedsBlockSpace.into());
+        let result =
+            shell.mempool_validate(&tx, MempoolTxType::RecheckTransaction);
+        assert_eq!(result.code, ResultCode::ExceedsBlockSpace.into());
+    }
+
     /// Test the that the shell can restore it's state
     /// from a snapshot if it is not syncing
     #[test]
@@ -1,13 +1,17 @@
 //! Implementation of the [`RequestPrepareProposal`] ABCI++ method for the Shell
 
 use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
 
 use namada_sdk::address::Address;
 use namada_sdk::gas::TxGasMeter;
 use namada_sdk::key::tm_raw_hash_to_string;
 use namada_sdk::parameters::get_gas_scale;
 use namada_sdk::proof_of_stake::storage::find_validator_by_raw_hash;
-use namada_sdk::state::{DBIter, StorageHasher, TempWlState, TxIndex, DB};
+use namada_sdk::state::{
+    DBIter, StorageHasher, StorageRead, TempWlState, TxIndex, DB,
+};
 use namada_sdk::token::{Amount, DenominatedAmount};
 use namada_sdk::tx::data::WrapperTx;
 use namada_sdk::tx::Tx;
@@ -126,8 +130,15 @@ where
         let mut vp_wasm_cache = self.vp_wasm_cache.clone();
         let mut tx_wasm_cache = self.tx_wasm_cache.clone();
 
+        // Give precedence to the wrappers paying the highest fees
+        let txs = sort_by_fee_priority(
+            txs,
+            &temp_state.in_mem().native_token,
+            &temp_state,
+        );
+
         let txs = txs
-            .iter()
+            .into_iter()
             .enumerate()
             .filter_map(|(tx_index, tx_bytes)| {
                 let result = validate_wrapper_bytes(
@@ -166,8 +177,9 @@ where
                                 false
                             }
                             AllocFailure::OverflowsBin { bin_resource} => {
-                                // TODO(namada#3250): handle tx whose size is greater
-                                // than bin size
+                                // NB: oversized wrappers are rejected by
+                                // `CheckTx`, so this should only happen to
+                                // txs exceeding the block gas
                                 tracing::warn!(
                                     ?tx_bytes,
                                     bin_resource,
@@ -356,6 +368,60 @@ where
         .map_or_else(|e| Err(Error::TxApply(e)), |_| Ok(()))
 }
 
+/// Sort the wrapper txs of the mempool by decreasing fee priority. Ties are
+/// broken by the order in which the txs were handed over by CometBFT.
+fn sort_by_fee_priority<'tx, S>(
+    txs: &'tx [TxBytes],
+    native_token: &Address,
+    storage: &S,
+) -> Vec<&'tx TxBytes>
+where
+    S: StorageRead,
+{
+    let gas_prices: BTreeMap<Address, Amount> = storage
+        .read(&namada_sdk::parameters::storage::get_gas_cost_key())
+        .expect("Must be able to read gas cost parameter")
+        .unwrap_or_default();
+    let mut txs: Vec<_> = txs.iter().collect();
+    // NB: this sort is stable, which preserves the mempool order of txs
+    // with the same priority
+    txs.sort_by_cached_key(|tx_bytes| {
+        Reverse(fee_priority(tx_bytes, native_token, &gas_prices, storage))
+    });
+    txs
+}
+
+/// Compute the fee paid per gas unit by a wrapper tx, converted to the native
+/// token using the ratio between the minimum gas prices of the native token
+/// and of the fee token. Returns `None` for txs that cannot be decoded or
+/// whose fee token is not whitelisted, which get the lowest priority.
+fn fee_priority<S>(
+    tx_bytes: &[u8],
+    native_token: &Address,
+    gas_prices: &BTreeMap<Address, Amount>,
+    storage: &S,
+) -> Option<Amount>
+where
+    S: StorageRead,
+{
+    let tx = Tx::try_from_bytes(tx_bytes).ok()?;
+    let wrapper = tx.header.wrapper()?;
+    let amount_per_gas_unit = namada_sdk::token::denom_to_amount(
+        wrapper.fee.amount_per_gas_unit,
+        &wrapper.fee.token,
+        storage,
+    )
+    .ok()?;
+    if &wrapper.fee.token == native_token {
+        return Some(amount_per_gas_unit);
+    }
+    let native_min_gas_price = gas_prices.get(native_token)?;
+    let token_min_gas_price = gas_prices.get(&wrapper.fee.token)?;
+    amount_per_gas_unit
+        .checked_mul(*native_min_gas_price)?
+        .checked_div(*token_min_gas_price)
+}
+
 fn compute_min_gas_price<D, H>(
     fee_token: &Address,
     proposer_local_config: Option<&ValidatorLocalConfig>,
@@ -1219,6 +1285,83 @@ mod test_prepare_proposal {
         assert_eq!(result.txs.first().unwrap(), &wrapper_tx.to_bytes());
     }
 
+    // Check that wrappers are included in the block by decreasing fee per gas
+    // unit, converting the fees paid in non-native tokens with the ratio of the
+    // minimum gas prices, and breaking ties with the mempool order
+    #[test]
+    fn test_fee_priority_ordering() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+
+        let apfel_denom = read_denom(&shell.state, &address::testing::apfel())
+            .expect("unable to read denomination from storage")
+            .expect("unable to find denomination of apfels");
+        let albert =
+            Address::from(&wallet::defaults::albert_keypair().to_public());
+        namada_sdk::token::credit_tokens(
+            &mut shell.state,
+            &address::testing::apfel(),
+            &albert,
+            GAS_LIMIT.into(),
+        )
+        .unwrap();
+
+        // One apfel is worth ten times the native token
+        let gas_cost_key = namada_sdk::parameters::storage::get_gas_cost_key();
+        let gas_prices = BTreeMap::from([
+            (shell.state.in_mem().native_token.clone(), Amount::from(10)),
+            (address::testing::apfel(), Amount::from(1)),
+        ]);
+        shell.shell.state.write(&gas_cost_key, gas_prices).unwrap();
+        shell.commit();
+
+        let make_wrapper = |amount_per_gas_unit, token| {
+            let wrapper = WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit,
+                    token,
+                },
+                wallet::defaults::albert_keypair().ref_to(),
+                GAS_LIMIT.into(),
+            );
+            let mut wrapper_tx =
+                Tx::from_type(TxType::Wrapper(Box::new(wrapper)));
+            wrapper_tx.header.chain_id = shell.chain_id.clone();
+            wrapper_tx
+                .set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+            wrapper_tx
+                .set_data(Data::new("transaction data".as_bytes().to_owned()));
+            wrapper_tx.sign_wrapper(wallet::defaults::albert_keypair());
+            wrapper_tx.to_bytes()
+        };
+        let native_token = shell.state.in_mem().native_token.clone();
+        let low_fee = make_wrapper(
+            DenominatedAmount::native(10.into()),
+            native_token.clone(),
+        );
+        let apfel_fee = make_wrapper(
+            DenominatedAmount::new(1.into(), apfel_denom),
+            address::testing::apfel(),
+        );
+        let high_fee =
+            make_wrapper(DenominatedAmount::native(20.into()), native_token);
+
+        let req = RequestPrepareProposal {
+            txs: vec![
+                low_fee.clone().into(),
+                apfel_fee.clone().into(),
+                high_fee.clone().into(),
+            ],
+            max_tx_bytes: 0,
+            time: None,
+            ..Default::default()
+        };
+        let result = shell.prepare_proposal(req);
+        assert_eq!(
+            result.txs,
+            vec![high_fee.into(), low_fee.into(), apfel_fee.into()]
+        );
+    }
+
     // Check that a wrapper setting a fee amount lower than the minimum accepted
     // by the validator is not included in the block
     #[test]
@@ -16,7 +16,10 @@ where
     /// INVARIANT: This method must be stateless.
     pub fn query(&self, query: request::Query) -> response::Query {
         // Invoke the root RPC handler - returns borsh-encoded data on success
-        let result = if query.path == RPC.shell().dry_run_tx_path() {
+        let result = if query.path == RPC.shell().dry_run_tx_path()
+            || query.path == RPC.shell().dry_run_tx_with_trace_path()
+            || query.path == RPC.shell().dry_run_tx_with_diff_path()
+        {
             dry_run_tx(
                 // This is safe as neither the inner `db` nor `in_mem` are
                 // actually mutable, only the `write_log` which is owned by
@@ -3,6 +3,10 @@
 use std::fmt::Display;
 
 use namada_sdk::collections::HashMap;
+use namada_sdk::gas::Gas;
+use namada_sdk::hash::Hash;
+
+use crate::metrics::{self, Metrics};
 
 #[derive(Debug, Default, Clone)]
 pub struct InternalStats {
@@ -17,6 +21,8 @@ pub struct InternalStats {
     tx_cache_size: (usize, usize),
     tx_executed: HashMap<String, u64>,
     wrapper_txs: u64,
+    // The gas consumed by each tx, including its wrapper
+    tx_gas: HashMap<Hash, u64>,
 }
 
 impl InternalStats {
@@ -77,6 +83,41 @@ impl InternalStats {
         self.wrapper_txs += 1;
     }
 
+    /// Set the gas consumed by a tx. The gas of the inner txs overrides the
+    /// gas of their wrapper, since it's accumulated on the same gas meter.
+    pub fn set_tx_gas(&mut self, tx_hash: Hash, gas: Gas) {
+        self.tx_gas.insert(tx_hash, gas.into());
+    }
+
+    /// The total gas consumed by the txs
+    pub fn gas_used(&self) -> u64 {
+        self.tx_gas.values().sum()
+    }
+
+    /// Record the stats of a finalized block in the node's metrics
+    pub fn record_metrics(&self, metrics: &Metrics) {
+        metrics.inc_txs("successful", self.successful_tx);
+        metrics.inc_txs("rejected", self.rejected_txs);
+        metrics.inc_txs("errored", self.errored_txs);
+        metrics.inc_txs("unrun", self.unrun_txs);
+        metrics.inc_txs(
+            "discarded_by_failed_batch",
+            self.successful_tx_in_failed_batch,
+        );
+        metrics.inc_txs("wrapper", self.wrapper_txs);
+        metrics.set_block_gas_used(self.gas_used());
+        metrics.set_wasm_cache_size(
+            metrics::CACHE_VP,
+            self.vp_cache_size.0,
+            self.vp_cache_size.1,
+        );
+        metrics.set_wasm_cache_size(
+            metrics::CACHE_TX,
+            self.tx_cache_size.0,
+            self.tx_cache_size.1,
+        );
+    }
+
     /// Merges two intances of [`InternalStats`]. The caches stats are left
     /// untouched.
     pub fn merge(&mut self, other: Self) {
@@ -93,6 +134,7 @@ impl InternalStats {
                 .or_insert(cnt);
         }
         self.wrapper_txs += other.wrapper_txs;
+        self.tx_gas.extend(other.tx_gas);
     }
 }
 
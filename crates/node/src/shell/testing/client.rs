@@ -86,6 +86,9 @@ pub fn run(
                 cmds::NamadaRelayer::ValidatorSet(sub_cmd) => {
                     NamadaRelayer::ValidatorSet(sub_cmd)
                 }
+                cmds::NamadaRelayer::Daemon(sub_cmd) => {
+                    NamadaRelayer::Daemon(sub_cmd)
+                }
             };
             rt.block_on(CliApi::handle_relayer_command(
                 Some(node.clone()),
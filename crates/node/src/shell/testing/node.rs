@@ -815,7 +815,10 @@ impl Client for MockNode {
             prove,
         };
         let borrowed = self.shell.lock().unwrap();
-        if request.path == RPC.shell().dry_run_tx_path() {
+        if request.path == RPC.shell().dry_run_tx_path()
+            || request.path == RPC.shell().dry_run_tx_with_trace_path()
+            || request.path == RPC.shell().dry_run_tx_with_diff_path()
+        {
             dry_run_tx(
                 // This is safe because nothing else is using `self.state`
                 // concurrently and the `TempWlState` will be dropped right
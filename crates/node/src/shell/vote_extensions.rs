@@ -5,11 +5,12 @@ pub mod eth_events;
 pub mod val_set_update;
 
 use drain_filter_polyfill::DrainFilter;
-use namada_sdk::eth_bridge::protocol::transactions::bridge_pool_roots::sign_bridge_pool_root;
-use namada_sdk::eth_bridge::protocol::transactions::ethereum_events::sign_ethereum_events;
+use namada_sdk::eth_bridge::protocol::transactions::bridge_pool_roots::sign_bridge_pool_root_with;
+use namada_sdk::eth_bridge::protocol::transactions::ethereum_events::sign_ethereum_events_with;
 use namada_sdk::eth_bridge::protocol::transactions::validator_set_update::sign_validator_set_update;
 pub use namada_sdk::eth_bridge::protocol::validation::VoteExtensionError;
 use namada_sdk::tx::Signed;
+use namada_sdk::wallet::remote_signer::SignStep;
 use namada_vote_ext::{
     bridge_pool_roots, ethereum_events, validator_set_update, VoteExtension,
 };
@@ -45,28 +46,25 @@ where
         &mut self,
     ) -> Option<Signed<ethereum_events::Vext>> {
         let events = self.new_ethereum_events();
-        self.sign_ethereum_events(events)
+        self.sign_ethereum_events(events, SignStep::EthEventsVext)
     }
 
-    /// Sign the given Ethereum events, and return the associated
-    /// vote extension protocol transaction.
+    /// Sign the given Ethereum events at the given step, and return the
+    /// associated vote extension protocol transaction.
     pub fn sign_ethereum_events(
         &self,
         ethereum_events: Vec<EthereumEvent>,
+        step: SignStep,
     ) -> Option<Signed<ethereum_events::Vext>> {
         let validator_addr = self
             .mode
             .get_validator_address()
             .expect(VALIDATOR_EXPECT_MSG);
-        let protocol_key = match &self.mode {
-            ShellMode::Validator { data, .. } => &data.keys.protocol_keypair,
-            _ => unreachable!("{VALIDATOR_EXPECT_MSG}"),
-        };
-        sign_ethereum_events(
+        sign_ethereum_events_with(
             &self.state,
             validator_addr,
-            protocol_key,
             ethereum_events,
+            |hash| self.sign_with_protocol_key(step, hash),
         )
         .map(|ethereum_events::SignedVext(ext)| ext)
     }
@@ -83,15 +81,11 @@ where
             .mode
             .get_eth_bridge_keypair()
             .expect(VALIDATOR_EXPECT_MSG);
-        let protocol_key = match &self.mode {
-            ShellMode::Validator { data, .. } => &data.keys.protocol_keypair,
-            _ => unreachable!("{VALIDATOR_EXPECT_MSG}"),
-        };
-        sign_bridge_pool_root(
+        sign_bridge_pool_root_with(
             &self.state,
             validator_addr,
             eth_hot_key,
-            protocol_key,
+            |hash| self.sign_with_protocol_key(SignStep::BridgePoolVext, hash),
         )
         .map(|bridge_pool_roots::SignedVext(ext)| ext)
     }
@@ -116,6 +110,71 @@ where
         )
     }
 
+    /// Sign the hash of a message with the protocol key of this validator,
+    /// using its remote signers if any are configured. Returns `None` if the
+    /// remote signers don't sign the message.
+    pub fn sign_with_protocol_key(
+        &self,
+        step: SignStep,
+        hash: &Hash,
+    ) -> Option<common::Signature> {
+        match &self.mode {
+            ShellMode::Validator {
+                remote_signer: Some(remote_signer),
+                ..
+            } => {
+                let height = self.state.in_mem().get_last_block_height();
+                remote_signer
+                    .sign(height, step, hash)
+                    .map_err(|err| {
+                        tracing::error!(
+                            ?step,
+                            %height,
+                            "Failed to sign with the remote signers: {err}"
+                        )
+                    })
+                    .ok()
+            }
+            ShellMode::Validator { data, .. } => {
+                Some(common::SigScheme::sign(&data.keys.protocol_keypair, hash))
+            }
+            _ => unreachable!("{VALIDATOR_EXPECT_MSG}"),
+        }
+    }
+
+    /// Sign the given protocol tx with the protocol key of this validator.
+    /// Returns `None` if the remote signers don't sign it.
+    pub fn sign_protocol_tx(&self, protocol_tx: &EthereumTxData) -> Option<Tx> {
+        // The protocol txs of the vote extensions are signed at each height,
+        // so each of them needs its own high-water mark
+        let step = match protocol_tx {
+            EthereumTxData::EthEventsVext(_) => SignStep::EthEventsTx,
+            EthereumTxData::BridgePoolVext(_) => SignStep::BridgePoolTx,
+            EthereumTxData::ValSetUpdateVext(_) => SignStep::ValSetUpdateTx,
+            _ => SignStep::ProtocolTx,
+        };
+        self.sign_protocol_tx_at_step(protocol_tx, step)
+    }
+
+    /// Sign the given protocol tx with the protocol key of this validator at
+    /// the given step. Returns `None` if the remote signers don't sign it.
+    pub fn sign_protocol_tx_at_step(
+        &self,
+        protocol_tx: &EthereumTxData,
+        step: SignStep,
+    ) -> Option<Tx> {
+        let protocol_key = self
+            .mode
+            .get_protocol_key()
+            .expect(VALIDATOR_EXPECT_MSG)
+            .to_public();
+        protocol_tx
+            .try_sign_with(protocol_key, self.chain_id.clone(), |_, hash| {
+                self.sign_with_protocol_key(step, hash).ok_or(())
+            })
+            .ok()
+    }
+
     /// Given a slice of [`TxBytes`], return an iterator over the
     /// ones we could deserialize to vote extension protocol txs.
     pub fn deserialize_vote_extensions<'shell>(
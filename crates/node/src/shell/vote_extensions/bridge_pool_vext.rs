@@ -228,6 +228,97 @@ mod test_bp_vote_extensions {
         )
     }
 
+    /// Test that the bridge pool root vext can be signed by a remote signer
+    /// of the protocol key, which refuses to double sign.
+    #[test]
+    fn test_remote_signer() {
+        use std::net::TcpListener;
+
+        use namada_sdk::hash::Hash;
+        use namada_sdk::wallet::remote_signer::{
+            RemoteSigner, SignRequest, SignStep, SignerAuthKey,
+            SignerEndpoint, ValidatorSigner, DEFAULT_MAX_HEIGHT_JUMP,
+        };
+
+        use crate::shell::ShellMode;
+
+        if !is_bridge_comptime_enabled() {
+            // NOTE: this test doesn't work if the ethereum bridge
+            // is disabled at compile time.
+            return;
+        }
+        let (mut shell, _broadcaster, _, _oracle_control_recv) =
+            setup_at_height(1u64);
+        shell.state.in_mem_mut().block.height =
+            shell.state.in_mem().get_last_block_height();
+        shell.commit();
+        let height = shell.state.in_mem().get_last_block_height();
+        let protocol_key =
+            shell.mode.get_protocol_key().expect("Test failed").clone();
+        let expected = shell.extend_vote_with_bp_roots().expect("Test failed");
+        let auth_key: SignerAuthKey = "ab".repeat(32).parse().unwrap();
+
+        // A stand-in signer of the protocol key, serving the given number of
+        // requests
+        let state_dir = tempfile::tempdir().unwrap();
+        let spawn_signer = |name: &str, conflict: bool, requests: usize| {
+            let mut signer = ValidatorSigner::open(
+                protocol_key.clone(),
+                state_dir.path().join(name),
+                DEFAULT_MAX_HEIGHT_JUMP,
+            )
+            .unwrap();
+            if conflict {
+                signer
+                    .sign(&SignRequest {
+                        height,
+                        step: SignStep::BridgePoolVext,
+                        hash: Hash::sha256(b"conflict"),
+                    })
+                    .unwrap();
+            }
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let endpoint =
+                SignerEndpoint::Tcp(listener.local_addr().unwrap().to_string());
+            let auth_key = auth_key.clone();
+            let handle = std::thread::spawn(move || {
+                for _ in 0..requests {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    signer
+                        .serve_authenticated_connection(&mut stream, &auth_key)
+                        .unwrap();
+                }
+            });
+            (endpoint, handle)
+        };
+        let new_remote_signer = |endpoint: SignerEndpoint| {
+            RemoteSigner::new(
+                vec![endpoint],
+                None,
+                protocol_key.to_public(),
+                Some(auth_key.clone()),
+            )
+            .unwrap()
+        };
+
+        // The vext is signed, and can be signed again at the same height
+        let (endpoint, signer) = spawn_signer("clean.toml", false, 2);
+        if let ShellMode::Validator { remote_signer, .. } = &mut shell.mode {
+            *remote_signer = Some(new_remote_signer(endpoint));
+        }
+        assert_eq!(shell.extend_vote_with_bp_roots(), Some(expected.clone()));
+        assert_eq!(shell.extend_vote_with_bp_roots(), Some(expected));
+        signer.join().unwrap();
+
+        // A signer which has signed a conflicting vext at the height refuses
+        let (endpoint, signer) = spawn_signer("conflict.toml", true, 1);
+        if let ShellMode::Validator { remote_signer, .. } = &mut shell.mode {
+            *remote_signer = Some(new_remote_signer(endpoint));
+        }
+        assert!(shell.extend_vote_with_bp_roots().is_none());
+        signer.join().unwrap();
+    }
+
     /// Test that we de-duplicate the bridge pool vexts
     /// in a block proposal by validator address.
     #[test]
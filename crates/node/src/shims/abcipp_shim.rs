@@ -24,6 +24,7 @@ use super::abcipp_shim_types::shim::{
 };
 use crate::config;
 use crate::config::{Action, ActionAtHeight};
+use crate::metrics::Metrics;
 use crate::shell::{EthereumOracleChannels, Shell};
 use crate::storage::DbSnapshot;
 use crate::tendermint::abci::{request, Request as Req, Response as Resp};
@@ -95,6 +96,11 @@ impl AbcippShim {
         )
     }
 
+    /// Get a handle to the shell's metrics
+    pub fn metrics(&self) -> Metrics {
+        self.service.metrics.clone()
+    }
+
     /// Get the hash of the txs in the block
     pub fn get_hash(&self) -> Hash {
         let bytes: Vec<u8> =
@@ -19,6 +19,7 @@
 //!     - `commit_only_data_commitment`
 //!     - `update_epoch_blocks_delay`
 //!   - `conversion_state`: MASP conversion state
+//!   - `pruned_height`: the latest block height whose history has been pruned
 //! - `subspace`: accounts sub-spaces
 //!   - `{address}/{dyn}`: any byte data associated with accounts
 //! - `diffs`: diffs in account subspaces' key-vals modified with `persist_diff
@@ -102,6 +103,7 @@ const ETHEREUM_HEIGHT_KEY: &str = "ethereum_height";
 const ETH_EVENTS_QUEUE_KEY: &str = "eth_events_queue";
 const RESULTS_KEY_PREFIX: &str = "results";
 const PRED_KEY_PREFIX: &str = "pred";
+const PRUNED_HEIGHT_KEY: &str = "pruned_height";
 
 const MERKLE_TREE_ROOT_KEY_SEGMENT: &str = "root";
 const MERKLE_TREE_STORE_KEY_SEGMENT: &str = "store";
@@ -273,6 +275,30 @@ impl RocksDB {
             .ok_or(Error::DBError("No {cf_name} column family".to_string()))
     }
 
+    /// Get the total size of the SST files of each column family in bytes.
+    /// The column families whose size cannot be read are skipped.
+    pub fn column_family_sizes(&self) -> Vec<(&'static str, u64)> {
+        [
+            SUBSPACE_CF,
+            DIFFS_CF,
+            ROLLBACK_CF,
+            STATE_CF,
+            BLOCK_CF,
+            REPLAY_PROTECTION_CF,
+        ]
+        .into_iter()
+        .filter_map(|cf_name| {
+            let cf = self.get_column_family(cf_name).ok()?;
+            let size = self
+                .inner
+                .property_int_value_cf(cf, "rocksdb.total-sst-files-size")
+                .ok()
+                .flatten()?;
+            Some((cf_name, size))
+        })
+        .collect()
+    }
+
     fn read_value<T>(
         &self,
         cf: &ColumnFamily,
@@ -1475,6 +1501,16 @@ impl DB for RocksDB {
         height: BlockHeight,
         last_height: BlockHeight,
     ) -> Result<Option<Vec<u8>>> {
+        // The diffs at or below the pruned height are gone
+        if let Some(pruned_height) = self.read_pruned_height()? {
+            if height <= pruned_height {
+                return Err(Error::Pruned {
+                    height,
+                    oldest_height: pruned_height.next_height(),
+                });
+            }
+        }
+
         // Check if the value changed at this height
         let diffs_cf = self.get_column_family(DIFFS_CF)?;
         let (old_val_key, new_val_key) = old_and_new_diff_key(key, height)?;
@@ -1781,6 +1817,50 @@ impl DB for RocksDB {
         Ok(())
     }
 
+    fn prune_history(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+    ) -> Result<()> {
+        // All the diffs at this height are under the `{height}/` prefix. A
+        // single range deletion is used instead of deleting the keys one by one
+        // to keep the batch small and let the compaction drop the range.
+        // Note that '0' is the successor of the '/' separator.
+        let diffs_cf = self.get_column_family(DIFFS_CF)?;
+        let prefix = height.raw();
+        batch.0.delete_range_cf(
+            diffs_cf,
+            format!("{prefix}/"),
+            format!("{prefix}0"),
+        );
+
+        // The block data at this height. We cannot use a range deletion here,
+        // because the Merkle tree stores of the epoch with the same number
+        // share the prefix.
+        let block_cf = self.get_column_family(BLOCK_CF)?;
+        for segment in [
+            BLOCK_HEADER_KEY_SEGMENT,
+            BLOCK_TIME_KEY_SEGMENT,
+            EPOCH_KEY_SEGMENT,
+            PRED_EPOCHS_KEY_SEGMENT,
+            ADDRESS_GEN_KEY_SEGMENT,
+        ] {
+            batch.0.delete_cf(block_cf, format!("{prefix}/{segment}"));
+        }
+        batch
+            .0
+            .delete_cf(block_cf, format!("{RESULTS_KEY_PREFIX}/{prefix}"));
+
+        let state_cf = self.get_column_family(STATE_CF)?;
+        self.add_value_to_batch(state_cf, PRUNED_HEIGHT_KEY, &height, batch);
+        Ok(())
+    }
+
+    fn read_pruned_height(&self) -> Result<Option<BlockHeight>> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        self.read_value(state_cf, PRUNED_HEIGHT_KEY)
+    }
+
     #[inline]
     fn overwrite_entry(
         &self,
@@ -2234,6 +2314,7 @@ mod imp {
 #[allow(clippy::arithmetic_side_effects)]
 #[cfg(test)]
 mod test {
+    use assert_matches::assert_matches;
     use namada_apps_lib::collections::HashMap;
     use namada_sdk::address::EstablishedAddressGen;
     use namada_sdk::state::{MerkleTree, Sha256Hasher};
@@ -2752,6 +2833,74 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_prune_history() {
+        let dir = tempdir().unwrap();
+        let mut db = RocksDB::open(dir.path(), None);
+
+        let key = Key::parse("with_diffs").unwrap();
+        let initial_val = vec![1_u8, 1, 0, 0];
+        let overwrite_val = vec![1_u8, 1, 1, 0];
+
+        // Write the key at two heights whose raw keys share a prefix
+        let height_0 = BlockHeight::first();
+        let height_1 = height_0 + 9;
+        let mut batch = RocksDB::batch();
+        db.batch_write_subspace_val(
+            &mut batch,
+            height_0,
+            &key,
+            &initial_val,
+            true,
+        )
+        .unwrap();
+        db.exec_batch(batch).unwrap();
+        let mut batch = RocksDB::batch();
+        db.batch_write_subspace_val(
+            &mut batch,
+            height_1,
+            &key,
+            &overwrite_val,
+            true,
+        )
+        .unwrap();
+        db.exec_batch(batch).unwrap();
+        assert!(db.read_pruned_height().unwrap().is_none());
+
+        let mut batch = RocksDB::batch();
+        db.prune_history(&mut batch, height_0).unwrap();
+        db.exec_batch(batch).unwrap();
+
+        assert_eq!(db.read_pruned_height().unwrap(), Some(height_0));
+        {
+            let diffs_cf = db.get_column_family(DIFFS_CF).unwrap();
+
+            // Diffs keys at height_0 must be gone
+            let (_old_h0, new_h0) =
+                old_and_new_diff_key(&key, height_0).unwrap();
+            assert!(db.inner.get_cf(diffs_cf, new_h0).unwrap().is_none());
+
+            // Diffs keys at height_1 must be present
+            let (old_h1, new_h1) =
+                old_and_new_diff_key(&key, height_1).unwrap();
+            assert!(db.inner.get_cf(diffs_cf, old_h1).unwrap().is_some());
+            assert!(db.inner.get_cf(diffs_cf, new_h1).unwrap().is_some());
+        }
+
+        // Reading the pruned height fails
+        assert_matches!(
+            db.read_subspace_val_with_height(&key, height_0, height_1),
+            Err(Error::Pruned { height, oldest_height })
+                if height == height_0 && oldest_height == height_0 + 1
+        );
+        // The remaining history is still readable
+        assert_eq!(
+            db.read_subspace_val_with_height(&key, height_1, height_1)
+                .unwrap(),
+            Some(overwrite_val)
+        );
+    }
+
     /// A test helper to write a block
     fn add_block_to_batch(
         db: &RocksDB,
@@ -2,9 +2,13 @@
 
 use std::str::FromStr;
 
-use namada_apps_lib::cli::args::{self, TestGenesis};
+use namada_apps_lib::cli::api::CliIo;
+use namada_apps_lib::cli::args::{self, PrecompileWasm, TestGenesis};
 use namada_apps_lib::client::utils::PRE_GENESIS_DIR;
 use namada_apps_lib::config::genesis::{self, AddrOrPk};
+use namada_apps_lib::wasm_loader::precompiled::{
+    precompile_targets, WasmCaches,
+};
 use namada_apps_lib::{cli, wallet};
 use namada_sdk::address::{Address, ImplicitAddress};
 use namada_sdk::key::common;
@@ -135,3 +139,46 @@ pub fn test_genesis(args: TestGenesis, global_args: args::Global) {
         }
     }
 }
+
+pub fn precompile_wasm(args: PrecompileWasm, global_args: args::Global) {
+    let PrecompileWasm { export_bundle } = args;
+
+    let chain_ctx = cli::Context::new::<CliIo>(global_args)
+        .expect("Failed to load the context")
+        .take_chain_or_exit();
+    let chain_dir = chain_ctx.config.ledger.chain_dir();
+    let wasm_dir = chain_ctx.wasm_dir();
+
+    let targets = match precompile_targets(&chain_dir, &wasm_dir) {
+        Ok(targets) => targets,
+        Err(err) => {
+            eprintln!("Unable to find the WASMs to compile: {err}");
+            cli::safe_exit(1)
+        }
+    };
+    let caches = WasmCaches::open(&chain_dir);
+    match caches.precompile(&targets) {
+        Ok(compiled) => println!(
+            "Compiled {compiled} new WASM module(s), {} in total are cached.",
+            targets.len()
+        ),
+        Err(err) => {
+            eprintln!("Failed to precompile the WASMs: {err}");
+            cli::safe_exit(1)
+        }
+    }
+
+    if let Some(bundle_path) = export_bundle {
+        match caches.export_bundle(&targets, &bundle_path) {
+            Ok(hash) => println!(
+                "Exported the compiled modules to {} with hash {hash}, to \
+                 import with `join-network --wasm-cache-bundle-hash {hash}`.",
+                bundle_path.to_string_lossy()
+            ),
+            Err(err) => {
+                eprintln!("Failed to export the compiled modules: {err}");
+                cli::safe_exit(1)
+            }
+        }
+    }
+}
@@ -475,6 +475,21 @@ where
     Ok(tx_size <= max_tx_bytes as usize)
 }
 
+/// Get the largest size in bytes of a wrapper tx that can be included in a
+/// block. Protocol txs may take up to half of the proposal space, so wrapper
+/// txs are only guaranteed the other half, further capped by the max tx bytes
+/// parameter.
+pub fn max_wrapper_tx_bytes<S>(storage: &S) -> Result<u64>
+where
+    S: StorageRead,
+{
+    let max_tx_bytes: u32 = storage
+        .read(&storage::get_max_tx_bytes_key())?
+        .expect("The max tx bytes param should be present in storage");
+    let max_proposal_bytes = read_max_proposal_bytes(storage)?.get();
+    Ok(u64::from(max_tx_bytes).min(max_proposal_bytes.div_ceil(2)))
+}
+
 /// Storage key for the Ethereum address of wNam.
 pub fn native_erc20_key() -> storage::Key {
     storage::get_native_erc20_key_at_addr(ADDRESS)
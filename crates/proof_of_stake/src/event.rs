@@ -15,6 +15,9 @@ pub mod types {
 
     /// Slash event.
     pub const SLASH: EventType = event_type!(PosEvent, "slash");
+
+    /// Auto-compounding of bond rewards event.
+    pub const AUTO_COMPOUND: EventType = event_type!(PosEvent, "auto-compound");
 }
 
 /// Proof of Stake event.
@@ -27,6 +30,15 @@ pub enum PosEvent {
         /// Amount of tokens that have been slashed.
         amount: token::Amount,
     },
+    /// Auto-compounding event.
+    AutoCompound {
+        /// The source of the bond whose rewards have been re-bonded.
+        source: Address,
+        /// The validator of the bond.
+        validator: Address,
+        /// Amount of reward tokens that have been re-bonded.
+        amount: token::Amount,
+    },
 }
 
 impl EventToEmit for PosEvent {
@@ -42,6 +54,15 @@ impl From<PosEvent> for Event {
                     .with(SlashedAmount(&amount.into()))
                     .into()
             }
+            PosEvent::AutoCompound {
+                source,
+                validator,
+                amount,
+            } => Event::new(types::AUTO_COMPOUND, EventLevel::Block)
+                .with(BondSource(source))
+                .with(BondValidator(validator))
+                .with(CompoundedAmount(&amount.into()))
+                .into(),
         }
     }
 }
@@ -73,3 +94,45 @@ impl<'amt> EventAttributeEntry<'amt> for SlashedAmount<'amt> {
         self.0
     }
 }
+
+/// Extend an [`Event`] with the source of a bond.
+pub struct BondSource(pub Address);
+
+impl EventAttributeEntry<'static> for BondSource {
+    type Value = Address;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "bond-source";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the validator of a bond.
+pub struct BondValidator(pub Address);
+
+impl EventAttributeEntry<'static> for BondValidator {
+    type Value = Address;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "bond-validator";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the amount of re-bonded reward tokens.
+pub struct CompoundedAmount<'amt>(pub &'amt Uint);
+
+impl<'amt> EventAttributeEntry<'amt> for CompoundedAmount<'amt> {
+    type Value = &'amt Uint;
+    type ValueOwned = Uint;
+
+    const KEY: &'static str = "compounded-amount";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
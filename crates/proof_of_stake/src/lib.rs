@@ -67,6 +67,7 @@ use storage::write_validator_name;
 pub use types::GenesisValidator;
 use types::{into_tm_voting_power, DelegationEpochs};
 
+use crate::event::PosEvent;
 use crate::queries::{find_bonds, has_bonds};
 use crate::rewards::{
     add_rewards_to_counter, compute_current_rewards_from_bonds,
@@ -77,7 +78,7 @@ use crate::slashing::{
     compute_amount_after_slashing_withdraw, find_validator_slashes,
 };
 use crate::storage::{
-    below_capacity_validator_set_handle, bond_handle,
+    auto_compound_handle, below_capacity_validator_set_handle, bond_handle,
     consensus_validator_set_handle, delegation_targets_handle,
     delegator_redelegated_bonds_handle, delegator_redelegated_unbonds_handle,
     get_last_reward_claim_epoch, liveness_missed_votes_handle,
@@ -205,6 +206,10 @@ pub const ADDRESS: Address = Address::Internal(InternalAddress::PoS);
 pub const SLASH_POOL_ADDRESS: Address =
     Address::Internal(InternalAddress::PosSlashPool);
 
+/// The maximum number of bonds with auto-compounding whose rewards are
+/// compounded in a single block
+pub const MAX_AUTO_COMPOUND_BONDS_PER_BLOCK: usize = 100;
+
 /// Address of the staking token (i.e. the native token)
 pub fn staking_token_address(storage: &impl StorageRead) -> Address {
     storage
@@ -2857,6 +2862,169 @@ where
     Ok(res)
 }
 
+/// Enable or disable the auto-compounding of a bond's rewards. The rewards of
+/// the bonds with auto-compounding are claimed and re-bonded to the same
+/// validator at the start of every epoch.
+pub fn set_auto_compound<S>(
+    storage: &mut S,
+    source: Option<&Address>,
+    validator: &Address,
+    enabled: bool,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if !is_validator(storage, validator)? {
+        return Err(BondError::NotAValidator(validator.clone()).into());
+    }
+    if let Some(source) = source {
+        if source != validator && is_validator(storage, source)? {
+            return Err(
+                BondError::SourceMustNotBeAValidator(source.clone()).into()
+            );
+        }
+    }
+    let source = source.unwrap_or(validator);
+    tracing::debug!(
+        "Setting auto-compounding of {source} --> Validator {validator} to \
+         {enabled}"
+    );
+
+    let validators = auto_compound_handle().at(source);
+    if enabled {
+        validators.insert(storage, validator.clone())?;
+    } else {
+        validators.remove(storage, validator)?;
+    }
+    Ok(())
+}
+
+/// Check if the rewards of a bond are auto-compounded.
+pub fn is_auto_compounding<S>(
+    storage: &S,
+    source: Option<&Address>,
+    validator: &Address,
+) -> Result<bool>
+where
+    S: StorageRead,
+{
+    let source = source.unwrap_or(validator);
+    auto_compound_handle()
+        .at(source)
+        .contains(storage, validator)
+}
+
+/// Get the next bonds with auto-compounding whose rewards are to be compounded
+/// in the current epoch, up to the given limit. The bonds are spread over the
+/// blocks of the epoch, and the progress is persisted so that every bond is
+/// only returned once per epoch. The bonds that are not reached before the end
+/// of the epoch, or that are skipped because other bonds opted in or out in
+/// the meantime, keep their rewards claimable until the next epoch.
+pub fn next_auto_compound_bonds<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+    limit: usize,
+) -> Result<Vec<BondId>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let cursor_key = storage_key::auto_compound_cursor_key();
+    let processed = match storage.read::<(Epoch, u64)>(&cursor_key)? {
+        Some((epoch, processed)) if epoch == current_epoch => processed,
+        _ => 0,
+    };
+    let bond_ids = auto_compound_handle()
+        .iter(storage)?
+        .skip(usize::try_from(processed).into_storage_result()?)
+        .take(limit)
+        .map(|res| {
+            let (
+                lazy_map::NestedSubKey::Data {
+                    key: source,
+                    nested_sub_key: lazy_set::SubKey::Data(validator),
+                },
+                (),
+            ) = res?;
+            Ok(BondId { source, validator })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if !bond_ids.is_empty() {
+        let count = u64::try_from(bond_ids.len()).into_storage_result()?;
+        storage.write(
+            &cursor_key,
+            (current_epoch, checked!(processed + count)?),
+        )?;
+    }
+    Ok(bond_ids)
+}
+
+/// Claim the rewards of a bond with auto-compounding and bond them to the
+/// same validator. The rewards products of the last epoch must have been
+/// updated.
+///
+/// The bonds that cannot be added to anymore are skipped and their rewards
+/// remain claimable.
+pub fn compound_bond_rewards<S, Gov, Token>(
+    storage: &mut S,
+    events: &mut impl EmitEvents,
+    bond_id: &BondId,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+    Gov: governance::Read<S>,
+    Token: trans_token::Write<S>,
+{
+    let BondId { source, validator } = bond_id;
+    if raw_bond_amount::<S, Gov>(storage, bond_id, current_epoch)?.is_zero() {
+        tracing::debug!("Skipping auto-compounding of empty bond {bond_id}");
+        return Ok(());
+    }
+    // The source may have become a validator since it opted in
+    if source != validator && is_validator(storage, source)? {
+        tracing::debug!(
+            "Skipping auto-compounding of bond {bond_id} from a validator"
+        );
+        return Ok(());
+    }
+    let params = read_pos_params::<S, Gov>(storage)?;
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+    if validator_state_handle(validator)
+        .get(storage, pipeline_epoch, &params)?
+        .is_none()
+    {
+        return Ok(());
+    }
+
+    let amount = claim_reward_tokens::<S, Gov, Token>(
+        storage,
+        Some(source),
+        validator,
+        current_epoch,
+    )?;
+    if amount.is_zero() {
+        return Ok(());
+    }
+    bond_tokens::<S, Gov, Token>(
+        storage,
+        Some(source),
+        validator,
+        amount,
+        current_epoch,
+        None,
+    )?;
+    tracing::debug!(
+        "Auto-compounded {} reward tokens of bond {bond_id}",
+        amount.to_string_native()
+    );
+    events.emit(PosEvent::AutoCompound {
+        source: source.clone(),
+        validator: validator.clone(),
+        amount,
+    });
+    Ok(())
+}
+
 /// Jail a validator by removing it from and updating the validator sets and
 /// changing a its state to `Jailed`. Validators are jailed for liveness and for
 /// misbehaving.
@@ -16,17 +16,17 @@ use namada_systems::governance;
 use crate::lazy_map::NestedSubKey;
 use crate::storage_key::consensus_keys_key;
 use crate::types::{
-    BelowCapacityValidatorSets, BondId, Bonds, CommissionRates,
-    ConsensusValidatorSets, DelegationTargets, DelegatorRedelegatedBonded,
-    DelegatorRedelegatedUnbonded, EpochedSlashes, IncomingRedelegations,
-    LivenessMissedVotes, LivenessSumMissedVotes, OutgoingRedelegations,
-    ReverseOrdTokenAmount, RewardsAccumulator, RewardsProducts, Slashes,
-    TotalConsensusStakes, TotalDeltas, TotalRedelegatedBonded,
-    TotalRedelegatedUnbonded, Unbonds, ValidatorAddresses,
-    ValidatorConsensusKeys, ValidatorDeltas, ValidatorEthColdKeys,
-    ValidatorEthHotKeys, ValidatorMetaData, ValidatorProtocolKeys,
-    ValidatorSetPositions, ValidatorState, ValidatorStates,
-    ValidatorTotalUnbonded, WeightedValidator,
+    AutoCompoundBonds, BelowCapacityValidatorSets, BondId, Bonds,
+    CommissionRates, ConsensusValidatorSets, DelegationTargets,
+    DelegatorRedelegatedBonded, DelegatorRedelegatedUnbonded, EpochedSlashes,
+    IncomingRedelegations, LivenessMissedVotes, LivenessSumMissedVotes,
+    OutgoingRedelegations, ReverseOrdTokenAmount, RewardsAccumulator,
+    RewardsProducts, Slashes, TotalConsensusStakes, TotalDeltas,
+    TotalRedelegatedBonded, TotalRedelegatedUnbonded, Unbonds,
+    ValidatorAddresses, ValidatorConsensusKeys, ValidatorDeltas,
+    ValidatorEthColdKeys, ValidatorEthHotKeys, ValidatorMetaData,
+    ValidatorProtocolKeys, ValidatorSetPositions, ValidatorState,
+    ValidatorStates, ValidatorTotalUnbonded, WeightedValidator,
 };
 use crate::{
     storage_key, LazyCollection, LazySet, MetadataError, OwnedPosParams,
@@ -258,6 +258,12 @@ pub fn delegation_targets_handle(delegator: &Address) -> DelegationTargets {
     DelegationTargets::open(key)
 }
 
+/// Get the storage handle to the bonds with auto-compounding rewards
+pub fn auto_compound_handle() -> AutoCompoundBonds {
+    let key = storage_key::auto_compound_key();
+    AutoCompoundBonds::open(key)
+}
+
 // ---- Storage read + write ----
 
 /// Read owned PoS parameters
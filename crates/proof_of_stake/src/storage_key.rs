@@ -62,6 +62,8 @@ const LAST_STAKED_RATIO_KEY: &str = "last_staked_ratio";
 const LAST_POS_INFLATION_AMOUNT_KEY: &str = "last_inflation_amount";
 const TOTAL_ACTIVE_DELTAS_KEY: &str = "total_active_deltas";
 const DELEGATION_TARGETS_PREFIX: &str = "delegation_targets";
+const AUTO_COMPOUND_KEY: &str = "auto_compound";
+const AUTO_COMPOUND_CURSOR_KEY: &str = "auto_compound_cursor";
 
 /// Is the given key a PoS storage key?
 pub fn is_pos_key(key: &Key) -> bool {
@@ -1122,3 +1124,49 @@ pub fn is_delegation_targets_key(key: &Key) -> bool {
         false
     }
 }
+
+/// Storage key for the bonds with auto-compounding rewards.
+pub fn auto_compound_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&AUTO_COMPOUND_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the progress of the auto-compounding of the current epoch.
+pub fn auto_compound_cursor_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&AUTO_COMPOUND_CURSOR_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the bonds with auto-compounding rewards?
+pub fn is_auto_compound_key(key: &Key) -> bool {
+    matches!(
+        &key.segments[..],
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), ..]
+            if addr == &ADDRESS && prefix == AUTO_COMPOUND_KEY
+    )
+}
+
+/// Is storage key for the auto-compounding flag of a bond? Returns the bond
+/// ID if so.
+pub fn is_auto_compound_bond_key(key: &Key) -> Option<BondId> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(data),
+            DbKeySeg::AddressSeg(source),
+            DbKeySeg::AddressSeg(validator),
+        ] if addr == &ADDRESS
+            && prefix == AUTO_COMPOUND_KEY
+            && data == lazy_map::DATA_SUBKEY =>
+        {
+            Some(BondId {
+                source: source.clone(),
+                validator: validator.clone(),
+            })
+        }
+        _ => None,
+    }
+}
@@ -198,6 +198,24 @@ where
     )
 }
 
+/// DI indirection
+pub fn compound_bond_rewards<S>(
+    storage: &mut S,
+    events: &mut impl EmitEvents,
+    bond_id: &BondId,
+    current_epoch: Epoch,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    crate::compound_bond_rewards::<S, GovStore<S>, token::Store<_>>(
+        storage,
+        events,
+        bond_id,
+        current_epoch,
+    )
+}
+
 /// DI indirection
 pub fn redelegate_tokens<S>(
     storage: &mut S,
@@ -24,6 +24,7 @@ use proptest::test_runner::Config;
 use test_log::test;
 
 use crate::epoched::EpochOffset;
+use crate::event::CompoundedAmount;
 use crate::lazy_map::Collectable;
 use crate::parameters::testing::arb_pos_params;
 use crate::parameters::OwnedPosParams;
@@ -33,11 +34,11 @@ use crate::rewards::{
     PosRewardsCalculator,
 };
 use crate::storage::{
-    delegation_targets_handle, get_consensus_key_set,
+    auto_compound_handle, delegation_targets_handle, get_consensus_key_set,
     liveness_sum_missed_votes_handle,
     read_consensus_validator_set_addresses_with_stake, read_total_stake,
     read_validator_deltas_value, rewards_accumulator_handle,
-    total_deltas_handle,
+    total_deltas_handle, validator_rewards_products_handle,
 };
 use crate::tests::helpers::{
     advance_epoch, arb_genesis_validators, arb_params_and_genesis_validators,
@@ -45,7 +46,7 @@ use crate::tests::helpers::{
 };
 use crate::tests::{
     bond_amount, bond_tokens, bonds_and_unbonds, change_consensus_key,
-    find_delegations, process_slashes,
+    compound_bond_rewards, find_delegations, process_slashes,
     read_below_threshold_validator_set_addresses, redelegate_tokens, slash,
     test_init_genesis, unbond_tokens, unjail_validator, withdraw_tokens,
     GovStore,
@@ -57,10 +58,12 @@ use crate::types::{
 };
 use crate::{
     below_capacity_validator_set_handle, bond_handle,
-    consensus_validator_set_handle, is_delegator, is_validator,
-    jail_for_liveness, read_validator_stake, staking_token_address,
-    unbond_handle, validator_consensus_key_handle,
-    validator_set_positions_handle, validator_state_handle, StorageRead,
+    consensus_validator_set_handle, is_auto_compounding, is_delegator,
+    is_validator, jail_for_liveness, next_auto_compound_bonds,
+    query_reward_tokens, read_validator_stake, set_auto_compound,
+    staking_token_address, storage_key, unbond_handle,
+    validator_consensus_key_handle, validator_set_positions_handle,
+    validator_state_handle, StorageRead, MAX_AUTO_COMPOUND_BONDS_PER_BLOCK,
 };
 
 proptest! {
@@ -2210,3 +2213,152 @@ fn test_delegation_targets() {
     assert!(de_2.prev_ranges.is_empty());
     assert_eq!(de_2.last_range.1, None);
 }
+
+#[test]
+fn test_auto_compound() {
+    let stake = token::Amount::native_whole(100);
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let params = OwnedPosParams::default();
+
+    let genesis_validators = get_genesis_validators(1, vec![stake]);
+    let validator = genesis_validators[0].address.clone();
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    // Only the bonds of validators can auto-compound
+    let non_validator = address::testing::gen_implicit_address();
+    assert_matches!(
+        set_auto_compound(&mut storage, Some(&validator), &non_validator, true),
+        Err(_)
+    );
+
+    set_auto_compound(&mut storage, None, &validator, true).unwrap();
+    assert!(is_auto_compounding(&storage, None, &validator).unwrap());
+    let self_bond_id = BondId {
+        source: validator.clone(),
+        validator: validator.clone(),
+    };
+    let flag_key = auto_compound_handle().at(&validator).get_key(&validator);
+    assert!(storage.has_key(&flag_key).unwrap());
+    assert_eq!(
+        storage_key::is_auto_compound_bond_key(&flag_key),
+        Some(self_bond_id.clone())
+    );
+
+    // Earn 10% of rewards on the self-bond in the genesis epoch
+    validator_rewards_products_handle(&validator)
+        .insert(&mut storage, current_epoch, Dec::new(1, 1).unwrap())
+        .unwrap();
+
+    let current_epoch = advance_epoch(&mut storage, &params);
+    let mut events = vec![];
+    let bond_ids = next_auto_compound_bonds(
+        &mut storage,
+        current_epoch,
+        MAX_AUTO_COMPOUND_BONDS_PER_BLOCK,
+    )
+    .unwrap();
+    assert_eq!(bond_ids, vec![self_bond_id.clone()]);
+    compound_bond_rewards(
+        &mut storage,
+        &mut events,
+        &self_bond_id,
+        current_epoch,
+    )
+    .unwrap();
+
+    let rewards = token::Amount::native_whole(10);
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events[0].read_attribute::<CompoundedAmount<'_>>().unwrap(),
+        rewards.into()
+    );
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    assert_eq!(
+        bond_amount(&storage, &self_bond_id, pipeline_epoch).unwrap(),
+        stake + rewards
+    );
+    assert!(
+        query_reward_tokens::<_, GovStore<_>>(
+            &storage,
+            None,
+            &validator,
+            current_epoch
+        )
+        .unwrap()
+        .is_zero()
+    );
+
+    // The rewards cannot be compounded twice
+    assert!(
+        next_auto_compound_bonds(
+            &mut storage,
+            current_epoch,
+            MAX_AUTO_COMPOUND_BONDS_PER_BLOCK
+        )
+        .unwrap()
+        .is_empty()
+    );
+    compound_bond_rewards(
+        &mut storage,
+        &mut events,
+        &self_bond_id,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(events.len(), 1);
+
+    set_auto_compound(&mut storage, None, &validator, false).unwrap();
+    assert!(!is_auto_compounding(&storage, None, &validator).unwrap());
+}
+
+#[test]
+fn test_auto_compound_bonds_per_block() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let params = OwnedPosParams::default();
+
+    let genesis_validators =
+        get_genesis_validators(1, vec![token::Amount::native_whole(100)]);
+    let validator = genesis_validators[0].address.clone();
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    for _ in 0..3 {
+        let delegator = address::testing::gen_implicit_address();
+        set_auto_compound(&mut storage, Some(&delegator), &validator, true)
+            .unwrap();
+    }
+
+    // The bonds are spread over the blocks of the epoch
+    let first =
+        next_auto_compound_bonds(&mut storage, current_epoch, 2).unwrap();
+    assert_eq!(first.len(), 2);
+    let second =
+        next_auto_compound_bonds(&mut storage, current_epoch, 2).unwrap();
+    assert_eq!(second.len(), 1);
+    assert!(!first.contains(&second[0]));
+    assert!(
+        next_auto_compound_bonds(&mut storage, current_epoch, 2)
+            .unwrap()
+            .is_empty()
+    );
+
+    // The cursor is reset in a new epoch
+    let current_epoch = advance_epoch(&mut storage, &params);
+    assert_eq!(
+        next_auto_compound_bonds(&mut storage, current_epoch, 2).unwrap(),
+        first
+    );
+}
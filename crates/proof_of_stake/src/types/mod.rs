@@ -279,6 +279,11 @@ pub struct DelegationEpochs {
 /// The set of all target validators for a given delegator.
 pub type DelegationTargets = LazyMap<Address, DelegationEpochs>;
 
+/// The bonds whose rewards are automatically re-bonded at the start of every
+/// epoch. The outer map keys are the bond sources (delegators or validators for
+/// self-bonds) and the inner sets contain their validators.
+pub type AutoCompoundBonds = NestedMap<Address, LazySet<Address>>;
+
 #[derive(
     Debug,
     Clone,
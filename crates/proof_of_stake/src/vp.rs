@@ -8,7 +8,8 @@ use namada_core::booleans::BoolResultUnitExt;
 use namada_core::storage::Key;
 use namada_systems::governance;
 use namada_tx::action::{
-    Action, Bond, ClaimRewards, PosAction, Redelegation, Unbond, Withdraw,
+    Action, AutoCompound, Bond, ClaimRewards, PosAction, Redelegation, Unbond,
+    Withdraw,
 };
 use namada_tx::BatchedTxRef;
 use namada_vp_env::{Error, Result, VpEnv};
@@ -100,6 +101,7 @@ where
         let mut redelegations: BTreeMap<BondId, (Address, token::Amount)> =
             Default::default();
         let mut claimed_rewards: BTreeSet<BondId> = Default::default();
+        let mut auto_compound: BTreeSet<BondId> = Default::default();
         let mut changed_commission: BTreeSet<Address> = Default::default();
         let mut changed_metadata: BTreeSet<Address> = Default::default();
         let mut changed_consensus_key: BTreeSet<Address> = Default::default();
@@ -252,6 +254,27 @@ where
                         }
                         claimed_rewards.insert(bond_id);
                     }
+                    PosAction::AutoCompound(AutoCompound {
+                        validator,
+                        source,
+                        enabled: _,
+                    }) => {
+                        let bond_id = BondId {
+                            source: source.unwrap_or_else(|| validator.clone()),
+                            validator,
+                        };
+                        if !verifiers.contains(&bond_id.source) {
+                            tracing::info!(
+                                "Unauthorized PosAction::AutoCompound"
+                            );
+                            return Err(VpError::Unauthorized(
+                                "AutoCompound",
+                                bond_id.source,
+                            )
+                            .into());
+                        }
+                        auto_compound.insert(bond_id);
+                    }
                     PosAction::CommissionChange(validator) => {
                         if !verifiers.contains(&validator) {
                             tracing::info!(
@@ -323,6 +346,16 @@ where
                      governance proposal that has been accepted",
                 ));
             }
+            // The auto-compounding flag of a bond can only be changed by an
+            // authorized action
+            if storage_key::is_auto_compound_key(key)
+                && !storage_key::is_auto_compound_bond_key(key)
+                    .is_some_and(|bond_id| auto_compound.contains(&bond_id))
+            {
+                return Err(Error::new_alloc(format!(
+                    "Unauthorized change of the auto-compounding bonds: {key}"
+                )));
+            }
             // TODO: validate changes keys against the accumulated changes
         }
         Ok(())
@@ -19,7 +19,8 @@ use namada_core::masp::{MaspEpoch, PaymentAddress};
 use namada_core::time::DateTimeUtc;
 use namada_core::{storage, token};
 use namada_governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, ParameterChangeProposal, PgfFundingProposal,
+    PgfStewardProposal,
 };
 use namada_ibc::IbcShieldingData;
 use namada_token::masp::utils::RetryStrategy;
@@ -32,7 +33,7 @@ use crate::eth_bridge::bridge_pool;
 use crate::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use crate::signing::SigningTxData;
 use crate::wallet::{DatedSpendingKey, DatedViewingKey};
-use crate::{rpc, tx, Namada};
+use crate::{multisig, rpc, tx, Namada};
 
 /// [`Duration`](StdDuration) wrapper that provides a
 /// method to parse a value from a string.
@@ -624,6 +625,8 @@ pub struct InitProposal<C: NamadaTypes = SdkTypes> {
     pub is_pgf_stewards: bool,
     /// Flag if proposal is of type Pgf funding
     pub is_pgf_funding: bool,
+    /// Flag if proposal is of type parameter change
+    pub is_param_change: bool,
     /// Path to the tx WASM file
     pub tx_code_path: PathBuf,
 }
@@ -665,6 +668,14 @@ impl<C: NamadaTypes> InitProposal<C> {
         }
     }
 
+    /// Flag if proposal is of type parameter change
+    pub fn is_param_change(self, is_param_change: bool) -> Self {
+        Self {
+            is_param_change,
+            ..self
+        }
+    }
+
     /// Path to the tx WASM file
     pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
         Self {
@@ -728,6 +739,35 @@ impl InitProposal {
                 })?;
 
             tx::build_pgf_stewards_proposal(context, self, proposal).await
+        } else if self.is_param_change {
+            let proposal = ParameterChangeProposal::try_from(
+                self.proposal_data.as_ref(),
+            )
+            .map_err(|e| {
+                crate::error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+            let nam_address = context.native_token();
+            let author_balance = rpc::get_token_balance(
+                context.client(),
+                &nam_address,
+                &proposal.proposal.author,
+                None,
+            )
+            .await?;
+            let proposal = proposal
+                .validate(
+                    &governance_parameters,
+                    current_epoch,
+                    author_balance,
+                    self.tx.force,
+                )
+                .map_err(|e| {
+                    crate::error::TxSubmitError::InvalidProposal(e.to_string())
+                })?;
+
+            tx::build_param_change_proposal(context, self, proposal).await
         } else {
             let proposal = DefaultProposal::try_from(
                 self.proposal_data.as_ref(),
@@ -900,6 +940,180 @@ impl TxInitAccount {
     }
 }
 
+/// Transaction to initialize a new account whose tokens are locked by a
+/// vesting schedule
+#[derive(Clone, Debug)]
+pub struct TxInitVestingAccount<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Path to the VP WASM code file for the new account
+    pub vp_code_path: PathBuf,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+    /// Public key for the new account
+    pub public_keys: Vec<C::PublicKey>,
+    /// The account multisignature threshold
+    pub threshold: Option<u8>,
+    /// The vested token
+    pub token: C::Address,
+    /// The amount of tokens subject to vesting
+    pub amount: InputAmount,
+    /// The epoch from which the linear vesting is computed
+    pub start: Epoch,
+    /// The first epoch at which any tokens are vested. Defaults to the
+    /// `start` epoch for a purely linear schedule.
+    pub cliff: Option<Epoch>,
+    /// The epoch at which all the tokens are vested
+    pub end: Epoch,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxInitVestingAccount<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxInitVestingAccount {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxInitVestingAccount<C> {
+    /// A vector of public key to associate with the new account
+    pub fn public_keys(self, public_keys: Vec<C::PublicKey>) -> Self {
+        Self {
+            public_keys,
+            ..self
+        }
+    }
+
+    /// A threshold to associate with the new account
+    pub fn threshold(self, threshold: u8) -> Self {
+        Self {
+            threshold: Some(threshold),
+            ..self
+        }
+    }
+
+    /// The cliff epoch of the vesting schedule
+    pub fn cliff(self, cliff: Epoch) -> Self {
+        Self {
+            cliff: Some(cliff),
+            ..self
+        }
+    }
+
+    /// Path to the VP WASM code file
+    pub fn vp_code_path(self, vp_code_path: PathBuf) -> Self {
+        Self {
+            vp_code_path,
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxInitVestingAccount {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_init_vesting_account(context, self).await
+    }
+}
+
+/// Transaction to schedule a signed raw tx for execution at a future block
+/// height or epoch
+#[derive(Clone, Debug)]
+pub struct ScheduleTx<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The serialized raw tx to schedule
+    pub scheduled_tx: C::Data,
+    /// The signatures of the scheduled tx
+    pub scheduled_tx_signatures: Vec<C::Data>,
+    /// The block height at which to execute the tx
+    pub height: Option<BlockHeight>,
+    /// The epoch in which to execute the tx
+    pub epoch: Option<Epoch>,
+    /// The gas limit of the scheduled tx
+    pub scheduled_gas_limit: GasLimit,
+    /// The account escrowing the fees of the scheduled tx
+    pub fee_payer: C::Address,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for ScheduleTx<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        ScheduleTx {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> ScheduleTx<C> {
+    /// The signatures of the scheduled tx
+    pub fn scheduled_tx_signatures(
+        self,
+        scheduled_tx_signatures: Vec<C::Data>,
+    ) -> Self {
+        Self {
+            scheduled_tx_signatures,
+            ..self
+        }
+    }
+
+    /// Execute the tx at the given block height
+    pub fn height(self, height: BlockHeight) -> Self {
+        Self {
+            height: Some(height),
+            epoch: None,
+            ..self
+        }
+    }
+
+    /// Execute the tx in the first block of the given epoch
+    pub fn epoch(self, epoch: Epoch) -> Self {
+        Self {
+            height: None,
+            epoch: Some(epoch),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl ScheduleTx {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_schedule_tx(context, self).await
+    }
+}
+
 /// Transaction to initialize a new account
 #[derive(Clone, Debug)]
 pub struct TxBecomeValidator<C: NamadaTypes = SdkTypes> {
@@ -1570,6 +1784,59 @@ impl ClaimRewards {
     }
 }
 
+/// Auto-compound arguments
+#[derive(Clone, Debug)]
+pub struct AutoCompound<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Validator address
+    pub validator: C::Address,
+    /// Source address of the bond. For self-bonds, the validator is also the
+    /// source
+    pub source: Option<C::Address>,
+    /// Disable the auto-compounding instead of enabling it
+    pub disable: bool,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for AutoCompound<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        AutoCompound {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> AutoCompound<C> {
+    /// Source address of the bond
+    pub fn source(self, source: C::Address) -> Self {
+        Self {
+            source: Some(source),
+            ..self
+        }
+    }
+
+    /// Disable the auto-compounding instead of enabling it
+    pub fn disable(self, disable: bool) -> Self {
+        Self { disable, ..self }
+    }
+}
+
+impl AutoCompound {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_auto_compound(context, self).await
+    }
+}
+
 /// Query asset conversions
 #[derive(Clone, Debug)]
 pub struct QueryConversions<C: NamadaTypes = SdkTypes> {
@@ -1617,6 +1884,44 @@ pub struct QueryRewardsEstimate<C: NamadaTypes = SdkTypes> {
     pub owner: C::ViewingKey,
 }
 
+/// Export the history of the shielded notes of a viewing key
+#[derive(Clone, Debug)]
+pub struct ShieldedHistory<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Viewing key whose history to export
+    pub owner: C::ViewingKey,
+    /// Format of the exported history
+    pub format: HistoryFormat,
+    /// File to write the history to. Printed to stdout if none.
+    pub output: Option<PathBuf>,
+}
+
+/// The formats in which the shielded history can be exported
+#[derive(Debug, Clone, Copy, Default)]
+pub enum HistoryFormat {
+    /// Comma-separated values, with a header line
+    #[default]
+    Csv,
+    /// A JSON array of entries
+    Json,
+}
+
+impl FromStr for HistoryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            raw => Err(format!(
+                "Unexpected history format \"{raw}\". Valid options are \
+                 \"csv\" or \"json\"."
+            )),
+        }
+    }
+}
+
 /// Query historical transfer(s)
 #[derive(Clone, Debug)]
 pub struct QueryTransfers<C: NamadaTypes = SdkTypes> {
@@ -2152,6 +2457,46 @@ pub struct SignTx<C: NamadaTypes = SdkTypes> {
     pub owner: C::Address,
 }
 
+#[derive(Clone, Debug)]
+/// Create a bundle to collect the signatures of a multisig account's
+/// transaction
+pub struct MultisigCreate<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Path to the serialized raw transaction
+    pub tx_path: PathBuf,
+    /// The multisig account address
+    pub owner: C::Address,
+    /// Path to the folder where the bundle is written
+    pub output_folder: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug)]
+/// Finalize a multisig bundle into a wrapper transaction and submit it
+pub struct MultisigSubmit<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The serialized multisig bundle
+    pub bundle: C::Data,
+}
+
+impl MultisigSubmit {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        let bundle =
+            multisig::MultisigTxBundle::try_from_json_bytes(&self.bundle)
+                .map_err(|err| {
+                    crate::error::Error::Encode(
+                        crate::error::EncodingError::Serde(err.to_string()),
+                    )
+                })?;
+        multisig::finalize_bundle(context, &self.tx, &bundle).await
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Sync notes from MASP owned by the provided spending /
 /// viewing keys. Syncing can be told to stop at a given
@@ -2307,6 +2652,10 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub dry_run: bool,
     /// Simulate applying both the wrapper and inner transactions
     pub dry_run_wrapper: bool,
+    /// Trace the host calls of the WASM executions of a dry run
+    pub dry_run_trace: bool,
+    /// Return the state changes, events and triggered VPs of a dry run
+    pub dry_run_diff: bool,
     /// Dump the raw transaction bytes to file
     pub dump_tx: bool,
     /// Dump the wrapper transaction bytes to file
@@ -2397,6 +2746,14 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
             ..x
         })
     }
+    /// Trace the host calls of the WASM executions of a dry run
+    fn dry_run_trace(self, dry_run_trace: bool) -> Self {
+        self.tx(|x| Tx { dry_run_trace, ..x })
+    }
+    /// Return the state changes, events and triggered VPs of a dry run
+    fn dry_run_diff(self, dry_run_diff: bool) -> Self {
+        self.tx(|x| Tx { dry_run_diff, ..x })
+    }
     /// Dump the transaction bytes to file
     fn dump_tx(self, dump_tx: bool) -> Self {
         self.tx(|x| Tx { dump_tx, ..x })
@@ -2575,6 +2932,12 @@ pub struct KeyDerive {
     /// Optional blockheight after which this key was created.
     /// Only used for MASP keys
     pub birthday: Option<BlockHeight>,
+    /// Number of shielded accounts to recover from the mnemonic code, along
+    /// with their birthdays
+    pub recover_accounts: Option<u32>,
+    /// Address of a MASP indexer used to find the birthdays of the
+    /// recovered shielded accounts
+    pub with_indexer: Option<String>,
 }
 
 /// Wallet list arguments
@@ -2908,6 +3271,47 @@ pub struct ValidatorSetUpdateRelay<C: NamadaTypes = SdkTypes> {
     pub success_dur: Option<StdDuration>,
 }
 
+/// Ethereum bridge relayer daemon arguments.
+#[derive(Debug, Clone)]
+pub struct RelayerDaemon<C: NamadaTypes = SdkTypes> {
+    /// The address of the ledger node as host:port
+    pub ledger_address: C::TendermintAddress,
+    /// The Ethereum RPC endpoint.
+    pub eth_rpc_endpoint: C::EthereumAddress,
+    /// The address of the Ethereum wallet to pay the gas fees.
+    /// The daemon manages the nonces of this account.
+    pub eth_addr: EthAddress,
+    /// The Namada address for receiving fees for relaying
+    pub relayer: Address,
+    /// Path to the file where the state of in-flight Ethereum
+    /// transactions is persisted.
+    pub state_path: PathBuf,
+    /// Path to a JSON object mapping token addresses to their
+    /// conversion rates in gwei. It is read again before every
+    /// Bridge pool relay.
+    pub conversion_table: PathBuf,
+    /// A fixed price of Ethereum gas, in wei. If unset, the
+    /// price suggested by the Ethereum node is used.
+    pub gas_price: Option<u64>,
+    /// The maximum price of Ethereum gas, in wei, to pay when
+    /// resubmitting stuck transactions.
+    pub max_gas_price: Option<u64>,
+    /// The maximum amount of gas to spend on a Bridge pool relay.
+    pub max_gas: Option<u64>,
+    /// An optional parameter indicating how much net
+    /// gas the relayer is willing to pay.
+    pub gas: Option<u64>,
+    /// The number of block confirmations on Ethereum.
+    pub confirmations: u64,
+    /// The number of Ethereum blocks to wait for before resubmitting
+    /// a transaction with a higher gas price.
+    pub resubmit_after_blocks: u64,
+    /// The amount of time to sleep between failed relays.
+    pub retry_dur: Option<StdDuration>,
+    /// The amount of time to sleep between successful relays.
+    pub success_dur: Option<StdDuration>,
+}
+
 /// IBC shielding transfer generation arguments
 #[derive(Clone, Debug)]
 pub struct GenIbcShieldingTransfer<C: NamadaTypes = SdkTypes> {
@@ -68,6 +68,10 @@ pub enum QueryError {
     /// synchronizing with the network.
     #[error("Node is still catching up with the network")]
     CatchingUp,
+    /// The storage proof returned by the node doesn't verify against the
+    /// trusted app hash
+    #[error("Invalid storage proof: {0}")]
+    InvalidProof(String),
 }
 
 /// Errors that deal with Decoding, Encoding, or Conversions
@@ -111,6 +115,12 @@ pub enum TxSubmitError {
     /// Error during broadcasting a transaction
     #[error("Encountered error while broadcasting transaction: {0}")]
     TxBroadcast(RpcError),
+    /// The transaction is too large to be admitted to the mempool
+    #[error(
+        "The transaction of {0} bytes exceeds the maximum admissible size of \
+         {1} bytes"
+    )]
+    ExceedsBlockSpace(u64, u64),
     /// Invalid commission rate set
     #[error("Invalid new commission rate, received {0}")]
     InvalidCommissionRate(Dec),
@@ -282,6 +292,15 @@ pub enum TxSubmitError {
     /// Invalid owner account
     #[error("The source account {0} is not valid or doesn't exist.")]
     InvalidAccount(String),
+    /// Invalid signature of a multisig account
+    #[error("Invalid multisig signature: {0}.")]
+    InvalidMultisigSignature(String),
+    /// The multisig account keys do not match the ones of a bundle
+    #[error(
+        "The public keys of the account {0} have changed since the multisig \
+         bundle was created."
+    )]
+    MultisigAccountMismatch(String),
     /// The redelegation amount is larger than the remaining bond amount
     #[error(
         "The redelegation amount is larger than the remaining bond amount. \
@@ -369,4 +388,7 @@ pub enum EthereumBridgeError {
     /// Transfer already in pool error.
     #[error("An identical transfer is already present in the Bridge pool")]
     TransferAlreadyInPool,
+    /// Error accessing the persisted state of the relayer.
+    #[error("Failed to access the relayer state: {0}")]
+    RelayerState(String),
 }
@@ -354,12 +354,11 @@ pub async fn query_bridge_pool(
     Ok(pool_contents)
 }
 
-/// Query the contents of the Ethereum bridge pool that
-/// is covered by the latest signed root.
-/// Prints out a json payload.
-pub async fn query_signed_bridge_pool(
+/// Read the contents of the Ethereum bridge pool that
+/// is covered by the latest signed root, indexed by the
+/// hashes of the transfers.
+pub(crate) async fn read_signed_bridge_pool(
     client: &(impl Client + Sync),
-    io: &impl Io,
 ) -> Result<HashMap<String, PendingTransfer>, Error> {
     let response: Vec<PendingTransfer> = RPC
         .shell()
@@ -371,10 +370,20 @@ pub async fn query_signed_bridge_pool(
                 e.to_string(),
             ))
         })?;
-    let pool_contents: HashMap<String, PendingTransfer> = response
+    Ok(response
         .into_iter()
         .map(|transfer| (transfer.keccak256().to_string(), transfer))
-        .collect();
+        .collect())
+}
+
+/// Query the contents of the Ethereum bridge pool that
+/// is covered by the latest signed root.
+/// Prints out a json payload.
+pub async fn query_signed_bridge_pool(
+    client: &(impl Client + Sync),
+    io: &impl Io,
+) -> Result<HashMap<String, PendingTransfer>, Error> {
+    let pool_contents = read_signed_bridge_pool(client).await?;
     if pool_contents.is_empty() {
         display_line!(io, "Bridge pool is empty.");
         return Ok(pool_contents);
@@ -485,6 +494,18 @@ async fn construct_bridge_pool_proof(
         }
     }
 
+    request_bridge_pool_proof(client, args).await.map_err(|e| {
+        edisplay_line!(io, "Encountered error constructing proof:\n{:?}", e);
+        e
+    })
+}
+
+/// Request a proof that a set of transfers are in the bridge pool
+/// from the ledger, without any further checks.
+pub(crate) async fn request_bridge_pool_proof(
+    client: &(impl Client + Sync),
+    args: GenBridgePoolProofReq<'_, '_>,
+) -> Result<GenBridgePoolProofRsp, Error> {
     let data = args.serialize_to_vec();
     let response = RPC
         .shell()
@@ -492,11 +513,6 @@ async fn construct_bridge_pool_proof(
         .generate_bridge_pool_proof(client, Some(data), None, false)
         .await
         .map_err(|e| {
-            edisplay_line!(
-                io,
-                "Encountered error constructing proof:\n{:?}",
-                e
-            );
             Error::EthereumBridge(EthereumBridgeError::GenBridgePoolProof(
                 e.to_string(),
             ))
@@ -814,18 +830,17 @@ mod recommendations {
     /// Batch of recommended transfers to Ethereum that generate
     /// a profit after a relay operation.
     #[derive(Debug, Eq, PartialEq)]
-    struct RecommendedBatch {
+    pub(crate) struct RecommendedBatch {
         /// Hashes of the recommended transfers to be relayed.
-        transfer_hashes: Vec<String>,
-        /// Estimate of the total fees, measured in gwei, that will be paid
-        /// on Ethereum.
-        ethereum_gas_fees: Uint,
+        pub transfer_hashes: Vec<String>,
+        /// Estimate of the total gas that will be paid on Ethereum.
+        pub ethereum_gas_fees: Uint,
         /// Net profitt in gwei, based on the conversion rates provided
         /// to the algorithm.
-        net_profit: I256,
+        pub net_profit: I256,
         /// Gas fees paid by the transfers considered for relaying,
         /// paid in various token types.
-        bridge_pool_gas_fees: HashMap<String, Uint>,
+        pub bridge_pool_gas_fees: HashMap<String, Uint>,
     }
 
     /// Recommend the most economical batch of transfers to relay based
@@ -835,12 +850,77 @@ mod recommendations {
         context: &impl Namada,
         args: args::RecommendBatch,
     ) -> Result<(), Error> {
+        let max_gas =
+            args.max_gas.map(Uint::from_u64).unwrap_or(uint::MAX_VALUE);
+        let max_cost = args.gas.map(I256::from).unwrap_or_default();
+
+        recommend(
+            context.client(),
+            context.io(),
+            &args.conversion_table,
+            // costs are estimated at a gas price of 1 gwei
+            uint::ONE,
+            max_gas,
+            max_cost,
+        )
+        .await?
+        .map(
+            |RecommendedBatch {
+                 transfer_hashes,
+                 ethereum_gas_fees,
+                 net_profit,
+                 bridge_pool_gas_fees,
+             }| {
+                display_line!(
+                    context.io(),
+                    "Recommended batch: {transfer_hashes:#?}"
+                );
+                display_line!(
+                    context.io(),
+                    "Estimated Ethereum transaction gas (in gwei): \
+                     {ethereum_gas_fees}",
+                );
+                display_line!(
+                    context.io(),
+                    "Estimated net profit (in gwei): {net_profit}"
+                );
+                display_line!(
+                    context.io(),
+                    "Total fees: {bridge_pool_gas_fees:#?}"
+                );
+            },
+        )
+        .unwrap_or_else(|| {
+            display_line!(
+                context.io(),
+                "Unable to find a recommendation satisfying the input \
+                 parameters."
+            );
+        });
+
+        Ok(())
+    }
+
+    /// Find the most economical batch of transfers in the signed
+    /// Bridge pool, given the price of gas on Ethereum (in gwei)
+    /// and the conversion rates of the gas fee tokens.
+    ///
+    /// The `max_gas` limit is measured in units of gas, and the
+    /// `max_cost` in gwei.
+    pub(crate) async fn recommend(
+        client: &(impl Client + Sync),
+        io: &impl Io,
+        conversion_table: &HashMap<Address, args::BpConversionTableEntry>,
+        gas_price: Uint,
+        max_gas: Uint,
+        max_cost: I256,
+    ) -> Result<Option<RecommendedBatch>, Error> {
         // get transfers that can already been relayed but are awaiting a quorum
         // of backing votes.
         let in_progress = RPC
             .shell()
             .eth_bridge()
-            .transfer_to_ethereum_progress(context.client())
+            .transfer_to_ethereum_progress(client)
             .await
             .map_err(|e| {
                 Error::EthereumBridge(
@@ -857,7 +937,7 @@ mod recommendations {
             <(BridgePoolRootProof, BlockHeight)>::try_from_slice(
                 &RPC.shell()
                     .storage_value(
-                        context.client(),
+                        client,
                         None,
                         None,
                         false,
@@ -866,7 +946,7 @@ mod recommendations {
                     .await
                     .map_err(|err| {
                         Error::Query(QueryError::General(echo_error!(
-                            context.io(),
+                            io,
                             "Failed to query Bridge pool proof: {err}"
                         )))
                     })?
@@ -874,7 +954,7 @@ mod recommendations {
             )
             .map_err(|err| {
                 Error::Encode(EncodingError::Decoding(echo_error!(
-                    context.io(),
+                    io,
                     "Failed to decode Bridge pool proof: {err}"
                 )))
             })?;
@@ -882,17 +962,11 @@ mod recommendations {
         // get the latest bridge pool nonce
         let latest_bp_nonce = EthUint::try_from_slice(
             &RPC.shell()
-                .storage_value(
-                    context.client(),
-                    None,
-                    None,
-                    false,
-                    &get_nonce_key(),
-                )
+                .storage_value(client, None, None, false, &get_nonce_key())
                 .await
                 .map_err(|err| {
                     Error::Query(QueryError::General(echo_error!(
-                        context.io(),
+                        io,
                         "Failed to query Bridge pool nonce: {err}"
                     )))
                 })?
@@ -900,14 +974,14 @@ mod recommendations {
         )
         .map_err(|err| {
             Error::Encode(EncodingError::Decoding(echo_error!(
-                context.io(),
+                io,
                 "Failed to decode Bridge pool nonce: {err}"
             )))
         })?;
 
         if latest_bp_nonce != bp_root.data.1 {
             edisplay_line!(
-                context.io(),
+                io,
                 "The signed Bridge pool nonce is not up to date, repeat this \
                  query at a later time"
             );
@@ -921,7 +995,7 @@ mod recommendations {
         let voting_powers = RPC
             .shell()
             .eth_bridge()
-            .voting_powers_at_height(context.client(), &height)
+            .voting_powers_at_height(client, &height)
             .await
             .map_err(|e| {
                 Error::EthereumBridge(EthereumBridgeError::QueryVotingPowers(
@@ -931,66 +1005,29 @@ mod recommendations {
         let valset_size = Uint::from_u64(voting_powers.len() as u64);
 
         // This is the gas cost for hashing the validator set and
-        // checking a quorum of signatures (in gwei).
+        // checking a quorum of signatures.
         let validator_gas = signature_fee()
             * signature_checks(voting_powers, &bp_root.signatures)?
             + valset_fee() * valset_size;
 
         // we don't recommend transfers that have already been relayed
         let eligible = generate_eligible(
-            context.io(),
-            &args.conversion_table,
+            io,
+            conversion_table,
             &in_progress,
-            query_signed_bridge_pool(context.client(), context.io()).await?,
+            gas_price,
+            read_signed_bridge_pool(client).await?,
         )?;
 
-        let max_gas =
-            args.max_gas.map(Uint::from_u64).unwrap_or(uint::MAX_VALUE);
-        let max_cost = args.gas.map(I256::from).unwrap_or_default();
-
         generate_recommendations(
-            context.io(),
+            io,
             eligible,
-            &args.conversion_table,
+            conversion_table,
             validator_gas,
+            gas_price,
             max_gas,
             max_cost,
-        )?
-        .map(
-            |RecommendedBatch {
-                 transfer_hashes,
-                 ethereum_gas_fees,
-                 net_profit,
-                 bridge_pool_gas_fees,
-             }| {
-                display_line!(
-                    context.io(),
-                    "Recommended batch: {transfer_hashes:#?}"
-                );
-                display_line!(
-                    context.io(),
-                    "Estimated Ethereum transaction gas (in gwei): \
-                     {ethereum_gas_fees}",
-                );
-                display_line!(
-                    context.io(),
-                    "Estimated net profit (in gwei): {net_profit}"
-                );
-                display_line!(
-                    context.io(),
-                    "Total fees: {bridge_pool_gas_fees:#?}"
-                );
-            },
         )
-        .unwrap_or_else(|| {
-            display_line!(
-                context.io(),
-                "Unable to find a recommendation satisfying the input \
-                 parameters."
-            );
-        });
-
-        Ok(())
     }
 
     /// Given an ordered list of signatures, figure out the size of the first
@@ -1038,8 +1075,18 @@ mod recommendations {
         io: &IO,
         conversion_table: &HashMap<Address, args::BpConversionTableEntry>,
         in_progress: &BTreeSet<String>,
+        gas_price: Uint,
         signed_pool: HashMap<String, PendingTransfer>,
     ) -> Result<Vec<EligibleRecommendation>, Error> {
+        let transfer_cost = unsigned_transfer_fee()
+            .checked_mul(gas_price)
+            .ok_or_else(|| "Overflowed calculating transfer cost".to_owned())
+            .and_then(|cost| I256::try_from(cost).map_err(|e| e.to_string()))
+            .map_err(|err| {
+                Error::EthereumBridge(EthereumBridgeError::RelayCost(
+                    echo_error!(io, "Failed to calculate relaying cost: {err}"),
+                ))
+            })?;
         let mut eligible: Vec<_> = signed_pool
             .into_iter()
             .filter_map(|(pending_hash, pending)| {
@@ -1091,7 +1138,7 @@ mod recommendations {
                         .and_then(I256::try_from)
                         .map_err(|err| err.to_string())
                         .and_then(|amt_of_earned_gwei| {
-                            transfer_fee()
+                            transfer_cost
                                 .checked_sub(amt_of_earned_gwei)
                                 .ok_or_else(|| {
                                     "Underflowed calculating relaying cost"
@@ -1125,6 +1172,7 @@ mod recommendations {
         contents: Vec<EligibleRecommendation>,
         conversion_table: &HashMap<Address, args::BpConversionTableEntry>,
         validator_gas: Uint,
+        gas_price: Uint,
         max_gas: Uint,
         max_cost: I256,
     ) -> Result<Option<RecommendedBatch>, Error> {
@@ -1140,7 +1188,8 @@ mod recommendations {
         };
 
         let mut total_gas = validator_gas;
-        let mut total_cost = I256::try_from(validator_gas).map_err(|err| {
+        let validator_cost = checked!(validator_gas * gas_price)?;
+        let mut total_cost = I256::try_from(validator_cost).map_err(|err| {
             Error::Encode(EncodingError::Conversion(echo_error!(
                 io,
                 "Failed to convert value to I256: {err}"
@@ -1191,11 +1240,6 @@ mod recommendations {
                 bridge_pool_gas_fees: total_fees,
             })
         } else {
-            edisplay_line!(
-                io,
-                "Unable to find a recommendation satisfying the input \
-                 parameters."
-            );
             None
         })
     }
@@ -1326,9 +1370,14 @@ mod recommendations {
                 signed_pool: &mut signed_pool,
                 expected_eligible: &mut expected,
             });
-            let eligible =
-                generate_eligible(&StdIo, &table, &in_progress, signed_pool)
-                    .unwrap();
+            let eligible = generate_eligible(
+                &StdIo,
+                &table,
+                &in_progress,
+                uint::ONE,
+                signed_pool,
+            )
+            .unwrap();
             assert_eq!(eligible, expected);
             eligible
         }
@@ -1422,6 +1471,7 @@ mod recommendations {
                 process_transfers(profitable),
                 &Default::default(),
                 Uint::from_u64(800_000),
+                uint::ONE,
                 uint::MAX_VALUE,
                 I256::zero(),
             )
@@ -1442,6 +1492,7 @@ mod recommendations {
                 process_transfers(transfers),
                 &Default::default(),
                 Uint::from_u64(800_000),
+                uint::ONE,
                 uint::MAX_VALUE,
                 I256::zero(),
             )
@@ -1461,6 +1512,7 @@ mod recommendations {
                 process_transfers(transfers),
                 &Default::default(),
                 Uint::from_u64(50_000),
+                uint::ONE,
                 Uint::from_u64(150_000),
                 I256(uint::MAX_SIGNED_VALUE),
             )
@@ -1484,6 +1536,7 @@ mod recommendations {
                 process_transfers(transfers),
                 &Default::default(),
                 Uint::from_u64(150_000),
+                uint::ONE,
                 uint::MAX_VALUE,
                 I256::from(20_000),
             )
@@ -1504,6 +1557,7 @@ mod recommendations {
                 process_transfers(transfers),
                 &Default::default(),
                 Uint::from_u64(150_000),
+                uint::ONE,
                 Uint::from_u64(330_000),
                 I256::from(20_000),
             )
@@ -1521,6 +1575,7 @@ mod recommendations {
                 process_transfers(transfers),
                 &Default::default(),
                 Uint::from_u64(300_000),
+                uint::ONE,
                 uint::MAX_VALUE,
                 I256::from(20_000),
             )
@@ -1601,6 +1656,8 @@ mod recommendations {
                 &conversion_table,
                 // gas spent by validator signature checks
                 VALIDATOR_GAS_FEE,
+                // at a gas price of 1 gwei
+                uint::ONE,
                 // unlimited amount of gas
                 uint::MAX_VALUE,
                 // only profitable
@@ -1619,4 +1676,5 @@ mod recommendations {
     }
 }
 
+pub(crate) use recommendations::recommend;
 pub use recommendations::recommend_batch;
@@ -1,6 +1,7 @@
 //! Ethereum bridge utilities shared between `wasm` and the `cli`.
 
 pub mod bridge_pool;
+pub mod relayer;
 pub mod validator_set;
 
 use std::ops::ControlFlow;
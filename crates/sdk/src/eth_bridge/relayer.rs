@@ -0,0 +1,995 @@
+//! Supervised Ethereum bridge relayer daemon.
+//!
+//! The daemon keeps watching Namada for signed Bridge pool roots and
+//! validator set updates, and relays them to the Ethereum bridge smart
+//! contracts once they become available. Bridge pool batches are only
+//! relayed if they are profitable, given the gas prices and token
+//! conversion rates fetched from a [`GasPriceSource`] and a
+//! [`TokenRateSource`].
+//!
+//! Ethereum transactions are sent with explicit nonces, and tracked
+//! until they have been confirmed. The state of in-flight transactions
+//! is persisted to disk, such that a restarted daemon can pick up where
+//! it left off, instead of relaying the same batch twice. Transactions
+//! that remain unmined for too long are resubmitted with the same nonce
+//! and a higher gas price.
+
+#![allow(clippy::result_large_err)]
+
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ethbridge_bridge_contract::Bridge;
+use ethers::providers::Middleware;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address as EthersAddress, BlockNumber, H256, U256, U64};
+use itertools::Either;
+use namada_core::address::Address;
+use namada_core::chain::Epoch;
+use namada_core::collections::HashMap;
+use namada_core::keccak::KeccakHash;
+use namada_core::uint::{self, Uint, I256};
+use namada_io::{Client, Io};
+use serde::{Deserialize, Serialize};
+
+use super::bridge_pool::{recommend, request_bridge_pool_proof};
+use super::eth_sync_or;
+use super::validator_set::{
+    read_validator_set_update_args, ValidatorSetUpdateArgs,
+};
+use crate::args::{self, BpConversionTableEntry};
+use crate::control_flow::time::{self, Duration};
+use crate::error::{EncodingError, Error, EthereumBridgeError, QueryError};
+use crate::eth_bridge::ethers::abi::AbiDecode;
+use crate::queries::{
+    GenBridgePoolProofReq, GenBridgePoolProofRsp, TransferToErcArgs, RPC,
+};
+
+/// A source of Ethereum gas prices.
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+pub trait GasPriceSource {
+    /// Fetch the price of Ethereum gas, in wei, to pay for
+    /// new relay transactions.
+    async fn gas_price(&self) -> Result<U256, Error>;
+}
+
+/// Always pay the same price for Ethereum gas.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedGasPrice(pub U256);
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl GasPriceSource for FixedGasPrice {
+    async fn gas_price(&self) -> Result<U256, Error> {
+        Ok(self.0)
+    }
+}
+
+/// Pay the price of Ethereum gas suggested by an Ethereum node.
+#[derive(Debug)]
+pub struct EthNodeGasPrice<E>(pub Arc<E>);
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl<E: Middleware> GasPriceSource for EthNodeGasPrice<E> {
+    async fn gas_price(&self) -> Result<U256, Error> {
+        self.0.get_gas_price().await.map_err(|err| {
+            Error::EthereumBridge(EthereumBridgeError::Rpc(err.to_string()))
+        })
+    }
+}
+
+/// A source of conversion rates from the tokens used to pay
+/// Bridge pool gas fees to gwei.
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+pub trait TokenRateSource {
+    /// Fetch the latest conversion rates. Tokens missing from
+    /// the table are not considered for relaying.
+    async fn conversion_table(
+        &self,
+    ) -> Result<HashMap<Address, BpConversionTableEntry>, Error>;
+}
+
+/// Conversion rates that never change.
+#[derive(Debug, Clone, Default)]
+pub struct StaticRates(pub HashMap<Address, BpConversionTableEntry>);
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl TokenRateSource for StaticRates {
+    async fn conversion_table(
+        &self,
+    ) -> Result<HashMap<Address, BpConversionTableEntry>, Error> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Conversion rates read from a JSON object mapping token addresses
+/// to their conversion rates in gwei. The file is read again every
+/// time the rates are requested, such that an external process can
+/// keep it up to date.
+#[derive(Debug, Clone)]
+pub struct ConversionTableFile(pub PathBuf);
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl TokenRateSource for ConversionTableFile {
+    async fn conversion_table(
+        &self,
+    ) -> Result<HashMap<Address, BpConversionTableEntry>, Error> {
+        let contents = std::fs::read(&self.0).map_err(|err| {
+            Error::Other(format!(
+                "Failed to read the conversion table at {}: {err}",
+                self.0.display()
+            ))
+        })?;
+        let table: HashMap<String, f64> = serde_json::from_slice(&contents)
+            .map_err(|err| EncodingError::Serde(err.to_string()))?;
+        table
+            .into_iter()
+            .map(|(token, conversion_rate)| {
+                let address = Address::decode(&token).map_err(|err| {
+                    EncodingError::Decoding(format!(
+                        "Invalid token address {token} in the conversion \
+                         table: {err}"
+                    ))
+                })?;
+                Ok::<_, Error>((
+                    address,
+                    BpConversionTableEntry {
+                        alias: token,
+                        conversion_rate,
+                    },
+                ))
+            })
+            .collect()
+    }
+}
+
+/// A relay operation performed by the daemon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Relay {
+    /// Relay of a batch of transfers in the Bridge pool.
+    BridgePool {
+        /// The nonce of the relayed batch.
+        batch_nonce: U256,
+    },
+    /// Relay of a validator set update.
+    ValidatorSet {
+        /// The epoch of the relayed validator set.
+        epoch: Epoch,
+    },
+}
+
+/// An Ethereum transaction that has been sent, but not yet confirmed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InFlightTx {
+    /// The relay operation performed by the transaction.
+    pub relay: Relay,
+    /// The last submitted version of the transaction.
+    pub tx: TypedTransaction,
+    /// The hashes of all the submitted versions of the transaction,
+    /// which share the same nonce. Any of them may get mined.
+    pub tx_hashes: Vec<H256>,
+    /// The nonce of the transaction.
+    pub nonce: U256,
+    /// The Ethereum block height at the time of the last submission.
+    pub sent_at_block: U64,
+}
+
+/// The state of the relayer daemon, persisted across restarts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RelayerState {
+    /// The nonce of the next transaction to send. If unknown,
+    /// it is fetched from the Ethereum node.
+    pub next_nonce: Option<U256>,
+    /// Transactions awaiting confirmation.
+    pub in_flight: Vec<InFlightTx>,
+}
+
+impl RelayerState {
+    /// Load the state of the relayer from the given path. A default
+    /// state is returned if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read(path).map_err(|err| {
+            EthereumBridgeError::RelayerState(format!(
+                "Failed to read {}: {err}",
+                path.display()
+            ))
+        })?;
+        serde_json::from_slice(&contents).map_err(|err| {
+            Error::EthereumBridge(EthereumBridgeError::RelayerState(format!(
+                "Failed to decode {}: {err}",
+                path.display()
+            )))
+        })
+    }
+
+    /// Persist the state of the relayer to the given path. The file is
+    /// replaced atomically, so it is never left half written.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let contents = serde_json::to_vec_pretty(self).map_err(|err| {
+            EthereumBridgeError::RelayerState(format!(
+                "Failed to encode the relayer state: {err}"
+            ))
+        })?;
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, contents)
+            .and_then(|()| std::fs::rename(&tmp_path, path))
+            .map_err(|err| {
+                Error::EthereumBridge(EthereumBridgeError::RelayerState(
+                    format!("Failed to write {}: {err}", path.display()),
+                ))
+            })
+    }
+}
+
+/// Run the Ethereum bridge relayer daemon.
+///
+/// The daemon only exits if its state cannot be persisted. Any other
+/// error is logged, and the relay operations are retried after
+/// sleeping for a while.
+pub async fn relay_daemon<E, C, IO, G, R>(
+    eth_client: Arc<E>,
+    client: &C,
+    io: &IO,
+    gas_prices: &G,
+    rates: &R,
+    args: args::RelayerDaemon,
+) -> Result<(), Error>
+where
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+    C: Client + Sync,
+    IO: Io,
+    G: GasPriceSource,
+    R: TokenRateSource,
+{
+    const DEFAULT_RETRY_DURATION: Duration = Duration::from_secs(1);
+    const DEFAULT_SUCCESS_DURATION: Duration = Duration::from_secs(10);
+
+    let retry_duration = args.retry_dur.unwrap_or(DEFAULT_RETRY_DURATION);
+    let success_duration = args.success_dur.unwrap_or(DEFAULT_SUCCESS_DURATION);
+
+    let mut relayer = Relayer {
+        eth_client: Arc::clone(&eth_client),
+        from: args.eth_addr.into(),
+        state: RelayerState::load(&args.state_path)?,
+        state_path: args.state_path.clone(),
+        confirmations: args.confirmations.into(),
+        resubmit_after_blocks: args.resubmit_after_blocks.into(),
+        max_gas_price: args.max_gas_price.map(U256::from),
+    };
+    let max_gas = args.max_gas.map(Uint::from_u64).unwrap_or(uint::MAX_VALUE);
+    let max_cost = args.gas.map(I256::from).unwrap_or_default();
+
+    tracing::info!(
+        in_flight = relayer.state.in_flight.len(),
+        "The Ethereum bridge relayer daemon has started"
+    );
+
+    let mut last_call_succeeded = true;
+
+    loop {
+        let sleep_for = if last_call_succeeded {
+            success_duration
+        } else {
+            retry_duration
+        };
+
+        tracing::debug!(?sleep_for, "Sleeping");
+        time::sleep(sleep_for).await;
+
+        let is_synchronized = matches!(
+            eth_sync_or(&*eth_client, io, || ()).await,
+            Ok(Either::Right(()))
+        );
+        if !is_synchronized {
+            tracing::debug!("The Ethereum node is synchronizing");
+            last_call_succeeded = false;
+            continue;
+        }
+
+        last_call_succeeded = true;
+        let steps = async {
+            relayer.reconcile().await?;
+            let gas_price = gas_prices.gas_price().await?;
+            log_relay_error(
+                relayer.relay_validator_set_update(client, gas_price).await,
+            )?;
+            log_relay_error(
+                relayer
+                    .relay_bridge_pool(
+                        client,
+                        io,
+                        rates,
+                        gas_price,
+                        &args.relayer,
+                        max_gas,
+                        max_cost,
+                    )
+                    .await,
+            )
+        };
+        if let Err(err) = steps.await {
+            if is_fatal(&err) {
+                tracing::error!(%err, "The relayer daemon is shutting down");
+                return Err(err);
+            }
+            tracing::error!(%err, "Failed to perform relay operations");
+            last_call_succeeded = false;
+        }
+    }
+}
+
+/// Errors that must stop the daemon, since continuing could
+/// result in duplicate relays.
+fn is_fatal(err: &Error) -> bool {
+    matches!(
+        err,
+        Error::EthereumBridge(EthereumBridgeError::RelayerState(_))
+    )
+}
+
+/// Log a non fatal relay error, such that other relay
+/// operations can still be attempted.
+fn log_relay_error(result: Result<(), Error>) -> Result<(), Error> {
+    match result {
+        Err(err) if !is_fatal(&err) => {
+            tracing::warn!(%err, "Relay operation failed");
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// The status of an in-flight transaction.
+enum InFlightStatus {
+    /// The transaction is still waiting to be mined or confirmed.
+    Pending,
+    /// The transaction has not been mined for too long.
+    Stuck,
+    /// The transaction no longer needs to be tracked.
+    Done,
+}
+
+/// The relayer daemon, along with its persisted state.
+struct Relayer<E> {
+    /// Client to the Ethereum node.
+    eth_client: Arc<E>,
+    /// The Ethereum account paying for the relays.
+    from: EthersAddress,
+    /// The state of the relayer.
+    state: RelayerState,
+    /// Where the state of the relayer is persisted.
+    state_path: PathBuf,
+    /// The number of block confirmations on Ethereum.
+    confirmations: U64,
+    /// The number of blocks after which a transaction is resubmitted.
+    resubmit_after_blocks: U64,
+    /// Upper bound on the gas price of resubmitted transactions.
+    max_gas_price: Option<U256>,
+}
+
+impl<E> Relayer<E>
+where
+    E: Middleware,
+{
+    /// Persist the state of the relayer.
+    fn persist(&self) -> Result<(), Error> {
+        self.state.save(&self.state_path)
+    }
+
+    /// Check if a relay of the given kind is awaiting confirmation.
+    fn is_in_flight<F>(&self, mut kind: F) -> bool
+    where
+        F: FnMut(&Relay) -> bool,
+    {
+        self.state.in_flight.iter().any(|tx| kind(&tx.relay))
+    }
+
+    /// Send a new transaction, managing its nonce.
+    async fn send(
+        &mut self,
+        relay: Relay,
+        mut tx: TypedTransaction,
+        gas_price: U256,
+    ) -> Result<(), Error> {
+        let pending_count = self
+            .eth_client
+            .get_transaction_count(self.from, Some(BlockNumber::Pending.into()))
+            .await
+            .map_err(rpc_error)?;
+        // NB: the node may not know about all the transactions we
+        // have sent, so never go back to a lower nonce
+        let nonce = self
+            .state
+            .next_nonce
+            .map_or(pending_count, |nonce| nonce.max(pending_count));
+        let sent_at_block = self
+            .eth_client
+            .get_block_number()
+            .await
+            .map_err(rpc_error)?;
+
+        tx.set_from(self.from);
+        tx.set_nonce(nonce);
+        tx.set_gas_price(gas_price);
+
+        let tx_hash =
+            match self.eth_client.send_transaction(tx.clone(), None).await {
+                Ok(pending_tx) => pending_tx.tx_hash(),
+                Err(err) => {
+                    // resynchronize the nonce with the node next time
+                    self.state.next_nonce = None;
+                    self.persist()?;
+                    return Err(Error::EthereumBridge(
+                        EthereumBridgeError::ContractCall(err.to_string()),
+                    ));
+                }
+            };
+        tracing::info!(?relay, ?tx_hash, %nonce, "Sent relay transaction");
+
+        self.state.next_nonce = Some(nonce.saturating_add(U256::one()));
+        self.state.in_flight.push(InFlightTx {
+            relay,
+            tx,
+            tx_hashes: vec![tx_hash],
+            nonce,
+            sent_at_block,
+        });
+        self.persist()
+    }
+
+    /// Update the in-flight transactions, dropping confirmed ones
+    /// and resubmitting the stuck ones.
+    async fn reconcile(&mut self) -> Result<(), Error> {
+        if self.state.in_flight.is_empty() {
+            return Ok(());
+        }
+        let current_block = self
+            .eth_client
+            .get_block_number()
+            .await
+            .map_err(rpc_error)?;
+        let confirmed_count = self
+            .eth_client
+            .get_transaction_count(self.from, Some(BlockNumber::Latest.into()))
+            .await
+            .map_err(rpc_error)?;
+
+        let mut index = 0;
+        while let Some(in_flight) = self.state.in_flight.get(index) {
+            match self
+                .in_flight_status(in_flight, current_block, confirmed_count)
+                .await?
+            {
+                InFlightStatus::Pending => {
+                    index = index.saturating_add(1);
+                }
+                InFlightStatus::Done => {
+                    self.state.in_flight.remove(index);
+                    self.persist()?;
+                }
+                InFlightStatus::Stuck => {
+                    self.resubmit(index, current_block).await?;
+                    index = index.saturating_add(1);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Determine the status of an in-flight transaction.
+    async fn in_flight_status(
+        &self,
+        in_flight: &InFlightTx,
+        current_block: U64,
+        confirmed_count: U256,
+    ) -> Result<InFlightStatus, Error> {
+        for tx_hash in &in_flight.tx_hashes {
+            let Some(receipt) = self
+                .eth_client
+                .get_transaction_receipt(*tx_hash)
+                .await
+                .map_err(rpc_error)?
+            else {
+                continue;
+            };
+            let Some(mined_at) = receipt.block_number else {
+                return Ok(InFlightStatus::Pending);
+            };
+            if current_block < mined_at.saturating_add(self.confirmations) {
+                return Ok(InFlightStatus::Pending);
+            }
+            if receipt.status == Some(1u64.into()) {
+                tracing::info!(
+                    relay = ?in_flight.relay,
+                    ?tx_hash,
+                    "Relay confirmed"
+                );
+            } else {
+                tracing::error!(
+                    relay = ?in_flight.relay,
+                    ?receipt,
+                    "Relay transaction failed"
+                );
+            }
+            return Ok(InFlightStatus::Done);
+        }
+        if confirmed_count > in_flight.nonce {
+            // some other transaction took our nonce, e.g. if the
+            // account is shared with another relayer
+            tracing::warn!(
+                relay = ?in_flight.relay,
+                nonce = %in_flight.nonce,
+                "The nonce of the relay transaction has been used by another \
+                 transaction"
+            );
+            return Ok(InFlightStatus::Done);
+        }
+        let resubmit_at = in_flight
+            .sent_at_block
+            .saturating_add(self.resubmit_after_blocks);
+        Ok(if current_block >= resubmit_at {
+            InFlightStatus::Stuck
+        } else {
+            InFlightStatus::Pending
+        })
+    }
+
+    /// Resubmit the in-flight transaction at the given index, with
+    /// the same nonce and a higher gas price.
+    async fn resubmit(
+        &mut self,
+        index: usize,
+        current_block: U64,
+    ) -> Result<(), Error> {
+        let in_flight = &self.state.in_flight[index];
+        let gas_price = in_flight.tx.gas_price().unwrap_or_default();
+        let bumped_gas_price = bump_gas_price(gas_price, self.max_gas_price);
+        if bumped_gas_price <= gas_price {
+            tracing::warn!(
+                relay = ?in_flight.relay,
+                %gas_price,
+                "The relay transaction is stuck, but the maximum gas price \
+                 has been reached"
+            );
+            return Ok(());
+        }
+
+        let mut tx = in_flight.tx.clone();
+        tx.set_gas_price(bumped_gas_price);
+        let tx_hash = self
+            .eth_client
+            .send_transaction(tx.clone(), None)
+            .await
+            .map_err(|err| {
+                Error::EthereumBridge(EthereumBridgeError::ContractCall(
+                    err.to_string(),
+                ))
+            })?
+            .tx_hash();
+        tracing::info!(
+            relay = ?in_flight.relay,
+            ?tx_hash,
+            %bumped_gas_price,
+            "Resubmitted stuck relay transaction"
+        );
+
+        let in_flight = &mut self.state.in_flight[index];
+        in_flight.tx = tx;
+        in_flight.tx_hashes.push(tx_hash);
+        in_flight.sent_at_block = current_block;
+        self.persist()
+    }
+
+    /// Fetch the Bridge smart contract. Its address may change
+    /// through governance, so it is always queried anew.
+    async fn bridge<C>(&self, client: &C) -> Result<Bridge<E>, Error>
+    where
+        C: Client + Sync,
+    {
+        let contract = RPC
+            .shell()
+            .eth_bridge()
+            .read_bridge_contract(client)
+            .await
+            .map_err(|err| {
+                Error::EthereumBridge(EthereumBridgeError::RetrieveContract(
+                    err.to_string(),
+                ))
+            })?;
+        Ok(Bridge::new(contract.address, Arc::clone(&self.eth_client)))
+    }
+
+    /// Relay the next validator set update, if the Bridge smart
+    /// contract has fallen behind Namada.
+    async fn relay_validator_set_update<C>(
+        &mut self,
+        client: &C,
+        gas_price: U256,
+    ) -> Result<(), Error>
+    where
+        C: Client + Sync,
+    {
+        if self
+            .is_in_flight(|relay| matches!(relay, Relay::ValidatorSet { .. }))
+        {
+            return Ok(());
+        }
+
+        let bridge = self.bridge(client).await?;
+        let bridge_epoch =
+            bridge.validator_set_nonce().call().await.map_err(|err| {
+                Error::EthereumBridge(EthereumBridgeError::ContractCall(
+                    err.to_string(),
+                ))
+            })?;
+        let bridge_epoch = Epoch(bridge_epoch.low_u64());
+        let nam_epoch = RPC.shell().epoch(client).await.map_err(|err| {
+            Error::Query(QueryError::General(err.to_string()))
+        })?;
+
+        // NB: the nonce in the bridge contract should always be one
+        // epoch ahead of namada, for the former to be up to date
+        if nam_epoch < bridge_epoch {
+            if nam_epoch.next() < bridge_epoch {
+                tracing::error!("The Bridge contract is ahead of Namada!");
+            }
+            return Ok(());
+        }
+
+        let epoch = bridge_epoch.next();
+        let ValidatorSetUpdateArgs {
+            validator_set_args,
+            bridge_hash,
+            gov_hash,
+            signatures,
+        } = read_validator_set_update_args(client, epoch).await?;
+        let relay_op = bridge.update_validator_set(
+            validator_set_args.into(),
+            bridge_hash,
+            gov_hash,
+            signatures,
+        );
+        self.send(Relay::ValidatorSet { epoch }, relay_op.tx, gas_price)
+            .await
+    }
+
+    /// Relay the most profitable batch of transfers in the signed
+    /// Bridge pool, if any.
+    #[allow(clippy::too_many_arguments)]
+    async fn relay_bridge_pool<C, IO, R>(
+        &mut self,
+        client: &C,
+        io: &IO,
+        rates: &R,
+        gas_price: U256,
+        relayer: &Address,
+        max_gas: Uint,
+        max_cost: I256,
+    ) -> Result<(), Error>
+    where
+        C: Client + Sync,
+        IO: Io,
+        R: TokenRateSource,
+    {
+        if self.is_in_flight(|relay| matches!(relay, Relay::BridgePool { .. }))
+        {
+            return Ok(());
+        }
+
+        let conversion_table = rates.conversion_table().await?;
+        let Some(batch) = recommend(
+            client,
+            io,
+            &conversion_table,
+            gas_price_in_gwei(gas_price)?,
+            max_gas,
+            max_cost,
+        )
+        .await?
+        else {
+            tracing::debug!("No profitable Bridge pool batch to relay");
+            return Ok(());
+        };
+
+        let transfers = batch
+            .transfer_hashes
+            .iter()
+            .map(|hash| KeccakHash::try_from(hash.as_str()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| EncodingError::Decoding(err.to_string()))?;
+        let GenBridgePoolProofRsp {
+            abi_encoded_args, ..
+        } = request_bridge_pool_proof(
+            client,
+            GenBridgePoolProofReq {
+                transfers: Cow::Owned(transfers),
+                relayer: Cow::Borrowed(relayer),
+                with_appendix: false,
+            },
+        )
+        .await?;
+        let (validator_set, signatures, bp_proof): TransferToErcArgs =
+            AbiDecode::decode(&abi_encoded_args).map_err(|err| {
+                EncodingError::Decoding(format!(
+                    "Unable to decode the generated proof: {err}"
+                ))
+            })?;
+
+        let bridge = self.bridge(client).await?;
+        let contract_nonce = bridge
+            .transfer_to_erc_20_nonce()
+            .call()
+            .await
+            .map_err(|err| {
+                Error::EthereumBridge(EthereumBridgeError::ContractCall(
+                    err.to_string(),
+                ))
+            })?;
+        if bp_proof.batch_nonce != contract_nonce {
+            // the last relayed batch has yet to be acknowledged by namada
+            tracing::debug!(
+                %contract_nonce,
+                batch_nonce = %bp_proof.batch_nonce,
+                "The Bridge pool nonces in Namada and Ethereum differ"
+            );
+            return Ok(());
+        }
+
+        tracing::info!(
+            transfers = ?batch.transfer_hashes,
+            net_profit = %batch.net_profit,
+            "Relaying Bridge pool batch"
+        );
+        let batch_nonce = bp_proof.batch_nonce;
+        let relay_op =
+            bridge.transfer_to_erc(validator_set, signatures, bp_proof);
+        self.send(Relay::BridgePool { batch_nonce }, relay_op.tx, gas_price)
+            .await
+    }
+}
+
+/// Bump a gas price by 12.5%, the minimum increase accepted by
+/// Ethereum nodes to replace a pending transaction.
+fn bump_gas_price(gas_price: U256, max_gas_price: Option<U256>) -> U256 {
+    let bump = gas_price
+        .checked_div(U256::from(8))
+        .unwrap_or_default()
+        .max(U256::one());
+    let bumped = gas_price.saturating_add(bump);
+    max_gas_price.map_or(bumped, |max| bumped.min(max))
+}
+
+/// Convert a gas price in wei to gwei, rounding up.
+fn gas_price_in_gwei(gas_price: U256) -> Result<Uint, Error> {
+    let (gwei, rem) = gas_price.div_mod(U256::exp10(9));
+    let gwei = if rem.is_zero() {
+        gwei
+    } else {
+        gwei.saturating_add(U256::one())
+    };
+    u64::try_from(gwei.max(U256::one()))
+        .map(Uint::from_u64)
+        .map_err(|_| {
+            Error::EthereumBridge(EthereumBridgeError::RelayCost(format!(
+                "The gas price of {gas_price} wei is too high"
+            )))
+        })
+}
+
+fn rpc_error(err: impl std::fmt::Display) -> Error {
+    Error::EthereumBridge(EthereumBridgeError::Rpc(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::providers::{MockProvider, Provider};
+    use ethers::types::{TransactionReceipt, TransactionRequest};
+
+    use super::*;
+
+    fn relayer(
+        state_path: PathBuf,
+    ) -> (Relayer<Provider<MockProvider>>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let relayer = Relayer {
+            eth_client: Arc::new(provider),
+            from: EthersAddress::repeat_byte(1),
+            state: RelayerState::default(),
+            state_path,
+            confirmations: 5u64.into(),
+            resubmit_after_blocks: 5u64.into(),
+            max_gas_price: None,
+        };
+        (relayer, mock)
+    }
+
+    fn in_flight_tx(nonce: u64, gas_price: u64) -> InFlightTx {
+        InFlightTx {
+            relay: Relay::ValidatorSet { epoch: Epoch(3) },
+            tx: TransactionRequest::new()
+                .to(EthersAddress::repeat_byte(2))
+                .nonce(nonce)
+                .gas(100_000)
+                .gas_price(gas_price)
+                .into(),
+            tx_hashes: vec![H256::repeat_byte(1)],
+            nonce: nonce.into(),
+            sent_at_block: 10u64.into(),
+        }
+    }
+
+    /// Test that the state of the relayer survives a restart.
+    #[test]
+    fn test_state_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("relayer.json");
+
+        assert_eq!(RelayerState::load(&path).unwrap(), RelayerState::default());
+
+        let state = RelayerState {
+            next_nonce: Some(4u64.into()),
+            in_flight: vec![in_flight_tx(3, 100)],
+        };
+        state.save(&path).unwrap();
+        assert_eq!(RelayerState::load(&path).unwrap(), state);
+    }
+
+    /// Test that new transactions never reuse a nonce.
+    #[tokio::test]
+    async fn test_nonce_management() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut relayer, mock) = relayer(dir.path().join("relayer.json"));
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(EthersAddress::repeat_byte(2))
+            .gas(100_000)
+            .into();
+
+        // the node hasn't seen our last transaction yet
+        relayer.state.next_nonce = Some(5u64.into());
+        // NB: responses are popped in reverse order
+        mock.push::<H256, _>(H256::repeat_byte(1)).unwrap();
+        mock.push::<U64, _>(U64::from(10)).unwrap();
+        mock.push::<U256, _>(U256::from(3)).unwrap();
+        relayer
+            .send(
+                Relay::ValidatorSet { epoch: Epoch(3) },
+                tx.clone(),
+                7u64.into(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(relayer.state.next_nonce, Some(6u64.into()));
+        let in_flight = &relayer.state.in_flight[0];
+        assert_eq!(in_flight.nonce, 5u64.into());
+        assert_eq!(in_flight.tx.nonce(), Some(&5u64.into()));
+        assert_eq!(in_flight.tx.gas_price(), Some(7u64.into()));
+        assert_eq!(in_flight.tx_hashes, vec![H256::repeat_byte(1)]);
+        assert_eq!(in_flight.sent_at_block, 10u64.into());
+
+        // the node knows about more transactions than we do
+        mock.push::<H256, _>(H256::repeat_byte(2)).unwrap();
+        mock.push::<U64, _>(U64::from(11)).unwrap();
+        mock.push::<U256, _>(U256::from(9)).unwrap();
+        relayer
+            .send(
+                Relay::BridgePool {
+                    batch_nonce: 0u64.into(),
+                },
+                tx,
+                7u64.into(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(relayer.state.next_nonce, Some(10u64.into()));
+        assert_eq!(relayer.state.in_flight[1].nonce, 9u64.into());
+
+        // the state has been persisted
+        assert_eq!(
+            RelayerState::load(&relayer.state_path).unwrap(),
+            relayer.state
+        );
+    }
+
+    /// Test that confirmed transactions are no longer tracked.
+    #[tokio::test]
+    async fn test_confirmed_tx_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut relayer, mock) = relayer(dir.path().join("relayer.json"));
+        relayer.state.in_flight.push(in_flight_tx(3, 100));
+
+        let receipt = TransactionReceipt {
+            transaction_hash: H256::repeat_byte(1),
+            block_number: Some(12u64.into()),
+            status: Some(1u64.into()),
+            ..Default::default()
+        };
+        // not enough confirmations yet
+        mock.push::<TransactionReceipt, _>(receipt.clone()).unwrap();
+        mock.push::<U256, _>(U256::from(4)).unwrap();
+        mock.push::<U64, _>(U64::from(16)).unwrap();
+        relayer.reconcile().await.unwrap();
+        assert_eq!(relayer.state.in_flight.len(), 1);
+
+        mock.push::<TransactionReceipt, _>(receipt).unwrap();
+        mock.push::<U256, _>(U256::from(4)).unwrap();
+        mock.push::<U64, _>(U64::from(17)).unwrap();
+        relayer.reconcile().await.unwrap();
+        assert!(relayer.state.in_flight.is_empty());
+        assert_eq!(
+            RelayerState::load(&relayer.state_path).unwrap(),
+            relayer.state
+        );
+    }
+
+    /// Test that stuck transactions are resubmitted with the same
+    /// nonce and a higher gas price.
+    #[tokio::test]
+    async fn test_stuck_tx_resubmitted() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut relayer, mock) = relayer(dir.path().join("relayer.json"));
+        relayer.max_gas_price = Some(120u64.into());
+        relayer.state.in_flight.push(in_flight_tx(3, 100));
+
+        // the tx is stuck, resubmit it
+        mock.push::<H256, _>(H256::repeat_byte(2)).unwrap();
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+        mock.push::<U256, _>(U256::from(3)).unwrap();
+        mock.push::<U64, _>(U64::from(15)).unwrap();
+        relayer.reconcile().await.unwrap();
+        let in_flight = &relayer.state.in_flight[0];
+        assert_eq!(in_flight.nonce, 3u64.into());
+        assert_eq!(in_flight.tx.nonce(), Some(&3u64.into()));
+        assert_eq!(in_flight.tx.gas_price(), Some(112u64.into()));
+        assert_eq!(
+            in_flight.tx_hashes,
+            vec![H256::repeat_byte(1), H256::repeat_byte(2)]
+        );
+        assert_eq!(in_flight.sent_at_block, 15u64.into());
+
+        // the gas price is capped
+        mock.push::<H256, _>(H256::repeat_byte(3)).unwrap();
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+        mock.push::<U256, _>(U256::from(3)).unwrap();
+        mock.push::<U64, _>(U64::from(20)).unwrap();
+        relayer.reconcile().await.unwrap();
+        let in_flight = &relayer.state.in_flight[0];
+        assert_eq!(in_flight.tx.gas_price(), Some(120u64.into()));
+        assert_eq!(in_flight.tx_hashes.len(), 3);
+
+        // another tx took the nonce
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+        mock.push::<U256, _>(U256::from(4)).unwrap();
+        mock.push::<U64, _>(U64::from(21)).unwrap();
+        relayer.reconcile().await.unwrap();
+        assert!(relayer.state.in_flight.is_empty());
+    }
+
+    /// Test the conversion of gas prices to gwei.
+    #[test]
+    fn test_gas_price_in_gwei() {
+        assert_eq!(gas_price_in_gwei(U256::zero()).unwrap(), uint::ONE);
+        assert_eq!(
+            gas_price_in_gwei(U256::exp10(10)).unwrap(),
+            Uint::from_u64(10)
+        );
+        assert_eq!(
+            gas_price_in_gwei(U256::exp10(10).saturating_add(U256::one()))
+                .unwrap(),
+            Uint::from_u64(11)
+        );
+        assert!(gas_price_in_gwei(U256::MAX).is_err());
+    }
+}
@@ -656,32 +656,6 @@ where
         )));
     }
 
-    let shell = RPC.shell().eth_bridge();
-    let encoded_proof_fut = shell
-        .read_valset_upd_proof(nam_client, &epoch_to_relay)
-        .map(|result| {
-            result.map_err(|err| {
-                let msg = format!(
-                    "Failed to fetch validator set update proof: {err}"
-                );
-                SdkError::Query(QueryError::General(msg))
-            })
-        });
-
-    let bridge_current_epoch = epoch_to_relay
-        .prev()
-        .expect("Epoch to relay must have prev");
-    let shell = RPC.shell().eth_bridge();
-    let validator_set_args_fut = shell
-        .read_bridge_valset(nam_client, &bridge_current_epoch)
-        .map(|result| {
-            result.map_err(|err| {
-                let msg =
-                    format!("Failed to fetch Bridge validator set: {err}");
-                SdkError::Query(QueryError::General(msg))
-            })
-        });
-
     let shell = RPC.shell().eth_bridge();
     let bridge_address_fut =
         shell.read_bridge_contract(nam_client).map(|result| {
@@ -692,19 +666,19 @@ where
             })
         });
 
-    let (encoded_proof, validator_set_args, bridge_contract) =
-        futures::try_join!(
-            encoded_proof_fut,
-            validator_set_args_fut,
-            bridge_address_fut
-        )
-        .map_err(|err| R::try_recover(err))?;
-
-    let (bridge_hash, gov_hash, signatures): (
-        [u8; 32],
-        [u8; 32],
-        Vec<Signature>,
-    ) = abi_decode_struct(encoded_proof);
+    let (
+        ValidatorSetUpdateArgs {
+            validator_set_args,
+            bridge_hash,
+            gov_hash,
+            signatures,
+        },
+        bridge_contract,
+    ) = futures::try_join!(
+        read_validator_set_update_args(nam_client, epoch_to_relay),
+        bridge_address_fut
+    )
+    .map_err(|err| R::try_recover(err))?;
 
     let bridge = Bridge::new(bridge_contract.address, eth_client);
 
@@ -750,6 +724,73 @@ where
     status
 }
 
+/// Arguments to pass to `updateValidatorSet` in the Bridge contract.
+pub(crate) struct ValidatorSetUpdateArgs {
+    /// The validator set currently in the Bridge contract.
+    pub validator_set_args: ValidatorSetArgs,
+    /// Hash of the new bridge validator set.
+    pub bridge_hash: [u8; 32],
+    /// Hash of the new governance validator set.
+    pub gov_hash: [u8; 32],
+    /// Signatures over the new validator set hashes.
+    pub signatures: Vec<Signature>,
+}
+
+/// Fetch the signed proof of the validator set update at the
+/// given epoch, along with the validator set it must be checked
+/// against.
+pub(crate) async fn read_validator_set_update_args<C>(
+    nam_client: &C,
+    epoch_to_relay: Epoch,
+) -> Result<ValidatorSetUpdateArgs, SdkError>
+where
+    C: Client + Sync,
+{
+    let shell = RPC.shell().eth_bridge();
+    let encoded_proof_fut = shell
+        .read_valset_upd_proof(nam_client, &epoch_to_relay)
+        .map(|result| {
+            result.map_err(|err| {
+                let msg = format!(
+                    "Failed to fetch validator set update proof: {err}"
+                );
+                SdkError::Query(QueryError::General(msg))
+            })
+        });
+
+    let bridge_current_epoch = epoch_to_relay.prev().ok_or_else(|| {
+        SdkError::Other(
+            "There is no validator set update proof for epoch 0".into(),
+        )
+    })?;
+    let shell = RPC.shell().eth_bridge();
+    let validator_set_args_fut = shell
+        .read_bridge_valset(nam_client, &bridge_current_epoch)
+        .map(|result| {
+            result.map_err(|err| {
+                let msg =
+                    format!("Failed to fetch Bridge validator set: {err}");
+                SdkError::Query(QueryError::General(msg))
+            })
+        });
+
+    let (encoded_proof, validator_set_args) =
+        futures::try_join!(encoded_proof_fut, validator_set_args_fut)?;
+
+    let (bridge_hash, gov_hash, signatures): (
+        [u8; 32],
+        [u8; 32],
+        Vec<Signature>,
+    ) = abi_decode_struct(encoded_proof);
+
+    Ok(ValidatorSetUpdateArgs {
+        validator_set_args,
+        bridge_hash,
+        gov_hash,
+        signatures,
+    })
+}
+
 // NOTE: there's a bug (or feature?!) in ethers, where
 // `EthAbiCodec` derived `AbiDecode` implementations
 // have a decode method that expects a tuple, but
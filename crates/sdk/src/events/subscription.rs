@@ -0,0 +1,588 @@
+//! Subscriptions to the events emitted by the ledger, streamed over
+//! CometBFT's websocket endpoint.
+//!
+//! An [`EventSubscription`] listens to the new blocks committed by the
+//! ledger and yields the Namada [events](Event) matching its
+//! [`EventFilter`]. If the connection to the node drops, the subscription
+//! reconnects on its own and resumes from the last height it has seen,
+//! fetching the results of any blocks it missed in the meantime.
+
+use std::collections::VecDeque;
+
+use futures::{Stream, StreamExt};
+use namada_core::address::Address;
+use namada_core::chain::BlockHeight;
+use namada_core::hash::Hash;
+use namada_core::uint::Uint;
+use namada_io::client::Client;
+use namada_token::event::types::TRANSFER;
+use namada_token::event::{
+    SourceAccount, SourceAccounts, TargetAccount, TargetAccounts, TokenEvent,
+};
+use tendermint_rpc::event::{Event as RpcEvent, EventData};
+use tendermint_rpc::query::{EventType as RpcEventType, Query};
+use tendermint_rpc::{SubscriptionClient, WebSocketClient, WebSocketClientUrl};
+use tokio::task::JoinHandle;
+
+use crate::control_flow::time::{sleep, Duration};
+use crate::error::{Error, QueryError};
+use crate::events::extend::{
+    ExtendEventAttributes, Height as HeightAttr, TxHash as TxHashAttr,
+    UserAccount,
+};
+use crate::events::log::dumb_queries::QueryMatcher;
+use crate::events::{Event, EventToEmit, EventType};
+
+/// The initial delay before reconnecting to the node.
+const DEFAULT_MIN_BACKOFF: Duration = Duration::from_millis(500);
+
+/// The maximum delay between two reconnection attempts.
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// The role of an account in the events matched by an [`EventFilter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountRole {
+    /// The account is the source of a token operation.
+    Source,
+    /// The account is the target of a token operation.
+    Target,
+    /// The account is either the source or the target of a token operation.
+    Any,
+}
+
+/// Filter of the events yielded by an [`EventSubscription`].
+///
+/// Events are matched against an [`EventType`] (either exactly or by
+/// prefix, e.g. on their domain), a set of attributes (such as
+/// [`TxHashAttr`] or [`HeightAttr`]) and, optionally, a [`UserAccount`]
+/// involved in a token operation.
+#[derive(Debug, Clone)]
+pub struct EventFilter {
+    matcher: QueryMatcher,
+    account: Option<(UserAccount, AccountRole)>,
+}
+
+impl Default for EventFilter {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl EventFilter {
+    /// Create a new [`EventFilter`] matching all events.
+    pub fn all() -> Self {
+        Self::with_prefix(EventType::new(""))
+    }
+
+    /// Create a new [`EventFilter`] matching the events in the domain of
+    /// `E`.
+    pub fn of_event_type<E: EventToEmit>() -> Self {
+        Self::from_matcher(QueryMatcher::of_event_type::<E>())
+    }
+
+    /// Create a new [`EventFilter`] matching event types with the given
+    /// `prefix`.
+    pub fn with_prefix(prefix: EventType) -> Self {
+        Self::from_matcher(QueryMatcher::with_prefix(prefix))
+    }
+
+    /// Create a new [`EventFilter`] matching the given event type.
+    pub fn with_event_type(event_type: EventType) -> Self {
+        Self::from_matcher(QueryMatcher::with_event_type(event_type))
+    }
+
+    /// Create a new [`EventFilter`] from a [`QueryMatcher`].
+    pub fn from_matcher(matcher: QueryMatcher) -> Self {
+        Self {
+            matcher,
+            account: None,
+        }
+    }
+
+    /// Returns a filter matching the transfers whose target is `owner`.
+    pub fn incoming_transfers(owner: Address) -> Self {
+        Self::with_event_type(TRANSFER)
+            .with_account(UserAccount::Internal(owner), AccountRole::Target)
+    }
+
+    /// Returns a filter matching the token events of `owner`.
+    pub fn token_events_of(owner: Address) -> Self {
+        Self::of_event_type::<TokenEvent>()
+            .with_account(UserAccount::Internal(owner), AccountRole::Any)
+    }
+
+    /// Only match events with the given attribute.
+    #[inline]
+    pub fn and_attribute<DATA>(mut self, data: DATA) -> Self
+    where
+        DATA: ExtendEventAttributes,
+    {
+        self.matcher = self.matcher.and_attribute(data);
+        self
+    }
+
+    /// Only match events emitted by the tx with the given hash.
+    #[inline]
+    pub fn with_tx_hash(self, tx_hash: Hash) -> Self {
+        self.and_attribute(TxHashAttr(tx_hash))
+    }
+
+    /// Only match events emitted at the given height.
+    #[inline]
+    pub fn with_height(self, height: BlockHeight) -> Self {
+        self.and_attribute(HeightAttr(height))
+    }
+
+    /// Only match token events involving `account` with the given `role`.
+    #[inline]
+    pub fn with_account(
+        mut self,
+        account: UserAccount,
+        role: AccountRole,
+    ) -> Self {
+        self.account = Some((account, role));
+        self
+    }
+
+    /// Checks if this [`EventFilter`] validates the given [`Event`].
+    pub fn matches(&self, event: &Event) -> bool {
+        if !self.matcher.matches(event) {
+            return false;
+        }
+        let Some((account, role)) = &self.account else {
+            return true;
+        };
+        match role {
+            AccountRole::Source => is_source_account(event, account),
+            AccountRole::Target => is_target_account(event, account),
+            AccountRole::Any => {
+                is_source_account(event, account)
+                    || is_target_account(event, account)
+            }
+        }
+    }
+}
+
+/// Check if `account` is the source of the token operation of `event`.
+fn is_source_account(event: &Event, account: &UserAccount) -> bool {
+    matches!(
+        event.read_attribute_opt::<SourceAccount>(),
+        Ok(Some(source)) if &source == account
+    ) || matches!(
+        event.read_attribute_opt::<SourceAccounts>(),
+        Ok(Some(sources)) if contains_account(&sources.0, account)
+    )
+}
+
+/// Check if `account` is the target of the token operation of `event`.
+fn is_target_account(event: &Event, account: &UserAccount) -> bool {
+    matches!(
+        event.read_attribute_opt::<TargetAccount>(),
+        Ok(Some(target)) if &target == account
+    ) || matches!(
+        event.read_attribute_opt::<TargetAccounts>(),
+        Ok(Some(targets)) if contains_account(&targets.0, account)
+    )
+}
+
+fn contains_account(
+    accounts: &[((UserAccount, Address), Uint)],
+    account: &UserAccount,
+) -> bool {
+    accounts
+        .iter()
+        .any(|((owner, _token), _amount)| owner == account)
+}
+
+/// An event yielded by an [`EventSubscription`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscribedEvent {
+    /// The height of the block in which the event was emitted.
+    pub height: BlockHeight,
+    /// The matched event.
+    pub event: Event,
+}
+
+/// An open connection to the websocket endpoint of a node.
+struct Connection {
+    client: WebSocketClient,
+    subscription: tendermint_rpc::Subscription,
+    driver: JoinHandle<Result<(), tendermint_rpc::Error>>,
+}
+
+impl Connection {
+    /// Connect to the node at `url` and subscribe to new blocks.
+    async fn open(url: WebSocketClientUrl) -> Result<Self, Error> {
+        let (client, driver) = WebSocketClient::new(url)
+            .await
+            .map_err(|err| QueryError::General(err.to_string()))?;
+        let driver = tokio::spawn(driver.run());
+        let subscription =
+            match client.subscribe(Query::from(RpcEventType::NewBlock)).await {
+                Ok(subscription) => subscription,
+                Err(err) => {
+                    client.close().ok();
+                    driver.abort();
+                    return Err(QueryError::General(err.to_string()).into());
+                }
+            };
+        Ok(Self {
+            client,
+            subscription,
+            driver,
+        })
+    }
+
+    /// Close the connection.
+    fn close(self) {
+        // NB: closing can only fail if the driver has already stopped
+        self.client.close().ok();
+        self.driver.abort();
+    }
+}
+
+/// A subscription to the events emitted by the ledger.
+///
+/// The subscription connects to the node lazily, on the first call to
+/// [`EventSubscription::next`].
+pub struct EventSubscription {
+    url: WebSocketClientUrl,
+    filter: EventFilter,
+    connection: Option<Connection>,
+    /// The last height whose events have been fully queued
+    last_height: Option<BlockHeight>,
+    /// The events that are yet to be yielded
+    pending: VecDeque<SubscribedEvent>,
+    min_backoff: Duration,
+    max_backoff: Duration,
+    max_reconnect_attempts: Option<u64>,
+}
+
+impl std::fmt::Debug for EventSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventSubscription")
+            .field("url", &self.url.to_string())
+            .field("filter", &self.filter)
+            .field("connected", &self.connection.is_some())
+            .field("last_height", &self.last_height)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventSubscription {
+    /// Create a new subscription to the events matching `filter`, emitted
+    /// by the node whose websocket endpoint is at `url` (e.g.
+    /// `ws://127.0.0.1:26657/websocket`).
+    pub fn new(url: WebSocketClientUrl, filter: EventFilter) -> Self {
+        Self {
+            url,
+            filter,
+            connection: None,
+            last_height: None,
+            pending: VecDeque::new(),
+            min_backoff: DEFAULT_MIN_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            max_reconnect_attempts: None,
+        }
+    }
+
+    /// Start streaming the events from the given height, instead of the
+    /// next committed block. This is useful to resume a subscription from
+    /// a height persisted by the caller.
+    pub fn from_height(mut self, height: BlockHeight) -> Self {
+        self.last_height = height.prev_height();
+        self
+    }
+
+    /// Set the bounds of the exponential backoff between reconnection
+    /// attempts.
+    pub fn backoff(mut self, min: Duration, max: Duration) -> Self {
+        self.min_backoff = min;
+        self.max_backoff = max.max(min);
+        self
+    }
+
+    /// Give up after the given number of consecutive failed reconnection
+    /// attempts. By default, the subscription keeps on retrying.
+    pub fn max_reconnect_attempts(mut self, attempts: u64) -> Self {
+        self.max_reconnect_attempts = Some(attempts);
+        self
+    }
+
+    /// The height of the last block whose events have been received.
+    pub fn last_height(&self) -> Option<BlockHeight> {
+        self.last_height
+    }
+
+    /// Wait for the next event matching the filter of this subscription.
+    ///
+    /// Errors are only returned once the maximum number of reconnection
+    /// attempts has been exhausted.
+    pub async fn next(&mut self) -> Result<SubscribedEvent, Error> {
+        let mut failed_attempts = 0_u64;
+        let mut backoff = self.min_backoff;
+
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(event);
+            }
+
+            let Err(err) = self.poll_connection().await else {
+                failed_attempts = 0;
+                backoff = self.min_backoff;
+                continue;
+            };
+
+            if let Some(connection) = self.connection.take() {
+                connection.close();
+            }
+            failed_attempts = failed_attempts.saturating_add(1);
+            if matches!(
+                self.max_reconnect_attempts,
+                Some(max) if failed_attempts > max
+            ) {
+                return Err(err);
+            }
+            tracing::warn!(
+                %err,
+                last_height = ?self.last_height,
+                "Event subscription interrupted, reconnecting in {backoff:?}"
+            );
+            sleep(backoff).await;
+            backoff = backoff.saturating_mul(2).min(self.max_backoff);
+        }
+    }
+
+    /// Convert this subscription into a [`Stream`] of events.
+    pub fn into_stream(
+        self,
+    ) -> impl Stream<Item = Result<SubscribedEvent, Error>> {
+        futures::stream::unfold(Some(self), |subscription| async move {
+            let mut subscription = subscription?;
+            match subscription.next().await {
+                Ok(event) => Some((Ok(event), Some(subscription))),
+                // NB: the stream ends after the first error, since the
+                // reconnection attempts have been exhausted
+                Err(err) => Some((Err(err), None)),
+            }
+        })
+    }
+
+    /// Connect to the node if needed, otherwise wait for the next block.
+    async fn poll_connection(&mut self) -> Result<(), Error> {
+        let Some(connection) = self.connection.as_mut() else {
+            let connection = Connection::open(self.url.clone()).await?;
+            self.connection = Some(connection);
+            return Ok(());
+        };
+        let next_block = connection.subscription.next().await;
+        match next_block {
+            Some(Ok(rpc_event)) => self.on_new_block(rpc_event).await,
+            Some(Err(err)) => Err(QueryError::General(err.to_string()).into()),
+            None => Err(QueryError::General(
+                "The websocket subscription was closed".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    /// Queue the events of a new block, after the events of any blocks
+    /// missed since the last seen height.
+    async fn on_new_block(&mut self, rpc_event: RpcEvent) -> Result<(), Error> {
+        let EventData::LegacyNewBlock {
+            block: Some(block),
+            result_end_block,
+            ..
+        } = rpc_event.data
+        else {
+            return Ok(());
+        };
+        let height = BlockHeight(block.header.height.value());
+
+        if let Some(last_height) = self.last_height {
+            if height <= last_height {
+                // The block has already been processed
+                return Ok(());
+            }
+            let mut missed = last_height.next_height();
+            while missed < height {
+                self.fetch_block_events(missed).await?;
+                missed = missed.next_height();
+            }
+        }
+
+        let events = result_end_block
+            .map(|end_block| end_block.events)
+            .unwrap_or_default();
+        self.queue_events(height, events);
+        Ok(())
+    }
+
+    /// Fetch the events of a block that was missed by the subscription.
+    async fn fetch_block_events(
+        &mut self,
+        height: BlockHeight,
+    ) -> Result<(), Error> {
+        let connection = self.connection.as_ref().ok_or_else(|| {
+            QueryError::General("Not connected to the node".to_string())
+        })?;
+        let events = Client::block_results(&connection.client, height.0)
+            .await
+            .map_err(|err| QueryError::General(err.to_string()))?
+            .end_block_events
+            .unwrap_or_default();
+        self.queue_events(height, events);
+        Ok(())
+    }
+
+    /// Queue the events of a block matching the filter, and mark the block
+    /// as seen.
+    fn queue_events(
+        &mut self,
+        height: BlockHeight,
+        events: Vec<namada_core::tendermint::abci::Event>,
+    ) {
+        self.pending
+            .extend(filter_events(&self.filter, height, events));
+        self.last_height = Some(height);
+    }
+}
+
+/// Convert the ABCI events of a block to Namada events, keeping the ones
+/// matching `filter`.
+fn filter_events(
+    filter: &EventFilter,
+    height: BlockHeight,
+    events: Vec<namada_core::tendermint::abci::Event>,
+) -> impl Iterator<Item = SubscribedEvent> + '_ {
+    events.into_iter().filter_map(move |event| {
+        let event = Event::try_from(event)
+            .inspect_err(|err| {
+                tracing::debug!(%err, %height, "Skipping a non-Namada event")
+            })
+            .ok()?;
+        filter
+            .matches(&event)
+            .then_some(SubscribedEvent { height, event })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address::testing::{established_address_1, nam};
+    use namada_events::extend::ComposeEvent;
+    use namada_token::event::TokenOperation;
+
+    use super::*;
+    use crate::events::EventLevel;
+    use crate::tx::event::types::APPLIED as APPLIED_TX;
+
+    const HASH: &str =
+        "DEADBEEFDEADBEEFDEADBEEFDEADBEEFDEADBEEFDEADBEEFDEADBEEFDEADBEEF";
+
+    fn transfer(source: Address, target: Address) -> Event {
+        TokenEvent {
+            level: EventLevel::Tx,
+            operation: TokenOperation::transfer(
+                UserAccount::Internal(source),
+                UserAccount::Internal(target),
+                nam(),
+                Uint::from(10_u64),
+                Uint::from(90_u64),
+                Some(Uint::from(10_u64)),
+            ),
+            descriptor: "transfer".into(),
+        }
+        .into()
+    }
+
+    /// Test that Namada events survive the conversion to and from ABCI
+    /// events.
+    #[test]
+    fn test_abci_event_roundtrip() {
+        let tx_hash: Hash = HASH.parse().unwrap();
+        let events = [
+            transfer(established_address_1(), nam()),
+            Event::new(APPLIED_TX, EventLevel::Tx)
+                .with(TxHashAttr(tx_hash))
+                .with(HeightAttr(BlockHeight(3)))
+                .into(),
+        ];
+        for event in events {
+            let abci_event: namada_core::tendermint::abci::Event =
+                event.clone().into();
+            assert_eq!(Event::try_from(abci_event).unwrap(), event);
+        }
+
+        // Events emitted by other modules are rejected
+        let abci_event = namada_core::tendermint::abci::Event {
+            kind: "message".to_string(),
+            attributes: vec![("module", "bank", true).into()],
+        };
+        assert!(Event::try_from(abci_event).is_err());
+    }
+
+    /// Test matching events with an [`EventFilter`].
+    #[test]
+    fn test_event_filter() {
+        let alice = established_address_1();
+        let incoming = transfer(nam(), alice.clone());
+        let outgoing = transfer(alice.clone(), nam());
+        let tx_hash: Hash = HASH.parse().unwrap();
+        let applied: Event = Event::new(APPLIED_TX, EventLevel::Tx)
+            .with(TxHashAttr(tx_hash))
+            .with(HeightAttr(BlockHeight(3)))
+            .into();
+
+        let filter = EventFilter::all();
+        assert!(filter.matches(&incoming));
+        assert!(filter.matches(&applied));
+
+        let filter = EventFilter::incoming_transfers(alice.clone());
+        assert!(filter.matches(&incoming));
+        assert!(!filter.matches(&outgoing));
+        assert!(!filter.matches(&applied));
+
+        let filter = EventFilter::token_events_of(alice.clone());
+        assert!(filter.matches(&incoming));
+        assert!(filter.matches(&outgoing));
+
+        let filter = EventFilter::of_event_type::<TokenEvent>()
+            .with_account(UserAccount::Internal(alice), AccountRole::Source);
+        assert!(!filter.matches(&incoming));
+        assert!(filter.matches(&outgoing));
+
+        let filter = EventFilter::with_event_type(APPLIED_TX)
+            .with_tx_hash(tx_hash)
+            .with_height(BlockHeight(3));
+        assert!(filter.matches(&applied));
+        assert!(!filter.matches(&incoming));
+        let filter = EventFilter::with_event_type(APPLIED_TX)
+            .with_height(BlockHeight(4));
+        assert!(!filter.matches(&applied));
+    }
+
+    /// Test that the events of a block are filtered and converted.
+    #[test]
+    fn test_filter_block_events() {
+        let alice = established_address_1();
+        let events: Vec<namada_core::tendermint::abci::Event> = vec![
+            transfer(nam(), alice.clone()).into(),
+            transfer(alice.clone(), nam()).into(),
+            namada_core::tendermint::abci::Event {
+                kind: "message".to_string(),
+                attributes: vec![],
+            },
+        ];
+        let filter = EventFilter::incoming_transfers(alice.clone());
+        let filtered: Vec<_> =
+            filter_events(&filter, BlockHeight(7), events).collect();
+        assert_eq!(
+            filtered,
+            vec![SubscribedEvent {
+                height: BlockHeight(7),
+                event: transfer(nam(), alice),
+            }]
+        );
+    }
+}
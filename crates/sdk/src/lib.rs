@@ -19,7 +19,7 @@ pub use {
     namada_gas as gas, namada_governance as governance, namada_ibc as ibc,
     namada_parameters as parameters, namada_proof_of_stake as proof_of_stake,
     namada_state as state, namada_storage as storage, namada_token as token,
-    tendermint_rpc, zeroize,
+    namada_tx_scheduler as tx_scheduler, tendermint_rpc, zeroize,
 };
 
 pub mod eth_bridge;
@@ -28,7 +28,9 @@ pub mod rpc;
 
 pub mod args;
 pub mod masp;
+pub mod multisig;
 pub mod signing;
+pub mod state_diff;
 #[allow(clippy::result_large_err)]
 pub mod tx;
 #[cfg(feature = "wasm-runtime")]
@@ -47,6 +49,7 @@ use std::str::FromStr;
 use args::{DeviceTransport, InputAmount, SdkTypes};
 use masp_primitives::zip32::PseudoExtendedKey;
 use namada_core::address::Address;
+use namada_core::chain::Epoch;
 use namada_core::dec::Dec;
 use namada_core::ethereum_events::EthAddress;
 use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
@@ -66,15 +69,16 @@ use signing::SigningTxData;
 use token::{DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tx::{
-    ProcessTxResponse, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
-    TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+    ProcessTxResponse, TX_AUTO_COMPOUND_WASM, TX_BECOME_VALIDATOR_WASM,
+    TX_BOND_WASM, TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
     TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
     TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
-    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
-    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
-    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
+    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_INIT_VESTING_ACCOUNT_WASM,
+    TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+    TX_REVEAL_PK, TX_SCHEDULE_WASM, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
     TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-    VP_USER_WASM,
+    VP_USER_WASM, VP_VESTING_WASM,
 };
 use wallet::{Wallet, WalletIo, WalletStorage};
 pub use {namada_io as io, namada_wallet as wallet};
@@ -127,6 +131,8 @@ pub trait Namada: NamadaIo {
         args::Tx {
             dry_run: false,
             dry_run_wrapper: false,
+            dry_run_trace: false,
+            dry_run_diff: false,
             dump_tx: false,
             dump_wrapper_tx: false,
             output_folder: None,
@@ -234,6 +240,50 @@ pub trait Namada: NamadaIo {
         }
     }
 
+    /// Make a InitVestingAccount builder from the given minimum set of
+    /// arguments
+    fn new_init_vesting_account(
+        &self,
+        public_keys: Vec<common::PublicKey>,
+        threshold: Option<u8>,
+        token: Address,
+        amount: InputAmount,
+        start: Epoch,
+        end: Epoch,
+    ) -> args::TxInitVestingAccount {
+        args::TxInitVestingAccount {
+            tx: self.tx_builder(),
+            vp_code_path: PathBuf::from(VP_VESTING_WASM),
+            tx_code_path: PathBuf::from(TX_INIT_VESTING_ACCOUNT_WASM),
+            public_keys,
+            threshold,
+            token,
+            amount,
+            start,
+            cliff: None,
+            end,
+        }
+    }
+
+    /// Make a ScheduleTx builder from the given minimum set of arguments
+    fn new_schedule_tx(
+        &self,
+        scheduled_tx: Vec<u8>,
+        scheduled_gas_limit: GasLimit,
+        fee_payer: Address,
+    ) -> args::ScheduleTx {
+        args::ScheduleTx {
+            tx: self.tx_builder(),
+            scheduled_tx,
+            scheduled_tx_signatures: vec![],
+            height: None,
+            epoch: None,
+            scheduled_gas_limit,
+            fee_payer,
+            tx_code_path: PathBuf::from(TX_SCHEDULE_WASM),
+        }
+    }
+
     /// Make a RevealPK builder from the given minimum set of arguments
     fn new_reveal_pk(&self, public_key: common::PublicKey) -> args::RevealPk {
         args::RevealPk {
@@ -330,6 +380,7 @@ pub trait Namada: NamadaIo {
             proposal_data,
             is_pgf_stewards: false,
             is_pgf_funding: false,
+            is_param_change: false,
             tx_code_path: PathBuf::from(TX_INIT_PROPOSAL),
             tx: self.tx_builder(),
         }
@@ -540,6 +591,17 @@ pub trait Namada: NamadaIo {
         }
     }
 
+    /// Make an Auto-compound builder from the given minimum set of arguments
+    fn new_auto_compound(&self, validator: Address) -> args::AutoCompound {
+        args::AutoCompound {
+            validator,
+            source: None,
+            disable: false,
+            tx_code_path: PathBuf::from(TX_AUTO_COMPOUND_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a Withdraw builder from the given minimum set of arguments
     fn new_add_erc20_transfer(
         &self,
@@ -706,6 +768,8 @@ where
             prototype: args::Tx {
                 dry_run: false,
                 dry_run_wrapper: false,
+                dry_run_trace: false,
+                dry_run_diff: false,
                 dump_tx: false,
                 dump_wrapper_tx: false,
                 output_folder: None,
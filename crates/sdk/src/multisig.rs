@@ -0,0 +1,454 @@
+//! Offline signing workflow for multisig established accounts.
+//!
+//! The inner tx of a multisig account has to be signed by at least
+//! `threshold` of the account's keys. The signers usually don't share a
+//! machine, so the tx is passed around in a [`MultisigTxBundle`] to which each
+//! of them adds their signatures. Once enough signatures have been collected,
+//! the bundle is checked against the account's on-chain keys and threshold and
+//! finalized into a wrapper tx that is ready to be signed by the fee payer and
+//! submitted.
+
+use std::io;
+
+use namada_account::{Account, AccountPublicKeysMap};
+use namada_core::address::Address;
+use namada_core::hash::Hash;
+use namada_core::key::*;
+use namada_tx::{Authorization, SignatureIndex, Signer, Tx};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, TxSubmitError};
+use crate::signing::{validate_fee, SigningTxData};
+use crate::tx::prepare_tx;
+use crate::{args, rpc, Namada};
+
+/// An inner tx of a multisig account together with the signatures collected
+/// for it so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigTxBundle {
+    /// The unsigned inner tx
+    pub tx: Tx,
+    /// The multisig account on behalf of which the tx is signed
+    pub owner: Address,
+    /// The public keys of the account at the time the bundle was created
+    pub public_keys_map: AccountPublicKeysMap,
+    /// The signature threshold of the account at the time the bundle was
+    /// created
+    pub threshold: u8,
+    /// The signatures collected so far, at most one per public key
+    pub signatures: Vec<SignatureIndex>,
+}
+
+impl MultisigTxBundle {
+    /// Create a new bundle with no signatures for the given tx of the given
+    /// account. The tx must not be wrapped yet, as the wrapper can only be
+    /// added once all the inner signatures have been collected.
+    pub fn new(tx: Tx, account: Account) -> Result<Self, Error> {
+        if tx.header.wrapper().is_some() {
+            return Err(Error::Other(
+                "A multisig bundle can only be created for a raw transaction, \
+                 typically produced by using the `--dump-tx` flag"
+                    .to_string(),
+            ));
+        }
+        Ok(Self {
+            tx,
+            owner: account.address,
+            public_keys_map: account.public_keys_map,
+            threshold: account.threshold,
+            signatures: vec![],
+        })
+    }
+
+    /// Serialize the bundle to pretty JSON into an I/O stream
+    pub fn to_writer_json<W>(&self, writer: W) -> serde_json::Result<()>
+    where
+        W: io::Write,
+    {
+        serde_json::to_writer_pretty(writer, self)
+    }
+
+    /// Try to parse a bundle from JSON string bytes
+    pub fn try_from_json_bytes(bytes: &[u8]) -> serde_json::Result<Self> {
+        serde_json::from_slice::<Self>(bytes)
+    }
+
+    /// The hash committed to by the signatures of the inner tx
+    pub fn signing_hash(&self) -> Hash {
+        // The signer of the section is not part of the signed commitment, so
+        // the same hash applies to signatures with and without an index
+        Authorization {
+            targets: vec![self.tx.raw_header_hash()],
+            signer: Signer::PubKeys(vec![]),
+            signatures: Default::default(),
+        }
+        .get_raw_hash()
+    }
+
+    /// Add a signature to the bundle. The signature must be made by one of the
+    /// account's keys over the raw header of the tx. A signature produced
+    /// without the account's address (e.g. with `sign-offline` and no owner) is
+    /// re-indexed under the account. Returns `false` if a signature by the
+    /// same key was already present, in which case it's replaced.
+    pub fn add_signature(
+        &mut self,
+        signature: SignatureIndex,
+    ) -> Result<bool, Error> {
+        let idx = self
+            .public_keys_map
+            .get_index_from_public_key(&signature.pubkey)
+            .ok_or_else(|| {
+                TxSubmitError::InvalidMultisigSignature(format!(
+                    "the public key {} is not a key of the account {}",
+                    signature.pubkey, self.owner
+                ))
+            })?;
+        if let Some((addr, sig_idx)) = &signature.index {
+            if *addr != self.owner || *sig_idx != idx {
+                return Err(TxSubmitError::InvalidMultisigSignature(format!(
+                    "the signature by {} is indexed as {} of {}, expected {} \
+                     of {}",
+                    signature.pubkey, sig_idx, addr, idx, self.owner
+                ))
+                .into());
+            }
+        }
+        common::SigScheme::verify_signature(
+            &signature.pubkey,
+            &self.signing_hash(),
+            &signature.signature,
+        )
+        .map_err(|err| {
+            TxSubmitError::InvalidMultisigSignature(format!(
+                "the signature by {} doesn't match the transaction: {err}",
+                signature.pubkey
+            ))
+        })?;
+
+        let signature = SignatureIndex {
+            index: Some((self.owner.clone(), idx)),
+            ..signature
+        };
+        let is_new = match self
+            .signatures
+            .iter_mut()
+            .find(|sig| sig.pubkey == signature.pubkey)
+        {
+            Some(existing) => {
+                *existing = signature;
+                false
+            }
+            None => {
+                self.signatures.push(signature);
+                true
+            }
+        };
+        self.signatures.sort();
+        Ok(is_new)
+    }
+
+    /// Sign the tx with the given secret keys, which must all belong to the
+    /// account, and add the signatures to the bundle
+    pub fn sign(
+        &mut self,
+        secret_keys: &[common::SecretKey],
+    ) -> Result<(), Error> {
+        if let Some(secret_key) = secret_keys.iter().find(|sk| {
+            self.public_keys_map
+                .get_index_from_public_key(&sk.to_public())
+                .is_none()
+        }) {
+            return Err(TxSubmitError::InvalidMultisigSignature(format!(
+                "the public key {} is not a key of the account {}",
+                secret_key.to_public(),
+                self.owner
+            ))
+            .into());
+        }
+        let signatures = self.tx.compute_section_signature(
+            secret_keys,
+            &self.public_keys_map,
+            Some(self.owner.clone()),
+        );
+        for signature in signatures {
+            self.add_signature(signature)?;
+        }
+        Ok(())
+    }
+
+    /// The number of signatures collected so far
+    pub fn signatures_count(&self) -> u8 {
+        u8::try_from(self.signatures.len())
+            .expect("Number of signatures must not exceed u8 capacity")
+    }
+
+    /// Check if the bundle has collected enough signatures for the threshold
+    /// known to the bundle
+    pub fn is_complete(&self) -> bool {
+        self.signatures_count() >= self.threshold
+    }
+
+    /// The public keys of the account that haven't signed the tx yet, sorted
+    /// by their index
+    pub fn missing_signers(&self) -> Vec<(u8, common::PublicKey)> {
+        let mut missing: Vec<_> = self
+            .public_keys_map
+            .idx_to_pk
+            .iter()
+            .filter(|(_, pk)| {
+                !self.signatures.iter().any(|sig| &sig.pubkey == *pk)
+            })
+            .map(|(idx, pk)| (*idx, pk.clone()))
+            .collect();
+        missing.sort_by_key(|(idx, _)| *idx);
+        missing
+    }
+
+    /// Check the bundle against the given state of the account. The keys of
+    /// the account must not have changed since the bundle was created and the
+    /// collected signatures must satisfy the account's threshold.
+    pub fn check_account(&self, account: &Account) -> Result<(), Error> {
+        if account.address != self.owner
+            || account.public_keys_map != self.public_keys_map
+        {
+            return Err(TxSubmitError::MultisigAccountMismatch(
+                self.owner.encode(),
+            )
+            .into());
+        }
+        let signatures_count = self.signatures_count();
+        if signatures_count < account.threshold {
+            return Err(TxSubmitError::MissingSigningKeys(
+                account.threshold,
+                signatures_count,
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Attach the collected signatures to the tx and verify them against the
+    /// given state of the account
+    pub fn signed_tx(&self, account: &Account) -> Result<Tx, Error> {
+        self.check_account(account)?;
+        let mut tx = self.tx.clone();
+        tx.add_signatures(self.signatures.clone());
+        tx.verify_signatures(
+            &[tx.raw_header_hash()],
+            account.public_keys_map.clone(),
+            &Some(account.address.clone()),
+            account.threshold,
+            || Ok(()),
+        )
+        .map_err(|err| {
+            TxSubmitError::InvalidMultisigSignature(err.to_string())
+        })?;
+        Ok(tx)
+    }
+}
+
+/// Query the current state of the bundle's account and check the bundle
+/// against it
+pub async fn check_bundle(
+    context: &impl Namada,
+    bundle: &MultisigTxBundle,
+) -> Result<Account, Error> {
+    let account = rpc::get_account_info(context.client(), &bundle.owner)
+        .await?
+        .ok_or_else(|| TxSubmitError::InvalidAccount(bundle.owner.encode()))?;
+    bundle.check_account(&account)?;
+    Ok(account)
+}
+
+/// Check the bundle against the on-chain account and finalize it into a
+/// wrapper tx. The returned signing data only requires the wrapper to be
+/// signed by the fee payer.
+pub async fn finalize_bundle(
+    context: &impl Namada,
+    args: &args::Tx,
+    bundle: &MultisigTxBundle,
+) -> Result<(Tx, SigningTxData), Error> {
+    if !args.signatures.is_empty() {
+        return Err(Error::Other(
+            "The inner signatures of a multisig bundle must be added to the \
+             bundle itself"
+                .to_string(),
+        ));
+    }
+    let account = check_bundle(context, bundle).await?;
+    let mut tx = bundle.signed_tx(&account)?;
+
+    let fee_payer = match &args.wrapper_fee_payer {
+        Some(fee_payer) => fee_payer.clone(),
+        None => args
+            .signing_keys
+            .first()
+            .cloned()
+            .ok_or(TxSubmitError::InvalidFeePayer)?,
+    };
+    let fee_amount = validate_fee(context, args).await?;
+    prepare_tx(args, &mut tx, fee_amount, fee_payer.clone()).await?;
+
+    // The inner signatures have already been verified against the on-chain
+    // threshold, so only the wrapper is left to be signed
+    let signing_data = SigningTxData {
+        owner: Some(bundle.owner.clone()),
+        public_keys: vec![],
+        threshold: 0,
+        account_public_keys_map: None,
+        fee_payer,
+        shielded_hash: None,
+    };
+    Ok((tx, signing_data))
+}
+
+#[cfg(test)]
+mod test {
+    use assert_matches::assert_matches;
+    use namada_core::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_core::chain::ChainId;
+    use namada_core::key::testing::{
+        keypair_1, keypair_2, keypair_3, keypair_4,
+    };
+
+    use super::*;
+
+    fn multisig_account() -> Account {
+        Account {
+            public_keys_map: AccountPublicKeysMap::from_iter([
+                keypair_1().to_public(),
+                keypair_2().to_public(),
+                keypair_3().to_public(),
+            ]),
+            threshold: 2,
+            address: established_address_1(),
+        }
+    }
+
+    fn new_bundle() -> MultisigTxBundle {
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None).add_data(vec![1, 2, 3]);
+        MultisigTxBundle::new(tx, multisig_account()).unwrap()
+    }
+
+    #[test]
+    fn test_multisig_bundle_incremental_signing() {
+        let account = multisig_account();
+        let mut bundle = new_bundle();
+        assert!(!bundle.is_complete());
+        assert_eq!(bundle.missing_signers().len(), 3);
+
+        // The first signer signs with their key
+        bundle.sign(&[keypair_2()]).unwrap();
+        assert_eq!(bundle.signatures_count(), 1);
+        assert!(!bundle.is_complete());
+        assert_matches!(
+            bundle.signed_tx(&account),
+            Err(Error::Tx(TxSubmitError::MissingSigningKeys(2, 1)))
+        );
+        // Signing again with the same key doesn't add a signature
+        bundle.sign(&[keypair_2()]).unwrap();
+        assert_eq!(bundle.signatures_count(), 1);
+
+        // The second signer signs offline without the account address
+        let offline_sig = bundle
+            .tx
+            .compute_section_signature(
+                &[keypair_1()],
+                &AccountPublicKeysMap::from_iter([keypair_1().to_public()]),
+                None,
+            )
+            .pop()
+            .unwrap();
+        assert!(bundle.add_signature(offline_sig).unwrap());
+        assert!(bundle.is_complete());
+        assert_eq!(
+            bundle.missing_signers(),
+            vec![(2, keypair_3().to_public())]
+        );
+
+        // The bundle survives a JSON round-trip
+        let mut bytes = vec![];
+        bundle.to_writer_json(&mut bytes).unwrap();
+        let bundle = MultisigTxBundle::try_from_json_bytes(&bytes).unwrap();
+
+        let tx = bundle.signed_tx(&account).unwrap();
+        assert!(
+            tx.verify_signatures(
+                &[tx.raw_header_hash()],
+                account.public_keys_map.clone(),
+                &Some(account.address.clone()),
+                account.threshold,
+                || Ok(()),
+            )
+            .is_ok()
+        );
+
+        // A raised on-chain threshold is not satisfied anymore
+        let raised = Account {
+            threshold: 3,
+            ..account.clone()
+        };
+        assert_matches!(
+            bundle.signed_tx(&raised),
+            Err(Error::Tx(TxSubmitError::MissingSigningKeys(3, 2)))
+        );
+        // Neither is an account whose keys have been updated
+        let updated = Account {
+            public_keys_map: AccountPublicKeysMap::from_iter([
+                keypair_1().to_public(),
+                keypair_2().to_public(),
+            ]),
+            ..account
+        };
+        assert_matches!(
+            bundle.signed_tx(&updated),
+            Err(Error::Tx(TxSubmitError::MultisigAccountMismatch(_)))
+        );
+    }
+
+    #[test]
+    fn test_multisig_bundle_rejects_invalid_signatures() {
+        let mut bundle = new_bundle();
+
+        // A key that doesn't belong to the account
+        assert_matches!(
+            bundle.sign(&[keypair_4()]),
+            Err(Error::Tx(TxSubmitError::InvalidMultisigSignature(_)))
+        );
+
+        // A signature over a different tx
+        let mut other_tx = Tx::new(ChainId::default(), None);
+        other_tx.add_code(vec![], None).add_data(vec![4, 5, 6]);
+        let sig = other_tx
+            .compute_section_signature(
+                &[keypair_1()],
+                &bundle.public_keys_map,
+                Some(bundle.owner.clone()),
+            )
+            .pop()
+            .unwrap();
+        assert_matches!(
+            bundle.add_signature(sig),
+            Err(Error::Tx(TxSubmitError::InvalidMultisigSignature(_)))
+        );
+
+        // A signature indexed under another account
+        let sig = bundle
+            .tx
+            .compute_section_signature(
+                &[keypair_1()],
+                &bundle.public_keys_map,
+                Some(established_address_2()),
+            )
+            .pop()
+            .unwrap();
+        assert_matches!(
+            bundle.add_signature(sig),
+            Err(Error::Tx(TxSubmitError::InvalidMultisigSignature(_)))
+        );
+        assert_eq!(bundle.signatures_count(), 0);
+    }
+}
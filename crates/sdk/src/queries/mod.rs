@@ -1,8 +1,12 @@
 //! Ledger read-only queries can be handled and dispatched via the [`RPC`]
 //! defined via `router!` macro.
 
+use namada_core::arith::checked;
+use namada_core::chain::BlockHeight;
+use namada_core::hints;
 // Re-export to show in rustdoc!
-use namada_state::{DBIter, StorageHasher, DB};
+use namada_state::{DBIter, DbError, StorageHasher, DB};
+use namada_storage::ResultExt;
 pub use shell::Shell;
 use shell::SHELL;
 pub use types::{
@@ -69,6 +73,60 @@ where
     Ok(())
 }
 
+/// For queries that can read the state at a past height, find the queried
+/// block height (`0` means the latest committed height) and check that the
+/// state at this height can still be read, i.e. it's within the configured
+/// `storage_read_past_height_limit` and it hasn't been pruned.
+pub fn require_readable_height<D, H, V, T>(
+    ctx: &RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+) -> namada_storage::Result<BlockHeight>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let last_committed_height = ctx.state.in_mem().get_last_block_height();
+    let queried_height = {
+        let height: BlockHeight = request.height.into();
+        let is_last_height_query = height.0 == 0;
+
+        if hints::likely(is_last_height_query) {
+            last_committed_height
+        } else {
+            height
+        }
+    };
+
+    if let Some(past_height_limit) = ctx.storage_read_past_height_limit {
+        if checked!(queried_height + past_height_limit)? < last_committed_height
+        {
+            return Err(namada_storage::Error::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Cannot query more than {past_height_limit} blocks in the \
+                     past (configured via \
+                     `shell.storage_read_past_height_limit`)."
+                ),
+            )));
+        }
+    }
+
+    if queried_height < last_committed_height {
+        if let Some(pruned_height) =
+            ctx.state.db().read_pruned_height().into_storage_result()?
+        {
+            if queried_height <= pruned_height {
+                return Err(namada_storage::Error::new(DbError::Pruned {
+                    height: queried_height,
+                    oldest_height: pruned_height.next_height(),
+                }));
+            }
+        }
+    }
+
+    Ok(queried_height)
+}
+
 /// For queries that do not support proofs, check that proof is not requested,
 /// otherwise return an error.
 pub fn require_no_proof(request: &RequestQuery) -> namada_storage::Result<()> {
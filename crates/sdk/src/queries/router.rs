@@ -68,6 +68,52 @@ macro_rules! handle_match {
         return Ok(result);
     };
 
+    // Handler function that reads the state via `trait StorageRead` and as such
+    // can be invoked at the latest or a past block height (`at_height`)
+    (
+        $ctx:ident, $request:ident, $start:ident, $end:ident,
+        (at_height $handle:tt), ( $( $matched_args:ident, )* ),
+    ) => {
+        // check that we're at the end of the path - trailing slash is optional
+        if !($end == $request.path.len() ||
+            // ignore trailing slashes
+            $end == $request.path.len() - 1 && &$request.path[$end..] == "/") {
+                // we're not at the end, no match
+                break
+        }
+        // Check that the request is not sent with unsupported non-default
+        $crate::queries::require_no_proof($request)?;
+        $crate::queries::require_no_data($request)?;
+
+        let last_committed_height = $ctx.state.in_mem().get_last_block_height();
+        let queried_height =
+            $crate::queries::require_readable_height(&$ctx, $request)?;
+        let data = if queried_height < last_committed_height {
+            // Read from the state as it was committed at the queried height
+            let historical = namada_state::HistoricalState::new(
+                $ctx.state,
+                queried_height,
+            )?;
+            $handle(&historical, $( $matched_args ),* )?
+        } else if queried_height == last_committed_height {
+            $handle($ctx.state, $( $matched_args ),* )?
+        } else {
+            return Err(namada_storage::Error::new_alloc(format!(
+                "Cannot query the state at the height {queried_height} \
+                 that is greater than the last committed height \
+                 {last_committed_height}"
+            )));
+        };
+        // Encode the returned data with borsh
+        let data = borsh::to_vec(&data).into_storage_result()?;
+        return Ok($crate::queries::EncodedResponseQuery {
+            data,
+            info: Default::default(),
+            proof: None,
+            height: queried_height,
+        });
+    };
+
     // Handler function that doesn't use the request, just the path args, if any
     (
         $ctx:ident, $request:ident, $start:ident, $end:ident,
@@ -272,6 +318,35 @@ macro_rules! try_match_segments {
             ( $( $matched_args, )* $arg, ), () );
     };
 
+    // The same special case as above for an `at_height` handler, where we try
+    // to match the rest of the path till the end.
+    //
+    // Try to match and parse a typed argument, declares the expected $arg into
+    // type $t, if it can be parsed
+    (
+        $ctx:ident, $request:ident, $start:ident, $end:ident,
+        (at_height $handle:ident),
+        ( $( $matched_args:ident, )* ),
+        (
+            [$arg:ident : $arg_ty:ty]
+        )
+    ) => {
+        let $arg: $arg_ty;
+        $end = $request.path.len();
+        match $request.path[$start..$end].parse::<$arg_ty>() {
+            Ok(parsed) => $arg = parsed,
+            #[allow(unreachable_patterns)] // sometimes the result is infallible
+            Err(_) =>
+            {
+                // If arg cannot be parsed, try to skip to next pattern
+                break
+            }
+        }
+        // Invoke the terminal pattern
+        try_match_segments!($ctx, $request, $start, $end, (at_height $handle),
+            ( $( $matched_args, )* $arg, ), () );
+    };
+
     // Try to match and parse a typed argument, declares the expected $arg into
     // type $t, if it can be parsed
     (
@@ -486,6 +561,75 @@ macro_rules! pattern_and_handler_to_method {
         }
     };
 
+    // terminal rule for $handle that can be queried at a past height
+    // (`at_height`)
+    (
+        ( $( $param:tt: $param_ty:ty ),* )
+        [ $( { $prefix:expr } ),* ]
+        $return_type:path,
+        (at_height $handle:tt),
+        ()
+    ) => {
+        // paste! used to construct the `fn $handle_path`'s name.
+        paste::paste! {
+            #[allow(dead_code)]
+            #[doc = "Get a path to query `" $handle "`."]
+            pub fn [<$handle _path>](&self, $( $param: &$param_ty ),* ) -> String {
+                itertools::join(
+                    [ Some(std::borrow::Cow::from(&self.prefix)), $( $prefix ),* ]
+                    .into_iter()
+                    .filter_map(|x| x), "/")
+            }
+
+            #[allow(dead_code)]
+            #[allow(clippy::too_many_arguments)]
+            #[doc = "Request a simple borsh-encoded value from `" $handle "`, \
+                without any additional request data, specified block height or \
+                proof."]
+            pub async fn $handle<CLIENT>(&self, client: &CLIENT,
+                $( $param: &$param_ty ),*
+            )
+                -> std::result::Result<
+                    $return_type,
+                    <CLIENT as namada_io::Client>::Error
+                >
+                where CLIENT: namada_io::Client + std::marker::Sync {
+                    let path = self.[<$handle _path>]( $( $param ),* );
+
+                    let data = client.simple_request(path).await?;
+
+                    let decoded: $return_type =
+                        borsh::BorshDeserialize::try_from_slice(&data[..])?;
+                    Ok(decoded)
+            }
+
+            #[allow(dead_code)]
+            #[allow(clippy::too_many_arguments)]
+            #[doc = "Request a borsh-encoded value from `" $handle "` as it \
+                was at the given block height, or at the latest committed \
+                height when `None`."]
+            pub async fn [<$handle _at_height>]<CLIENT>(&self, client: &CLIENT,
+                height: Option<namada_core::chain::BlockHeight>,
+                $( $param: &$param_ty ),*
+            )
+                -> std::result::Result<
+                    $return_type,
+                    <CLIENT as namada_io::Client>::Error
+                >
+                where CLIENT: namada_io::Client + std::marker::Sync {
+                    let path = self.[<$handle _path>]( $( $param ),* );
+
+                    let $crate::queries::ResponseQuery {
+                        data, ..
+                    } = client.request(path, None, height, false).await?;
+
+                    let decoded: $return_type =
+                        borsh::BorshDeserialize::try_from_slice(&data[..])?;
+                    Ok(decoded)
+            }
+        }
+    };
+
     // terminal rule that $handle that doesn't use request
     (
         ( $( $param:tt: $param_ty:ty ),* )
@@ -761,6 +905,11 @@ macro_rules! router_type {
 ///   // necessary), which can have some `info` string and a proof.
 ///   ( "pattern_e" ) -> ReturnType = (with_options handler),
 ///
+///   // The handler reads the state via `trait StorageRead` and can be
+///   // queried at the latest or a past block height, if its state is still
+///   // available.
+///   ( "pattern_f" ) -> ReturnType = (at_height handler),
+///
 ///   ( "another" / "pattern" / "that" / "goes" / "deep" ) -> ReturnType = handler,
 ///
 ///   // Inlined sub-tree
@@ -796,6 +945,16 @@ macro_rules! router_type {
 ///     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
 ///     H: 'static + StorageHasher + Sync;
 /// ```
+///
+/// A handler defined as `(at_height $handler)` only has access to the state
+/// (at the latest height or at the height specified in the request) and the
+/// expected signature is:
+/// ```rust,ignore
+/// fn handler<S>(storage: &S, args ...)
+///   -> namada_storage::Result<ReturnType>
+/// where
+///     S: StorageRead;
+/// ```
 #[macro_export]
 macro_rules! router {
     { $name:ident, $( $pattern:tt $( -> $return_type:path )? = $handle:tt , )* } => (
@@ -857,8 +1016,9 @@ macro_rules! router {
 #[cfg(test)]
 mod test_rpc_handlers {
     use namada_core::chain::Epoch;
-    use namada_core::token;
+    use namada_core::{storage, token};
     use namada_state::{DBIter, StorageHasher, DB};
+    use namada_storage::StorageRead;
 
     use crate::borsh::BorshSerializeExt;
     use crate::queries::{
@@ -976,6 +1136,21 @@ mod test_rpc_handlers {
             ..ResponseQuery::default()
         })
     }
+
+    /// A storage key read by the `d` handler
+    pub fn d_key() -> storage::Key {
+        storage::Key::parse("d").unwrap()
+    }
+
+    /// This handler is hand-written, because the test helper macro doesn't
+    /// support handlers with `at_height`.
+    pub fn d<S>(storage: &S) -> namada_storage::Result<String>
+    where
+        S: StorageRead,
+    {
+        let value: Option<u64> = storage.read(&d_key())?;
+        Ok(format!("d/{}", value.unwrap_or_default()))
+    }
 }
 
 /// You can expand the `router!` macro invocation with e.g.:
@@ -1011,6 +1186,7 @@ mod test_rpc {
             },
         },
         ( "c" ) -> String = (with_options c),
+        ( "d" ) -> String = (at_height d),
     }
 
     router! {TEST_SUB_RPC,
@@ -1022,12 +1198,14 @@ mod test_rpc {
 
 #[cfg(test)]
 mod test {
-    use namada_core::chain::Epoch;
+    use namada_core::chain::{BlockHeight, Epoch};
     use namada_core::tendermint::block;
     use namada_core::token;
     use namada_core::token::NATIVE_MAX_DECIMAL_PLACES;
+    use namada_storage::StorageWrite;
 
     use super::test_rpc::TEST_RPC;
+    use super::test_rpc_handlers::d_key;
     use crate::queries::testing::TestClient;
     use crate::queries::{RequestCtx, RequestQuery, Router};
 
@@ -1175,6 +1353,52 @@ mod test {
         let result = TEST_RPC.test_sub_rpc().z(&client, arg).await.unwrap();
         assert_eq!(result, format!("z/{arg}"));
 
+        let result = TEST_RPC.d(&client).await.unwrap();
+        assert_eq!(result, format!("d/0"));
+
         Ok(())
     }
+
+    /// Test that the `at_height` handlers can be queried at past heights.
+    #[tokio::test]
+    async fn test_router_macro_at_height() {
+        let mut client = TestClient::new(TEST_RPC);
+
+        // Commit a few blocks, each with a different value of the key read by
+        // the handler
+        client.state.in_mem_mut().block.height = BlockHeight(1);
+        for value in 1..=3_u64 {
+            client.state.write(&d_key(), value).unwrap();
+            client.state.commit_block().unwrap();
+            client.state.in_mem_mut().block.height =
+                client.state.in_mem().block.height.next_height();
+        }
+
+        // The latest height
+        let result = TEST_RPC.d(&client).await.unwrap();
+        assert_eq!(result, format!("d/3"));
+        let result = TEST_RPC.d_at_height(&client, None).await.unwrap();
+        assert_eq!(result, format!("d/3"));
+        let result = TEST_RPC
+            .d_at_height(&client, Some(BlockHeight(3)))
+            .await
+            .unwrap();
+        assert_eq!(result, format!("d/3"));
+
+        // Past heights
+        let result = TEST_RPC
+            .d_at_height(&client, Some(BlockHeight(1)))
+            .await
+            .unwrap();
+        assert_eq!(result, format!("d/1"));
+        let result = TEST_RPC
+            .d_at_height(&client, Some(BlockHeight(2)))
+            .await
+            .unwrap();
+        assert_eq!(result, format!("d/2"));
+
+        // A height that hasn't been committed yet
+        let result = TEST_RPC.d_at_height(&client, Some(BlockHeight(4))).await;
+        assert!(result.is_err());
+    }
 }
@@ -8,7 +8,6 @@ use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
 use namada_account::{Account, AccountPublicKeysMap};
 use namada_core::address::Address;
-use namada_core::arith::checked;
 use namada_core::chain::{BlockHeader, BlockHeight, Epoch};
 use namada_core::dec::Dec;
 use namada_core::hash::Hash;
@@ -23,6 +22,8 @@ use namada_state::{DBIter, LastBlock, StateRead, StorageHasher, DB};
 use namada_storage::{ResultExt, StorageRead};
 use namada_token::masp::MaspTokenRewardData;
 use namada_token::storage_key::masp_token_map_key;
+use namada_tx::data::diff::DiffedDryRunResult;
+use namada_tx::data::trace::TracedDryRunResult;
 use namada_tx::data::DryRunResult;
 
 use self::eth_bridge::{EthBridge, ETH_BRIDGE};
@@ -33,7 +34,9 @@ use crate::ibc::core::host::types::identifiers::{
     ChannelId, ClientId, PortId, Sequence,
 };
 use crate::queries::types::{RequestCtx, RequestQuery};
-use crate::queries::{require_latest_height, EncodedResponseQuery};
+use crate::queries::{
+    require_latest_height, require_readable_height, EncodedResponseQuery,
+};
 use crate::tendermint::merkle::proof::ProofOps;
 
 type ConversionWithoutPath = (
@@ -84,6 +87,12 @@ router! {SHELL,
     // Dry run a transaction
     ( "dry_run_tx" ) -> DryRunResult = (with_options dry_run_tx),
 
+    // Dry run a transaction and trace its WASM executions
+    ( "dry_run_tx_with_trace" ) -> TracedDryRunResult = (with_options dry_run_tx_with_trace),
+
+    // Dry run a transaction and return the changes it would apply
+    ( "dry_run_tx_with_diff" ) -> DiffedDryRunResult = (with_options dry_run_tx_with_diff),
+
     // Raw storage access - prefix iterator
     ( "prefix" / [storage_key: storage::Key] )
         -> Vec<PrefixValue> = (with_options storage_prefix),
@@ -124,6 +133,9 @@ router! {SHELL,
 
     // Return an estimate of the maximum time taken to decide a block
     ( "max_block_time" ) -> DurationSecs = max_block_time,
+
+    // The largest size in bytes of a wrapper tx admitted to the mempool
+    ( "max_wrapper_tx_bytes" ) -> u64 = max_wrapper_tx_bytes,
 }
 
 // Handlers:
@@ -139,6 +151,28 @@ where
     unimplemented!("Dry running tx requires \"wasm-runtime\" feature.")
 }
 
+fn dry_run_tx_with_trace<D, H, V, T>(
+    _ctx: RequestCtx<'_, D, H, V, T>,
+    _request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    unimplemented!("Dry running tx requires \"wasm-runtime\" feature.")
+}
+
+fn dry_run_tx_with_diff<D, H, V, T>(
+    _ctx: RequestCtx<'_, D, H, V, T>,
+    _request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    unimplemented!("Dry running tx requires \"wasm-runtime\" feature.")
+}
+
 /// Return an estimate of the maximum time taken to decide a block
 fn max_block_time<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -157,6 +191,16 @@ where
     )
 }
 
+fn max_wrapper_tx_bytes<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<u64>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_parameters::max_wrapper_tx_bytes(ctx.state)
+}
+
 /// Get the block header associated with the requested height
 fn block_header<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -438,31 +482,7 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
-    let last_committed_height = ctx.state.in_mem().get_last_block_height();
-    let queried_height = {
-        let height: BlockHeight = request.height.into();
-        let is_last_height_query = height.0 == 0;
-
-        if hints::likely(is_last_height_query) {
-            last_committed_height
-        } else {
-            height
-        }
-    };
-
-    if let Some(past_height_limit) = ctx.storage_read_past_height_limit {
-        if checked!(queried_height + past_height_limit)? < last_committed_height
-        {
-            return Err(namada_storage::Error::new(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                format!(
-                    "Cannot query more than {past_height_limit} blocks in the \
-                     past (configured via \
-                     `shell.storage_read_past_height_limit`)."
-                ),
-            )));
-        }
-    }
+    let queried_height = require_readable_height(&ctx, request)?;
 
     match ctx
         .state
@@ -681,10 +701,19 @@ mod test {
         let path = RPC.shell().dry_run_tx_path();
         assert_eq!("/shell/dry_run_tx", path);
 
+        let path = RPC.shell().dry_run_tx_with_trace_path();
+        assert_eq!("/shell/dry_run_tx_with_trace", path);
+
+        let path = RPC.shell().dry_run_tx_with_diff_path();
+        assert_eq!("/shell/dry_run_tx_with_diff", path);
+
         let path = RPC.shell().storage_prefix_path(&key);
         assert_eq!(format!("/shell/prefix/{}", key), path);
 
         let path = RPC.shell().storage_has_key_path(&key);
         assert_eq!(format!("/shell/has_key/{}", key), path);
+
+        let path = RPC.shell().max_wrapper_tx_bytes_path();
+        assert_eq!("/shell/max_wrapper_tx_bytes", path);
     }
 }
@@ -33,10 +33,12 @@ use namada_proof_of_stake::types::{
     LivenessInfo, Slash, ValidatorLiveness, ValidatorMetaData,
     WeightedValidator,
 };
-use namada_proof_of_stake::{bond_amount, query_reward_tokens};
+use namada_proof_of_stake::{
+    bond_amount, is_auto_compounding, query_reward_tokens,
+};
 use namada_state::{DBIter, KeySeg, StorageHasher, DB};
 use namada_storage::collections::lazy_map;
-use namada_storage::OptionExt;
+use namada_storage::{OptionExt, StorageRead};
 
 use crate::governance;
 use crate::queries::types::RequestCtx;
@@ -44,53 +46,55 @@ use crate::queries::types::RequestCtx;
 // PoS validity predicate queries
 router! {POS,
     ( "validator" ) = {
-        ( "is_validator" / [addr: Address] ) -> bool = is_validator,
+        ( "is_validator" / [addr: Address] ) -> bool = (at_height is_validator),
 
-        ( "consensus_key" / [addr: Address] ) -> Option<common::PublicKey> = consensus_key,
+        ( "consensus_key" / [addr: Address] ) -> Option<common::PublicKey> = (at_height consensus_key),
 
         ( "addresses" / [epoch: opt Epoch] )
-            -> HashSet<Address> = validator_addresses,
+            -> HashSet<Address> = (at_height validator_addresses),
 
-        ( "liveness_info" ) -> LivenessInfo = liveness_info,
+        ( "liveness_info" ) -> LivenessInfo = (at_height liveness_info),
 
         ( "stake" / [validator: Address] / [epoch: opt Epoch] )
-            -> Option<token::Amount> = validator_stake,
+            -> Option<token::Amount> = (at_height validator_stake),
 
         ( "slashes" / [validator: Address] )
-            -> Vec<Slash> = validator_slashes,
+            -> Vec<Slash> = (at_height validator_slashes),
 
         ( "commission" / [validator: Address] / [epoch: opt Epoch] )
-            -> CommissionPair = validator_commission,
+            -> CommissionPair = (at_height validator_commission),
 
         ( "metadata" / [validator: Address] )
-            -> Option<ValidatorMetaData> = validator_metadata,
+            -> Option<ValidatorMetaData> = (at_height validator_metadata),
 
         ( "state" / [validator: Address] / [epoch: opt Epoch] )
-            -> ValidatorStateInfo = validator_state,
+            -> ValidatorStateInfo = (at_height validator_state),
 
         ( "incoming_redelegation" / [src_validator: Address] / [delegator: Address] )
-            -> Option<Epoch> = validator_incoming_redelegation,
+            -> Option<Epoch> = (at_height validator_incoming_redelegation),
 
         ( "last_infraction_epoch" / [validator: Address] )
-            -> Option<Epoch> = validator_last_infraction_epoch,
+            -> Option<Epoch> = (at_height validator_last_infraction_epoch),
     },
 
     ( "validator_set" ) = {
         ( "consensus" / [epoch: opt Epoch] )
-            -> BTreeSet<WeightedValidator> = consensus_validator_set,
+            -> BTreeSet<WeightedValidator> = (at_height consensus_validator_set),
 
         ( "below_capacity" / [epoch: opt Epoch] )
-            -> BTreeSet<WeightedValidator> = below_capacity_validator_set,
+            -> BTreeSet<WeightedValidator> = (at_height below_capacity_validator_set),
     },
 
-    ( "pos_params") -> PosParams = pos_params,
+    ( "pos_params") -> PosParams = (at_height pos_params),
 
     ( "total_stake" / [epoch: opt Epoch] )
-        -> token::Amount = total_stake,
+        -> token::Amount = (at_height total_stake),
 
     ( "total_active_voting_power" / [epoch: opt Epoch] )
-        -> token::Amount = total_active_voting_power,
+        -> token::Amount = (at_height total_active_voting_power),
 
+    // NOTE: The delegation targets are not stored with diffs, so these can
+    // only be queried at the latest height
     ( "delegations" / [owner: Address] / [epoch: opt Epoch] )
         -> HashSet<Address> = delegation_validators,
 
@@ -98,47 +102,50 @@ router! {POS,
         -> HashMap<Address, token::Amount> = delegations,
 
     ( "bond_deltas" / [source: Address] / [validator: Address] )
-        -> HashMap<Epoch, token::Change> = bond_deltas,
+        -> HashMap<Epoch, token::Change> = (at_height bond_deltas),
 
     ( "bond" / [source: Address] / [validator: Address] / [epoch: opt Epoch] )
-        -> token::Amount = bond,
+        -> token::Amount = (at_height bond),
 
     ( "rewards" / [validator: Address] / [source: opt Address] )
-        -> token::Amount = rewards,
+        -> token::Amount = (at_height rewards),
+
+    ( "auto_compound" / [validator: Address] / [source: opt Address] )
+        -> bool = (at_height auto_compound),
 
     ( "bond_with_slashing" / [source: Address] / [validator: Address] / [epoch: opt Epoch] )
-        -> token::Amount = bond_with_slashing,
+        -> token::Amount = (at_height bond_with_slashing),
 
     ( "unbond" / [source: Address] / [validator: Address] )
-        -> HashMap<(Epoch, Epoch), token::Amount> = unbond,
+        -> HashMap<(Epoch, Epoch), token::Amount> = (at_height unbond),
 
     ( "unbond_with_slashing" / [source: Address] / [validator: Address] )
-        -> HashMap<(Epoch, Epoch), token::Amount> = unbond_with_slashing,
+        -> HashMap<(Epoch, Epoch), token::Amount> = (at_height unbond_with_slashing),
 
     ( "withdrawable_tokens" / [source: Address] / [validator: Address] / [epoch: opt Epoch] )
-        -> token::Amount = withdrawable_tokens,
+        -> token::Amount = (at_height withdrawable_tokens),
 
     // NOTE: The literal "to" between source and validator is needed because
     // they are both optional and have the same types so when only one is
     // specified, without the  separator it wouldn't be clear which one (and
     // would always parse as `source`)
     ( "bonds_and_unbonds" / [source: opt Address] / "to" / [validator: opt Address] )
-        -> BondsAndUnbondsDetails = bonds_and_unbonds,
+        -> BondsAndUnbondsDetails = (at_height bonds_and_unbonds),
 
     ( "enqueued_slashes" )
-        -> HashMap<Address, BTreeMap<Epoch, Vec<Slash>>> = enqueued_slashes,
+        -> HashMap<Address, BTreeMap<Epoch, Vec<Slash>>> = (at_height enqueued_slashes),
 
-    ( "all_slashes" ) -> HashMap<Address, Vec<Slash>> = slashes,
+    ( "all_slashes" ) -> HashMap<Address, Vec<Slash>> = (at_height slashes),
 
-    ( "is_delegator" / [addr: Address ] / [epoch: opt Epoch] ) -> bool = is_delegator,
+    ( "is_delegator" / [addr: Address ] / [epoch: opt Epoch] ) -> bool = (at_height is_delegator),
 
     ( "validator_by_tm_addr" / [tm_addr: String] )
-        -> Option<Address> = validator_by_tm_addr,
+        -> Option<Address> = (at_height validator_by_tm_addr),
 
-    ( "consensus_keys" ) -> BTreeSet<common::PublicKey> = consensus_key_set,
+    ( "consensus_keys" ) -> BTreeSet<common::PublicKey> = (at_height consensus_key_set),
 
     ( "has_bonds" / [source: Address] )
-        -> bool = has_bonds,
+        -> bool = (at_height has_bonds),
 
 }
 
@@ -185,94 +192,81 @@ impl<T> Enriched<T> {
 // Handlers that implement the functions via `trait StorageRead`:
 
 /// Get the PoS parameters
-fn pos_params<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
-) -> namada_storage::Result<PosParams>
+fn pos_params<S>(storage: &S) -> namada_storage::Result<PosParams>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    read_pos_params::<_, governance::Store<_>>(ctx.state)
+    read_pos_params::<_, governance::Store<_>>(storage)
 }
 
 /// Find if the given address belongs to a validator account.
-fn is_validator<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
-    addr: Address,
-) -> namada_storage::Result<bool>
+fn is_validator<S>(storage: &S, addr: Address) -> namada_storage::Result<bool>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    namada_proof_of_stake::is_validator(ctx.state, &addr)
+    namada_proof_of_stake::is_validator(storage, &addr)
 }
 
 /// Find a consensus key of a validator account.
-fn consensus_key<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn consensus_key<S>(
+    storage: &S,
     addr: Address,
 ) -> namada_storage::Result<Option<common::PublicKey>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let current_epoch = ctx.state.in_mem().last_epoch;
+    let current_epoch = storage.get_block_epoch()?;
     namada_proof_of_stake::storage::get_consensus_key::<_, governance::Store<_>>(
-        ctx.state,
+        storage,
         &addr,
         current_epoch,
     )
 }
 
 /// Find if the given address is a delegator
-fn is_delegator<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn is_delegator<S>(
+    storage: &S,
     addr: Address,
     epoch: Option<Epoch>,
 ) -> namada_storage::Result<bool>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    namada_proof_of_stake::is_delegator(ctx.state, &addr, epoch)
+    namada_proof_of_stake::is_delegator(storage, &addr, epoch)
 }
 
 /// Get all the validator known addresses. These validators may be in any state,
 /// e.g. consensus, below-capacity, inactive or jailed.
-fn validator_addresses<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn validator_addresses<S>(
+    storage: &S,
     epoch: Option<Epoch>,
 ) -> namada_storage::Result<HashSet<Address>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let epoch = epoch.unwrap_or(ctx.state.in_mem().last_epoch);
-    read_all_validator_addresses(ctx.state, epoch)
+    let epoch = epoch.unwrap_or(storage.get_block_epoch()?);
+    read_all_validator_addresses(storage, epoch)
 }
 
 /// Get liveness information for all consensus validators in the current epoch.
-fn liveness_info<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
-) -> namada_storage::Result<LivenessInfo>
+fn liveness_info<S>(storage: &S) -> namada_storage::Result<LivenessInfo>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let epoch = ctx.state.in_mem().last_epoch;
+    let epoch = storage.get_block_epoch()?;
     let consensus_validators =
-        read_consensus_validator_set_addresses(ctx.state, epoch)?;
-    let params = read_pos_params::<_, governance::Store<_>>(ctx.state)?;
+        read_consensus_validator_set_addresses(storage, epoch)?;
+    let params = read_pos_params::<_, governance::Store<_>>(storage)?;
 
     let mut result = Vec::with_capacity(consensus_validators.len());
     for validator in consensus_validators {
         if let Some(pubkey) = get_consensus_key::<_, governance::Store<_>>(
-            ctx.state, &validator, epoch,
+            storage, &validator, epoch,
         )? {
             let comet_address = tm_consensus_key_raw_hash(&pubkey);
             let sum_liveness_handle = liveness_sum_missed_votes_handle();
             let missed_votes = sum_liveness_handle
-                .get(ctx.state, &validator)?
+                .get(storage, &validator)?
                 .unwrap_or_default();
             result.push(ValidatorLiveness {
                 native_address: validator,
@@ -290,21 +284,20 @@ where
 }
 
 /// Get the validator commission rate and max commission rate change per epoch
-fn validator_commission<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn validator_commission<S>(
+    storage: &S,
     validator: Address,
     epoch: Option<Epoch>,
 ) -> namada_storage::Result<CommissionPair>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let epoch = epoch.unwrap_or(ctx.state.in_mem().last_epoch);
-    let params = read_pos_params::<_, governance::Store<_>>(ctx.state)?;
+    let epoch = epoch.unwrap_or(storage.get_block_epoch()?);
+    let params = read_pos_params::<_, governance::Store<_>>(storage)?;
     let commission_rate = validator_commission_rate_handle(&validator)
-        .get(ctx.state, epoch, &params)?;
+        .get(storage, epoch, &params)?;
     let max_commission_change_per_epoch =
-        read_validator_max_commission_rate_change(ctx.state, &validator)?;
+        read_validator_max_commission_rate_change(storage, &validator)?;
 
     Ok(CommissionPair {
         commission_rate,
@@ -314,45 +307,42 @@ where
 }
 
 /// Get the validator metadata
-fn validator_metadata<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn validator_metadata<S>(
+    storage: &S,
     validator: Address,
 ) -> namada_storage::Result<Option<ValidatorMetaData>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    read_validator_metadata(ctx.state, &validator)
+    read_validator_metadata(storage, &validator)
 }
 
 /// Get the validator state
-fn validator_state<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn validator_state<S>(
+    storage: &S,
     validator: Address,
     epoch: Option<Epoch>,
 ) -> namada_storage::Result<ValidatorStateInfo>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let epoch = epoch.unwrap_or(ctx.state.in_mem().last_epoch);
+    let epoch = epoch.unwrap_or(storage.get_block_epoch()?);
     let state = namada_proof_of_stake::storage::read_validator_state::<
         _,
         governance::Store<_>,
-    >(ctx.state, &validator, epoch)?;
+    >(storage, &validator, epoch)?;
     Ok((state, epoch))
 }
 
 /// Get the validator state
-fn validator_last_infraction_epoch<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn validator_last_infraction_epoch<S>(
+    storage: &S,
     validator: Address,
 ) -> namada_storage::Result<Option<Epoch>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    read_validator_last_slash_epoch(ctx.state, &validator)
+    read_validator_last_slash_epoch(storage, &validator)
 }
 
 /// Get the total stake of a validator at the given epoch or current when
@@ -360,20 +350,18 @@ where
 /// to their address.
 /// Returns `None` when the given address is not a validator address. For a
 /// validator with `0` stake, this returns `Ok(token::Amount::zero())`.
-fn validator_stake<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn validator_stake<S>(
+    storage: &S,
     validator: Address,
     epoch: Option<Epoch>,
 ) -> namada_storage::Result<Option<token::Amount>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let epoch = epoch.unwrap_or(ctx.state.in_mem().last_epoch);
-    let params = read_pos_params::<_, governance::Store<_>>(ctx.state)?;
-    if namada_proof_of_stake::is_validator(ctx.state, &validator)? {
-        let stake =
-            read_validator_stake(ctx.state, &params, &validator, epoch)?;
+    let epoch = epoch.unwrap_or(storage.get_block_epoch()?);
+    let params = read_pos_params::<_, governance::Store<_>>(storage)?;
+    if namada_proof_of_stake::is_validator(storage, &validator)? {
+        let stake = read_validator_stake(storage, &params, &validator, epoch)?;
         Ok(Some(stake))
     } else {
         Ok(None)
@@ -382,139 +370,129 @@ where
 
 /// Get the incoming redelegation epoch for a source validator - delegator pair,
 /// if there is any.
-fn validator_incoming_redelegation<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn validator_incoming_redelegation<S>(
+    storage: &S,
     src_validator: Address,
     delegator: Address,
 ) -> namada_storage::Result<Option<Epoch>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
     let handle = validator_incoming_redelegations_handle(&src_validator);
-    handle.get(ctx.state, &delegator)
+    handle.get(storage, &delegator)
 }
 
 /// Get all the validator in the consensus set with their bonded stake.
-fn consensus_validator_set<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn consensus_validator_set<S>(
+    storage: &S,
     epoch: Option<Epoch>,
 ) -> namada_storage::Result<BTreeSet<WeightedValidator>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let epoch = epoch.unwrap_or(ctx.state.in_mem().last_epoch);
-    read_consensus_validator_set_addresses_with_stake(ctx.state, epoch)
+    let epoch = epoch.unwrap_or(storage.get_block_epoch()?);
+    read_consensus_validator_set_addresses_with_stake(storage, epoch)
 }
 
 /// Get all the validator in the below-capacity set with their bonded stake.
-fn below_capacity_validator_set<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn below_capacity_validator_set<S>(
+    storage: &S,
     epoch: Option<Epoch>,
 ) -> namada_storage::Result<BTreeSet<WeightedValidator>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let epoch = epoch.unwrap_or(ctx.state.in_mem().last_epoch);
-    read_below_capacity_validator_set_addresses_with_stake(ctx.state, epoch)
+    let epoch = epoch.unwrap_or(storage.get_block_epoch()?);
+    read_below_capacity_validator_set_addresses_with_stake(storage, epoch)
 }
 
 /// Get the total stake in PoS system at the given epoch or current when `None`.
-fn total_stake<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn total_stake<S>(
+    storage: &S,
     epoch: Option<Epoch>,
 ) -> namada_storage::Result<token::Amount>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let epoch = epoch.unwrap_or(ctx.state.in_mem().last_epoch);
-    let params = read_pos_params::<_, governance::Store<_>>(ctx.state)?;
-    read_total_stake(ctx.state, &params, epoch)
+    let epoch = epoch.unwrap_or(storage.get_block_epoch()?);
+    let params = read_pos_params::<_, governance::Store<_>>(storage)?;
+    read_total_stake(storage, &params, epoch)
 }
 
 /// Get the total active voting power in PoS system at the given epoch or
 /// current when `None`.
-fn total_active_voting_power<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn total_active_voting_power<S>(
+    storage: &S,
     epoch: Option<Epoch>,
 ) -> namada_storage::Result<token::Amount>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let epoch = epoch.unwrap_or(ctx.state.in_mem().last_epoch);
-    let params = read_pos_params::<_, governance::Store<_>>(ctx.state)?;
-    read_total_active_stake(ctx.state, &params, epoch)
+    let epoch = epoch.unwrap_or(storage.get_block_epoch()?);
+    let params = read_pos_params::<_, governance::Store<_>>(storage)?;
+    read_total_active_stake(storage, &params, epoch)
 }
 
-fn bond_deltas<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn bond_deltas<S>(
+    storage: &S,
     source: Address,
     validator: Address,
 ) -> namada_storage::Result<HashMap<Epoch, token::Amount>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    bond_handle(&source, &validator).to_hashmap(ctx.state)
+    bond_handle(&source, &validator).to_hashmap(storage)
 }
 
 /// Find the sum of bond amount up the given epoch when `Some`, or up to the
 /// pipeline length parameter offset otherwise
-fn bond<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn bond<S>(
+    storage: &S,
     source: Address,
     validator: Address,
     epoch: Option<Epoch>,
 ) -> namada_storage::Result<token::Amount>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let params = read_pos_params::<_, governance::Store<_>>(ctx.state)?;
+    let params = read_pos_params::<_, governance::Store<_>>(storage)?;
     let epoch = epoch.unwrap_or(
-        ctx.state
-            .in_mem()
-            .last_epoch
+        storage
+            .get_block_epoch()?
             .unchecked_add(params.pipeline_len),
     );
 
     let handle = bond_handle(&source, &validator);
     handle
-        .get_sum(ctx.state, epoch, &params)?
+        .get_sum(storage, epoch, &params)?
         .ok_or_err_msg("Cannot find bond")
 }
 
-fn bond_with_slashing<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn bond_with_slashing<S>(
+    storage: &S,
     source: Address,
     validator: Address,
     epoch: Option<Epoch>,
 ) -> namada_storage::Result<token::Amount>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let epoch = epoch.unwrap_or(ctx.state.in_mem().last_epoch);
+    let epoch = epoch.unwrap_or(storage.get_block_epoch()?);
     let bond_id = BondId { source, validator };
 
-    bond_amount::<_, governance::Store<_>>(ctx.state, &bond_id, epoch)
+    bond_amount::<_, governance::Store<_>>(storage, &bond_id, epoch)
 }
 
-fn unbond<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn unbond<S>(
+    storage: &S,
     source: Address,
     validator: Address,
 ) -> namada_storage::Result<HashMap<(Epoch, Epoch), token::Amount>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
     let handle = unbond_handle(&source, &validator);
-    let iter = handle.iter(ctx.state)?;
+    let iter = handle.iter(storage)?;
     iter.map(|next_result| {
         next_result.map(
             |(
@@ -529,18 +507,17 @@ where
     .collect()
 }
 
-fn unbond_with_slashing<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn unbond_with_slashing<S>(
+    storage: &S,
     source: Address,
     validator: Address,
 ) -> namada_storage::Result<HashMap<(Epoch, Epoch), token::Amount>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
     // TODO slashes
     let handle = unbond_handle(&source, &validator);
-    let iter = handle.iter(ctx.state)?;
+    let iter = handle.iter(storage)?;
     iter.map(|next_result| {
         next_result.map(
             |(
@@ -555,21 +532,20 @@ where
     .collect()
 }
 
-fn withdrawable_tokens<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn withdrawable_tokens<S>(
+    storage: &S,
     source: Address,
     validator: Address,
     epoch: Option<Epoch>,
 ) -> namada_storage::Result<token::Amount>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let epoch = epoch.unwrap_or(ctx.state.in_mem().last_epoch);
+    let epoch = epoch.unwrap_or(storage.get_block_epoch()?);
 
     let handle = unbond_handle(&source, &validator);
     let mut total = token::Amount::zero();
-    for result in handle.iter(ctx.state)? {
+    for result in handle.iter(storage)? {
         let (
             lazy_map::NestedSubKey::Data {
                 key: _start,
@@ -584,35 +560,44 @@ where
     Ok(total)
 }
 
-fn rewards<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn rewards<S>(
+    storage: &S,
     validator: Address,
     source: Option<Address>,
 ) -> namada_storage::Result<token::Amount>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let current_epoch = ctx.state.in_mem().last_epoch;
+    let current_epoch = storage.get_block_epoch()?;
     query_reward_tokens::<_, governance::Store<_>>(
-        ctx.state,
+        storage,
         source.as_ref(),
         &validator,
         current_epoch,
     )
 }
 
-fn bonds_and_unbonds<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn auto_compound<S>(
+    storage: &S,
+    validator: Address,
+    source: Option<Address>,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
+{
+    is_auto_compounding(storage, source.as_ref(), &validator)
+}
+
+fn bonds_and_unbonds<S>(
+    storage: &S,
     source: Option<Address>,
     validator: Option<Address>,
 ) -> namada_storage::Result<BondsAndUnbondsDetails>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
     namada_proof_of_stake::queries::bonds_and_unbonds::<_, governance::Store<_>>(
-        ctx.state, source, validator,
+        storage, source, validator,
     )
 }
 
@@ -647,49 +632,45 @@ where
 }
 
 /// Validator slashes
-fn validator_slashes<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn validator_slashes<S>(
+    storage: &S,
     validator: Address,
 ) -> namada_storage::Result<Vec<Slash>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
     let slash_handle = validator_slashes_handle(&validator);
-    slash_handle.iter(ctx.state)?.collect()
+    slash_handle.iter(storage)?.collect()
 }
 
 /// All slashes
-fn slashes<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn slashes<S>(
+    storage: &S,
 ) -> namada_storage::Result<HashMap<Address, Vec<Slash>>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    find_all_slashes(ctx.state)
+    find_all_slashes(storage)
 }
 
 /// Enqueued slashes
-fn enqueued_slashes<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn enqueued_slashes<S>(
+    storage: &S,
 ) -> namada_storage::Result<HashMap<Address, BTreeMap<Epoch, Vec<Slash>>>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    let current_epoch = ctx.state.in_mem().last_epoch;
-    find_all_enqueued_slashes(ctx.state, current_epoch)
+    let current_epoch = storage.get_block_epoch()?;
+    find_all_enqueued_slashes(storage, current_epoch)
 }
 
 /// Native validator address by looking up the Tendermint address
-fn validator_by_tm_addr<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn validator_by_tm_addr<S>(
+    storage: &S,
     tm_addr: String,
 ) -> namada_storage::Result<Option<Address>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
     // Sanitize the input to make sure it doesn't crash in
     // `namada_proof_of_stake::storage_key::validator_address_raw_hash_key`
@@ -698,33 +679,26 @@ where
             "Invalid Tendermint address",
         ));
     }
-    namada_proof_of_stake::storage::find_validator_by_raw_hash(
-        ctx.state, tm_addr,
-    )
+    namada_proof_of_stake::storage::find_validator_by_raw_hash(storage, tm_addr)
 }
 
 /// Native validator address by looking up the Tendermint address
-fn consensus_key_set<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn consensus_key_set<S>(
+    storage: &S,
 ) -> namada_storage::Result<BTreeSet<common::PublicKey>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    namada_proof_of_stake::storage::get_consensus_key_set(ctx.state)
+    namada_proof_of_stake::storage::get_consensus_key_set(storage)
 }
 
 /// Find if the given source address has any bonds.
-fn has_bonds<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
-    source: Address,
-) -> namada_storage::Result<bool>
+fn has_bonds<S>(storage: &S, source: Address) -> namada_storage::Result<bool>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
     namada_proof_of_stake::queries::has_bonds::<_, governance::Store<_>>(
-        ctx.state, &source,
+        storage, &source,
     )
 }
 
@@ -832,10 +806,56 @@ fn enrich_bonds_and_unbonds(
 
 #[cfg(test)]
 mod test {
+    use namada_core::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_core::chain::BlockHeight;
+
     use super::*;
     use crate::queries::testing::TestClient;
     use crate::queries::{RequestCtx, RequestQuery, Router};
 
+    /// Test that a delegator can be found at a past height after the bond has
+    /// been removed
+    #[tokio::test]
+    async fn test_is_delegator_at_past_height() {
+        let mut client = TestClient::new(POS);
+        let delegator = established_address_1();
+        let validator = established_address_2();
+        let bonds = bond_handle(&delegator, &validator);
+
+        // Height 1: delegate
+        client.state.in_mem_mut().block.height = BlockHeight(1);
+        bonds
+            .get_data_handler()
+            .insert(&mut client.state, Epoch(0), token::Amount::from(10))
+            .unwrap();
+        client.state.commit_block().unwrap();
+        // Height 2: remove the bond
+        client.state.in_mem_mut().block.height = BlockHeight(2);
+        bonds
+            .get_data_handler()
+            .remove(&mut client.state, &Epoch(0))
+            .unwrap();
+        client.state.commit_block().unwrap();
+
+        let is_delegator = POS
+            .is_delegator_at_height(&client, None, &delegator, &None)
+            .await
+            .unwrap();
+        assert!(!is_delegator);
+        let is_delegator = POS
+            .is_delegator_at_height(
+                &client,
+                Some(BlockHeight(1)),
+                &delegator,
+                &None,
+            )
+            .await
+            .unwrap();
+        assert!(is_delegator);
+    }
+
     #[tokio::test]
     async fn test_validator_by_tm_addr_sanitized_input() {
         let client = TestClient::new(POS);
@@ -6,6 +6,7 @@ use namada_proof_of_stake::rewards::{
     estimate_staking_reward_rate, PosRewardsRates,
 };
 use namada_state::{DBIter, StorageHasher, DB};
+use namada_storage::StorageRead;
 use namada_token::{
     get_effective_total_native_supply, read_denom, read_total_supply,
 };
@@ -13,46 +14,46 @@ use namada_token::{
 use crate::queries::RequestCtx;
 
 router! {TOKEN,
-    ( "denomination" / [token: Address] ) -> Option<token::Denomination> = denomination,
-    ( "total_supply" / [token: Address] ) -> token::Amount = total_supply,
-    ( "effective_native_supply" ) -> token::Amount = effective_native_supply,
+    ( "denomination" / [token: Address] ) -> Option<token::Denomination> = (at_height denomination),
+    ( "total_supply" / [token: Address] ) -> token::Amount = (at_height total_supply),
+    ( "effective_native_supply" ) -> token::Amount = (at_height effective_native_supply),
+    // NOTE: The estimate relies on the token write interface, which is not
+    // available on a historical state, hence it's only answered at the latest
+    // height
     ( "staking_rewards_rate" ) -> PosRewardsRates = staking_rewards_rate,
 }
 
 /// Get the number of decimal places (in base 10) for a
 /// token specified by `addr`.
-fn denomination<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn denomination<S>(
+    storage: &S,
     token: Address,
 ) -> namada_storage::Result<Option<token::Denomination>>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    read_denom(ctx.state, &token)
+    read_denom(storage, &token)
 }
 
 /// Get the total supply for a token address
-fn total_supply<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn total_supply<S>(
+    storage: &S,
     token: Address,
 ) -> namada_storage::Result<token::Amount>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    read_total_supply(ctx.state, &token)
+    read_total_supply(storage, &token)
 }
 
 /// Get the effective total supply of the native token
-fn effective_native_supply<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+fn effective_native_supply<S>(
+    storage: &S,
 ) -> namada_storage::Result<token::Amount>
 where
-    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
-    H: 'static + StorageHasher + Sync,
+    S: StorageRead,
 {
-    get_effective_total_native_supply(ctx.state)
+    get_effective_total_native_supply(storage)
 }
 
 /// Get the effective total supply of the native token
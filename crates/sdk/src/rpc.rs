@@ -47,6 +47,8 @@ use namada_proof_of_stake::types::{
 };
 use namada_state::LastBlock;
 use namada_token::masp::MaspTokenRewardData;
+use namada_tx::data::diff::DiffedDryRunResult;
+use namada_tx::data::trace::TracedDryRunResult;
 use namada_tx::data::{BatchedTxResult, DryRunResult, ResultCode, TxResult};
 use namada_tx::event::{Batch as BatchAttr, Code as CodeAttr};
 use serde::Serialize;
@@ -60,6 +62,7 @@ use crate::queries::vp::pos::{
     EnrichedBondsAndUnbondsDetails, ValidatorStateInfo,
 };
 use crate::queries::RPC;
+use crate::state_diff::{decode_state_diff, StateChange};
 use crate::tendermint::block::Height;
 use crate::tendermint::merkle::proof::ProofOps;
 use crate::tendermint_rpc::query::Query;
@@ -75,6 +78,13 @@ pub async fn query_max_block_time_estimate<C: Client + Sync>(
         .map_err(|err| Error::from(QueryError::NoResponse(err.to_string())))
 }
 
+/// Query the largest size in bytes of a wrapper tx admitted to the mempool.
+pub async fn query_max_wrapper_tx_bytes<C: Client + Sync>(
+    client: &C,
+) -> Result<u64, Error> {
+    convert_response::<C, _>(RPC.shell().max_wrapper_tx_bytes(client).await)
+}
+
 /// Identical to [`query_tx_status`], but does not need a [`Namada`]
 /// context.
 pub async fn query_tx_status2<C, IO>(
@@ -577,7 +587,104 @@ pub async fn dry_run_tx<N: Namada>(
             .await,
     )?
     .data;
+    display_dry_run_result(context, &result);
+
+    Ok(result)
+}
+
+/// Dry run a transaction, tracing the host calls of its WASM executions
+pub async fn dry_run_tx_with_trace<N: Namada>(
+    context: &N,
+    tx_bytes: Vec<u8>,
+) -> Result<TracedDryRunResult, Error> {
+    let (data, height, prove) = (Some(tx_bytes), None, false);
+    let result = convert_response::<N::Client, _>(
+        RPC.shell()
+            .dry_run_tx_with_trace(context.client(), data, height, prove)
+            .await,
+    )?
+    .data;
+    display_dry_run_result(context, &result.result);
 
+    Ok(result)
+}
+
+/// Dry run a transaction, returning the changes it would apply to the state,
+/// the events it would emit and the VPs it would trigger
+pub async fn dry_run_tx_with_diff<N: Namada>(
+    context: &N,
+    tx_bytes: Vec<u8>,
+) -> Result<DiffedDryRunResult, Error> {
+    let (data, height, prove) = (Some(tx_bytes), None, false);
+    let result = convert_response::<N::Client, _>(
+        RPC.shell()
+            .dry_run_tx_with_diff(context.client(), data, height, prove)
+            .await,
+    )?
+    .data;
+    display_dry_run_result(context, &result.result);
+    display_dry_run_diff(context, &result).await;
+
+    Ok(result)
+}
+
+async fn display_dry_run_diff<N: Namada>(
+    context: &N,
+    result: &DiffedDryRunResult,
+) {
+    if result.diff.is_empty() {
+        display_line!(context.io(), "No state changes.");
+    } else {
+        display_line!(context.io(), "State changes:");
+    }
+    for change in decode_state_diff(&result.diff) {
+        match change {
+            StateChange::Balance {
+                token,
+                owner,
+                pre,
+                post,
+            } => {
+                let pre = format_denominated_amount(
+                    context.client(),
+                    context.io(),
+                    &token,
+                    pre,
+                )
+                .await;
+                let post = format_denominated_amount(
+                    context.client(),
+                    context.io(),
+                    &token,
+                    post,
+                )
+                .await;
+                display_line!(
+                    context.io(),
+                    "  Balance of {owner} in token {token}: {pre} -> {post}"
+                );
+            }
+            change => display_line!(context.io(), "  {change}"),
+        }
+    }
+    display_line!(
+        context.io(),
+        "Triggered VPs: {}",
+        result
+            .triggered_vps
+            .iter()
+            .map(Address::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    display_line!(
+        context.io(),
+        "Events: {}",
+        serde_json::to_string_pretty(&result.events).unwrap()
+    );
+}
+
+fn display_dry_run_result<N: Namada>(context: &N, result: &DryRunResult) {
     display_line!(context.io(), "Dry-run result:");
     let mut all_inners_successful = true;
     for (inner_hash, cmt_result) in result.0.iter() {
@@ -628,8 +735,6 @@ pub async fn dry_run_tx<N: Namada>(
             result.1
         );
     }
-
-    Ok(result)
 }
 
 /// Data needed for broadcasting a tx and monitoring its progress on chain.
@@ -50,13 +50,13 @@ use crate::governance::storage::proposal::{AddRemove, PGFAction, PGFTarget};
 use crate::rpc::validate_amount;
 use crate::token::Account;
 use crate::tx::{
-    Commitment, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
-    TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-    TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
-    TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-    TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-    TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
+    Commitment, TX_AUTO_COMPOUND_WASM, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
+    TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
+    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
+    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
+    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
     TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
     VP_USER_WASM,
 };
@@ -165,6 +165,25 @@ pub fn find_key_by_pk<U: WalletIo>(
         })
 }
 
+/// Sign the given hash with the key store backend of the wallet holding the
+/// given public key
+pub fn sign_with_key_store<U: WalletIo>(
+    wallet: &Wallet<U>,
+    args: &args::Tx,
+    public_key: &common::PublicKey,
+    hash: &namada_core::hash::Hash,
+) -> Result<common::Signature, Error> {
+    wallet
+        .sign_with_key_store(public_key, hash, args.password.clone())
+        .map_err(|err| {
+            Error::Other(format!(
+                "Unable to sign with the key store of the wallet for public \
+                 key {}. Failed with: {}",
+                public_key, err
+            ))
+        })
+}
+
 /// Given CLI arguments and some defaults, determine the rightful transaction
 /// signer.
 ///
@@ -287,9 +306,32 @@ where
         if !signing_tx_keypairs.is_empty() {
             tx.sign_raw(
                 signing_tx_keypairs,
+                account_public_keys_map.clone(),
+                signing_data.owner.clone(),
+            );
+        }
+
+        // Then try to sign the raw header with the key store backends of the
+        // wallet that hold the remaining keys
+        let key_store_pubkeys: Vec<_> = signing_data
+            .public_keys
+            .iter()
+            .filter(|public_key| {
+                !used_pubkeys.contains(*public_key)
+                    && wallet.find_key_store(public_key).is_some()
+            })
+            .cloned()
+            .collect();
+        if !key_store_pubkeys.is_empty() {
+            tx.try_sign_raw_with(
+                key_store_pubkeys.clone(),
                 account_public_keys_map,
                 signing_data.owner,
-            );
+                |public_key, hash| {
+                    sign_with_key_store(&wallet, args, public_key, hash)
+                },
+            )?;
+            used_pubkeys.extend(key_store_pubkeys);
         }
     }
 
@@ -321,16 +363,29 @@ where
             .map_err(|e| Error::Encode(EncodingError::Serde(e.to_string())))?;
         tx.add_section(Section::Authorization(auth));
     } else {
-        let key = {
+        let (key, key_store) = {
             // Lock the wallet just long enough to extract a key from it without
             // interfering with the sign closure call
             let mut wallet = wallet.write().await;
-            find_key_by_pk(&mut *wallet, args, &signing_data.fee_payer)
+            let key =
+                find_key_by_pk(&mut *wallet, args, &signing_data.fee_payer);
+            let key_store = key.is_err()
+                && wallet.find_key_store(&signing_data.fee_payer).is_some();
+            (key, key_store)
         };
         match key {
             Ok(fee_payer_keypair) => {
                 tx.sign_wrapper(fee_payer_keypair);
             }
+            Err(_) if key_store => {
+                let wallet = wallet.read().await;
+                tx.try_sign_wrapper_with(
+                    signing_data.fee_payer.clone(),
+                    |public_key, hash| {
+                        sign_with_key_store(&wallet, args, public_key, hash)
+                    },
+                )?;
+            }
             Err(_) => {
                 *tx = sign(
                     tx.clone(),
@@ -978,6 +1033,13 @@ fn proposal_type_to_ledger_vector(
                 }
             }
         }
+        ProposalType::ParameterChange(changes) => {
+            output.push("Proposal type : Parameter Change".to_string());
+            for change in changes {
+                output.push(format!("Parameter : {}", change.parameter()));
+                output.push(format!("Value : {}", change.value()));
+            }
+        }
     }
     Ok(())
 }
@@ -1786,6 +1848,33 @@ pub async fn to_ledger_vector(
             }
             tv.output_expert
                 .push(format!("Validator : {}", claim.validator));
+        } else if code_sec.tag == Some(TX_AUTO_COMPOUND_WASM.to_string()) {
+            let auto_compound = pos::AutoCompound::try_from_slice(
+                &tx.data(cmt)
+                    .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+            )
+            .map_err(|err| {
+                Error::from(EncodingError::Conversion(err.to_string()))
+            })?;
+
+            tv.name = "Auto_Compound_0".to_string();
+
+            tv.output.push("Type : Auto-compound".to_string());
+            if let Some(source) = auto_compound.source.as_ref() {
+                tv.output.push(format!("Source : {}", source));
+            }
+            tv.output
+                .push(format!("Validator : {}", auto_compound.validator));
+            tv.output
+                .push(format!("Enabled : {}", auto_compound.enabled));
+
+            if let Some(source) = auto_compound.source.as_ref() {
+                tv.output_expert.push(format!("Source : {}", source));
+            }
+            tv.output_expert
+                .push(format!("Validator : {}", auto_compound.validator));
+            tv.output_expert
+                .push(format!("Enabled : {}", auto_compound.enabled));
         } else if code_sec.tag == Some(TX_CHANGE_COMMISSION_WASM.to_string()) {
             let commission_change = pos::CommissionChange::try_from_slice(
                 &tx.data(cmt)
@@ -2118,11 +2207,15 @@ mod test_signing {
     use masp_primitives::consensus::BlockHeight;
     use masp_primitives::transaction::components::sapling::builder::SaplingMetadata;
     use namada_core::chain::ChainId;
+    use namada_core::dec::Dec;
     use namada_core::hash::Hash;
     use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
     use namada_core::ibc::PGFIbcTarget;
     use namada_core::masp::TxIdInner;
     use namada_core::token::{Denomination, MaspDigitPos};
+    use namada_governance::storage::param_change::{
+        GovernanceParamChange, ParameterChange, PosParamChange,
+    };
     use namada_governance::storage::proposal::PGFInternalTarget;
     use namada_io::client::EncodedResponseQuery;
     use namada_tx::{Code, Data};
@@ -2140,6 +2233,8 @@ mod test_signing {
         args::Tx {
             dry_run: false,
             dry_run_wrapper: false,
+            dry_run_trace: false,
+            dry_run_diff: false,
             dump_tx: false,
             dump_wrapper_tx: false,
             output_folder: None,
@@ -3031,5 +3126,31 @@ mod test_signing {
             ],
         );
         output.clear();
+
+        // Parameter changes
+        proposal_type_to_ledger_vector(
+            &ProposalType::ParameterChange(BTreeSet::from([
+                ParameterChange::Pos(PosParamChange::MaxInflationRate(
+                    Dec::from_str("0.05").unwrap(),
+                )),
+                ParameterChange::Governance(
+                    GovernanceParamChange::MinProposalVotingPeriod(3),
+                ),
+            ])),
+            &tx,
+            &mut output,
+        )
+        .expect("Test failed");
+        assert_eq!(
+            output,
+            vec![
+                "Proposal type : Parameter Change".to_string(),
+                "Parameter : pos/max_inflation_rate".to_string(),
+                "Value : 0.05".to_string(),
+                "Parameter : governance/min_proposal_voting_period".to_string(),
+                "Value : 3".to_string(),
+            ],
+        );
+        output.clear();
     }
 }
@@ -0,0 +1,383 @@
+//! Decoding of the storage changes returned by a dry run with a diff into
+//! readable changes, to preview what a transaction will do before signing it
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+use borsh::BorshDeserialize;
+use namada_core::address::Address;
+use namada_core::chain::Epoch;
+use namada_core::token;
+use namada_governance::storage::keys as gov_storage;
+use namada_governance::{ProposalType, ProposalVote};
+use namada_proof_of_stake::storage_key::{is_bond_key, is_unbond_key};
+use namada_token::storage_key::is_any_token_balance_key;
+use namada_tx::data::diff::KeyDiff;
+use serde::{Deserialize, Serialize};
+
+/// A decoded change of the state
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum StateChange {
+    /// A change of the balance of a token owner. The amounts are displayed
+    /// raw, as the denomination of the token has to be read from storage.
+    Balance {
+        /// The token address
+        token: Address,
+        /// The owner of the balance
+        owner: Address,
+        /// The balance before the tx
+        pre: token::Amount,
+        /// The balance after the tx
+        post: token::Amount,
+    },
+    /// A change of the amount bonded at a given epoch
+    Bond {
+        /// The owner of the bond
+        source: Address,
+        /// The validator of the bond
+        validator: Address,
+        /// The epoch at which the bond becomes active
+        start: Epoch,
+        /// The bonded amount before the tx
+        pre: token::Amount,
+        /// The bonded amount after the tx
+        post: token::Amount,
+    },
+    /// A change of the amount unbonded from a bond
+    Unbond {
+        /// The owner of the unbond
+        source: Address,
+        /// The validator of the unbond
+        validator: Address,
+        /// The epoch at which the unbonded bond became active
+        start: Epoch,
+        /// The epoch from which the unbond can be withdrawn
+        withdraw: Epoch,
+        /// The unbonded amount before the tx
+        pre: token::Amount,
+        /// The unbonded amount after the tx
+        post: token::Amount,
+    },
+    /// A change of a field of a governance proposal
+    Proposal {
+        /// The proposal id
+        id: u64,
+        /// The name of the changed field
+        field: String,
+        /// The value of the field before the tx, if any
+        pre: Option<String>,
+        /// The value of the field after the tx, if any
+        post: Option<String>,
+    },
+    /// A vote on a governance proposal
+    Vote {
+        /// The proposal id
+        id: u64,
+        /// The voter
+        voter: Address,
+        /// The validator that the vote is delegated to
+        validator: Address,
+        /// The vote after the tx, if any
+        vote: Option<ProposalVote>,
+    },
+    /// A change of a key that is not decoded
+    Raw(KeyDiff),
+}
+
+impl Display for StateChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateChange::Balance {
+                token,
+                owner,
+                pre,
+                post,
+            } => write!(
+                f,
+                "Balance of {owner} in token {token}: {pre} -> {post} (raw)"
+            ),
+            StateChange::Bond {
+                source,
+                validator,
+                start,
+                pre,
+                post,
+            } => write!(
+                f,
+                "Bond from {source} to validator {validator} starting at \
+                 epoch {start}: {} -> {}",
+                pre.to_string_native(),
+                post.to_string_native()
+            ),
+            StateChange::Unbond {
+                source,
+                validator,
+                start,
+                withdraw,
+                pre,
+                post,
+            } => write!(
+                f,
+                "Unbond from {source} of validator {validator} bonded at \
+                 epoch {start}, withdrawable at epoch {withdraw}: {} -> {}",
+                pre.to_string_native(),
+                post.to_string_native()
+            ),
+            StateChange::Proposal {
+                id,
+                field,
+                pre,
+                post,
+            } => write!(
+                f,
+                "Proposal {id} {field}: {} -> {}",
+                pre.as_deref().unwrap_or("none"),
+                post.as_deref().unwrap_or("none")
+            ),
+            StateChange::Vote {
+                id,
+                voter,
+                validator,
+                vote,
+            } => match vote {
+                Some(vote) => write!(
+                    f,
+                    "Vote of {voter} on proposal {id} via validator \
+                     {validator}: {vote}"
+                ),
+                None => write!(
+                    f,
+                    "Vote of {voter} on proposal {id} via validator \
+                     {validator}: removed"
+                ),
+            },
+            StateChange::Raw(KeyDiff { key, pre, post }) => write!(
+                f,
+                "Key {key}: {} -> {}",
+                pre.as_ref().map_or("none".to_string(), |value| format!(
+                    "{} bytes",
+                    value.len()
+                )),
+                post.as_ref().map_or("deleted".to_string(), |value| format!(
+                    "{} bytes",
+                    value.len()
+                ))
+            ),
+        }
+    }
+}
+
+/// Decode the changes of known storage keys (balances, bonds, unbonds and
+/// governance proposals). The changes of other keys or of values that cannot
+/// be decoded are returned as they are.
+pub fn decode_state_diff(diff: &[KeyDiff]) -> Vec<StateChange> {
+    diff.iter()
+        .map(|key_diff| {
+            decode_key_diff(key_diff)
+                .unwrap_or_else(|| StateChange::Raw(key_diff.clone()))
+        })
+        .collect()
+}
+
+/// Decode the change of a single known storage key
+fn decode_key_diff(diff: &KeyDiff) -> Option<StateChange> {
+    let KeyDiff { key, pre, post } = diff;
+    if let Some([token, owner]) = is_any_token_balance_key(key) {
+        return Some(StateChange::Balance {
+            token: token.clone(),
+            owner: owner.clone(),
+            pre: decode_amount(pre)?,
+            post: decode_amount(post)?,
+        });
+    }
+    if let Some((bond_id, start)) = is_bond_key(key) {
+        return Some(StateChange::Bond {
+            source: bond_id.source,
+            validator: bond_id.validator,
+            start,
+            pre: decode_amount(pre)?,
+            post: decode_amount(post)?,
+        });
+    }
+    if let Some((bond_id, start, withdraw)) = is_unbond_key(key) {
+        return Some(StateChange::Unbond {
+            source: bond_id.source,
+            validator: bond_id.validator,
+            start,
+            withdraw,
+            pre: decode_amount(pre)?,
+            post: decode_amount(post)?,
+        });
+    }
+    if gov_storage::is_vote_key(key) {
+        return Some(StateChange::Vote {
+            id: gov_storage::get_proposal_id(key)?,
+            voter: gov_storage::get_voter_address(key)?.clone(),
+            validator: gov_storage::get_vote_delegation_address(key)?.clone(),
+            vote: decode::<ProposalVote>(post)?,
+        });
+    }
+    let (field, decode_field): (&str, DecodeField) =
+        if gov_storage::is_author_key(key) {
+            ("author", |value| decode_display::<Address>(value))
+        } else if gov_storage::is_content_key(key) {
+            ("content", |value| {
+                decode::<BTreeMap<String, String>>(value).map(|content| {
+                    content
+                        .map(|content| serde_json::to_string(&content).unwrap())
+                })
+            })
+        } else if gov_storage::is_proposal_type_key(key) {
+            ("type", |value| decode_display::<ProposalType>(value))
+        } else if gov_storage::is_start_epoch_key(key) {
+            ("voting start epoch", |value| decode_display::<Epoch>(value))
+        } else if gov_storage::is_end_epoch_key(key) {
+            ("voting end epoch", |value| decode_display::<Epoch>(value))
+        } else if gov_storage::is_activation_epoch_key(key) {
+            ("activation epoch", |value| decode_display::<Epoch>(value))
+        } else if gov_storage::is_balance_key(key) {
+            ("funds", |value| {
+                decode::<token::Amount>(value)
+                    .map(|funds| funds.map(|funds| funds.to_string_native()))
+            })
+        } else if gov_storage::is_proposal_code_key(key) {
+            ("code", |value| {
+                Some(value.as_ref().map(|code| format!("{} bytes", code.len())))
+            })
+        } else {
+            return None;
+        };
+    Some(StateChange::Proposal {
+        id: gov_storage::get_proposal_id(key)?,
+        field: field.to_string(),
+        pre: decode_field(pre)?,
+        post: decode_field(post)?,
+    })
+}
+
+/// Decode an optional value of a proposal field into its displayed form
+type DecodeField = fn(&Option<Vec<u8>>) -> Option<Option<String>>;
+
+/// Decode an optional value. Returns [`None`] if the value cannot be decoded.
+fn decode<T: BorshDeserialize>(value: &Option<Vec<u8>>) -> Option<Option<T>> {
+    match value {
+        Some(bytes) => T::try_from_slice(bytes).ok().map(Some),
+        None => Some(None),
+    }
+}
+
+/// Decode an optional value and display it
+fn decode_display<T: BorshDeserialize + Display>(
+    value: &Option<Vec<u8>>,
+) -> Option<Option<String>> {
+    decode::<T>(value).map(|value| value.map(|value| value.to_string()))
+}
+
+/// Decode an optional amount, a missing amount being zero
+fn decode_amount(value: &Option<Vec<u8>>) -> Option<token::Amount> {
+    decode::<token::Amount>(value).map(Option::unwrap_or_default)
+}
+
+#[cfg(test)]
+mod test {
+    use namada_core::address::testing::{
+        established_address_1, established_address_2, nam,
+    };
+    use namada_core::borsh::BorshSerializeExt;
+    use namada_core::storage::Key;
+    use namada_proof_of_stake::storage_key::bond_key;
+    use namada_proof_of_stake::types::BondId;
+    use namada_token::storage_key::balance_key;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_state_diff() {
+        let owner = established_address_1();
+        let validator = established_address_2();
+        let balance_key = balance_key(&nam(), &owner);
+        let bond_id = BondId {
+            source: owner.clone(),
+            validator: validator.clone(),
+        };
+        let bond_key = bond_key(&bond_id)
+            .push(&"lazy_map".to_owned())
+            .unwrap()
+            .push(&"data".to_owned())
+            .unwrap()
+            .push(&Epoch(5))
+            .unwrap();
+        let author_key = gov_storage::get_author_key(3);
+        let vote_key = gov_storage::get_vote_proposal_key(
+            3,
+            owner.clone(),
+            validator.clone(),
+        );
+        let other_key = Key::parse("some_key").unwrap();
+
+        let diff = vec![
+            KeyDiff {
+                key: balance_key,
+                pre: Some(token::Amount::native_whole(10).serialize_to_vec()),
+                post: Some(token::Amount::native_whole(4).serialize_to_vec()),
+            },
+            KeyDiff {
+                key: bond_key,
+                pre: None,
+                post: Some(token::Amount::native_whole(6).serialize_to_vec()),
+            },
+            KeyDiff {
+                key: author_key,
+                pre: None,
+                post: Some(owner.serialize_to_vec()),
+            },
+            KeyDiff {
+                key: vote_key,
+                pre: None,
+                post: Some(ProposalVote::Yay.serialize_to_vec()),
+            },
+            KeyDiff {
+                key: other_key.clone(),
+                pre: Some(vec![1, 2]),
+                post: None,
+            },
+        ];
+
+        let changes = decode_state_diff(&diff);
+        assert_eq!(
+            changes,
+            vec![
+                StateChange::Balance {
+                    token: nam(),
+                    owner: owner.clone(),
+                    pre: token::Amount::native_whole(10),
+                    post: token::Amount::native_whole(4),
+                },
+                StateChange::Bond {
+                    source: owner.clone(),
+                    validator: validator.clone(),
+                    start: Epoch(5),
+                    pre: token::Amount::zero(),
+                    post: token::Amount::native_whole(6),
+                },
+                StateChange::Proposal {
+                    id: 3,
+                    field: "author".to_string(),
+                    pre: None,
+                    post: Some(owner.to_string()),
+                },
+                StateChange::Vote {
+                    id: 3,
+                    voter: owner,
+                    validator,
+                    vote: Some(ProposalVote::Yay),
+                },
+                StateChange::Raw(KeyDiff {
+                    key: other_key,
+                    pre: Some(vec![1, 2]),
+                    post: None,
+                }),
+            ]
+        );
+    }
+}
@@ -21,10 +21,12 @@ use masp_primitives::transaction::components::transparent::fees::{
 use masp_primitives::transaction::components::I128Sum;
 use masp_primitives::transaction::Transaction as MaspTransaction;
 use masp_primitives::zip32::PseudoExtendedKey;
-use namada_account::{InitAccount, UpdateAccount};
+use namada_account::{
+    InitAccount, InitVestingAccount, UpdateAccount, VestingSchedule,
+};
 use namada_core::address::{Address, IBC, MASP};
 use namada_core::arith::checked;
-use namada_core::chain::Epoch;
+use namada_core::chain::{BlockHeight, Epoch};
 use namada_core::collections::HashSet;
 use namada_core::dec::Dec;
 use namada_core::hash::Hash;
@@ -45,7 +47,8 @@ use namada_core::masp::{AssetData, MaspEpoch, TransferSource, TransferTarget};
 use namada_core::storage;
 use namada_core::time::DateTimeUtc;
 use namada_governance::cli::onchain::{
-    DefaultProposal, OnChainProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, OnChainProposal, ParameterChangeProposal,
+    PgfFundingProposal, PgfStewardProposal,
 };
 use namada_governance::pgf::cli::steward::Commission;
 use namada_governance::storage::proposal::{
@@ -65,12 +68,15 @@ use namada_token::masp::shielded_wallet::ShieldedApi;
 use namada_token::masp::{MaspFeeData, MaspTransferData, ShieldedTransfer};
 use namada_token::storage_key::balance_key;
 use namada_token::DenominatedAmount;
+use namada_tx::data::diff::DiffedDryRunResult;
 use namada_tx::data::pgf::UpdateStewardCommission;
 use namada_tx::data::pos::{BecomeValidator, ConsensusKeyChange};
+use namada_tx::data::trace::TracedDryRunResult;
 use namada_tx::data::{
     compute_inner_tx_hash, pos, BatchedTxResult, DryRunResult, ResultCode,
 };
 pub use namada_tx::{Authorization, *};
+use namada_tx_scheduler::{ScheduledTx, TxSchedule};
 use num_traits::Zero;
 use rand_core::{OsRng, RngCore};
 
@@ -95,6 +101,8 @@ use crate::{args, Namada};
 
 /// Initialize account transaction WASM
 pub const TX_INIT_ACCOUNT_WASM: &str = "tx_init_account.wasm";
+/// Initialize vesting account transaction WASM
+pub const TX_INIT_VESTING_ACCOUNT_WASM: &str = "tx_init_vesting_account.wasm";
 /// Become validator transaction WASM path
 pub const TX_BECOME_VALIDATOR_WASM: &str = "tx_become_validator.wasm";
 /// Unjail validator transaction WASM path
@@ -117,6 +125,8 @@ pub const TX_TRANSFER_WASM: &str = "tx_transfer.wasm";
 pub const TX_IBC_WASM: &str = "tx_ibc.wasm";
 /// User validity predicate WASM path
 pub const VP_USER_WASM: &str = "vp_user.wasm";
+/// Vesting account validity predicate WASM path
+pub const VP_VESTING_WASM: &str = "vp_vesting.wasm";
 /// Bond WASM path
 pub const TX_BOND_WASM: &str = "tx_bond.wasm";
 /// Unbond WASM path
@@ -125,6 +135,8 @@ pub const TX_UNBOND_WASM: &str = "tx_unbond.wasm";
 pub const TX_WITHDRAW_WASM: &str = "tx_withdraw.wasm";
 /// Claim-rewards WASM path
 pub const TX_CLAIM_REWARDS_WASM: &str = "tx_claim_rewards.wasm";
+/// Auto-compound WASM path
+pub const TX_AUTO_COMPOUND_WASM: &str = "tx_auto_compound.wasm";
 /// Bridge pool WASM path
 pub const TX_BRIDGE_POOL_WASM: &str = "tx_bridge_pool.wasm";
 /// Change commission WASM path
@@ -141,6 +153,8 @@ pub const TX_UPDATE_STEWARD_COMMISSION: &str =
     "tx_update_steward_commission.wasm";
 /// Redelegate transaction WASM path
 pub const TX_REDELEGATE_WASM: &str = "tx_redelegate.wasm";
+/// Schedule transaction WASM path
+pub const TX_SCHEDULE_WASM: &str = "tx_schedule.wasm";
 
 /// Refund target alias prefix for IBC shielded transfers
 const IBC_REFUND_ALIAS_PREFIX: &str = "ibc-refund-target";
@@ -261,7 +275,7 @@ pub async fn process_tx(
     // println!("HTTP request body: {}", request_body);
 
     if args.dry_run || args.dry_run_wrapper {
-        expect_dry_broadcast(TxBroadcastData::DryRun(tx), context).await
+        expect_dry_broadcast(TxBroadcastData::DryRun(tx), context, args).await
     } else {
         // We use this to determine when the wrapper tx makes it on-chain
         let tx_hash = tx.header_hash().to_string();
@@ -364,14 +378,34 @@ pub async fn broadcast_tx(
         }
     }?;
 
+    let tx_bytes = tx.to_bytes();
+    // The mempool would reject a tx that cannot fit in a block, so check its
+    // size before broadcasting it
+    match rpc::query_max_wrapper_tx_bytes(context.client()).await {
+        Ok(max_wrapper_tx_bytes)
+            if tx_bytes.len() as u64 > max_wrapper_tx_bytes =>
+        {
+            return Err(Error::from(TxSubmitError::ExceedsBlockSpace(
+                tx_bytes.len() as u64,
+                max_wrapper_tx_bytes,
+            )));
+        }
+        Ok(_) => {}
+        Err(err) => {
+            tracing::debug!(
+                %err,
+                "Could not query the max wrapper tx size, skipping the check",
+            );
+        }
+    }
+
     tracing::debug!(
         transaction = ?to_broadcast,
         "Broadcasting transaction",
     );
 
-    let response = lift_rpc_error(
-        context.client().broadcast_tx_sync(tx.to_bytes()).await,
-    )?;
+    let response =
+        lift_rpc_error(context.client().broadcast_tx_sync(tx_bytes).await)?;
 
     if response.code == 0.into() {
         display_line!(context.io(), "Transaction added to mempool.");
@@ -458,6 +492,9 @@ pub fn display_batch_resp(context: &impl Namada, resp: &TxResponse) {
             ResultCode::InvalidVoteExtension => "invalid vote extension",
             ResultCode::TooLarge => "transaction too large",
             ResultCode::TxNotAllowlisted => "transaction not allowlisted",
+            ResultCode::ExceedsBlockSpace => {
+                "transaction exceeds the available block space"
+            }
         };
         let err_msg = if resp.info.is_empty() {
             err.to_string()
@@ -1747,6 +1784,65 @@ pub async fn build_claim_rewards(
     .map(|tx| (tx, signing_data))
 }
 
+/// Build a transaction to enable or disable the auto-compounding of a bond's
+/// rewards
+pub async fn build_auto_compound(
+    context: &impl Namada,
+    args::AutoCompound {
+        tx: tx_args,
+        validator,
+        source,
+        disable,
+        tx_code_path,
+    }: &args::AutoCompound,
+) -> Result<(Tx, SigningTxData)> {
+    let default_address = source.clone().unwrap_or(validator.clone());
+    let default_signer = Some(default_address.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(default_address),
+        default_signer,
+        vec![],
+        false,
+    )
+    .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+
+    // Check that the validator address is actually a validator
+    let validator =
+        known_validator_or_err(validator.clone(), tx_args.force, context)
+            .await?;
+
+    // Check that the source address exists on chain
+    let source = match source.clone() {
+        Some(source) => source_exists_or_err(source, tx_args.force, context)
+            .await
+            .map(Some),
+        None => Ok(source.clone()),
+    }?;
+
+    let data = pos::AutoCompound {
+        validator,
+        source,
+        enabled: !disable,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit a transaction to unbond
 pub async fn build_unbond(
     context: &impl Namada,
@@ -2108,6 +2204,7 @@ pub async fn build_default_proposal(
         proposal_data: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_param_change: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: DefaultProposal,
@@ -2512,6 +2609,7 @@ pub async fn build_pgf_funding_proposal(
         proposal_data: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_param_change: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfFundingProposal,
@@ -2559,6 +2657,7 @@ pub async fn build_pgf_stewards_proposal(
         proposal_data: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_param_change: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfStewardProposal,
@@ -2599,6 +2698,55 @@ pub async fn build_pgf_stewards_proposal(
     .map(|tx| (tx, signing_data))
 }
 
+/// Build a parameter change proposal governance
+pub async fn build_param_change_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_param_change: _,
+        tx_code_path,
+    }: &args::InitProposal,
+    proposal: ParameterChangeProposal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(proposal.proposal.author.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(proposal.proposal.author.clone()),
+        default_signer,
+        vec![],
+        false,
+    )
+    .await?;
+    let (fee_amount, _updated_balance) =
+        validate_transparent_fee(context, tx, &signing_data.fee_payer).await?;
+
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxSubmitError::InvalidProposal(e.to_string()))?;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?, None);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build(
+        context,
+        tx,
+        tx_code_path.clone(),
+        init_proposal_data,
+        add_section,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit an IBC transfer
 pub async fn build_ibc_transfer(
     context: &impl Namada,
@@ -3584,13 +3732,124 @@ pub async fn build_init_account(
 
     let vp_code_hash = query_wasm_code_hash_buf(context, vp_code_path).await?;
 
-    let threshold = match threshold {
+    let threshold =
+        init_account_threshold(context, tx_args, public_keys, *threshold)?;
+
+    let data = InitAccount {
+        public_keys: public_keys.clone(),
+        // We will add the hash inside the add_code_hash function
+        vp_code_hash: Hash::zero(),
+        threshold,
+    };
+
+    let add_code_hash = |tx: &mut Tx, data: &mut InitAccount| {
+        let extra_section_hash = tx.add_extra_section_from_hash(
+            vp_code_hash,
+            Some(vp_code_path.to_string_lossy().into_owned()),
+        );
+        data.vp_code_hash = extra_section_hash;
+        Ok(())
+    };
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        add_code_hash,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Submit a transaction to initialize a vesting account
+pub async fn build_init_vesting_account(
+    context: &impl Namada,
+    args::TxInitVestingAccount {
+        tx: tx_args,
+        vp_code_path,
+        tx_code_path,
+        public_keys,
+        threshold,
+        token,
+        amount,
+        start,
+        cliff,
+        end,
+    }: &args::TxInitVestingAccount,
+) -> Result<(Tx, SigningTxData)> {
+    let signing_data =
+        signing::aux_signing_data(context, tx_args, None, None, vec![], false)
+            .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+
+    let vp_code_hash = query_wasm_code_hash_buf(context, vp_code_path).await?;
+
+    let threshold =
+        init_account_threshold(context, tx_args, public_keys, *threshold)?;
+
+    let amount =
+        validate_amount(context, amount.clone(), token, tx_args.force).await?;
+    let schedule = VestingSchedule {
+        token: token.clone(),
+        amount: amount.amount(),
+        start: *start,
+        cliff: cliff.unwrap_or(*start),
+        end: *end,
+    };
+    if let Err(err) = schedule.validate() {
+        edisplay_line!(context.io(), "Invalid vesting schedule: {err}");
+        if !tx_args.force {
+            return Err(Error::Other(err));
+        }
+    }
+
+    let data = InitVestingAccount {
+        public_keys: public_keys.clone(),
+        // We will add the hash inside the add_code_hash function
+        vp_code_hash: Hash::zero(),
+        threshold,
+        schedule,
+    };
+
+    let add_code_hash = |tx: &mut Tx, data: &mut InitVestingAccount| {
+        let extra_section_hash = tx.add_extra_section_from_hash(
+            vp_code_hash,
+            Some(vp_code_path.to_string_lossy().into_owned()),
+        );
+        data.vp_code_hash = extra_section_hash;
+        Ok(())
+    };
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        add_code_hash,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Determine the signature threshold of a new account from the given public
+/// keys and optional threshold
+fn init_account_threshold(
+    context: &impl Namada,
+    tx_args: &args::Tx,
+    public_keys: &[common::PublicKey],
+    threshold: Option<u8>,
+) -> Result<u8> {
+    match threshold {
         Some(threshold) => {
-            let threshold = *threshold;
             if (threshold > 0 && public_keys.len() as u8 >= threshold)
                 || tx_args.force
             {
-                threshold
+                Ok(threshold)
             } else {
                 edisplay_line!(
                     context.io(),
@@ -3603,41 +3862,127 @@ pub async fn build_init_account(
                         TxSubmitError::InvalidAccountThreshold,
                     ));
                 }
-                threshold
+                Ok(threshold)
             }
         }
         None => {
             if public_keys.len() == 1 {
-                1u8
+                Ok(1u8)
             } else {
-                return Err(Error::from(
-                    TxSubmitError::MissingAccountThreshold,
-                ));
+                Err(Error::from(TxSubmitError::MissingAccountThreshold))
             }
         }
-    };
+    }
+}
 
-    let data = InitAccount {
-        public_keys: public_keys.clone(),
-        // We will add the hash inside the add_code_hash function
-        vp_code_hash: Hash::zero(),
-        threshold,
-    };
+/// Build a transaction to schedule a signed raw tx for a later execution,
+/// escrowing its fees. The fees of the scheduled tx are paid in the fee token
+/// and at the gas price of the scheduling tx.
+pub async fn build_schedule_tx(
+    context: &impl Namada,
+    args::ScheduleTx {
+        tx: tx_args,
+        scheduled_tx,
+        scheduled_tx_signatures,
+        height,
+        epoch,
+        scheduled_gas_limit,
+        fee_payer,
+        tx_code_path,
+    }: &args::ScheduleTx,
+) -> Result<(Tx, SigningTxData)> {
+    let mut scheduled_tx = Tx::try_from_json_bytes(scheduled_tx.as_ref())
+        .map_err(|_| {
+            Error::Other(
+                "Invalid tx deserialization. Please make sure you are passing \
+                 a file in .tx format, typically produced from using the \
+                 `--dump-tx` flag."
+                    .to_string(),
+            )
+        })?;
+    if !matches!(scheduled_tx.header.tx_type, data::TxType::Raw) {
+        return Err(Error::Other(
+            "Only raw txs can be scheduled, the scheduled tx must not be \
+             wrapped"
+                .to_string(),
+        ));
+    }
+    let signatures = scheduled_tx_signatures.iter().try_fold(
+        vec![],
+        |mut acc, bytes| -> Result<Vec<_>> {
+            let sig =
+                SignatureIndex::try_from_json_bytes(bytes).map_err(|err| {
+                    Error::Encode(EncodingError::Serde(err.to_string()))
+                })?;
+            acc.push(sig);
+            Ok(acc)
+        },
+    )?;
+    if !signatures.is_empty() {
+        scheduled_tx.add_signatures(signatures);
+    }
 
-    let add_code_hash = |tx: &mut Tx, data: &mut InitAccount| {
-        let extra_section_hash = tx.add_extra_section_from_hash(
-            vp_code_hash,
-            Some(vp_code_path.to_string_lossy().into_owned()),
+    let schedule = match (height, epoch) {
+        (Some(height), None) => TxSchedule::Height(*height),
+        (None, Some(epoch)) => TxSchedule::Epoch(*epoch),
+        _ => {
+            return Err(Error::Other(
+                "Exactly one of a block height or an epoch must be provided \
+                 to schedule a tx"
+                    .to_string(),
+            ));
+        }
+    };
+    // The scheduling tx is applied at the earliest in the next block
+    let next_height = rpc::query_block(context.client())
+        .await?
+        .map(|block| block.height.next_height())
+        .unwrap_or_else(BlockHeight::first);
+    let current_epoch = rpc::query_epoch(context.client()).await?;
+    if !schedule.is_after(next_height, current_epoch) {
+        edisplay_line!(
+            context.io(),
+            "Txs can only be scheduled in the future, got {schedule:?} at \
+             height {next_height} and epoch {current_epoch}."
         );
-        data.vp_code_hash = extra_section_hash;
-        Ok(())
+        if !tx_args.force {
+            return Err(Error::Other(
+                "The tx must be scheduled in the future".to_string(),
+            ));
+        }
+    }
+
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(fee_payer.clone()),
+        Some(fee_payer.clone()),
+        vec![],
+        false,
+    )
+    .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+    let gas_limit = u64::from(*scheduled_gas_limit);
+    let fee = checked!(fee_amount.amount() * gas_limit)?;
+
+    let data = ScheduledTx {
+        schedule,
+        tx_hash: scheduled_tx.raw_header_hash(),
+        tx: scheduled_tx.to_bytes(),
+        fee_payer: fee_payer.clone(),
+        fee_token: tx_args.fee_token.clone(),
+        fee,
+        gas_limit,
     };
+
     build(
         context,
         tx_args,
         tx_code_path.clone(),
         data,
-        add_code_hash,
+        do_nothing,
         fee_amount,
         &signing_data.fee_payer,
     )
@@ -3981,8 +4326,43 @@ async fn get_ibc_src_port_channel(
 async fn expect_dry_broadcast(
     to_broadcast: TxBroadcastData,
     context: &impl Namada,
+    args: &args::Tx,
 ) -> Result<ProcessTxResponse> {
     match to_broadcast {
+        TxBroadcastData::DryRun(tx) if args.dry_run_trace => {
+            let TracedDryRunResult { result, traces } =
+                rpc::dry_run_tx_with_trace(context, tx.to_bytes()).await?;
+            let traces = serde_json::to_string_pretty(&traces)
+                .expect("Should be able to json encode the traces.");
+            match &args.output_folder {
+                Some(path) => {
+                    let trace_path = path.join(format!(
+                        "{}.trace.json",
+                        tx.header_hash().to_string().to_lowercase()
+                    ));
+                    std::fs::write(&trace_path, traces).map_err(|err| {
+                        Error::Other(format!(
+                            "Failed to write the dry-run traces to {}: {err}",
+                            trace_path.to_string_lossy()
+                        ))
+                    })?;
+                    display_line!(
+                        context.io(),
+                        "Dry-run traces written to {}.",
+                        trace_path.to_string_lossy()
+                    );
+                }
+                None => {
+                    display_line!(context.io(), "Dry-run traces:\n{traces}");
+                }
+            }
+            Ok(ProcessTxResponse::DryRun(result))
+        }
+        TxBroadcastData::DryRun(tx) if args.dry_run_diff => {
+            let DiffedDryRunResult { result, .. } =
+                rpc::dry_run_tx_with_diff(context, tx.to_bytes()).await?;
+            Ok(ProcessTxResponse::DryRun(result))
+        }
         TxBroadcastData::DryRun(tx) => {
             let result = rpc::dry_run_tx(context, tx.to_bytes()).await?;
             Ok(ProcessTxResponse::DryRun(result))
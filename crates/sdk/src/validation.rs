@@ -7,7 +7,10 @@ use namada_vp::native_vp::{self, CtxPostStorageRead, CtxPreStorageRead};
 use namada_vp::VpEnv;
 
 use crate::state::StateRead;
-use crate::{eth_bridge, governance, ibc, parameters, proof_of_stake, token};
+use crate::{
+    eth_bridge, governance, ibc, parameters, proof_of_stake, token,
+    tx_scheduler,
+};
 
 /// Native VP context
 pub type NativeVpCtx<'a, S, CA> =
@@ -98,6 +101,14 @@ pub type EthBridgePoolVp<'ctx, CTX> =
 pub type EthBridgeNutVp<'ctx, CTX> =
     eth_bridge::vp::NonUsableTokens<'ctx, CTX, TokenKeys>;
 
+/// Native scheduled txs VP
+pub type TxSchedulerVp<'ctx, CTX> = tx_scheduler::vp::TxSchedulerVp<
+    'ctx,
+    CTX,
+    parameters::Store<<CTX as VpEnv<'ctx>>::Pre>,
+    TokenKeys,
+>;
+
 /// Governance store implementation over the native prior context
 pub type GovPreStore<'a, S, CA> =
     governance::Store<CtxPreStorageRead<'a, 'a, S, VpCache<CA>, Eval<S, CA>>>;
@@ -17,13 +17,14 @@ use masp_primitives::consensus::MainNetwork as Network;
 #[cfg(not(feature = "mainnet"))]
 use masp_primitives::consensus::TestNetwork as Network;
 use masp_primitives::convert::AllowedConversion;
+use masp_primitives::memo::MemoBytes;
 use masp_primitives::merkle_tree::{IncrementalWitness, MerklePath};
 use masp_primitives::sapling::keys::FullViewingKey;
 use masp_primitives::sapling::{Diversifier, Node, ViewingKey};
 use masp_primitives::transaction::builder::{self, *};
 use masp_primitives::transaction::components::sapling::builder::SaplingMetadata;
 use masp_primitives::transaction::components::{I128Sum, ValueSum};
-use masp_primitives::transaction::Transaction;
+use masp_primitives::transaction::{Transaction, TransparentAddress};
 use masp_primitives::zip32::{
     ExtendedFullViewingKey, ExtendedKey,
     ExtendedSpendingKey as MaspExtendedSpendingKey, PseudoExtendedKey,
@@ -49,7 +50,7 @@ pub use crate::masp::shielded_sync::dispatcher::{Dispatcher, DispatcherCache};
 #[cfg(not(target_family = "wasm"))]
 pub use crate::masp::shielded_sync::MaspLocalTaskEnv;
 pub use crate::masp::shielded_sync::{
-    utils, ShieldedSyncConfig, ShieldedSyncConfigBuilder,
+    find_key_birthdays, utils, ShieldedSyncConfig, ShieldedSyncConfigBuilder,
 };
 pub use crate::validation::{
     partial_deauthorize, preload_verifying_keys, PVKs, CONVERT_NAME,
@@ -318,6 +319,80 @@ pub type NoteIndex = BTreeMap<IndexedTx, usize>;
 /// Maps the note index (in the commitment tree) to a witness
 pub type WitnessMap = HashMap<usize, IncrementalWitness<Node>>;
 
+/// The transparent addresses involved in a shielded tx, which are the
+/// counterparts of the notes received or spent in it
+#[derive(
+    BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq,
+)]
+pub struct TransparentCounterparts {
+    /// The transparent addresses that sent funds to the MASP in the tx
+    pub sources: Vec<TransparentAddress>,
+    /// The transparent addresses that received funds from the MASP in the tx
+    pub targets: Vec<TransparentAddress>,
+}
+
+impl TransparentCounterparts {
+    /// Get the transparent counterparts of the given shielded tx
+    pub fn from_transaction(transaction: &Transaction) -> Self {
+        let mut counterparts = Self::default();
+        if let Some(bundle) = transaction.transparent_bundle() {
+            for address in bundle.vin.iter().map(|vin| vin.address) {
+                if !counterparts.sources.contains(&address) {
+                    counterparts.sources.push(address);
+                }
+            }
+            for address in bundle.vout.iter().map(|vout| vout.address) {
+                if !counterparts.targets.contains(&address) {
+                    counterparts.targets.push(address);
+                }
+            }
+        }
+        counterparts
+    }
+}
+
+/// The history of the notes of a shielded context. It is saved apart from
+/// the context, so that the contexts saved before the history was tracked can
+/// still be loaded.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct ShieldedHistory {
+    /// Maps note positions to the shielded tx that created them
+    pub note_txs: HashMap<usize, IndexedTx>,
+    /// Maps spent note positions to the shielded tx that spent them
+    pub spent_txs: HashMap<usize, IndexedTx>,
+    /// Maps the shielded txs that created or spent our notes to their
+    /// transparent counterparts
+    pub tx_counterparts: BTreeMap<IndexedTx, TransparentCounterparts>,
+}
+
+/// Whether a note was received or spent in the tx of a history entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NoteDirection {
+    /// The note was received by the viewing key
+    Incoming,
+    /// The note was spent by the viewing key
+    Outgoing,
+}
+
+/// An entry of the history of the notes of a viewing key
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShieldedHistoryEntry {
+    /// The shielded tx in which the note was received or spent
+    pub indexed_tx: IndexedTx,
+    /// Whether the note was received or spent
+    pub direction: NoteDirection,
+    /// The position of the note in the commitment tree
+    pub note_pos: usize,
+    /// The asset type of the note
+    pub asset_type: AssetType,
+    /// The value of the note, in its asset type's denomination
+    pub value: u64,
+    /// The memo attached to the note
+    pub memo: MemoBytes,
+    /// The transparent counterparts of the shielded tx
+    pub counterparts: TransparentCounterparts,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 /// The possible sync states of the shielded context
 pub enum ContextSyncStatus {
@@ -987,6 +1062,13 @@ pub mod fs {
     const TMP_FILE_PREFIX: &str = "shielded.tmp";
     const SPECULATIVE_FILE_NAME: &str = "speculative_shielded.dat";
     const SPECULATIVE_TMP_FILE_PREFIX: &str = "speculative_shielded.tmp";
+    /// Shielded history file name
+    const HISTORY_FILE_NAME: &str = "shielded_history.dat";
+    const HISTORY_TMP_FILE_PREFIX: &str = "shielded_history.tmp";
+    const SPECULATIVE_HISTORY_FILE_NAME: &str =
+        "speculative_shielded_history.dat";
+    const SPECULATIVE_HISTORY_TMP_FILE_PREFIX: &str =
+        "speculative_shielded_history.tmp";
     const CACHE_FILE_NAME: &str = "shielded_sync.cache";
     const CACHE_FILE_TMP_PREFIX: &str = "shielded_sync.cache.tmp";
 
@@ -1082,6 +1164,9 @@ pub mod fs {
                     )
                 });
                 ctx_file.write_all(&bytes[..])?;
+                // Make sure the data hits the disk before the rename, lest
+                // a crash leaves us with a truncated file
+                ctx_file.sync_all()?;
             }
             // Atomically update the old shielded context file with new data.
             // Atomicity is required to prevent other client instances from
@@ -1122,20 +1207,37 @@ pub mod fs {
             force_confirmed: bool,
         ) -> std::io::Result<()> {
             // Try to load shielded context from file
-            let file_name = if force_confirmed {
-                FILE_NAME
+            let (file_name, history_file_name) = if force_confirmed {
+                (FILE_NAME, HISTORY_FILE_NAME)
             } else {
                 match ctx.sync_status {
-                    ContextSyncStatus::Confirmed => FILE_NAME,
-                    ContextSyncStatus::Speculative => SPECULATIVE_FILE_NAME,
+                    ContextSyncStatus::Confirmed => {
+                        (FILE_NAME, HISTORY_FILE_NAME)
+                    }
+                    ContextSyncStatus::Speculative => {
+                        (SPECULATIVE_FILE_NAME, SPECULATIVE_HISTORY_FILE_NAME)
+                    }
                 }
             };
             let mut ctx_file = File::open(self.context_dir.join(file_name))?;
             let mut bytes = Vec::new();
             ctx_file.read_to_end(&mut bytes)?;
+            // The history is missing if the context was saved before it was
+            // tracked
+            let history =
+                match File::open(self.context_dir.join(history_file_name)) {
+                    Ok(mut history_file) => {
+                        ShieldedHistory::try_from_reader(&mut history_file)?
+                    }
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                        ShieldedHistory::default()
+                    }
+                    Err(err) => return Err(err),
+                };
             // Fill the supplied context with the deserialized object
             *ctx = ShieldedWallet {
                 utils: ctx.utils.clone(),
+                history,
                 ..ShieldedWallet::<U>::deserialize(&mut &bytes[..])?
             };
             Ok(())
@@ -1153,20 +1255,42 @@ pub mod fs {
                     (SPECULATIVE_TMP_FILE_PREFIX, SPECULATIVE_FILE_NAME)
                 }
             };
-            let tmp_file_name = {
-                let t = tempfile::Builder::new()
-                    .prefix(tmp_file_pref)
-                    .tempfile()?;
-                t.path().file_name().unwrap().to_owned()
+            let (history_tmp_file_pref, history_file_name) =
+                match ctx.sync_status {
+                    ContextSyncStatus::Confirmed => {
+                        (HISTORY_TMP_FILE_PREFIX, HISTORY_FILE_NAME)
+                    }
+                    ContextSyncStatus::Speculative => (
+                        SPECULATIVE_HISTORY_TMP_FILE_PREFIX,
+                        SPECULATIVE_HISTORY_FILE_NAME,
+                    ),
+                };
+            let tmp_file_name = |prefix: &str| -> std::io::Result<_> {
+                let t = tempfile::Builder::new().prefix(prefix).tempfile()?;
+                Ok(t.path().file_name().unwrap().to_owned())
             };
-            self.atomic_file_write(tmp_file_name, file_name, ctx)?;
-
-            // Remove the speculative file if present since it's state is
+            // Save the history first, so that the txs missing from it are
+            // scanned again if the context can't be saved
+            self.atomic_file_write(
+                tmp_file_name(history_tmp_file_pref)?,
+                history_file_name,
+                &ctx.history,
+            )?;
+            self.atomic_file_write(
+                tmp_file_name(tmp_file_pref)?,
+                file_name,
+                ctx,
+            )?;
+
+            // Remove the speculative files if present since their state is
             // overruled by the confirmed one we just saved
             if let ContextSyncStatus::Confirmed = ctx.sync_status {
                 let _ = std::fs::remove_file(
                     self.context_dir.join(SPECULATIVE_FILE_NAME),
                 );
+                let _ = std::fs::remove_file(
+                    self.context_dir.join(SPECULATIVE_HISTORY_FILE_NAME),
+                );
             }
 
             Ok(())
@@ -231,6 +231,7 @@ pub struct Config<T, I> {
     pub wait_for_last_query_height: bool,
     pub retry_strategy: RetryStrategy,
     pub block_batch_size: usize,
+    pub checkpoint_interval: usize,
     pub channel_buffer_size: usize,
     pub fetched_tracker: T,
     pub scanned_tracker: T,
@@ -252,6 +253,8 @@ where
     /// We are syncing up to this height
     height_to_sync: BlockHeight,
     interrupt_flag: AtomicFlag,
+    /// Number of updates to the cache since it was last persisted
+    updates_since_checkpoint: usize,
 }
 
 /// Create a new dispatcher in the initial state.
@@ -303,6 +306,7 @@ where
         config,
         cache,
         interrupt_flag: Default::default(),
+        updates_since_checkpoint: 0,
     }
 }
 
@@ -336,6 +340,7 @@ where
         while let Some(message) = self.tasks.get_next_message().await {
             self.check_exit_conditions();
             self.handle_incoming_message(message);
+            self.checkpoint_cache().await;
         }
 
         match std::mem::replace(&mut self.state, DispatcherState::Normal) {
@@ -381,6 +386,18 @@ where
         }
     }
 
+    /// Persist the cache once enough updates have been made to it, so that
+    /// a sync that gets killed can resume from the last checkpoint.
+    async fn checkpoint_cache(&mut self) {
+        if !matches!(self.state, DispatcherState::Normal)
+            || self.updates_since_checkpoint < self.config.checkpoint_interval
+        {
+            return;
+        }
+        self.updates_since_checkpoint = 0;
+        self.save_cache().await;
+    }
+
     fn apply_cache_to_shielded_context(
         &mut self,
         InitialState {
@@ -410,6 +427,7 @@ where
                 self.ctx.update_witness_map(indexed_tx, &stx_batch)?;
             }
             let first_note_pos = self.ctx.note_index[&indexed_tx];
+            let mut received_notes = vec![];
             let mut vk_heights = BTreeMap::new();
             std::mem::swap(&mut vk_heights, &mut self.ctx.vk_heights);
             for (vk, _) in vk_heights
@@ -430,10 +448,13 @@ where
                         pa,
                         memo,
                     )?;
+                    received_notes.push(first_note_pos + note_pos_offset);
                     self.config.applied_tracker.increment_by(1);
                 }
             }
             std::mem::swap(&mut vk_heights, &mut self.ctx.vk_heights);
+            self.ctx
+                .save_tx_history(indexed_tx, &stx_batch, &received_notes);
         }
 
         for (_, h) in self
@@ -668,6 +689,8 @@ where
                     self.spawn_trial_decryptions(*itx, txs);
                 }
                 self.cache.fetched.extend(tx_batch);
+                self.cache.fetched.insert_range(from, to);
+                self.updates_since_checkpoint += 1;
 
                 self.config.fetched_tracker.increment_by(to.0 - from.0 + 1);
                 self.config
@@ -689,6 +712,7 @@ where
                             + decrypted_data.len() as u64,
                     );
                     self.cache.trial_decrypted.insert(itx, vk, decrypted_data);
+                    self.updates_since_checkpoint += 1;
                     self.config.scanned_tracker.increment_by(1);
                 }
             }
@@ -1389,6 +1413,95 @@ mod dispatcher_tests {
             })
             .await;
     }
+    /// Test that the cache is checkpointed while syncing, and that a sync
+    /// that gets killed resumes from the last checkpoint, without fetching
+    /// blocks again, including the ones without any shielded transfers.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_resume_from_checkpoint() {
+        let temp_dir = tempdir().unwrap();
+        let utils = FsShieldedUtils {
+            context_dir: temp_dir.path().to_path_buf(),
+        };
+        let (client, masp_tx_sender) = TestingMaspClient::new(4.into());
+        let (_send, shutdown_sig) = shutdown_signal();
+        let config = ShieldedSyncConfig::builder()
+            .fetched_tracker(DevNullProgressBar)
+            .scanned_tracker(DevNullProgressBar)
+            .applied_tracker(DevNullProgressBar)
+            .shutdown_signal(shutdown_sig)
+            .client(client)
+            .retry_strategy(RetryStrategy::Times(0))
+            .checkpoint_interval(2)
+            .build();
+        let itx = IndexedTx {
+            height: 1.into(),
+            index: TxIndex(1),
+            batch_index: None,
+        };
+        let masp_tx = arbitrary_masp_tx();
+
+        MaspLocalTaskEnv::new(4)
+            .expect("Test failed")
+            .run(|s| async {
+                let mut dispatcher = config.clone().dispatcher(s, &utils).await;
+
+                dispatcher.handle_incoming_message(Message::FetchTxs(Ok((
+                    1.into(),
+                    2.into(),
+                    vec![(itx, masp_tx.clone())],
+                ))));
+                dispatcher.checkpoint_cache().await;
+                // nothing was persisted yet
+                assert!(utils.cache_load().await.is_err());
+
+                dispatcher.handle_incoming_message(Message::FetchTxs(Ok((
+                    3.into(),
+                    4.into(),
+                    vec![],
+                ))));
+                dispatcher.checkpoint_cache().await;
+                let cache = utils.cache_load().await.expect("Test failed");
+                assert_eq!(
+                    cache.fetched.txs,
+                    BTreeMap::from([(itx, masp_tx.clone())])
+                );
+                assert!(
+                    blocks_left_to_fetch(1.into(), 4.into(), &cache.fetched)
+                        .is_empty()
+                );
+                // the dispatcher is dropped without saving its cache, as
+                // if the process had been killed
+            })
+            .await;
+
+        MaspLocalTaskEnv::new(4)
+            .expect("Test failed")
+            .run(|s| async {
+                // any request to the client makes the sync fail
+                masp_tx_sender.send(None).expect("Test failed");
+                let dispatcher = config.clone().dispatcher(s, &utils).await;
+                let ctx = dispatcher
+                    .run(None, None, &[], &[dated_arbitrary_vk()])
+                    .await
+                    .expect("Test failed")
+                    .expect("Test failed");
+                assert_eq!(masp_tx_sender.len(), 1);
+                assert_eq!(
+                    ctx.note_index.keys().cloned().collect::<Vec<_>>(),
+                    vec![itx]
+                );
+                assert_eq!(
+                    ctx.vk_heights[&arbitrary_vk()],
+                    Some(IndexedTx::entire_block(4.into()))
+                );
+                // the cache was drained once applied
+                let cache = utils.cache_load().await.expect("Test failed");
+                assert!(cache.fetched.is_empty());
+                assert!(cache.trial_decrypted.is_empty());
+            })
+            .await;
+    }
+
     /// Test the the birthdays of keys are properly reflected in the key
     /// sync heights when starting shielded sync.
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
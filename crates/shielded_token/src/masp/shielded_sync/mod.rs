@@ -11,6 +11,7 @@ use masp_primitives::sapling::note_encryption::{
 use masp_primitives::sapling::ViewingKey;
 use masp_primitives::transaction::components::OutputDescription;
 use masp_primitives::transaction::{Authorization, Authorized, Transaction};
+use namada_core::chain::BlockHeight;
 #[cfg(not(target_family = "wasm"))]
 use namada_core::task_env::{
     LocalSetSpawner, LocalSetTaskEnvironment, TaskEnvironment,
@@ -28,6 +29,7 @@ pub mod utils;
 
 const DEFAULT_BUF_SIZE: usize = 32;
 const DEFAULT_BATCH_SIZE: usize = 10;
+const DEFAULT_CHECKPOINT_INTERVAL: usize = 1000;
 
 /// A configuration used to tune the concurrency parameters of
 /// the shielded sync and the client used to fetch data.
@@ -46,6 +48,11 @@ pub struct ShieldedSyncConfig<M, T, I> {
     channel_buffer_size: usize,
     #[builder(default = DEFAULT_BATCH_SIZE)]
     block_batch_size: usize,
+    /// Number of updates to the shielded sync cache (i.e. fetched batches of
+    /// blocks and trial decrypted txs) after which the cache is persisted,
+    /// such that an interrupted sync can resume from that point.
+    #[builder(default = DEFAULT_CHECKPOINT_INTERVAL)]
+    checkpoint_interval: usize,
 }
 
 /// A task env whose backing thread-pool uses a no-op
@@ -104,6 +111,7 @@ where
                 shutdown_signal: self.shutdown_signal,
                 retry_strategy: self.retry_strategy,
                 block_batch_size: self.block_batch_size,
+                checkpoint_interval: self.checkpoint_interval,
                 channel_buffer_size: self.channel_buffer_size,
                 wait_for_last_query_height: self.wait_for_last_query_height,
             },
@@ -147,3 +155,123 @@ pub fn trial_decrypt(
             ControlFlow::Continue(accum)
         })
 }
+
+/// Look for the first activity of each of the given viewing keys in the
+/// blocks of the range `[from, to]`, fetching shielded transfers from the
+/// client by batches of `batch_size` blocks. Returns, for each key, a
+/// birthday that can be stored alongside it so that shielded sync can skip
+/// all the blocks preceding its first received note. Keys without any
+/// activity in the range get `to` as their birthday.
+///
+/// The scan stops as soon as the first note of every key has been found.
+pub async fn find_key_birthdays<M: MaspClient>(
+    client: &M,
+    vks: &[ViewingKey],
+    from: BlockHeight,
+    to: BlockHeight,
+    batch_size: u64,
+) -> Result<Vec<BlockHeight>, M::Error> {
+    let mut first_activity: Vec<Option<BlockHeight>> = vec![None; vks.len()];
+    let mut batch_from = from;
+
+    while batch_from <= to && first_activity.iter().any(Option::is_none) {
+        let batch_to = batch_from
+            .checked_add(batch_size.saturating_sub(1))
+            .map_or(to, |batch_to| batch_to.min(to));
+        let mut txs = client
+            .fetch_shielded_transfers(batch_from, batch_to)
+            .await?;
+        txs.sort_unstable_by_key(|(itx, _)| *itx);
+
+        for (itx, tx) in txs {
+            for (vk, height) in vks.iter().zip(first_activity.iter_mut()) {
+                if height.is_some() {
+                    continue;
+                }
+                let decrypted = trial_decrypt(tx.clone(), *vk, || false);
+                if matches!(decrypted, ControlFlow::Continue(notes) if !notes.is_empty())
+                {
+                    *height = Some(itx.height);
+                }
+            }
+        }
+        batch_from = batch_to.next_height();
+    }
+
+    // A birthday marks its whole block as synced, so it must precede the
+    // block holding the first note of the key
+    Ok(first_activity
+        .into_iter()
+        .map(|height| {
+            height.map_or(to, |height| {
+                height.prev_height().unwrap_or_else(BlockHeight::sentinel)
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use masp_primitives::zip32::{ExtendedFullViewingKey, ExtendedSpendingKey};
+    use namada_tx::{IndexedTx, TxIndex};
+
+    use super::*;
+    use crate::masp::test_utils::{
+        arbitrary_masp_tx, arbitrary_vk, TestingMaspClient,
+    };
+
+    /// Test that the birthday found for a key precedes the block of its
+    /// first note, that keys without notes get the last scanned height and
+    /// that the scan stops once all the keys have been found.
+    #[tokio::test]
+    async fn test_find_key_birthdays() {
+        let (client, masp_tx_sender) = TestingMaspClient::new(4.into());
+        let send_txs = || {
+            // The testing client returns one entry per fetched height
+            for height in 2..=4 {
+                masp_tx_sender
+                    .send(Some((
+                        IndexedTx {
+                            height: height.into(),
+                            index: TxIndex(0),
+                            batch_index: None,
+                        },
+                        arbitrary_masp_tx(),
+                    )))
+                    .expect("Test failed");
+            }
+        };
+        let unused_vk = ExtendedFullViewingKey::from(
+            &ExtendedSpendingKey::master(&[0; 32]),
+        )
+        .fvk
+        .vk;
+
+        send_txs();
+        let birthdays = find_key_birthdays(
+            &client,
+            &[arbitrary_vk(), unused_vk],
+            2.into(),
+            4.into(),
+            2,
+        )
+        .await
+        .expect("Test failed");
+        assert_eq!(birthdays, vec![BlockHeight(1), BlockHeight(4)]);
+        assert!(masp_tx_sender.is_empty());
+
+        send_txs();
+        let birthdays = find_key_birthdays(
+            &client,
+            &[arbitrary_vk()],
+            2.into(),
+            4.into(),
+            2,
+        )
+        .await
+        .expect("Test failed");
+        assert_eq!(birthdays, vec![BlockHeight(1)]);
+        // Only the first batch of blocks was fetched
+        assert_eq!(masp_tx_sender.len(), 1);
+    }
+}
@@ -89,6 +89,10 @@ impl TrialDecrypted {
 #[derive(Debug, Default, Clone, BorshSerialize, BorshDeserialize)]
 pub struct Fetched {
     pub(crate) txs: IndexedNoteData,
+    /// Inclusive ranges of block heights that have been fetched in full,
+    /// indexed by their lower bound. These allow us to remember blocks
+    /// without any shielded transfers.
+    pub(crate) ranges: BTreeMap<BlockHeight, BlockHeight>,
 }
 
 impl Fetched {
@@ -112,9 +116,30 @@ impl Fetched {
     /// they appear in blocks, whilst taking ownership of
     /// the returned data.
     pub fn take(&mut self) -> impl IntoIterator<Item = IndexedNoteEntry> {
+        self.ranges.clear();
         std::mem::take(&mut self.txs)
     }
 
+    /// Record that all the blocks in the inclusive range `[from, to]` have
+    /// been fetched, merging it with any overlapping or adjacent range.
+    pub fn insert_range(&mut self, mut from: BlockHeight, mut to: BlockHeight) {
+        let succ =
+            |height: BlockHeight| BlockHeight(height.0.saturating_add(1));
+        let overlapping = self
+            .ranges
+            .range(..=succ(to))
+            .rev()
+            .take_while(|(_, range_to)| succ(**range_to) >= from)
+            .map(|(range_from, range_to)| (*range_from, *range_to))
+            .collect::<Vec<_>>();
+        for (range_from, range_to) in overlapping {
+            self.ranges.remove(&range_from);
+            from = from.min(range_from);
+            to = to.max(range_to);
+        }
+        self.ranges.insert(from, to);
+    }
+
     /// Add a single entry to the cache.
     pub fn insert(&mut self, (k, v): IndexedNoteEntry) {
         self.txs.insert(k, v);
@@ -127,6 +152,11 @@ impl Fetched {
             .range(IndexedTxRange::with_height(height))
             .next()
             .is_some()
+            || self
+                .ranges
+                .range(..=height)
+                .next_back()
+                .is_some_and(|(_, to)| *to >= height)
     }
 
     /// Check if empty
@@ -349,7 +379,10 @@ mod test_blocks_left_to_fetch {
                 )
             })
             .collect();
-        Fetched { txs }
+        Fetched {
+            txs,
+            ranges: Default::default(),
+        }
     }
 
     fn blocks_in_range(
@@ -543,4 +576,33 @@ mod test_blocks_left_to_fetch {
         let blocks_to_fetch = blocks_left_to_fetch(2.into(), 4.into(), &cache);
         assert!(blocks_to_fetch.is_empty());
     }
+
+    /// Test that fetched ranges of blocks without any transactions are
+    /// not fetched again, and that adjacent ranges are merged.
+    #[test]
+    fn test_fetched_ranges() {
+        let mut cache = fetched_cache_with_blocks([]);
+        cache.insert_range(5.into(), 6.into());
+        cache.insert_range(1.into(), 2.into());
+        let blocks_to_fetch = blocks_left_to_fetch(1.into(), 7.into(), &cache);
+        assert_eq!(
+            &blocks_to_fetch,
+            &[
+                [BlockHeight(3), BlockHeight(4)],
+                [BlockHeight(7), BlockHeight(7)],
+            ],
+        );
+
+        cache.insert_range(3.into(), 4.into());
+        assert_eq!(
+            cache.ranges,
+            BTreeMap::from([(BlockHeight(1), BlockHeight(6))])
+        );
+        let blocks_to_fetch = blocks_left_to_fetch(1.into(), 7.into(), &cache);
+        assert_eq!(&blocks_to_fetch, &[[BlockHeight(7), BlockHeight(7)]]);
+
+        _ = cache.take();
+        let blocks_to_fetch = blocks_left_to_fetch(1.into(), 7.into(), &cache);
+        assert_eq!(&blocks_to_fetch, &[[BlockHeight(1), BlockHeight(7)]]);
+    }
 }
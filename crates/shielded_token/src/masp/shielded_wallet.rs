@@ -52,9 +52,10 @@ use crate::masp::utils::MaspClient;
 use crate::masp::{
     cloned_pair, ContextSyncStatus, Conversions, MaspAmount, MaspDataLogEntry,
     MaspFeeData, MaspSourceTransferData, MaspTargetTransferData,
-    MaspTransferData, MaspTxReorderedData, NoteIndex, ShieldedSyncConfig,
-    ShieldedTransfer, ShieldedUtils, SpentNotesTracker, TransferErr, WalletMap,
-    WitnessMap, NETWORK,
+    MaspTransferData, MaspTxReorderedData, NoteDirection, NoteIndex,
+    ShieldedHistory, ShieldedHistoryEntry, ShieldedSyncConfig,
+    ShieldedTransfer, ShieldedUtils, SpentNotesTracker, TransferErr,
+    TransparentCounterparts, WalletMap, WitnessMap, NETWORK,
 };
 #[cfg(any(test, feature = "testing"))]
 use crate::masp::{testing, ENV_VAR_MASP_TEST_SEED};
@@ -93,6 +94,9 @@ pub struct ShieldedWallet<U: ShieldedUtils> {
     pub note_index: NoteIndex,
     /// The sync state of the context
     pub sync_status: ContextSyncStatus,
+    /// The history of our notes, which is saved apart from the context
+    #[borsh(skip)]
+    pub history: ShieldedHistory,
 }
 
 /// Default implementation to ease construction of TxContexts. Derive cannot be
@@ -114,6 +118,7 @@ impl<U: ShieldedUtils + Default> Default for ShieldedWallet<U> {
             asset_types: HashMap::default(),
             vk_map: HashMap::default(),
             sync_status: ContextSyncStatus::Confirmed,
+            history: ShieldedHistory::default(),
         }
     }
 }
@@ -272,6 +277,99 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedWallet<U> {
         }
     }
 
+    /// Record the given shielded tx in the history of our notes, provided it
+    /// created some of them (at the given positions) or spent some of them.
+    pub fn save_tx_history(
+        &mut self,
+        indexed_tx: IndexedTx,
+        transaction: &Transaction,
+        received_notes: &[usize],
+    ) {
+        let mut is_ours = !received_notes.is_empty();
+        for note_pos in received_notes {
+            self.history.note_txs.insert(*note_pos, indexed_tx);
+        }
+        for ss in transaction
+            .sapling_bundle()
+            .map_or(&vec![], |x| &x.shielded_spends)
+        {
+            if let Some(note_pos) = self.nf_map.get(&ss.nullifier) {
+                self.history.spent_txs.insert(*note_pos, indexed_tx);
+                is_ours = true;
+            }
+        }
+        if is_ours {
+            self.history.tx_counterparts.insert(
+                indexed_tx,
+                TransparentCounterparts::from_transaction(transaction),
+            );
+        }
+    }
+
+    /// Get the history of the notes received and spent by the given viewing
+    /// key, ordered by the shielded txs in which this happened. Notes whose
+    /// creating tx is unknown are omitted, see
+    /// [`Self::notes_without_history`].
+    pub fn shielded_history(
+        &self,
+        vk: &ViewingKey,
+    ) -> Result<Vec<ShieldedHistoryEntry>, eyre::Error> {
+        let mut history = vec![];
+        for note_pos in self.pos_map.get(vk).into_iter().flatten() {
+            let Some(received_tx) = self.history.note_txs.get(note_pos) else {
+                continue;
+            };
+            let note = self
+                .note_map
+                .get(note_pos)
+                .ok_or_else(|| eyre!("Unable to get note {note_pos}"))?;
+            let memo = self
+                .memo_map
+                .get(note_pos)
+                .cloned()
+                .unwrap_or_else(MemoBytes::empty);
+            let events =
+                std::iter::once((received_tx, NoteDirection::Incoming)).chain(
+                    self.history
+                        .spent_txs
+                        .get(note_pos)
+                        .map(|spent_tx| (spent_tx, NoteDirection::Outgoing)),
+                );
+            for (indexed_tx, direction) in events {
+                history.push(ShieldedHistoryEntry {
+                    indexed_tx: *indexed_tx,
+                    direction,
+                    note_pos: *note_pos,
+                    asset_type: note.asset_type,
+                    value: note.value,
+                    memo: memo.clone(),
+                    counterparts: self
+                        .history
+                        .tx_counterparts
+                        .get(indexed_tx)
+                        .cloned()
+                        .unwrap_or_default(),
+                });
+            }
+        }
+        history.sort_by_key(|entry| {
+            (entry.indexed_tx, entry.direction, entry.note_pos)
+        });
+        Ok(history)
+    }
+
+    /// Count the notes of the given viewing key whose creating tx is unknown.
+    /// These are the notes found before the history was tracked, which are
+    /// missing from it until the context is synced again from scratch.
+    pub fn notes_without_history(&self, vk: &ViewingKey) -> usize {
+        self.pos_map
+            .get(vk)
+            .into_iter()
+            .flatten()
+            .filter(|note_pos| !self.history.note_txs.contains_key(note_pos))
+            .count()
+    }
+
     /// Compute the total unspent notes associated with the viewing key in the
     /// context. If the key is not in the context, then we do not know the
     /// balance and hence we return None.
@@ -1765,6 +1863,7 @@ mod test_shielded_wallet {
     use super::*;
     use crate::masp::fs::FsShieldedUtils;
     use crate::masp::test_utils::{
+        arbitrary_masp_tx, arbitrary_masp_tx_with_fee_unshielding,
         arbitrary_pa, arbitrary_vk, create_note, MockNamadaIo, TestingContext,
     };
 
@@ -1857,6 +1956,147 @@ mod test_shielded_wallet {
         assert!(wallet.compute_shielded_balance(&vk).await.is_err())
     }
 
+    /// Test that the history of a viewing key lists the txs in which its
+    /// notes were received and spent, along with their transparent
+    /// counterparts
+    #[test]
+    fn test_shielded_history() {
+        let temp_dir = tempdir().unwrap();
+        let mut wallet = TestingContext::new(FsShieldedUtils::new(
+            temp_dir.path().to_path_buf(),
+        ));
+        let vk = arbitrary_vk();
+        let pa = arbitrary_pa();
+        let asset_data = AssetData {
+            token: Address::Internal(InternalAddress::Masp),
+            denom: 0.into(),
+            position: MaspDigitPos::Zero,
+            epoch: None,
+        };
+        let itx = |height: u64| IndexedTx {
+            height: height.into(),
+            index: Default::default(),
+            batch_index: None,
+        };
+        let shielding_tx = arbitrary_masp_tx();
+        let unshielding_tx = arbitrary_masp_tx_with_fee_unshielding();
+
+        // no history before any note is found
+        assert!(
+            wallet
+                .shielded_history(&vk)
+                .expect("Test failed")
+                .is_empty()
+        );
+
+        let note = create_note(asset_data.clone(), 10, pa);
+        wallet
+            .save_decrypted_shielded_outputs(
+                &vk,
+                0,
+                note,
+                pa.into(),
+                MemoBytes::empty(),
+            )
+            .expect("Test failed");
+        wallet.save_tx_history(itx(1), &shielding_tx, &[0]);
+        // txs without any of our notes are not recorded
+        wallet.save_tx_history(itx(2), &shielding_tx, &[]);
+        assert!(!wallet.history.tx_counterparts.contains_key(&itx(2)));
+        // spend the note in the unshielding tx
+        let nullifier = unshielding_tx
+            .sapling_bundle()
+            .expect("Test failed")
+            .shielded_spends[0]
+            .nullifier;
+        wallet.nf_map.insert(nullifier, 0);
+        wallet.save_tx_history(itx(3), &unshielding_tx, &[]);
+
+        let history = wallet.shielded_history(&vk).expect("Test failed");
+        let shielding_counterparts =
+            TransparentCounterparts::from_transaction(&shielding_tx);
+        assert!(!shielding_counterparts.sources.is_empty());
+        assert_eq!(
+            history,
+            vec![
+                ShieldedHistoryEntry {
+                    indexed_tx: itx(1),
+                    direction: NoteDirection::Incoming,
+                    note_pos: 0,
+                    asset_type: asset_data.encode().unwrap(),
+                    value: 10,
+                    memo: MemoBytes::empty(),
+                    counterparts: shielding_counterparts,
+                },
+                ShieldedHistoryEntry {
+                    indexed_tx: itx(3),
+                    direction: NoteDirection::Outgoing,
+                    note_pos: 0,
+                    asset_type: asset_data.encode().unwrap(),
+                    value: 10,
+                    memo: MemoBytes::empty(),
+                    counterparts: TransparentCounterparts::from_transaction(
+                        &unshielding_tx
+                    ),
+                },
+            ]
+        );
+    }
+
+    /// Test that the history is saved apart from the shielded context, which
+    /// still loads without it
+    #[tokio::test]
+    async fn test_shielded_history_file() {
+        let temp_dir = tempdir().unwrap();
+        let mut wallet = TestingContext::new(FsShieldedUtils::new(
+            temp_dir.path().to_path_buf(),
+        ));
+        let vk = arbitrary_vk();
+        let pa = arbitrary_pa();
+        let asset_data = AssetData {
+            token: Address::Internal(InternalAddress::Masp),
+            denom: 0.into(),
+            position: MaspDigitPos::Zero,
+            epoch: None,
+        };
+        let itx = IndexedTx {
+            height: 1.into(),
+            index: Default::default(),
+            batch_index: None,
+        };
+        let note = create_note(asset_data, 10, pa);
+        wallet
+            .save_decrypted_shielded_outputs(
+                &vk,
+                0,
+                note,
+                pa.into(),
+                MemoBytes::empty(),
+            )
+            .expect("Test failed");
+        wallet.save_tx_history(itx, &arbitrary_masp_tx(), &[0]);
+        wallet.save().await.expect("Test failed");
+
+        let mut loaded = FsShieldedUtils::new(temp_dir.path().to_path_buf());
+        loaded.load().await.expect("Test failed");
+        assert_eq!(loaded.shielded_history(&vk).expect("Test failed").len(), 1);
+        assert_eq!(loaded.notes_without_history(&vk), 0);
+
+        // a context saved before the history was tracked still loads, but
+        // its notes have no history
+        std::fs::remove_file(temp_dir.path().join("shielded_history.dat"))
+            .expect("Test failed");
+        let mut loaded = FsShieldedUtils::new(temp_dir.path().to_path_buf());
+        loaded.load().await.expect("Test failed");
+        assert!(
+            loaded
+                .shielded_history(&vk)
+                .expect("Test failed")
+                .is_empty()
+        );
+        assert_eq!(loaded.notes_without_history(&vk), 1);
+    }
+
     #[tokio::test]
     async fn test_estimate_rewards_no_conversions() {
         let (channel, context) = MockNamadaIo::new();
@@ -0,0 +1,281 @@
+//! Read-only access to the state committed at a past block height.
+
+use std::collections::BTreeMap;
+
+use namada_core::address::Address;
+use namada_core::chain::ChainId;
+use namada_storage::{
+    BlockHeader, BlockHeight, DBIter, Epoch, Epochs, Key, StorageHasher,
+    StorageRead, TxIndex, DB,
+};
+
+use crate::wl_state::WlState;
+use crate::{ResultExt, StateRead};
+
+/// The maximum number of blocks between the queried height and the last
+/// committed height for which a prefix can be iterated. The iteration has to
+/// go through the diffs of every block in between.
+pub const MAX_PREFIX_ITER_HEIGHTS: u64 = 100_000;
+
+/// A read-only view of the state as it was committed at a past block height.
+///
+/// The values are reconstructed from the diffs persisted in the DB, hence the
+/// keys that are not stored with diffs (see `WlState::diff_key_filter`) cannot
+/// be read and reading them returns an error.
+#[derive(Debug)]
+pub struct HistoricalState<'a, D, H>
+where
+    D: DB + for<'iter> DBIter<'iter>,
+    H: StorageHasher,
+{
+    state: &'a WlState<D, H>,
+    height: BlockHeight,
+}
+
+impl<'a, D, H> HistoricalState<'a, D, H>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    /// Create a view of the given state at the given past height. Returns an
+    /// error if the height is not lower than the last committed height.
+    pub fn new(
+        state: &'a WlState<D, H>,
+        height: BlockHeight,
+    ) -> namada_storage::Result<Self> {
+        let last_height = state.in_mem().get_last_block_height();
+        // NOTE: height `0` is reserved to refer to the last committed height
+        if height == BlockHeight(0) || height >= last_height {
+            return Err(namada_storage::Error::new_alloc(format!(
+                "A historical state can only be created for a height lower \
+                 than the last committed height {last_height}, got {height}"
+            )));
+        }
+        Ok(Self { state, height })
+    }
+
+    /// The height at which the state is viewed
+    pub fn height(&self) -> BlockHeight {
+        self.height
+    }
+
+    /// Check that the given key is stored with diffs and as such it can be
+    /// read at a past height
+    fn require_diff_key(&self, key: &Key) -> namada_storage::Result<()> {
+        if (self.state.diff_key_filter)(key) {
+            Ok(())
+        } else {
+            Err(namada_storage::Error::new_alloc(format!(
+                "The key {key} is not stored with diffs and cannot be read at \
+                 a past height"
+            )))
+        }
+    }
+}
+
+impl<D, H> StorageRead for HistoricalState<'_, D, H>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    type PrefixIter<'iter> = std::vec::IntoIter<(String, Vec<u8>)> where Self: 'iter;
+
+    fn read_bytes(&self, key: &Key) -> namada_storage::Result<Option<Vec<u8>>> {
+        self.require_diff_key(key)?;
+        let (value, _gas) = self.state.db_read_with_height(key, self.height)?;
+        Ok(value)
+    }
+
+    fn has_key(&self, key: &Key) -> namada_storage::Result<bool> {
+        Ok(self.read_bytes(key)?.is_some())
+    }
+
+    fn iter_prefix<'iter>(
+        &'iter self,
+        prefix: &Key,
+    ) -> namada_storage::Result<Self::PrefixIter<'iter>> {
+        let db = self.state.db();
+        let last_height = self.state.in_mem().get_last_block_height();
+        let heights = last_height.0.saturating_sub(self.height.0);
+        if heights > MAX_PREFIX_ITER_HEIGHTS {
+            return Err(namada_storage::Error::new_alloc(format!(
+                "Cannot iterate a prefix more than {MAX_PREFIX_ITER_HEIGHTS} \
+                 blocks in the past, got {heights} blocks"
+            )));
+        }
+
+        // Walk the diffs of the blocks after the queried height in a single
+        // pass. The first diff found for a key determines its value at the
+        // queried height: the old value if it was updated or deleted, or no
+        // value if it was created since.
+        let mut values: BTreeMap<String, Option<Vec<u8>>> = BTreeMap::new();
+        let mut height = self.height.next_height();
+        while height <= last_height {
+            let new_keys: Vec<String> = db
+                .iter_new_diffs(height, Some(prefix))
+                .map(|(key, _val, _gas)| key)
+                .collect();
+            for (key, old_val, _gas) in db.iter_old_diffs(height, Some(prefix))
+            {
+                values.entry(key).or_insert(Some(old_val));
+            }
+            for key in new_keys {
+                values.entry(key).or_insert(None);
+            }
+            height = height.next_height();
+        }
+        // The keys that haven't changed since the queried height have the
+        // same value as at the last height
+        for (key, val, _gas) in db.iter_prefix(Some(prefix)) {
+            values.entry(key).or_insert(Some(val));
+        }
+
+        let mut items = Vec::with_capacity(values.len());
+        for (key_str, value) in values {
+            let key = Key::parse(&key_str).into_storage_result()?;
+            self.require_diff_key(&key)?;
+            if let Some(value) = value {
+                items.push((key_str, value));
+            }
+        }
+        Ok(items.into_iter())
+    }
+
+    fn iter_next<'iter>(
+        &'iter self,
+        iter: &mut Self::PrefixIter<'iter>,
+    ) -> namada_storage::Result<Option<(String, Vec<u8>)>> {
+        Ok(iter.next())
+    }
+
+    fn get_chain_id(&self) -> namada_storage::Result<ChainId> {
+        let (chain_id, _gas) = self.state.in_mem().get_chain_id();
+        Ok(chain_id)
+    }
+
+    fn get_block_height(&self) -> namada_storage::Result<BlockHeight> {
+        Ok(self.height)
+    }
+
+    fn get_block_header(
+        &self,
+        height: BlockHeight,
+    ) -> namada_storage::Result<Option<BlockHeader>> {
+        if height > self.height {
+            return Ok(None);
+        }
+        let (header, _gas) =
+            StateRead::get_block_header(self.state, Some(height))?;
+        Ok(header)
+    }
+
+    fn get_block_epoch(&self) -> namada_storage::Result<Epoch> {
+        self.state
+            .in_mem()
+            .block
+            .pred_epochs
+            .get_epoch(self.height)
+            .ok_or_else(|| {
+                namada_storage::Error::new_alloc(format!(
+                    "No epoch found for the height {}",
+                    self.height
+                ))
+            })
+    }
+
+    fn get_pred_epochs(&self) -> namada_storage::Result<Epochs> {
+        // Forget the epochs that started after the queried height
+        let mut pred_epochs = self.state.in_mem().block.pred_epochs.clone();
+        pred_epochs
+            .first_block_heights
+            .retain(|first_height| *first_height <= self.height);
+        Ok(pred_epochs)
+    }
+
+    fn get_tx_index(&self) -> namada_storage::Result<TxIndex> {
+        // No tx is being applied in a block that has already been committed
+        Ok(TxIndex::default())
+    }
+
+    fn get_native_token(&self) -> namada_storage::Result<Address> {
+        Ok(self.state.in_mem().native_token.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_storage::StorageWrite;
+
+    use super::*;
+    use crate::testing::TestState;
+
+    /// Commit the current block and move on to the next height
+    fn commit_block(state: &mut TestState) {
+        state.commit_block().unwrap();
+        state.in_mem_mut().block.height =
+            state.in_mem().block.height.next_height();
+    }
+
+    #[test]
+    fn test_historical_state_reads() {
+        let mut state = TestState::default();
+        state.in_mem_mut().block.height = BlockHeight(1);
+        state
+            .in_mem_mut()
+            .block
+            .pred_epochs
+            .new_epoch(BlockHeight(1));
+
+        let prefix = Key::parse("prefix").unwrap();
+        let key1 = prefix.push(&"1".to_string()).unwrap();
+        let key2 = prefix.push(&"2".to_string()).unwrap();
+
+        // Height 1: write key1
+        state.write(&key1, 1_u64).unwrap();
+        commit_block(&mut state);
+        // Height 2: update key1 and write key2
+        state.write(&key1, 2_u64).unwrap();
+        state.write(&key2, 2_u64).unwrap();
+        commit_block(&mut state);
+        // Height 3: start a new epoch and delete key1
+        state
+            .in_mem_mut()
+            .block
+            .pred_epochs
+            .new_epoch(BlockHeight(3));
+        state.delete(&key1).unwrap();
+        commit_block(&mut state);
+
+        // The last committed height cannot be viewed as historical
+        assert!(HistoricalState::new(&state, BlockHeight(3)).is_err());
+
+        let historical = HistoricalState::new(&state, BlockHeight(1)).unwrap();
+        assert_eq!(historical.get_block_height().unwrap(), BlockHeight(1));
+        assert_eq!(historical.get_block_epoch().unwrap(), Epoch(0));
+        assert_eq!(
+            historical.get_pred_epochs().unwrap().first_block_heights,
+            vec![BlockHeight(1)]
+        );
+        assert_eq!(historical.read::<u64>(&key1).unwrap(), Some(1));
+        assert_eq!(historical.read::<u64>(&key2).unwrap(), None);
+        let kvs: Vec<_> =
+            namada_storage::iter_prefix::<u64>(&historical, &prefix)
+                .unwrap()
+                .map(|res| res.unwrap())
+                .collect();
+        assert_eq!(kvs, vec![(key1.clone(), 1)]);
+
+        let historical = HistoricalState::new(&state, BlockHeight(2)).unwrap();
+        assert_eq!(historical.read::<u64>(&key1).unwrap(), Some(2));
+        assert_eq!(historical.read::<u64>(&key2).unwrap(), Some(2));
+        let kvs: Vec<_> =
+            namada_storage::iter_prefix::<u64>(&historical, &prefix)
+                .unwrap()
+                .map(|res| res.unwrap())
+                .collect();
+        assert_eq!(kvs, vec![(key1.clone(), 2), (key2.clone(), 2)]);
+
+        // The latest state doesn't have key1 anymore
+        assert_eq!(state.read::<u64>(&key1).unwrap(), None);
+    }
+}
@@ -18,7 +18,7 @@ use namada_storage::tx_queue::ExpiredTxsQueue;
 use namada_storage::types::CommitOnlyData;
 use namada_storage::{
     BlockHeader, BlockHeight, BlockResults, Epoch, Epochs, EthEventsQueue, Key,
-    KeySeg, StorageHasher, TxIndex, EPOCH_TYPE_LENGTH,
+    KeySeg, PruningMode, StorageHasher, TxIndex, EPOCH_TYPE_LENGTH,
 };
 
 use crate::Result;
@@ -74,6 +74,8 @@ where
     pub eth_events_queue: EthEventsQueue,
     /// How many block heights in the past can the storage be queried
     pub storage_read_past_height_limit: Option<u64>,
+    /// The policy for pruning the history of the state in the DB
+    pub pruning_mode: PruningMode,
     /// Data that needs to be committed to the merkle tree
     pub commit_only_data: CommitOnlyData,
     /// Cache of the results of process proposal for the next height to decide.
@@ -164,6 +166,7 @@ where
             ethereum_height: None,
             eth_events_queue: EthEventsQueue::default(),
             storage_read_past_height_limit,
+            pruning_mode: PruningMode::default(),
             commit_only_data: CommitOnlyData::default(),
             block_proposals_cache: CLruCache::new(
                 NonZeroUsize::new(10).unwrap(),
@@ -322,4 +325,24 @@ where
             .get_epoch(oldest_height)
             .unwrap_or_default()
     }
+
+    /// Get the oldest block height whose history must be kept in the DB
+    /// according to the pruning mode. The history below this height can be
+    /// pruned. Returns `None` if nothing can be pruned.
+    pub fn get_pruning_horizon(&self) -> Option<BlockHeight> {
+        let last_height = self.get_last_block_height();
+        let horizon = self
+            .pruning_mode
+            .oldest_retained_height(last_height, &self.block.pred_epochs)?;
+        // The diffs since the start of the epoch of the previous block are
+        // always kept, because they're needed to restore the Merkle tree on a
+        // restart or on a rollback
+        let prev_height = last_height.prev_height()?;
+        let prev_epoch = self.block.pred_epochs.get_epoch(prev_height)?;
+        let prev_epoch_start_height = self
+            .block
+            .pred_epochs
+            .get_start_height_of_epoch(prev_epoch)?;
+        Some(std::cmp::min(horizon, prev_epoch_start_height))
+    }
 }
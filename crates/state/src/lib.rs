@@ -17,6 +17,7 @@
     clippy::print_stderr
 )]
 
+mod historical;
 mod host_env;
 mod in_memory;
 pub mod prefix_iter;
@@ -26,6 +27,7 @@ pub mod write_log;
 use std::fmt::Debug;
 use std::iter::Peekable;
 
+pub use historical::HistoricalState;
 pub use host_env::{TxHostEnvState, VpHostEnvState};
 pub use in_memory::{
     BlockStorage, InMemory, LastBlock, ProcessProposalCachedResult,
@@ -60,13 +62,12 @@ pub use namada_storage::types::{KVBytes, PatternIterator, PrefixIterator};
 pub use namada_storage::{
     collections, iter_prefix, iter_prefix_bytes, iter_prefix_with_filter,
     mockdb, tx_queue, BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch,
-    DbError, DbResult, Error, OptionExt, Result, ResultExt, StorageHasher,
-    StorageRead, StorageWrite, DB,
+    DbError, DbResult, Error, OptionExt, PruningMode, Result, ResultExt,
+    StorageHasher, StorageRead, StorageWrite, DB,
 };
 use namada_systems::parameters;
 use thiserror::Error;
-use wl_state::TxWlState;
-pub use wl_state::{FullAccessState, TempWlState, WlState};
+pub use wl_state::{FullAccessState, TempWlState, TxWlState, WlState};
 use write_log::WriteLog;
 
 /// We delay epoch change 2 blocks to keep it in sync with Tendermint, because
@@ -30,6 +30,10 @@ use crate::{
     EPOCH_SWITCH_BLOCKS_DELAY, STORAGE_ACCESS_GAS_PER_BYTE,
 };
 
+/// The maximum number of block heights whose history is pruned in a single
+/// block commit
+const MAX_PRUNED_HEIGHTS_PER_COMMIT: u64 = 100;
+
 /// Owned state with full R/W access.
 #[derive(Debug)]
 pub struct FullAccessState<D, H>(pub(crate) WlState<D, H>)
@@ -467,6 +471,29 @@ where
         Ok(())
     }
 
+    // Prune the history of the blocks behind the horizon of the pruning mode.
+    // Use after updating self.last_block in the commit. At most
+    // `MAX_PRUNED_HEIGHTS_PER_COMMIT` heights are pruned in a single commit so
+    // that enabling the pruning on an existing DB catches up gradually.
+    fn prune_history(&mut self, batch: &mut D::WriteBatch) -> Result<()> {
+        let horizon = match self.in_mem.get_pruning_horizon() {
+            Some(horizon) => horizon,
+            None => return Ok(()),
+        };
+        let mut height = match self.db.read_pruned_height()? {
+            Some(pruned_height) => pruned_height.next_height(),
+            None => BlockHeight::first(),
+        };
+        for _ in 0..MAX_PRUNED_HEIGHTS_PER_COMMIT {
+            if height >= horizon {
+                break;
+            }
+            self.db.prune_history(batch, height)?;
+            height = height.next_height();
+        }
+        Ok(())
+    }
+
     /// Check it the given transaction's hash is already present in storage
     pub fn has_replay_protection_entry(&self, hash: &Hash) -> Result<bool> {
         Ok(self.db.has_replay_protection_entry(hash)?)
@@ -677,6 +704,8 @@ where
         if let Some(height) = self.in_mem.block.height.prev_height() {
             self.db.prune_non_persisted_diffs(&mut batch, height)?;
         }
+        // prune the history behind the pruning horizon
+        self.prune_history(&mut batch)?;
         self.db.exec_batch(batch)?;
         Ok(())
     }
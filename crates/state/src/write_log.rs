@@ -575,6 +575,23 @@ impl WriteLog {
             .collect()
     }
 
+    /// Get the storage modifications of the transactions committed to the
+    /// current block and to the current batch, sorted by their storage key.
+    /// When a key was modified by more than one transaction, only the latest
+    /// modification is returned.
+    pub fn get_block_and_batch_modifications(
+        &self,
+    ) -> BTreeMap<&storage::Key, &StorageModification> {
+        self.block_write_log
+            .iter()
+            .chain(
+                self.batch_write_log
+                    .iter()
+                    .flat_map(|log| log.write_log.iter()),
+            )
+            .collect()
+    }
+
     /// Take the events of the current transaction
     pub fn take_events(&mut self) -> BTreeSet<Event> {
         std::mem::take(&mut self.tx_write_log.events.tree)
@@ -1005,6 +1022,48 @@ mod tests {
         assert_eq!(value, None);
     }
 
+    #[test]
+    fn test_block_and_batch_modifications() {
+        let mut write_log = WriteLog::default();
+        let key1 =
+            storage::Key::parse("key1").expect("cannot parse the key string");
+        let key2 =
+            storage::Key::parse("key2").expect("cannot parse the key string");
+        let key3 =
+            storage::Key::parse("key3").expect("cannot parse the key string");
+
+        // first tx writes all the keys and is committed to the block, like a
+        // wrapper tx
+        write_log.write(&key1, vec![1]).unwrap();
+        write_log.write(&key2, vec![1]).unwrap();
+        write_log.write(&key3, vec![1]).unwrap();
+        write_log.commit_batch_and_current_tx();
+
+        // second tx overwrites the second key
+        write_log.write(&key2, vec![2]).unwrap();
+        write_log.commit_tx_to_batch();
+
+        // third tx deletes the first key
+        write_log.delete(&key1).unwrap();
+        write_log.commit_tx_to_batch();
+
+        // fourth tx is dropped
+        write_log.write(&key3, vec![4]).unwrap();
+        write_log.drop_tx();
+
+        let modifications = write_log.get_block_and_batch_modifications();
+        assert_eq!(modifications.len(), 3);
+        assert_eq!(modifications[&key1], &StorageModification::Delete);
+        assert_eq!(
+            modifications[&key2],
+            &StorageModification::Write { value: vec![2] }
+        );
+        assert_eq!(
+            modifications[&key3],
+            &StorageModification::Write { value: vec![1] }
+        );
+    }
+
     #[test]
     fn test_replay_protection_commit() {
         let mut state = crate::testing::TestState::default();
@@ -15,7 +15,7 @@ use namada_merkle_tree::{
 };
 use regex::Regex;
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::conversion_state::ConversionState;
@@ -40,6 +40,14 @@ pub enum Error {
     BorshCodingError(std::io::Error),
     #[error("Merkle tree at the height {height} is not stored")]
     NoMerkleTree { height: BlockHeight },
+    #[error(
+        "The state at the height {height} has been pruned, the oldest \
+         available height is {oldest_height}"
+    )]
+    Pruned {
+        height: BlockHeight,
+        oldest_height: BlockHeight,
+    },
     #[error("Code hash error: {0}")]
     InvalidCodeHash(HashError),
     #[error("Numeric conversion error: {0}")]
@@ -51,6 +59,43 @@ pub enum Error {
 /// A result of a function that may fail
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The policy for pruning the history of the state persisted in the DB. The
+/// history consists of the diffs of the keys written with persisted diffs and
+/// of the per-block data (header, time, epoch, results, etc.).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PruningMode {
+    /// Keep the full history of the state
+    #[default]
+    Archive,
+    /// Keep the history of the given number of the most recent blocks
+    KeepBlocks(u64),
+    /// Keep the history of the given number of the most recent epochs
+    KeepEpochs(u64),
+}
+
+impl PruningMode {
+    /// Get the oldest block height whose history must be kept with this
+    /// pruning mode. Returns `None` if there's nothing to prune.
+    pub fn oldest_retained_height(
+        &self,
+        last_height: BlockHeight,
+        pred_epochs: &Epochs,
+    ) -> Option<BlockHeight> {
+        match self {
+            PruningMode::Archive => None,
+            PruningMode::KeepBlocks(num_blocks) => {
+                last_height.0.checked_sub(*num_blocks).map(BlockHeight)
+            }
+            PruningMode::KeepEpochs(num_epochs) => {
+                let last_epoch = pred_epochs.get_epoch(last_height)?;
+                let oldest_epoch = last_epoch.checked_sub(*num_epochs)?;
+                pred_epochs.get_start_height_of_epoch(oldest_epoch)
+            }
+        }
+    }
+}
+
 /// The block's state as stored in the database.
 pub struct BlockStateRead {
     /// Height of the block
@@ -289,6 +334,18 @@ pub trait DB: Debug {
         height: BlockHeight,
     ) -> Result<()>;
 
+    /// Prune the history at the given height, i.e. the persisted diffs and the
+    /// block's data, and record it as the latest pruned height. The heights
+    /// must be pruned in increasing order.
+    fn prune_history(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+    ) -> Result<()>;
+
+    /// Read the latest block height whose history has been pruned, if any
+    fn read_pruned_height(&self) -> Result<Option<BlockHeight>>;
+
     /// Overwrite a new value in storage, taking into
     /// account values stored at a previous height
     fn overwrite_entry(
@@ -36,6 +36,7 @@ const CONVERSION_STATE_KEY: &str = "conversion_state";
 const ETHEREUM_HEIGHT_KEY: &str = "ethereum_height";
 const ETH_EVENTS_QUEUE_KEY: &str = "eth_events_queue";
 const RESULTS_KEY_PREFIX: &str = "results";
+const PRUNED_HEIGHT_KEY: &str = "pruned_height";
 
 const MERKLE_TREE_ROOT_KEY_SEGMENT: &str = "root";
 const MERKLE_TREE_STORE_KEY_SEGMENT: &str = "store";
@@ -380,13 +381,44 @@ impl DB for MockDB {
     fn read_subspace_val_with_height(
         &self,
         key: &Key,
-        _height: BlockHeight,
-        _last_height: BlockHeight,
+        height: BlockHeight,
+        last_height: BlockHeight,
     ) -> Result<Option<Vec<u8>>> {
-        tracing::warn!(
-            "read_subspace_val_with_height is not implemented, will read \
-             subspace value from latest height"
-        );
+        if let Some(pruned_height) = self.read_pruned_height()? {
+            if height <= pruned_height {
+                return Err(Error::Pruned {
+                    height,
+                    oldest_height: pruned_height.next_height(),
+                });
+            }
+        }
+
+        // If it has a "new" val, it was written at this height
+        if let Some(new_val) = self.read_diffs_val(key, height, false)? {
+            return Ok(Some(new_val));
+        }
+        // If it has an "old" val, it was deleted at this height
+        if self.read_diffs_val(key, height, true)?.is_some() {
+            return Ok(None);
+        }
+
+        // If the value didn't change at the given height, we try to look for it
+        // at successor heights, up to the `last_height`
+        let mut height = height.next_height();
+        while height <= last_height {
+            // If it has an "old" val, it's the one we're looking for
+            if let Some(old_val) = self.read_diffs_val(key, height, true)? {
+                return Ok(Some(old_val));
+            }
+            // If it was created at this height instead, it wasn't present
+            // before
+            if self.read_diffs_val(key, height, false)?.is_some() {
+                return Ok(None);
+            }
+            height = height.next_height();
+        }
+
+        // Read from latest height
         self.read_subspace_val(key)
     }
 
@@ -635,6 +667,40 @@ impl DB for MockDB {
         Ok(())
     }
 
+    fn prune_history(
+        &mut self,
+        _batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+    ) -> Result<()> {
+        let prefix = height.raw();
+        let diff_prefixes = [
+            format!("{prefix}/{OLD_DIFF_PREFIX}/"),
+            format!("{prefix}/{NEW_DIFF_PREFIX}/"),
+        ];
+        let mut db = self.0.borrow_mut();
+        db.retain(|key, _| {
+            !diff_prefixes.iter().any(|prefix| key.starts_with(prefix))
+        });
+        for segment in [
+            BLOCK_HEADER_KEY_SEGMENT,
+            BLOCK_TIME_KEY_SEGMENT,
+            EPOCH_KEY_SEGMENT,
+            PRED_EPOCHS_KEY_SEGMENT,
+            ADDRESS_GEN_KEY_SEGMENT,
+        ] {
+            db.remove(&format!("{prefix}/{segment}"));
+        }
+        db.remove(&format!("{RESULTS_KEY_PREFIX}/{prefix}"));
+        drop(db);
+
+        self.write_value(PRUNED_HEIGHT_KEY, &height);
+        Ok(())
+    }
+
+    fn read_pruned_height(&self) -> Result<Option<BlockHeight>> {
+        self.read_value(PRUNED_HEIGHT_KEY)
+    }
+
     fn overwrite_entry(
         &self,
         _batch: &mut Self::WriteBatch,
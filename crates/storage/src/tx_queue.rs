@@ -1,11 +1,18 @@
 //! Transaction queue
 
-use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::address::{Address, TX_SCHEDULER};
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::chain::{BlockHeight, Epoch};
 pub use namada_core::ethereum_events::EthereumEvent;
+use namada_core::hash::Hash;
+use namada_core::storage::{DbKeySeg, Key, KeySeg};
+use namada_core::token;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
 
+use crate::{iter_prefix, Result, StorageRead};
+
 /// Expired transaction kinds.
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshDeserializer)]
 pub enum ExpiredTx {
@@ -34,3 +41,159 @@ impl ExpiredTxsQueue {
         self.inner.drain(..)
     }
 }
+
+const HEIGHT_SEG: &str = "height";
+const EPOCH_SEG: &str = "epoch";
+
+/// The time at which a scheduled transaction is executed.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub enum TxSchedule {
+    /// Execute the tx at the given block height.
+    Height(BlockHeight),
+    /// Execute the tx in the first block of the given epoch.
+    Epoch(Epoch),
+}
+
+impl TxSchedule {
+    /// Check if the schedule is strictly after the given block height and
+    /// epoch.
+    pub fn is_after(&self, height: BlockHeight, epoch: Epoch) -> bool {
+        match self {
+            Self::Height(scheduled) => *scheduled > height,
+            Self::Epoch(scheduled) => *scheduled > epoch,
+        }
+    }
+}
+
+/// An inner transaction scheduled for a later execution, along with the fees
+/// escrowed to pay for it.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub struct ScheduledTx {
+    /// When to execute the tx
+    pub schedule: TxSchedule,
+    /// The raw header hash of the tx
+    pub tx_hash: Hash,
+    /// The encoded raw tx, carrying the signatures required by the VPs of
+    /// the accounts it touches
+    pub tx: Vec<u8>,
+    /// The account that escrowed the fees
+    pub fee_payer: Address,
+    /// The token used to pay the fees
+    pub fee_token: Address,
+    /// The escrowed fees, covering the whole gas limit
+    pub fee: token::Amount,
+    /// The gas limit of the tx, in whole gas units
+    pub gas_limit: u64,
+}
+
+/// Get the storage key prefix of the txs scheduled at the given time.
+pub fn scheduled_txs_prefix(schedule: &TxSchedule) -> Key {
+    let key = Key::from(TX_SCHEDULER.to_db_key());
+    match schedule {
+        TxSchedule::Height(height) => key
+            .push(&HEIGHT_SEG.to_owned())
+            .and_then(|key| key.push(height)),
+        TxSchedule::Epoch(epoch) => key
+            .push(&EPOCH_SEG.to_owned())
+            .and_then(|key| key.push(epoch)),
+    }
+    .expect("Cannot obtain a storage key")
+}
+
+/// Get the storage key of a scheduled tx.
+pub fn scheduled_tx_key(schedule: &TxSchedule, tx_hash: &Hash) -> Key {
+    scheduled_txs_prefix(schedule)
+        .push(tx_hash)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given storage key is the key of a scheduled tx. If it is,
+/// return its schedule and raw header hash.
+pub fn is_scheduled_tx_key(key: &Key) -> Option<(TxSchedule, Hash)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(kind),
+            DbKeySeg::StringSeg(time),
+            DbKeySeg::StringSeg(hash),
+        ] if addr == &TX_SCHEDULER => {
+            let schedule = match kind.as_str() {
+                // The height key segment is encoded as a `u64`
+                HEIGHT_SEG => TxSchedule::Height(BlockHeight(
+                    u64::parse(time.clone()).ok()?,
+                )),
+                EPOCH_SEG => {
+                    TxSchedule::Epoch(Epoch::parse(time.clone()).ok()?)
+                }
+                _ => return None,
+            };
+            let hash = Hash::parse(hash.clone()).ok()?;
+            Some((schedule, hash))
+        }
+        _ => None,
+    }
+}
+
+/// Read the txs scheduled at the given time, with their storage keys.
+pub fn read_scheduled_txs<S>(
+    storage: &S,
+    schedule: &TxSchedule,
+) -> Result<Vec<(Key, ScheduledTx)>>
+where
+    S: StorageRead,
+{
+    iter_prefix(storage, &scheduled_txs_prefix(schedule))?.collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheduled_tx_keys() {
+        let tx_hash = Hash::sha256(b"scheduled tx");
+        let schedules = [
+            TxSchedule::Height(BlockHeight(42)),
+            TxSchedule::Epoch(Epoch(7)),
+        ];
+        for schedule in schedules {
+            let key = scheduled_tx_key(&schedule, &tx_hash);
+            assert!(
+                key.split_prefix(&scheduled_txs_prefix(&schedule)).is_some()
+            );
+            assert_eq!(is_scheduled_tx_key(&key), Some((schedule, tx_hash)));
+        }
+        assert_eq!(
+            is_scheduled_tx_key(&scheduled_txs_prefix(&schedules[0])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_schedule_is_after() {
+        let (height, epoch) = (BlockHeight(10), Epoch(2));
+        assert!(TxSchedule::Height(BlockHeight(11)).is_after(height, epoch));
+        assert!(!TxSchedule::Height(BlockHeight(10)).is_after(height, epoch));
+        assert!(TxSchedule::Epoch(Epoch(3)).is_after(height, epoch));
+        assert!(!TxSchedule::Epoch(Epoch(2)).is_after(height, epoch));
+    }
+}
@@ -52,6 +52,9 @@ use namada_sdk::ibc::core::connection::types::{
 pub use namada_sdk::ibc::core::host::types::identifiers::{
     ChannelId, ClientId, ConnectionId, PortId, Sequence,
 };
+use namada_sdk::ibc::event::{packet_from_event_attributes, IbcEvent};
+use namada_sdk::ibc::forward::ForwardMetadata;
+use namada_sdk::ibc::ica::{InterchainAccountPacketData, Metadata};
 use namada_sdk::ibc::parameters::IbcParameters;
 use namada_sdk::ibc::primitives::proto::{Any, Protobuf};
 use namada_sdk::ibc::primitives::Timestamp;
@@ -193,6 +196,7 @@ pub fn init_storage() -> (Address, Address) {
             default_per_epoch_throughput_limit: token::Amount::native_whole(
                 100,
             ),
+            ..Default::default()
         };
         ibc_params.init_storage(&mut env.state).unwrap();
 
@@ -528,6 +532,22 @@ pub fn msg_channel_open_try(
     }
 }
 
+pub fn msg_ica_channel_open_try(
+    conn_id: ConnectionId,
+    controller_port_id: PortId,
+) -> MsgChannelOpenTry {
+    let counterparty = dummy_connection_counterparty();
+    let metadata = Metadata::new(
+        counterparty.connection_id().expect("no connection ID"),
+        &conn_id,
+    );
+    let mut msg = msg_channel_open_try(ibc::ica::host_port_id(), conn_id);
+    msg.port_id_on_a = controller_port_id;
+    msg.version_supported_on_a = metadata.to_version();
+    msg.ordering = Order::Ordered;
+    msg
+}
+
 pub fn msg_channel_open_ack(
     port_id: PortId,
     channel_id: ChannelId,
@@ -674,6 +694,57 @@ pub fn received_packet(
     }
 }
 
+pub fn forward_received_packet(
+    port_id: PortId,
+    channel_id: ChannelId,
+    sequence: Sequence,
+    token: String,
+    forward: &ForwardMetadata,
+) -> Packet {
+    let receiver = address::testing::gen_established_address();
+    let mut packet =
+        received_packet(port_id, channel_id, sequence, token, &receiver);
+    let mut data: PacketData =
+        serde_json::from_slice(&packet.data).expect("invalid packet data");
+    data.memo = forward.to_memo().into();
+    packet.data = serde_json::to_vec(&data).unwrap();
+    packet
+}
+
+pub fn ica_received_packet(
+    controller_port_id: PortId,
+    channel_id: ChannelId,
+    sequence: Sequence,
+    messages: Vec<Any>,
+) -> Packet {
+    let counterparty = dummy_channel_counterparty();
+    let timestamp = (Timestamp::now() + Duration::from_secs(100)).unwrap();
+    let data = InterchainAccountPacketData::execute_tx(messages, "memo");
+    Packet {
+        seq_on_a: sequence,
+        port_id_on_a: controller_port_id,
+        chan_id_on_a: counterparty.channel_id().unwrap().clone(),
+        port_id_on_b: ibc::ica::host_port_id(),
+        chan_id_on_b: channel_id,
+        data: data.to_json(),
+        timeout_height_on_b: TimeoutHeight::Never,
+        timeout_timestamp_on_b: TimeoutTimestamp::At(timestamp),
+    }
+}
+
+/// Get the packets sent by the current transaction from its events
+pub fn sent_packets(env: &TestTxEnv) -> Vec<Packet> {
+    env.state
+        .write_log()
+        .get_events_of::<IbcEvent>()
+        .filter(|event| event.kind().sub_domain() == "send_packet")
+        .map(|event| {
+            packet_from_event_attributes(event.attributes())
+                .expect("invalid packet event")
+        })
+        .collect()
+}
+
 pub fn msg_timeout(packet: Packet, next_sequence_recv: Sequence) -> MsgTimeout {
     MsgTimeout {
         packet,
@@ -29,9 +29,11 @@ mod tests {
     use namada_sdk::hash::Hash;
     use namada_sdk::ibc::context::nft_transfer_mod::testing::DummyNftTransferModule;
     use namada_sdk::ibc::context::transfer_mod::testing::DummyTransferModule;
+    use namada_sdk::ibc::forward::{ForwardMetadata, DEFAULT_FORWARD_TIMEOUT};
     use namada_sdk::ibc::primitives::ToProto;
     use namada_sdk::ibc::{
-        storage as ibc_storage, trace as ibc_trace, Error as IbcActionError,
+        ica, storage as ibc_storage, trace as ibc_trace,
+        Error as IbcActionError,
     };
     use namada_sdk::key::*;
     use namada_sdk::storage::{self, BlockHeight, Key, KeySeg};
@@ -1473,6 +1475,475 @@ mod tests {
         assert_eq!(minted, Some(Amount::native_whole(100)));
     }
 
+    #[test]
+    fn test_ibc_forward_token_and_refund() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, _) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        writes.extend(channel_writes);
+
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.state.write_bytes(&key, &val).expect("write error");
+            });
+        });
+
+        // packet to be forwarded back through the same channel
+        let forward = ForwardMetadata {
+            receiver: "cosmos1receiver".to_string(),
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            timeout: DEFAULT_FORWARD_TIMEOUT,
+            next: None,
+        };
+        let packet = ibc::forward_received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &forward,
+        );
+
+        // Start a transaction to receive a packet
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs.clone(), pks_map.clone(), None)
+            .sign_wrapper(keypair.clone());
+        // receive a packet with the message
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute::<token::Transfer>(&tx_data)
+            .expect("receiving the token failed");
+
+        // Check
+        let mut env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(
+            &env,
+            &tx.batch_ref_first_tx().unwrap(),
+        );
+        assert!(
+            result.is_ok(),
+            "Expected VP to accept the tx, got {result:?}"
+        );
+        // Check if the tokens were sent to the next chain
+        let sent_packets = ibc::sent_packets(&env);
+        assert_eq!(sent_packets.len(), 1);
+        let forwarded = sent_packets[0].clone();
+        assert_eq!(forwarded.seq_on_a, ibc::Sequence::from(1));
+        let in_flight_key = ibc_storage::in_flight_forward_key(
+            &port_id,
+            &channel_id,
+            forwarded.seq_on_a,
+        );
+        let commitment_key = ibc_storage::commitment_key(
+            &port_id,
+            &channel_id,
+            forwarded.seq_on_a,
+        );
+        assert!(env.state.has_key(&in_flight_key).expect("read error"));
+        assert!(env.state.has_key(&commitment_key).expect("read error"));
+        // The received tokens aren't left with the IBC account
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let key = ibc::balance_key_with_ibc_prefix(
+            denom,
+            &address::Address::Internal(address::InternalAddress::Ibc),
+        );
+        let balance: Option<Amount> = env.state.read(&key).expect("read error");
+        assert_eq!(balance, Some(Amount::zero()));
+
+        // Commit
+        env.commit_tx_and_block();
+        // for the next block
+        env.state.in_mem_mut().begin_block(BlockHeight(2)).unwrap();
+        env.state
+            .in_mem_mut()
+            .set_header(get_dummy_header())
+            .unwrap();
+        tx_host_env::set(env);
+
+        // Start a transaction to acknowledge the forwarded packet with an
+        // error
+        let mut msg = ibc::msg_packet_ack(forwarded);
+        msg.acknowledgement = ibc::transfer_ack_with_error().into();
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        // ack the packet with the message
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute::<token::Transfer>(&tx_data)
+            .expect("the ack failed");
+
+        // Check
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(
+            &env,
+            &tx.batch_ref_first_tx().unwrap(),
+        );
+        assert!(
+            result.is_ok(),
+            "Expected VP to accept the tx, got {result:?}"
+        );
+        // Check if the tokens were sent back to the original sender
+        assert!(!env.state.has_key(&in_flight_key).expect("read error"));
+        let sent_packets = ibc::sent_packets(&env);
+        assert_eq!(sent_packets.len(), 1);
+        let refund = &sent_packets[0];
+        assert_eq!(refund.seq_on_a, ibc::Sequence::from(2));
+        assert_eq!(refund.chan_id_on_a, channel_id);
+        let refund_commitment_key =
+            ibc_storage::commitment_key(&port_id, &channel_id, refund.seq_on_a);
+        assert!(
+            env.state
+                .has_key(&refund_commitment_key)
+                .expect("read error")
+        );
+        // The refund isn't tracked
+        let refund_in_flight_key = ibc_storage::in_flight_forward_key(
+            &port_id,
+            &channel_id,
+            refund.seq_on_a,
+        );
+        assert!(
+            !env.state
+                .has_key(&refund_in_flight_key)
+                .expect("read error")
+        );
+    }
+
+    #[test]
+    fn test_ibc_ica_host_transfer() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, receiver) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.state.write_bytes(&key, &val).expect("write error");
+            });
+        });
+
+        // Start a transaction for ChannelOpenTry on the host port
+        let controller_port_id =
+            ibc::PortId::new("icacontroller-cosmos1owner".to_string()).unwrap();
+        let msg = ibc::msg_ica_channel_open_try(
+            conn_id.clone(),
+            controller_port_id.clone(),
+        );
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs.clone(), pks_map.clone(), None)
+            .sign_wrapper(keypair.clone());
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute::<token::Transfer>(&tx_data)
+            .expect("creating the channel failed");
+
+        // Check
+        let mut env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(
+            &env,
+            &tx.batch_ref_first_tx().unwrap(),
+        );
+        assert!(result.is_ok());
+        let channel_id = ibc::ChannelId::new(0);
+        let active_channel = ibc_storage::ica_active_channel(
+            &env.state,
+            &controller_port_id,
+            &conn_id,
+        )
+        .expect("read error");
+        assert_eq!(active_channel, Some(channel_id.clone()));
+        let account = ica::host_account(&conn_id, &controller_port_id);
+        let key = ibc_storage::ica_account_key(&controller_port_id, &conn_id);
+        let stored: Option<String> = env.state.read(&key).expect("read error");
+        assert_eq!(stored, Some(account.to_string()));
+
+        // Commit
+        env.commit_tx_and_block();
+        // for the next block
+        env.state.in_mem_mut().begin_block(BlockHeight(2)).unwrap();
+        env.state
+            .in_mem_mut()
+            .set_header(get_dummy_header())
+            .unwrap();
+        tx_host_env::set(env);
+
+        // Start the next transaction for ChannelOpenConfirm
+        let msg = ibc::msg_channel_open_confirm(
+            ica::host_port_id(),
+            channel_id.clone(),
+        );
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs.clone(), pks_map.clone(), None)
+            .sign_wrapper(keypair.clone());
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute::<token::Transfer>(&tx_data)
+            .expect("opening the channel failed");
+
+        // Check
+        let mut env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(
+            &env,
+            &tx.batch_ref_first_tx().unwrap(),
+        );
+        assert!(result.is_ok());
+
+        // Fund the interchain account
+        let amount = Amount::native_whole(100);
+        env.credit_tokens(&account, &token, amount);
+        // Commit
+        env.commit_tx_and_block();
+        // for the next block
+        env.state.in_mem_mut().begin_block(BlockHeight(3)).unwrap();
+        env.state
+            .in_mem_mut()
+            .set_header(get_dummy_header())
+            .unwrap();
+        tx_host_env::set(env);
+
+        // Receive a packet to transfer the tokens from the interchain account
+        let send = ica::MsgSend {
+            from_address: account.to_string(),
+            to_address: receiver.to_string(),
+            amount: vec![ica::Coin {
+                denom: token.to_string(),
+                amount: amount.to_string(),
+            }],
+        };
+        let packet = ibc::ica_received_packet(
+            controller_port_id,
+            channel_id,
+            ibc::Sequence::from(1),
+            vec![send.into()],
+        );
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        let mut actions = tx_host_env::ibc::ibc_actions(tx::ctx());
+        actions
+            .execute::<token::Transfer>(&tx_data)
+            .expect("receiving the packet failed");
+        assert!(actions.is_receiving_success(&msg).unwrap());
+
+        // Check
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(
+            &env,
+            &tx.batch_ref_first_tx().unwrap(),
+        );
+        assert!(result.is_ok());
+        // The interchain account must be a verifier to be debited
+        assert!(env.verifiers.contains(&account));
+        // Check the balances
+        let key = token::storage_key::balance_key(&token, &account);
+        let balance: Option<Amount> = env.state.read(&key).expect("read error");
+        assert_eq!(balance, Some(Amount::zero()));
+        let key = token::storage_key::balance_key(&token, &receiver);
+        let balance: Option<Amount> = env.state.read(&key).expect("read error");
+        assert_eq!(
+            balance,
+            Some(Amount::native_whole(100).checked_add(amount).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_ibc_forward_to_unknown_channel_acked_with_error() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, _) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        writes.extend(channel_writes);
+
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.state.write_bytes(&key, &val).expect("write error");
+            });
+        });
+
+        // packet to be forwarded through a channel that doesn't exist
+        let forward = ForwardMetadata {
+            receiver: "cosmos1receiver".to_string(),
+            port_id: port_id.clone(),
+            channel_id: ibc::ChannelId::new(99),
+            timeout: DEFAULT_FORWARD_TIMEOUT,
+            next: None,
+        };
+        let packet = ibc::forward_received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &forward,
+        );
+
+        // Start a transaction to receive a packet
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        // receiving the packet doesn't fail, but the packet is acknowledged
+        // with an error
+        let mut actions = tx_host_env::ibc::ibc_actions(tx::ctx());
+        actions
+            .execute::<token::Transfer>(&tx_data)
+            .expect("receiving the packet failed");
+        assert!(!actions.is_receiving_success(&msg).unwrap());
+
+        // Check
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(
+            &env,
+            &tx.batch_ref_first_tx().unwrap(),
+        );
+        assert!(
+            result.is_ok(),
+            "Expected VP to accept the tx, got {result:?}"
+        );
+        // Nothing is forwarded and the tokens aren't received
+        assert!(ibc::sent_packets(&env).is_empty());
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let key = ibc::balance_key_with_ibc_prefix(
+            denom,
+            &address::Address::Internal(address::InternalAddress::Ibc),
+        );
+        let balance: Option<Amount> = env.state.read(&key).expect("read error");
+        assert_eq!(balance, None);
+    }
+
+    #[test]
+    fn test_ibc_ica_host_debit_without_packet_rejected() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, receiver) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        writes.extend(channel_writes);
+
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.state.write_bytes(&key, &val).expect("write error");
+            });
+        });
+
+        // Fund the interchain account
+        let controller_port_id =
+            ibc::PortId::new("icacontroller-cosmos1owner".to_string()).unwrap();
+        let account = ica::host_account(&conn_id, &controller_port_id);
+        let amount = Amount::native_whole(100);
+        tx_host_env::with(|env| env.credit_tokens(&account, &token, amount));
+
+        // Start a transaction to receive an unrelated packet
+        let packet = ibc::received_packet(
+            port_id,
+            channel_id,
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &receiver,
+        );
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute::<token::Transfer>(&tx_data)
+            .expect("receiving the token failed");
+        // Debit the interchain account in the same transaction
+        namada_tx_prelude::token::transfer(
+            tx::ctx(),
+            &account,
+            &receiver,
+            &token,
+            amount,
+        )
+        .expect("transfer failed");
+
+        // Check
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(
+            &env,
+            &tx.batch_ref_first_tx().unwrap(),
+        );
+        assert!(
+            result.is_err(),
+            "Expected VP to reject the tx, got {result:?}"
+        );
+    }
+
     #[test]
     fn test_ibc_receive_no_token() {
         // The environment must be initialized first
@@ -234,6 +234,7 @@ impl TestTxEnv {
             &self.batched_tx.cmt,
             &mut self.vp_wasm_cache,
             &mut self.tx_wasm_cache,
+            None,
         )
         .and(Ok(()))
     }
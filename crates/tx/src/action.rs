@@ -15,7 +15,7 @@ use namada_core::storage::KeySeg;
 use namada_core::{address, storage};
 
 pub use crate::data::pos::{
-    Bond, ClaimRewards, Redelegation, Unbond, Withdraw,
+    AutoCompound, Bond, ClaimRewards, Redelegation, Unbond, Withdraw,
 };
 
 /// Actions applied from txs.
@@ -48,6 +48,7 @@ pub enum PosAction {
     CommissionChange(Address),
     MetadataChange(Address),
     ConsensusKeyChange(Address),
+    AutoCompound(AutoCompound),
 }
 
 /// Gov tx actions.
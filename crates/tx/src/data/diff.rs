@@ -0,0 +1,54 @@
+//! The changes that a transaction would apply to the state, returned when dry
+//! running it with a diff
+
+use std::collections::BTreeSet;
+
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::storage;
+use namada_events::Event;
+use serde::{Deserialize, Serialize};
+
+use crate::data::DryRunResult;
+
+/// The change of the value of a storage key
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct KeyDiff {
+    /// The modified storage key
+    pub key: storage::Key,
+    /// The value before the transaction, if any
+    pub pre: Option<Vec<u8>>,
+    /// The value after the transaction, [`None`] if the key was deleted
+    pub post: Option<Vec<u8>>,
+}
+
+impl KeyDiff {
+    /// Check if the key was deleted by the transaction
+    pub fn is_deleted(&self) -> bool {
+        self.post.is_none()
+    }
+}
+
+/// The result of a dry run together with the changes it would apply
+#[derive(Debug, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct DiffedDryRunResult {
+    /// The result of the dry run
+    pub result: DryRunResult,
+    /// The storage changes of the wrapper and of the accepted inner
+    /// transactions, sorted by their storage key
+    pub diff: Vec<KeyDiff>,
+    /// The events that would be emitted by the accepted inner transactions
+    pub events: BTreeSet<Event>,
+    /// The addresses of all the VPs triggered by the inner transactions,
+    /// whether they accepted them or not
+    pub triggered_vps: BTreeSet<Address>,
+}
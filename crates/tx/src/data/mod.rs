@@ -1,5 +1,7 @@
 //! Data-Types that are used in transactions.
 
+/// state diffs of dry runs
+pub mod diff;
 pub mod eval_vp;
 /// txs to manage pgf
 pub mod pgf;
@@ -7,6 +9,8 @@ pub mod pgf;
 pub mod pos;
 /// transaction protocols made by validators
 pub mod protocol;
+/// traces of wasm executions
+pub mod trace;
 /// wrapper txs
 pub mod wrapper;
 
@@ -85,6 +89,8 @@ pub enum ResultCode {
     TooLarge = 11,
     /// Tx code is not allowlisted
     TxNotAllowlisted = 12,
+    /// Tx is larger than the block space guaranteed to wrapper txs
+    ExceedsBlockSpace = 13,
     // =========================================================================
     // WARN: These codes shouldn't be changed between version!
 }
@@ -100,7 +106,8 @@ impl ResultCode {
             Ok | WasmRuntimeError => true,
             InvalidTx | InvalidSig | AllocationError | ReplayTx
             | InvalidChainId | ExpiredTx | TxGasLimit | FeeError
-            | InvalidVoteExtension | TooLarge | TxNotAllowlisted => false,
+            | InvalidVoteExtension | TooLarge | TxNotAllowlisted
+            | ExceedsBlockSpace => false,
         }
     }
 
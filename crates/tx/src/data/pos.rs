@@ -130,6 +130,31 @@ pub struct ClaimRewards {
     pub source: Option<Address>,
 }
 
+/// A change of the auto-compounding of a bond's rewards.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct AutoCompound {
+    /// Validator address
+    pub validator: Address,
+    /// Source address of the bond. For self-bonds, the validator is also the
+    /// source
+    pub source: Option<Address>,
+    /// Whether the rewards of the bond should be re-bonded automatically
+    pub enabled: bool,
+}
+
 /// A redelegation of bonded tokens from one validator to another.
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(
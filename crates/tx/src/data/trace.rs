@@ -0,0 +1,107 @@
+//! Traces of the WASM executions of a transaction, recorded when dry running
+//! it with tracing enabled
+
+use std::collections::BTreeMap;
+
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::hash::Hash;
+use namada_core::storage;
+use namada_gas::Gas;
+use serde::{Deserialize, Serialize};
+
+use crate::data::DryRunResult;
+
+/// A call from a WASM guest to a host function
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct HostCall {
+    /// The name of the host function, e.g. `tx_read` or `vp_eval`
+    pub name: String,
+    /// The gas charged during the call
+    pub gas: Gas,
+    /// The storage keys (or key prefixes, for iterators) touched by the call
+    pub keys: Vec<storage::Key>,
+}
+
+/// The trace of a single WASM execution
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct WasmTrace {
+    /// The host calls, in the order in which they were made. Calls to the
+    /// injected gas metering hook are not included.
+    pub host_calls: Vec<HostCall>,
+    /// The gas consumed by the whole execution. The difference with the gas
+    /// charged in host calls is the gas of the WASM instructions, of the
+    /// compilation of the code and of the loading of the VP.
+    pub gas: Gas,
+}
+
+impl WasmTrace {
+    /// Record a new host call
+    pub fn start_host_call(&mut self, name: &str) {
+        self.host_calls.push(HostCall {
+            name: name.to_owned(),
+            ..Default::default()
+        });
+    }
+
+    /// Record a storage key touched by the last host call
+    pub fn touch_key(&mut self, key: &storage::Key) {
+        if let Some(call) = self.host_calls.last_mut() {
+            call.keys.push(key.clone());
+        }
+    }
+
+    /// Set the gas charged by the last host call
+    pub fn end_host_call(&mut self, gas: Gas) {
+        if let Some(call) = self.host_calls.last_mut() {
+            call.gas = gas;
+        }
+    }
+}
+
+/// The traces of an inner transaction of a batch. Only the WASM VPs are
+/// traced, native VPs don't make any host calls.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct BatchedTxTrace {
+    /// The trace of the transaction code
+    pub tx: WasmTrace,
+    /// The traces of the triggered WASM VPs, by the address of their owner
+    pub vps: BTreeMap<Address, WasmTrace>,
+}
+
+/// The result of a dry run together with the traces of its WASM executions
+#[derive(Debug, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct TracedDryRunResult {
+    /// The result of the dry run
+    pub result: DryRunResult,
+    /// The traces of the inner transactions, indexed by the same inner tx
+    /// hashes as the result
+    pub traces: BTreeMap<Hash, BatchedTxTrace>,
+}
@@ -46,6 +46,10 @@ pub mod types {
     /// Applied transaction.
     pub const APPLIED: EventType =
         namada_events::event_type!(TxEvent, "applied");
+
+    /// Applied scheduled transaction.
+    pub const SCHEDULED_APPLIED: EventType =
+        namada_events::event_type!(TxEvent, "scheduled", "applied");
 }
 
 /// Creates a new event with the hash and height of the transaction
@@ -64,6 +68,16 @@ pub fn new_tx_event(tx: &Tx, height: u64) -> Event {
         .into()
 }
 
+/// Creates a new event for a raw transaction executed by the protocol from the
+/// scheduled transactions queue, with its hash and height already filled in.
+pub fn new_scheduled_tx_event(tx: &Tx, height: u64) -> Event {
+    Event::new(types::SCHEDULED_APPLIED, EventLevel::Tx)
+        .with(TxHash(tx.raw_header_hash()))
+        .with(Height(height.into()))
+        .with(Log(String::new()))
+        .into()
+}
+
 /// Extend an [`Event`] with result code data.
 pub struct Code(pub ResultCode);
 
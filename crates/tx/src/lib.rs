@@ -27,7 +27,7 @@ mod types;
 
 use data::TxType;
 pub use either;
-pub use event::new_tx_event;
+pub use event::{new_scheduled_tx_event, new_tx_event};
 pub use namada_core::key::SignableEthMessage;
 pub use section::{
     Authorization, Code, Commitment, CompressedAuthorization, Data, Header,
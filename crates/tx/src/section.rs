@@ -488,6 +488,50 @@ impl Authorization {
         }
     }
 
+    /// Sign the given section hash with the given signing function, which
+    /// holds the secret keys of the given public keys, and return a section
+    pub fn try_new_with<E>(
+        targets: Vec<namada_core::hash::Hash>,
+        public_keys: BTreeMap<u8, common::PublicKey>,
+        signer: Option<Address>,
+        mut sign: impl FnMut(
+            &common::PublicKey,
+            &namada_core::hash::Hash,
+        ) -> Result<common::Signature, E>,
+    ) -> Result<Self, E> {
+        let signer = if let Some(addr) = signer {
+            Signer::Address(addr)
+        } else {
+            assert!(
+                public_keys
+                    .keys()
+                    .cloned()
+                    .eq(0..(u8::try_from(public_keys.len())
+                        .expect("Number of PKs must not exceed `u8::MAX`"))),
+                "public keys must be enumerated when signer address is absent"
+            );
+            Signer::PubKeys(public_keys.values().cloned().collect())
+        };
+
+        // Commit to the given targets
+        let partial = Self {
+            targets,
+            signer,
+            signatures: BTreeMap::new(),
+        };
+        let target = partial.get_raw_hash();
+        let signatures = public_keys
+            .iter()
+            .map(|(index, public_key)| {
+                sign(public_key, &target).map(|sig| (*index, sig))
+            })
+            .collect::<Result<_, E>>()?;
+        Ok(Self {
+            signatures,
+            ..partial
+        })
+    }
+
     /// Hash this signature section
     pub fn hash<'a>(&self, hasher: &'a mut Sha256) -> &'a mut Sha256 {
         hasher.update(self.serialize_to_vec());
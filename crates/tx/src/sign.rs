@@ -9,7 +9,9 @@ use std::marker::PhantomData;
 use borsh::schema::{self, Declaration, Definition};
 use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
-use namada_core::key::{common, SerializeWithBorsh, SigScheme, Signable};
+use namada_core::key::{
+    common, SerializeWithBorsh, SigScheme, Signable, SignableBytes,
+};
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
@@ -116,6 +118,21 @@ impl<T, S: Signable<T>> Signed<T, S> {
         Self::new_from(data, sig)
     }
 
+    /// Initialize a new [`Signed`] instance with a function signing the
+    /// hash of the data, e.g. with a key that isn't held by this process.
+    pub fn try_new_with<E>(
+        data: T,
+        sign: impl FnOnce(
+            &namada_core::hash::Hash,
+        ) -> std::result::Result<common::Signature, E>,
+    ) -> std::result::Result<Self, E> {
+        let to_sign = S::as_signable(&data);
+        let hash =
+            namada_core::hash::Hash(to_sign.signable_hash::<S::Hasher>());
+        let sig = sign(&hash)?;
+        Ok(Self::new_from(data, sig))
+    }
+
     /// Verify that the data has been signed by the secret key
     /// counterpart of the given public key.
     pub fn verify(
@@ -281,4 +298,25 @@ mod test {
             Err(VerifySigError::VerifySig(_))
         );
     }
+
+    #[test]
+    fn test_signing_with_function() {
+        let sk = key::testing::keypair_1();
+        let data = vec![30_u8, 1, 5];
+        let expected = Signed::<_, SerializeWithBorsh>::new(&sk, data.clone());
+
+        // Signing the hash of the data is the same as signing the data
+        let signed = Signed::<_, SerializeWithBorsh>::try_new_with(
+            data.clone(),
+            |hash| Ok::<_, ()>(key::common::SigScheme::sign(&sk, hash)),
+        )
+        .unwrap();
+        assert_eq!(signed, expected);
+        assert_matches!(signed.verify(&sk.to_public()), Ok(()));
+
+        assert_matches!(
+            Signed::<_, SerializeWithBorsh>::try_new_with(data, |_| Err(())),
+            Err(())
+        );
+    }
 }
@@ -725,6 +725,62 @@ impl Tx {
         self
     }
 
+    /// Add fee payer signature made by the given signing function to the tx
+    /// builder
+    pub fn try_sign_wrapper_with<E>(
+        &mut self,
+        fee_payer: common::PublicKey,
+        sign: impl FnMut(
+            &common::PublicKey,
+            &namada_core::hash::Hash,
+        ) -> Result<common::Signature, E>,
+    ) -> Result<&mut Self, E> {
+        self.protocol_filter();
+        let auth = Authorization::try_new_with(
+            self.sechashes(),
+            [(0, fee_payer)].into_iter().collect(),
+            None,
+            sign,
+        )?;
+        self.add_section(Section::Authorization(auth));
+        Ok(self)
+    }
+
+    /// Add signatures made by the given signing function for the given public
+    /// keys to the tx builder
+    pub fn try_sign_raw_with<E>(
+        &mut self,
+        public_keys: Vec<common::PublicKey>,
+        account_public_keys_map: AccountPublicKeysMap,
+        signer: Option<Address>,
+        sign: impl FnMut(
+            &common::PublicKey,
+            &namada_core::hash::Hash,
+        ) -> Result<common::Signature, E>,
+    ) -> Result<&mut Self, E> {
+        // The inner tx signer signs the Raw version of the Header
+        let hashes = vec![self.raw_header_hash()];
+        self.protocol_filter();
+
+        let public_keys = if signer.is_some() {
+            public_keys
+                .into_iter()
+                .filter_map(|public_key| {
+                    account_public_keys_map
+                        .get_index_from_public_key(&public_key)
+                        .map(|index| (index, public_key))
+                })
+                .collect()
+        } else {
+            (0..).zip(public_keys).collect()
+        };
+
+        let auth =
+            Authorization::try_new_with(hashes, public_keys, signer, sign)?;
+        self.add_section(Section::Authorization(auth));
+        Ok(self)
+    }
+
     /// Add signing keys to the tx builder
     pub fn sign_raw(
         &mut self,
@@ -1303,6 +1359,56 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_tx_signing_with_function() {
+        let sk1 = key::testing::keypair_1();
+        let pk1 = sk1.to_public();
+        let pks_map = AccountPublicKeysMap::from_iter(vec![pk1.clone()]);
+
+        let mut tx = Tx::default();
+        tx.add_wrapper(
+            data::wrapper::Fee {
+                amount_per_gas_unit: DenominatedAmount::native(1.into()),
+                token: nam(),
+            },
+            pk1.clone(),
+            1.into(),
+        );
+        let sign = |pk: &common::PublicKey, hash: &namada_core::hash::Hash| {
+            assert_eq!(*pk, sk1.to_public());
+            Ok::<_, ()>(common::SigScheme::sign(&sk1, hash))
+        };
+
+        // Signing with a function holding the keys is the same as signing
+        // with the keys
+        let mut expected = tx.clone();
+        expected.sign_raw(vec![sk1.clone()], pks_map.clone(), None);
+        expected.sign_wrapper(sk1.clone());
+        let mut actual = tx.clone();
+        actual
+            .try_sign_raw_with(vec![pk1.clone()], pks_map.clone(), None, sign)
+            .unwrap()
+            .try_sign_wrapper_with(pk1, sign)
+            .unwrap();
+        assert_eq!(actual.serialize_to_vec(), expected.serialize_to_vec());
+        actual
+            .validate_tx()
+            .expect("valid tx")
+            .expect("with authorization");
+
+        // The signing error is returned
+        let mut tx = tx.clone();
+        assert!(
+            tx.try_sign_raw_with(
+                vec![sk1.to_public()],
+                pks_map,
+                None,
+                |_, _| Err(())
+            )
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_inner_tx_sections() {
         let mut tx = Tx::default();
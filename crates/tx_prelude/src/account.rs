@@ -18,3 +18,19 @@ pub fn init_account(
         data.threshold,
     )
 }
+
+/// Init the storage of a new vesting account
+#[inline]
+pub fn init_vesting_account(
+    ctx: &mut Ctx,
+    owner: &Address,
+    data: InitVestingAccount,
+) -> Result<()> {
+    namada_account::init_vesting_account_storage(
+        ctx,
+        owner,
+        &data.public_keys,
+        data.threshold,
+        &data.schedule,
+    )
+}
@@ -14,8 +14,8 @@ pub use namada_ibc::storage::{
 };
 pub use namada_ibc::trace::ibc_token;
 pub use namada_ibc::{
-    IbcActions, IbcCommonContext, IbcStorageContext, NftTransferModule,
-    ProofSpec, TransferModule,
+    IbcActions, IbcCommonContext, IbcStorageContext, InterchainAccountModule,
+    NftTransferModule, ProofSpec, TransferModule,
 };
 use namada_tx_env::TxEnv;
 
@@ -32,7 +32,9 @@ pub fn ibc_actions(
     let mut actions = IbcActions::new(ctx.clone(), verifiers.clone());
     let module = TransferModule::new(ctx.clone(), verifiers);
     actions.add_transfer_module(module);
-    let module = NftTransferModule::<Ctx, token::Store<Ctx>>::new(ctx);
+    let module = NftTransferModule::<Ctx, token::Store<Ctx>>::new(ctx.clone());
+    actions.add_transfer_module(module);
+    let module = InterchainAccountModule::<Ctx, token::Store<Ctx>>::new(ctx);
     actions.add_transfer_module(module);
     actions
 }
@@ -18,6 +18,7 @@ pub mod key;
 pub mod pgf;
 pub mod proof_of_stake;
 pub mod token;
+pub mod tx_scheduler;
 
 use core::slice;
 use std::marker::PhantomData;
@@ -10,13 +10,15 @@ use namada_proof_of_stake::{
     become_validator, bond_tokens, change_consensus_key,
     change_validator_commission_rate, change_validator_metadata,
     claim_reward_tokens, deactivate_validator, reactivate_validator,
-    redelegate_tokens, unbond_tokens, unjail_validator, withdraw_tokens,
+    redelegate_tokens, set_auto_compound, unbond_tokens, unjail_validator,
+    withdraw_tokens,
 };
 pub use namada_proof_of_stake::{
     is_validator, parameters, storage, storage_key, types,
 };
 use namada_tx::action::{
-    Action, ClaimRewards, PosAction, Redelegation, Unbond, Withdraw, Write,
+    Action, AutoCompound, ClaimRewards, PosAction, Redelegation, Unbond,
+    Withdraw, Write,
 };
 use namada_tx::data::pos::{BecomeValidator, Bond};
 
@@ -222,6 +224,26 @@ impl Ctx {
         )
     }
 
+    /// Enable or disable the auto-compounding of a bond's rewards
+    pub fn set_auto_compound(
+        &mut self,
+        source: Option<&Address>,
+        validator: &Address,
+        enabled: bool,
+    ) -> TxResult {
+        // The tx must be authorized by the source address
+        let verifier = source.as_ref().unwrap_or(&validator);
+        self.insert_verifier(verifier)?;
+
+        self.push_action(Action::Pos(PosAction::AutoCompound(AutoCompound {
+            validator: validator.clone(),
+            source: source.cloned(),
+            enabled,
+        })))?;
+
+        set_auto_compound(self, source, validator, enabled)
+    }
+
     /// Attempt to initialize a validator account. On success, returns the
     /// initialized validator account's address.
     pub fn become_validator(
@@ -0,0 +1,19 @@
+//! Scheduled transactions related functions.
+
+pub use namada_tx_scheduler::*;
+
+use super::*;
+
+/// Schedule an inner tx for a later execution, escrowing its fees from the
+/// balance of the fee payer
+pub fn schedule_tx(ctx: &mut Ctx, scheduled: &ScheduledTx) -> TxResult {
+    token::transfer(
+        ctx,
+        &scheduled.fee_payer,
+        &TX_SCHEDULER,
+        &scheduled.fee_token,
+        scheduled.fee,
+    )?;
+    let key = scheduled_tx_key(&scheduled.schedule, &scheduled.tx_hash);
+    ctx.write(&key, scheduled)
+}
@@ -0,0 +1,37 @@
+//! Scheduled transactions
+//!
+//! Inner transactions can be scheduled for execution at a future block
+//! height or epoch. The fees of a scheduled tx are escrowed at the time of
+//! scheduling in the balance of the [`TX_SCHEDULER`] internal address and
+//! the protocol executes the tx at the start of the scheduled block, paying
+//! the escrowed fees to the block proposer. No key has to be online when the
+//! tx gets executed.
+
+#![doc(html_favicon_url = "https://dev.namada.net/master/favicon.png")]
+#![doc(html_logo_url = "https://dev.namada.net/master/rustdoc-logo.png")]
+#![deny(rustdoc::broken_intra_doc_links)]
+#![deny(rustdoc::private_intra_doc_links)]
+#![warn(
+    missing_docs,
+    rust_2018_idioms,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_lossless,
+    clippy::arithmetic_side_effects,
+    clippy::dbg_macro,
+    clippy::print_stdout,
+    clippy::print_stderr
+)]
+
+pub mod vp;
+
+pub use namada_core::address::TX_SCHEDULER;
+pub use namada_state::tx_queue::{
+    is_scheduled_tx_key, read_scheduled_txs, scheduled_tx_key,
+    scheduled_txs_prefix, ScheduledTx, TxSchedule,
+};
+
+/// The maximum number of txs that can be scheduled for the same block height
+/// or epoch
+pub const MAX_SCHEDULED_TXS: usize = 64;
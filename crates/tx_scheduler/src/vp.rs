@@ -0,0 +1,237 @@
+//! Native VP for scheduled transactions
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::marker::PhantomData;
+
+use namada_core::address::{Address, TX_SCHEDULER};
+use namada_core::token;
+use namada_systems::{parameters, trans_token};
+use namada_tx::data::TxType;
+use namada_tx::{BatchedTxRef, Section, Tx};
+use namada_vp_env::{Error, Key, Result, VpEnv};
+
+use crate::{
+    is_scheduled_tx_key, read_scheduled_txs, ScheduledTx, TxSchedule,
+    MAX_SCHEDULED_TXS,
+};
+
+/// Scheduled transactions VP
+pub struct TxSchedulerVp<'ctx, CTX, Params, TransToken> {
+    /// Generic types for VP context and DI
+    pub _marker: PhantomData<(&'ctx CTX, Params, TransToken)>,
+}
+
+impl<'ctx, CTX, Params, TransToken> TxSchedulerVp<'ctx, CTX, Params, TransToken>
+where
+    CTX: VpEnv<'ctx>,
+    Params: parameters::Read<<CTX as VpEnv<'ctx>>::Pre>,
+    TransToken: trans_token::Keys,
+{
+    /// Run the validity predicate
+    pub fn validate_tx(
+        ctx: &'ctx CTX,
+        _batched_tx: &BatchedTxRef<'_>,
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<()> {
+        // The fees escrowed by the newly scheduled txs, by token
+        let mut escrowed: BTreeMap<Address, token::Amount> = BTreeMap::new();
+        // The tokens whose balance of the scheduler has changed
+        let mut changed_balances: BTreeSet<Address> = BTreeSet::new();
+        // The times at which new txs have been scheduled
+        let mut schedules: Vec<TxSchedule> = vec![];
+
+        for key in keys_changed {
+            if let Some([token, owner]) =
+                TransToken::is_any_token_balance_key(key)
+            {
+                if *owner == TX_SCHEDULER {
+                    changed_balances.insert(token.clone());
+                }
+                continue;
+            }
+            if is_scheduled_tx_key(key).is_none() {
+                return Err(Error::new_alloc(format!(
+                    "Unexpected change to the scheduled txs storage: {key}"
+                )));
+            }
+            let scheduled = Self::validate_scheduled_tx(ctx, key, verifiers)?;
+            if !schedules.contains(&scheduled.schedule) {
+                schedules.push(scheduled.schedule);
+            }
+            let total = escrowed.entry(scheduled.fee_token).or_default();
+            *total = total.checked_add(scheduled.fee).ok_or_else(|| {
+                Error::new_const("Overflow in the escrowed fees")
+            })?;
+        }
+
+        for schedule in &schedules {
+            Self::validate_schedule_capacity(ctx, schedule)?;
+        }
+
+        // The balance of the scheduler can only increase by the fees escrowed
+        // by the newly scheduled txs
+        for token in changed_balances.iter().chain(escrowed.keys()) {
+            let balance_key = TransToken::balance_key(token, &TX_SCHEDULER);
+            let pre: token::Amount =
+                ctx.read_pre(&balance_key)?.unwrap_or_default();
+            let post: token::Amount =
+                ctx.read_post(&balance_key)?.unwrap_or_default();
+            let expected = escrowed.get(token).copied().unwrap_or_default();
+            if post.checked_sub(pre) != Some(expected) {
+                return Err(Error::new_alloc(format!(
+                    "The scheduler's balance of token {token} must increase \
+                     by exactly the escrowed fees of {}, but it went from {} \
+                     to {}",
+                    expected.to_string_native(),
+                    pre.to_string_native(),
+                    post.to_string_native(),
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate that the txs scheduled at the given time don't exceed the
+    /// number of txs and the gas that can be executed in a single block. The
+    /// first block of an epoch may execute both the txs scheduled for its
+    /// height and for its epoch, so the txs of each schedule may only use
+    /// half of the max block gas.
+    fn validate_schedule_capacity(
+        ctx: &'ctx CTX,
+        schedule: &TxSchedule,
+    ) -> Result<()> {
+        let scheduled_txs = read_scheduled_txs(&ctx.post(), schedule)?;
+        if scheduled_txs.len() > MAX_SCHEDULED_TXS {
+            return Err(Error::new_alloc(format!(
+                "At most {MAX_SCHEDULED_TXS} txs can be scheduled at \
+                 {schedule:?}, got {}",
+                scheduled_txs.len()
+            )));
+        }
+
+        let params = Params::read(&ctx.pre())?;
+        let max_gas = params.max_block_gas / 2;
+        let total_gas = scheduled_txs
+            .iter()
+            .try_fold(0_u64, |total, (_key, scheduled)| {
+                total.checked_add(scheduled.gas_limit)
+            })
+            .ok_or_else(|| {
+                Error::new_const("Overflow in the gas of the scheduled txs")
+            })?;
+        if total_gas > max_gas {
+            return Err(Error::new_alloc(format!(
+                "The total gas limit of the txs scheduled at {schedule:?} \
+                 must not exceed {max_gas}, got {total_gas}"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Validate a newly scheduled tx written under the given key
+    fn validate_scheduled_tx(
+        ctx: &'ctx CTX,
+        key: &Key,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<ScheduledTx> {
+        if ctx.has_key_pre(key)? {
+            return Err(Error::new_alloc(format!(
+                "Scheduled txs cannot be modified or removed: {key}"
+            )));
+        }
+        let scheduled: ScheduledTx = ctx.read_post(key)?.ok_or_else(|| {
+            Error::new_alloc(format!("Missing scheduled tx: {key}"))
+        })?;
+        if is_scheduled_tx_key(key)
+            != Some((scheduled.schedule, scheduled.tx_hash))
+        {
+            return Err(Error::new_alloc(format!(
+                "The scheduled tx doesn't match its storage key {key}"
+            )));
+        }
+
+        // Only the raw inner txs of this chain can be scheduled
+        let tx = Tx::try_from_bytes(&scheduled.tx).map_err(|err| {
+            Error::new_alloc(format!(
+                "Failed to decode the scheduled tx: {err}"
+            ))
+        })?;
+        if !matches!(tx.header.tx_type, TxType::Raw) {
+            return Err(Error::new_const("Only raw txs can be scheduled"));
+        }
+        if tx.raw_header_hash() != scheduled.tx_hash {
+            return Err(Error::new_const(
+                "The scheduled tx hash doesn't match the tx",
+            ));
+        }
+        if tx.header.chain_id != ctx.get_chain_id()? {
+            return Err(Error::new_const(
+                "The scheduled tx was built for another chain",
+            ));
+        }
+        if tx.header.batch.is_empty() {
+            return Err(Error::new_const("The scheduled tx batch is empty"));
+        }
+        // The shielded sync reads the MASP txs from the blocks, which don't
+        // contain the scheduled txs
+        if tx
+            .sections
+            .iter()
+            .any(|section| matches!(section, Section::MaspTx(_)))
+        {
+            return Err(Error::new_const("Shielded txs cannot be scheduled"));
+        }
+
+        let height = ctx.get_block_height()?;
+        let epoch = ctx.get_block_epoch()?;
+        if !scheduled.schedule.is_after(height, epoch) {
+            return Err(Error::new_alloc(format!(
+                "Txs can only be scheduled in the future, got {:?} at height \
+                 {height} and epoch {epoch}",
+                scheduled.schedule
+            )));
+        }
+
+        // The escrowed fees must cover the gas limit at the minimum gas price
+        let params = Params::read(&ctx.pre())?;
+        if scheduled.gas_limit == 0
+            || scheduled.gas_limit > params.max_block_gas
+        {
+            return Err(Error::new_alloc(format!(
+                "The gas limit of a scheduled tx must be between 1 and the \
+                 max block gas of {}, got {}",
+                params.max_block_gas, scheduled.gas_limit
+            )));
+        }
+        let min_gas_price = params
+            .minimum_gas_price
+            .get(&scheduled.fee_token)
+            .ok_or_else(|| {
+                Error::new_alloc(format!(
+                    "Fee payment in token {} is not allowed",
+                    scheduled.fee_token
+                ))
+            })?;
+        let min_fee = min_gas_price
+            .checked_mul(token::Amount::from_u64(scheduled.gas_limit))
+            .ok_or_else(|| Error::new_const("Overflow in the minimum fee"))?;
+        if scheduled.fee < min_fee {
+            return Err(Error::new_alloc(format!(
+                "The escrowed fee of {} doesn't cover the minimum fee of {}",
+                scheduled.fee.to_string_native(),
+                min_fee.to_string_native()
+            )));
+        }
+        if !verifiers.contains(&scheduled.fee_payer) {
+            return Err(Error::new_alloc(format!(
+                "The fee payer {} of the scheduled tx must be a verifier",
+                scheduled.fee_payer
+            )));
+        }
+
+        Ok(scheduled)
+    }
+}
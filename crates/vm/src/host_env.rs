@@ -32,6 +32,7 @@ use namada_token::storage_key::{
     is_any_token_parameter_key,
 };
 use namada_token::MaspTransaction;
+use namada_tx::data::trace::WasmTrace;
 use namada_tx::data::TxSentinel;
 use namada_tx::{BatchedTx, BatchedTxRef, Tx, TxCommitments};
 use namada_vp::vp_host_fns;
@@ -128,6 +129,8 @@ where
     pub result_buffer: HostRef<RwAccess, Option<Vec<u8>>>,
     /// Storage for byte buffer values yielded from the guest.
     pub yielded_value: HostRef<RwAccess, Option<Vec<u8>>>,
+    /// The trace of the host calls, only recorded when tracing is enabled.
+    pub trace: Option<HostRef<RwAccess, WasmTrace>>,
     /// VP WASM compilation cache (this is available in tx context, because
     /// we're pre-compiling VPs from [`tx_init_account`])
     #[cfg(feature = "wasm-runtime")]
@@ -168,6 +171,7 @@ where
         verifiers: &mut BTreeSet<Address>,
         result_buffer: &mut Option<Vec<u8>>,
         yielded_value: &mut Option<Vec<u8>>,
+        trace: Option<&mut WasmTrace>,
         #[cfg(feature = "wasm-runtime")] vp_wasm_cache: &mut VpCache<CA>,
         #[cfg(feature = "wasm-runtime")] tx_wasm_cache: &mut TxCache<CA>,
     ) -> Self {
@@ -183,6 +187,7 @@ where
         let verifiers = unsafe { RwHostRef::new(verifiers) };
         let result_buffer = unsafe { RwHostRef::new(result_buffer) };
         let yielded_value = unsafe { RwHostRef::new(yielded_value) };
+        let trace = trace.map(|trace| unsafe { RwHostRef::new(trace) });
         #[cfg(feature = "wasm-runtime")]
         let vp_wasm_cache = unsafe { RwHostRef::new(vp_wasm_cache) };
         #[cfg(feature = "wasm-runtime")]
@@ -200,6 +205,7 @@ where
             verifiers,
             result_buffer,
             yielded_value,
+            trace,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache,
             #[cfg(feature = "wasm-runtime")]
@@ -262,6 +268,14 @@ where
         let sentinel = unsafe { self.sentinel.get() };
         (gas_meter, sentinel)
     }
+
+    /// Record a storage key touched by the current host call, if tracing is
+    /// enabled
+    pub fn trace_key(&self, key: &Key) {
+        if let Some(trace) = self.trace {
+            unsafe { trace.get_mut() }.touch_key(key);
+        }
+    }
 }
 
 impl<D, H, CA> Clone for TxCtx<D, H, CA>
@@ -284,6 +298,7 @@ where
             verifiers: self.verifiers,
             result_buffer: self.result_buffer,
             yielded_value: self.yielded_value,
+            trace: self.trace,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache: self.vp_wasm_cache,
             #[cfg(feature = "wasm-runtime")]
@@ -342,6 +357,8 @@ where
     /// The verifiers whose validity predicates should be triggered. Used for
     /// calls to `eval`.
     pub verifiers: HostRef<RoAccess, BTreeSet<Address>>,
+    /// The trace of the host calls, only recorded when tracing is enabled.
+    pub trace: Option<HostRef<RwAccess, WasmTrace>>,
     /// VP WASM compilation cache
     #[cfg(feature = "wasm-runtime")]
     pub vp_wasm_cache: HostRef<RwAccess, VpCache<CA>>,
@@ -405,6 +422,7 @@ where
         yielded_value: &mut Option<Vec<u8>>,
         keys_changed: &BTreeSet<Key>,
         eval_runner: &EVAL,
+        trace: Option<&mut WasmTrace>,
         #[cfg(feature = "wasm-runtime")] vp_wasm_cache: &mut VpCache<CA>,
     ) -> Self {
         let ctx = VpCtx::new(
@@ -422,6 +440,7 @@ where
             yielded_value,
             keys_changed,
             eval_runner,
+            trace,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache,
         );
@@ -481,6 +500,7 @@ where
         yielded_value: &mut Option<Vec<u8>>,
         keys_changed: &BTreeSet<Key>,
         eval_runner: &EVAL,
+        trace: Option<&mut WasmTrace>,
         #[cfg(feature = "wasm-runtime")] vp_wasm_cache: &mut VpCache<CA>,
     ) -> Self {
         let address = unsafe { RoHostRef::new(address) };
@@ -497,6 +517,7 @@ where
         let yielded_value = unsafe { RwHostRef::new(yielded_value) };
         let keys_changed = unsafe { RoHostRef::new(keys_changed) };
         let eval_runner = unsafe { RoHostRef::new(eval_runner) };
+        let trace = trace.map(|trace| unsafe { RwHostRef::new(trace) });
         #[cfg(feature = "wasm-runtime")]
         let vp_wasm_cache = unsafe { RwHostRef::new(vp_wasm_cache) };
         Self {
@@ -514,6 +535,7 @@ where
             yielded_value,
             keys_changed,
             verifiers,
+            trace,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache,
             #[cfg(not(feature = "wasm-runtime"))]
@@ -540,6 +562,14 @@ where
         let gas_meter = unsafe { self.gas_meter.get() };
         gas_meter
     }
+
+    /// Record a storage key touched by the current host call, if tracing is
+    /// enabled
+    pub fn trace_key(&self, key: &Key) {
+        if let Some(trace) = self.trace {
+            unsafe { trace.get_mut() }.touch_key(key);
+        }
+    }
 }
 
 impl<D, H, EVAL, CA> Clone for VpCtx<D, H, EVAL, CA>
@@ -565,6 +595,7 @@ where
             yielded_value: self.yielded_value,
             keys_changed: self.keys_changed,
             verifiers: self.verifiers,
+            trace: self.trace,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache: self.vp_wasm_cache,
             #[cfg(not(feature = "wasm-runtime"))]
@@ -634,6 +665,65 @@ where
     vp_host_fns::add_gas(gas_meter, used_gas.into())
 }
 
+/// Call a tx host function. When tracing is enabled, the call is recorded
+/// together with the gas that it charged.
+pub fn tx_traced_call<MEM, D, H, CA, RET>(
+    env: &mut TxVmEnv<MEM, D, H, CA>,
+    name: &str,
+    host_fn: impl FnOnce(&mut TxVmEnv<MEM, D, H, CA>) -> RET,
+) -> RET
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let Some(trace) = env.ctx.trace else {
+        return host_fn(env);
+    };
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let gas_before = gas_meter.borrow().get_tx_consumed_gas();
+    unsafe { trace.get_mut() }.start_host_call(name);
+    let result = host_fn(env);
+    let gas = gas_meter
+        .borrow()
+        .get_tx_consumed_gas()
+        .checked_sub(gas_before)
+        .unwrap_or_default();
+    unsafe { trace.get_mut() }.end_host_call(gas);
+    result
+}
+
+/// Call a VP host function. When tracing is enabled, the call is recorded
+/// together with the gas that it charged.
+pub fn vp_traced_call<MEM, D, H, EVAL, CA, RET>(
+    env: &mut VpVmEnv<MEM, D, H, EVAL, CA>,
+    name: &str,
+    host_fn: impl FnOnce(&mut VpVmEnv<MEM, D, H, EVAL, CA>) -> RET,
+) -> RET
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let Some(trace) = env.ctx.trace else {
+        return host_fn(env);
+    };
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let gas_before = gas_meter.borrow().get_vp_consumed_gas();
+    unsafe { trace.get_mut() }.start_host_call(name);
+    let result = host_fn(env);
+    let gas = gas_meter
+        .borrow()
+        .get_vp_consumed_gas()
+        .checked_sub(gas_before)
+        .unwrap_or_default();
+    unsafe { trace.get_mut() }.end_host_call(gas);
+    result
+}
+
 /// Storage `has_key` function exposed to the wasm VM Tx environment. It will
 /// try to check the write log first and if no entry found then the storage.
 pub fn tx_has_key<MEM, D, H, CA>(
@@ -656,6 +746,7 @@ where
     tracing::debug!("tx_has_key {}, key {}", key, key_ptr,);
 
     let key = Key::parse(key)?;
+    env.ctx.trace_key(&key);
 
     // try to read from the write log first
     let state = env.state();
@@ -688,6 +779,7 @@ where
     tracing::debug!("tx_read {}, key {}", key, key_ptr,);
 
     let key = Key::parse(key)?;
+    env.ctx.trace_key(&key);
 
     let state = env.state();
     let value = state.read_bytes(&key)?;
@@ -731,6 +823,7 @@ where
     tracing::debug!("tx_read {}, key {}", key, key_ptr,);
 
     let key = Key::parse(key)?;
+    env.ctx.trace_key(&key);
 
     let write_log = unsafe { env.ctx.write_log.get() };
     let (log_val, gas) = write_log.read_temp(&key).into_storage_result()?;
@@ -801,6 +894,7 @@ where
     tracing::debug!("tx_iter_prefix {}", prefix);
 
     let prefix = Key::parse(prefix)?;
+    env.ctx.trace_key(&prefix);
 
     let write_log = unsafe { env.ctx.write_log.get() };
     let db = unsafe { env.ctx.db.get() };
@@ -836,11 +930,11 @@ where
     let iter_id = PrefixIteratorId::new(iter_id);
     while let Some((key, val, iter_gas)) = iterators.next(iter_id) {
         let (log_val, log_gas) = {
+            let parsed_key = Key::parse(key.clone())?;
+            env.ctx.trace_key(&parsed_key);
             let state = env.state();
-            let (log_val, log_gas) = state
-                .write_log()
-                .read(&Key::parse(key.clone())?)
-                .into_storage_result()?;
+            let (log_val, log_gas) =
+                state.write_log().read(&parsed_key).into_storage_result()?;
             (log_val.cloned(), log_gas)
         };
         consume_tx_gas::<MEM, D, H, CA>(env, checked!(iter_gas + log_gas)?)?;
@@ -909,6 +1003,7 @@ where
     tracing::debug!("tx_update {}, {:?}", key, value);
 
     let key = Key::parse(key)?;
+    env.ctx.trace_key(&key);
     if key.is_validity_predicate().is_some() {
         tx_validate_vp_code_hash::<MEM, D, H, CA>(env, &value, &None)?;
     }
@@ -949,6 +1044,7 @@ where
     tracing::debug!("tx_write_temp {}, {:?}", key, value);
 
     let key = Key::parse(key)?;
+    env.ctx.trace_key(&key);
 
     check_address_existence::<MEM, D, H, CA>(env, &key)?;
 
@@ -1025,6 +1121,7 @@ where
     tracing::debug!("tx_delete {}", key);
 
     let key = Key::parse(key)?;
+    env.ctx.trace_key(&key);
     if key.is_validity_predicate().is_some() {
         return Err(TxRuntimeError::CannotDeleteVp.into());
     }
@@ -1124,6 +1221,7 @@ where
 
     // try to read from the storage
     let key = Key::parse(key)?;
+    env.ctx.trace_key(&key);
     let state = env.state();
     let value = vp_host_fns::read_pre(gas_meter, &state, &key)?;
     tracing::debug!(
@@ -1172,6 +1270,7 @@ where
 
     // try to read from the write log first
     let key = Key::parse(key)?;
+    env.ctx.trace_key(&key);
     let state = env.state();
     let value = vp_host_fns::read_post(gas_meter, &state, &key)?;
     Ok(match value {
@@ -1213,6 +1312,7 @@ where
 
     // try to read from the write log
     let key = Key::parse(key)?;
+    env.ctx.trace_key(&key);
     let state = env.state();
     let value = vp_host_fns::read_temp(gas_meter, &state, &key)?;
     Ok(match value {
@@ -1281,6 +1381,7 @@ where
     tracing::debug!("vp_has_key_pre {}, key {}", key, key_ptr,);
 
     let key = Key::parse(key)?;
+    env.ctx.trace_key(&key);
     let state = env.state();
     let present = vp_host_fns::has_key_pre(gas_meter, &state, &key)?;
     Ok(HostEnvResult::from(present).to_i64())
@@ -1311,6 +1412,7 @@ where
     tracing::debug!("vp_has_key_post {}, key {}", key, key_ptr,);
 
     let key = Key::parse(key)?;
+    env.ctx.trace_key(&key);
     let state = env.state();
     let present = vp_host_fns::has_key_post(gas_meter, &state, &key)?;
     Ok(HostEnvResult::from(present).to_i64())
@@ -1343,6 +1445,7 @@ where
     tracing::debug!("vp_iter_prefix_pre {}", prefix);
 
     let prefix = Key::parse(prefix)?;
+    env.ctx.trace_key(&prefix);
 
     let write_log = unsafe { env.ctx.write_log.get() };
     let db = unsafe { env.ctx.db.get() };
@@ -1382,6 +1485,7 @@ where
     tracing::debug!("vp_iter_prefix_post {}", prefix);
 
     let prefix = Key::parse(prefix)?;
+    env.ctx.trace_key(&prefix);
 
     let write_log = unsafe { env.ctx.write_log.get() };
     let db = unsafe { env.ctx.db.get() };
@@ -1418,6 +1522,9 @@ where
     if let Some(iter) = iterators.get_mut(iter_id) {
         let gas_meter = env.ctx.gas_meter();
         if let Some((key, val)) = vp_host_fns::iter_next(gas_meter, iter)? {
+            if env.ctx.trace.is_some() {
+                env.ctx.trace_key(&Key::parse(key.clone())?);
+            }
             let key_val = KeyVal { key, val }.serialize_to_vec();
             let len: i64 = key_val.len().try_into()?;
             let result_buffer = unsafe { env.ctx.result_buffer.get_mut() };
@@ -2252,9 +2359,13 @@ where
         tx: &tx.tx,
         cmt: &tx.cmt,
     };
-    Ok(eval_runner
-        .eval(env.ctx.clone(), vp_code_hash, batch_ref)
-        .to_i64())
+    // NB: the host calls of the evaluated VP are not traced on their own,
+    // their gas is accounted for in the trace of this call
+    let ctx = VpCtx {
+        trace: None,
+        ..env.ctx.clone()
+    };
+    Ok(eval_runner.eval(ctx, vp_code_hash, batch_ref).to_i64())
 }
 
 /// Get the native token's address
@@ -2370,6 +2481,7 @@ pub mod testing {
             verifiers,
             result_buffer,
             yielded_value,
+            None,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache,
             #[cfg(feature = "wasm-runtime")]
@@ -2419,6 +2531,7 @@ pub mod testing {
             verifiers,
             result_buffer,
             yielded_value,
+            None,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache,
             #[cfg(feature = "wasm-runtime")]
@@ -2467,6 +2580,7 @@ pub mod testing {
             yielded_value,
             keys_changed,
             eval_runner,
+            None,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache,
         )
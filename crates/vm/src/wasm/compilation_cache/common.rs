@@ -87,18 +87,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
         );
         let in_memory = Arc::new(RwLock::new(cache));
 
-        let target_hash = {
-            use std::hash::{Hash, Hasher};
-            let mut hasher = std::hash::DefaultHasher::new();
-            wasmer::Target::default().hash(&mut hasher);
-            hasher.finish()
-        };
-        let version = format!(
-            "{}_{:x}",
-            concat!(env!("CARGO_PKG_VERSION"), "_", env!("RUSTUP_TOOLCHAIN")),
-            target_hash,
-        );
-        let dir = dir.into().join(version);
+        let dir = dir.into().join(cache_version());
 
         fs::create_dir_all(&dir)
             .expect("Couldn't create the wasm cache directory");
@@ -496,6 +485,45 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
         }
     }
 
+    /// Compile a WASM module ahead of time and persist it to the file cache,
+    /// unless it's already there. Returns whether the module was compiled.
+    pub fn compile_to_file(
+        &self,
+        code: impl AsRef<[u8]>,
+    ) -> Result<bool, wasm::run::Error> {
+        let hash = hash_of_code(&code);
+        if module_file_exists(&self.dir, &hash) {
+            return Ok(false);
+        }
+        tracing::info!("Compiling {} {}.", N::name(), hash.to_string());
+        let code = wasm::run::prepare_wasm_code(code)?;
+        let module = compile(code, &self.store)?;
+        file_write_module(&self.dir, &module, &hash);
+        Ok(true)
+    }
+
+    /// Get the path of the file storing the compiled module of the WASM code
+    /// with the given hash, if it's in the file cache.
+    pub fn module_file(&self, hash: &Hash) -> Option<PathBuf> {
+        let file = module_file_path(&self.dir, hash);
+        file.exists().then_some(file)
+    }
+
+    /// Write a compiled module of the WASM code with the given hash to the
+    /// file cache. The module must have been serialized by a cache of the
+    /// same [`cache_version`].
+    pub fn import_module_file(
+        &self,
+        hash: &Hash,
+        module: &[u8],
+    ) -> std::io::Result<()> {
+        let file = module_file_path(&self.dir, hash);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(file, module)
+    }
+
     /// Get a read-only cache handle.
     pub fn read_only(&self) -> Cache<N, WasmCacheRoAccess> {
         Cache {
@@ -509,6 +537,23 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
     }
 }
 
+/// The version of the compiled modules stored in the file cache. Modules can
+/// only be loaded by a node built with the same version of Namada and of the
+/// Rust toolchain, for the same target.
+pub fn cache_version() -> String {
+    let target_hash = {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::hash::DefaultHasher::new();
+        wasmer::Target::default().hash(&mut hasher);
+        hasher.finish()
+    };
+    format!(
+        "{}_{:x}",
+        concat!(env!("CARGO_PKG_VERSION"), "_", env!("RUSTUP_TOOLCHAIN")),
+        target_hash,
+    )
+}
+
 fn hash_of_code(code: impl AsRef<[u8]>) -> Hash {
     Hash::sha256(code.as_ref())
 }
@@ -563,16 +608,18 @@ fn fs_cache(dir: impl AsRef<Path>, hash: &Hash) -> FileSystemCache {
     fs_cache
 }
 
+fn module_file_path(dir: impl AsRef<Path>, hash: &Hash) -> PathBuf {
+    dir.as_ref()
+        .join(hash.to_string().to_lowercase())
+        .join(format!(
+            "{}.{}",
+            hash.to_string().to_lowercase(),
+            file_ext()
+        ))
+}
+
 fn module_file_exists(dir: impl AsRef<Path>, hash: &Hash) -> bool {
-    let file =
-        dir.as_ref()
-            .join(hash.to_string().to_lowercase())
-            .join(format!(
-                "{}.{}",
-                hash.to_string().to_lowercase(),
-                file_ext()
-            ));
-    file.exists()
+    module_file_path(dir, hash).exists()
 }
 
 /// A universal engine compilation. The module can be serialized to/from bytes.
@@ -1062,6 +1109,43 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_compile_to_file_and_import() {
+        let vp_always_true = load_wasm(TestWasms::VpAlwaysTrue.path());
+        let (cache, _tmp_dir) = cache(1);
+
+        // Compile the module ahead of time
+        assert!(cache.compile_to_file(&vp_always_true.code).unwrap());
+        assert!(
+            !cache.compile_to_file(&vp_always_true.code).unwrap(),
+            "An existing module must not be compiled again"
+        );
+        assert!(
+            cache.in_memory.read().unwrap().is_empty(),
+            "The module must not be added to the in-memory cache"
+        );
+        let module_file = cache
+            .module_file(&vp_always_true.hash)
+            .expect("The file must be written");
+
+        // Import it into another cache, from which it can be loaded without
+        // compilation
+        let (mut other_cache, _other_tmp_dir) = self::cache(1);
+        assert!(other_cache.module_file(&vp_always_true.hash).is_none());
+        other_cache
+            .import_module_file(
+                &vp_always_true.hash,
+                &fs::read(module_file).unwrap(),
+            )
+            .unwrap();
+        let fetched = other_cache.fetch(&vp_always_true.hash).unwrap();
+        assert_matches!(
+            fetched,
+            Some(_),
+            "The module must be loaded from the file cache"
+        );
+    }
+
     /// Get the WASM code bytes, its hash and find the compiled module's size
     fn load_wasm(file: impl AsRef<Path>) -> WasmWithMeta {
         let file = file.as_ref();
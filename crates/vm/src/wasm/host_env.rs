@@ -28,34 +28,34 @@ where
         // Default namespace
         "env" => {
             // Gas injection hook
-            "gas" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1(host_env::tx_charge_gas)),
+            "gas" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::gas(host_env::tx_charge_gas)),
             // Tx Host functions
-            "namada_tx_delete" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_delete)),
-            "namada_tx_emit_event" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_emit_event)),
-            "namada_tx_get_block_epoch" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0(host_env::tx_get_block_epoch)),
-            "namada_tx_get_block_header" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1(host_env::tx_get_block_header)),
-            "namada_tx_get_block_height" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0(host_env::tx_get_block_height)),
-            "namada_tx_get_chain_id" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1(host_env::tx_get_chain_id)),
-            "namada_tx_get_events" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_get_events)),
-            "namada_tx_get_native_token" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1(host_env::tx_get_native_token)),
-            "namada_tx_get_pred_epochs" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0(host_env::tx_get_pred_epochs)),
-            "namada_tx_get_tx_index" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0(host_env::tx_get_tx_index)),
-            "namada_tx_has_key" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_has_key)),
-            "namada_tx_init_account" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_7(host_env::tx_init_account)),
-            "namada_tx_insert_verifier" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_insert_verifier)),
-            "namada_tx_iter_next" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1(host_env::tx_iter_next)),
-            "namada_tx_iter_prefix" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_iter_prefix)),
-            "namada_tx_log_string" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_log_string)),
-            "namada_tx_read" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_read)),
-            "namada_tx_read_temp" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_read_temp)),
-            "namada_tx_result_buffer" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1(host_env::tx_result_buffer)),
-            "namada_tx_set_commitment_sentinel" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0(host_env::tx_set_commitment_sentinel)),
-            "namada_tx_update_masp_note_commitment_tree" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_update_masp_note_commitment_tree)),
-            "namada_tx_update_validity_predicate" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_6(host_env::tx_update_validity_predicate)),
-            "namada_tx_verify_tx_section_signature" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_5(host_env::tx_verify_tx_section_signature)),
-            "namada_tx_write" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_4(host_env::tx_write)),
-            "namada_tx_write_temp" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_4(host_env::tx_write_temp)),
-            "namada_tx_yield_value" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_yield_value)),
+            "namada_tx_delete" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("tx_delete", host_env::tx_delete)),
+            "namada_tx_emit_event" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("tx_emit_event", host_env::tx_emit_event)),
+            "namada_tx_get_block_epoch" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0("tx_get_block_epoch", host_env::tx_get_block_epoch)),
+            "namada_tx_get_block_header" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1("tx_get_block_header", host_env::tx_get_block_header)),
+            "namada_tx_get_block_height" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0("tx_get_block_height", host_env::tx_get_block_height)),
+            "namada_tx_get_chain_id" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1("tx_get_chain_id", host_env::tx_get_chain_id)),
+            "namada_tx_get_events" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("tx_get_events", host_env::tx_get_events)),
+            "namada_tx_get_native_token" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1("tx_get_native_token", host_env::tx_get_native_token)),
+            "namada_tx_get_pred_epochs" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0("tx_get_pred_epochs", host_env::tx_get_pred_epochs)),
+            "namada_tx_get_tx_index" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0("tx_get_tx_index", host_env::tx_get_tx_index)),
+            "namada_tx_has_key" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("tx_has_key", host_env::tx_has_key)),
+            "namada_tx_init_account" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_7("tx_init_account", host_env::tx_init_account)),
+            "namada_tx_insert_verifier" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("tx_insert_verifier", host_env::tx_insert_verifier)),
+            "namada_tx_iter_next" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1("tx_iter_next", host_env::tx_iter_next)),
+            "namada_tx_iter_prefix" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("tx_iter_prefix", host_env::tx_iter_prefix)),
+            "namada_tx_log_string" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("tx_log_string", host_env::tx_log_string)),
+            "namada_tx_read" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("tx_read", host_env::tx_read)),
+            "namada_tx_read_temp" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("tx_read_temp", host_env::tx_read_temp)),
+            "namada_tx_result_buffer" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1("tx_result_buffer", host_env::tx_result_buffer)),
+            "namada_tx_set_commitment_sentinel" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0("tx_set_commitment_sentinel", host_env::tx_set_commitment_sentinel)),
+            "namada_tx_update_masp_note_commitment_tree" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("tx_update_masp_note_commitment_tree", host_env::tx_update_masp_note_commitment_tree)),
+            "namada_tx_update_validity_predicate" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_6("tx_update_validity_predicate", host_env::tx_update_validity_predicate)),
+            "namada_tx_verify_tx_section_signature" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_5("tx_verify_tx_section_signature", host_env::tx_verify_tx_section_signature)),
+            "namada_tx_write" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_4("tx_write", host_env::tx_write)),
+            "namada_tx_write_temp" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_4("tx_write_temp", host_env::tx_write_temp)),
+            "namada_tx_yield_value" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("tx_yield_value", host_env::tx_yield_value)),
         },
     }
 }
@@ -78,29 +78,29 @@ where
         // Default namespace
         "env" => {
             // Gas injection hook
-            "gas" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_charge_gas)),
+            "gas" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::gas(host_env::vp_charge_gas)),
             // VP Host functions
-            "namada_vp_eval" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_4(host_env::vp_eval)),
-            "namada_vp_get_block_header" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_get_block_header)),
-            "namada_vp_get_block_height" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_0(host_env::vp_get_block_height)),
-            "namada_vp_get_chain_id" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_get_chain_id)),
-            "namada_vp_get_events" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_get_events)),
-            "namada_vp_get_native_token" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_get_native_token)),
-            "namada_vp_get_pred_epochs" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_0(host_env::vp_get_pred_epochs)),
-            "namada_vp_get_tx_code_hash" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_get_tx_code_hash)),
-            "namada_vp_get_tx_index" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_0(host_env::vp_get_tx_index)),
-            "namada_vp_has_key_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_has_key_post)),
-            "namada_vp_has_key_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_has_key_pre)),
-            "namada_vp_iter_next" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_iter_next)),
-            "namada_vp_iter_prefix_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_iter_prefix_post)),
-            "namada_vp_iter_prefix_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_iter_prefix_pre)),
-            "namada_vp_log_string" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_log_string)),
-            "namada_vp_read_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_read_post)),
-            "namada_vp_read_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_read_pre)),
-            "namada_vp_read_temp" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_read_temp)),
-            "namada_vp_result_buffer" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_result_buffer)),
-            "namada_vp_verify_tx_section_signature" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_7(host_env::vp_verify_tx_section_signature)),
-            "namada_vp_yield_value" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_yield_value)),
+            "namada_vp_eval" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_4("vp_eval", host_env::vp_eval)),
+            "namada_vp_get_block_header" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("vp_get_block_header", host_env::vp_get_block_header)),
+            "namada_vp_get_block_height" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_0("vp_get_block_height", host_env::vp_get_block_height)),
+            "namada_vp_get_chain_id" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("vp_get_chain_id", host_env::vp_get_chain_id)),
+            "namada_vp_get_events" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("vp_get_events", host_env::vp_get_events)),
+            "namada_vp_get_native_token" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("vp_get_native_token", host_env::vp_get_native_token)),
+            "namada_vp_get_pred_epochs" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_0("vp_get_pred_epochs", host_env::vp_get_pred_epochs)),
+            "namada_vp_get_tx_code_hash" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("vp_get_tx_code_hash", host_env::vp_get_tx_code_hash)),
+            "namada_vp_get_tx_index" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_0("vp_get_tx_index", host_env::vp_get_tx_index)),
+            "namada_vp_has_key_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("vp_has_key_post", host_env::vp_has_key_post)),
+            "namada_vp_has_key_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("vp_has_key_pre", host_env::vp_has_key_pre)),
+            "namada_vp_iter_next" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("vp_iter_next", host_env::vp_iter_next)),
+            "namada_vp_iter_prefix_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("vp_iter_prefix_post", host_env::vp_iter_prefix_post)),
+            "namada_vp_iter_prefix_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("vp_iter_prefix_pre", host_env::vp_iter_prefix_pre)),
+            "namada_vp_log_string" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("vp_log_string", host_env::vp_log_string)),
+            "namada_vp_read_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("vp_read_post", host_env::vp_read_post)),
+            "namada_vp_read_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("vp_read_pre", host_env::vp_read_pre)),
+            "namada_vp_read_temp" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("vp_read_temp", host_env::vp_read_temp)),
+            "namada_vp_result_buffer" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("vp_result_buffer", host_env::vp_result_buffer)),
+            "namada_vp_verify_tx_section_signature" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_7("vp_verify_tx_section_signature", host_env::vp_verify_tx_section_signature)),
+            "namada_vp_yield_value" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("vp_yield_value", host_env::vp_yield_value)),
         },
     }
 }
@@ -116,11 +116,26 @@ mod wrap_tx {
     use namada_state::{DBIter, StorageHasher, DB};
     use wasmer::FunctionEnvMut;
 
-    use crate::host_env::TxVmEnv;
+    use crate::host_env::{self, TxVmEnv};
     use crate::wasm::memory::WasmMemory;
     use crate::WasmCacheAccess;
 
+    /// The gas hook is injected in every basic block of the guest code, so
+    /// unlike the other host functions it is not traced.
+    pub(super) fn gas<F, RET, D, H, CA>(
+        f: F,
+    ) -> impl Fn(FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>, u64) -> RET
+    where
+        D: DB + for<'iter> DBIter<'iter> + 'static,
+        H: StorageHasher + 'static,
+        CA: WasmCacheAccess + 'static,
+        F: Fn(&mut TxVmEnv<WasmMemory, D, H, CA>, u64) -> RET,
+    {
+        move |mut env, used_gas| f(env.data_mut(), used_gas)
+    }
+
     pub(super) fn _0<F, RET, D, H, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>) -> RET
     where
@@ -129,10 +144,13 @@ mod wrap_tx {
         CA: WasmCacheAccess + 'static,
         F: Fn(&mut TxVmEnv<WasmMemory, D, H, CA>) -> RET,
     {
-        move |mut env| f(env.data_mut())
+        move |mut env| {
+            host_env::tx_traced_call(env.data_mut(), name, |env| f(env))
+        }
     }
 
     pub(super) fn _1<F, ARG0, RET, D, H, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>, ARG0) -> RET
     where
@@ -141,10 +159,13 @@ mod wrap_tx {
         CA: WasmCacheAccess + 'static,
         F: Fn(&mut TxVmEnv<WasmMemory, D, H, CA>, ARG0) -> RET,
     {
-        move |mut env, arg0| f(env.data_mut(), arg0)
+        move |mut env, arg0| {
+            host_env::tx_traced_call(env.data_mut(), name, |env| f(env, arg0))
+        }
     }
 
     pub(super) fn _2<F, ARG0, ARG1, RET, D, H, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>, ARG0, ARG1) -> RET
     where
@@ -153,10 +174,15 @@ mod wrap_tx {
         CA: WasmCacheAccess + 'static,
         F: Fn(&mut TxVmEnv<WasmMemory, D, H, CA>, ARG0, ARG1) -> RET,
     {
-        move |mut env, arg0, arg1| f(env.data_mut(), arg0, arg1)
+        move |mut env, arg0, arg1| {
+            host_env::tx_traced_call(env.data_mut(), name, |env| {
+                f(env, arg0, arg1)
+            })
+        }
     }
 
     pub(super) fn _4<F, ARG0, ARG1, ARG2, ARG3, RET, D, H, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>,
@@ -178,11 +204,14 @@ mod wrap_tx {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3| {
-            f(env.data_mut(), arg0, arg1, arg2, arg3)
+            host_env::tx_traced_call(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3)
+            })
         }
     }
 
     pub(super) fn _5<F, ARG0, ARG1, ARG2, ARG3, ARG4, RET, D, H, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>,
@@ -206,11 +235,14 @@ mod wrap_tx {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3, arg4| {
-            f(env.data_mut(), arg0, arg1, arg2, arg3, arg4)
+            host_env::tx_traced_call(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3, arg4)
+            })
         }
     }
 
     pub(super) fn _6<F, ARG0, ARG1, ARG2, ARG3, ARG4, ARG5, RET, D, H, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>,
@@ -236,7 +268,9 @@ mod wrap_tx {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3, arg4, arg5| {
-            f(env.data_mut(), arg0, arg1, arg2, arg3, arg4, arg5)
+            host_env::tx_traced_call(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3, arg4, arg5)
+            })
         }
     }
 
@@ -254,6 +288,7 @@ mod wrap_tx {
         H,
         CA,
     >(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>,
@@ -281,7 +316,9 @@ mod wrap_tx {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3, arg4, arg5, arg6| {
-            f(env.data_mut(), arg0, arg1, arg2, arg3, arg4, arg5, arg6)
+            host_env::tx_traced_call(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3, arg4, arg5, arg6)
+            })
         }
     }
 }
@@ -297,11 +334,27 @@ mod wrap_vp {
     use namada_state::{DBIter, StorageHasher, DB};
     use wasmer::FunctionEnvMut;
 
-    use crate::host_env::{VpEvaluator, VpVmEnv};
+    use crate::host_env::{self, VpEvaluator, VpVmEnv};
     use crate::wasm::memory::WasmMemory;
     use crate::WasmCacheAccess;
 
+    /// The gas hook is injected in every basic block of the guest code, so
+    /// unlike the other host functions it is not traced.
+    pub(super) fn gas<F, RET, D, H, EVAL, CA>(
+        f: F,
+    ) -> impl Fn(FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>, u64) -> RET
+    where
+        D: DB + for<'iter> DBIter<'iter> + 'static,
+        H: StorageHasher + 'static,
+        CA: WasmCacheAccess + 'static,
+        EVAL: VpEvaluator<Db = D, H = H, Eval = EVAL, CA = CA> + 'static,
+        F: Fn(&mut VpVmEnv<WasmMemory, D, H, EVAL, CA>, u64) -> RET,
+    {
+        move |mut env, used_gas| f(env.data_mut(), used_gas)
+    }
+
     pub(super) fn _0<F, RET, D, H, EVAL, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>) -> RET
     where
@@ -311,10 +364,13 @@ mod wrap_vp {
         EVAL: VpEvaluator<Db = D, H = H, Eval = EVAL, CA = CA> + 'static,
         F: Fn(&mut VpVmEnv<WasmMemory, D, H, EVAL, CA>) -> RET,
     {
-        move |mut env| f(env.data_mut())
+        move |mut env| {
+            host_env::vp_traced_call(env.data_mut(), name, |env| f(env))
+        }
     }
 
     pub(super) fn _1<F, ARG0, RET, D, H, EVAL, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>, ARG0) -> RET
     where
@@ -324,10 +380,13 @@ mod wrap_vp {
         EVAL: VpEvaluator<Db = D, H = H, Eval = EVAL, CA = CA> + 'static,
         F: Fn(&mut VpVmEnv<WasmMemory, D, H, EVAL, CA>, ARG0) -> RET,
     {
-        move |mut env, arg0| f(env.data_mut(), arg0)
+        move |mut env, arg0| {
+            host_env::vp_traced_call(env.data_mut(), name, |env| f(env, arg0))
+        }
     }
 
     pub(super) fn _2<F, ARG0, ARG1, RET, D, H, EVAL, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>,
@@ -341,10 +400,15 @@ mod wrap_vp {
         EVAL: VpEvaluator<Db = D, H = H, Eval = EVAL, CA = CA> + 'static,
         F: Fn(&mut VpVmEnv<WasmMemory, D, H, EVAL, CA>, ARG0, ARG1) -> RET,
     {
-        move |mut env, arg0, arg1| f(env.data_mut(), arg0, arg1)
+        move |mut env, arg0, arg1| {
+            host_env::vp_traced_call(env.data_mut(), name, |env| {
+                f(env, arg0, arg1)
+            })
+        }
     }
 
     pub(super) fn _4<F, ARG0, ARG1, ARG2, ARG3, RET, D, H, EVAL, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>,
@@ -367,7 +431,9 @@ mod wrap_vp {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3| {
-            f(env.data_mut(), arg0, arg1, arg2, arg3)
+            host_env::vp_traced_call(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3)
+            })
         }
     }
 
@@ -386,6 +452,7 @@ mod wrap_vp {
         EVAL,
         CA,
     >(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>,
@@ -414,7 +481,9 @@ mod wrap_vp {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3, arg4, arg5, arg6| {
-            f(env.data_mut(), arg0, arg1, arg2, arg3, arg4, arg5, arg6)
+            host_env::vp_traced_call(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3, arg4, arg5, arg6)
+            })
         }
     }
 
@@ -435,6 +504,7 @@ mod wrap_vp {
         EVAL,
         CA,
     >(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>,
@@ -467,18 +537,9 @@ mod wrap_vp {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3, arg4, arg5, arg6, arg7, arg8| {
-            f(
-                env.data_mut(),
-                arg0,
-                arg1,
-                arg2,
-                arg3,
-                arg4,
-                arg5,
-                arg6,
-                arg7,
-                arg8,
-            )
+            host_env::vp_traced_call(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3, arg4, arg5, arg6, arg7, arg8)
+            })
         }
     }
 }
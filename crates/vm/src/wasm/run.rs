@@ -17,6 +17,7 @@ use namada_core::validity_predicate::VpError;
 use namada_gas::{GasMetering, TxGasMeter, VpGasMeter, WASM_MEMORY_PAGE_GAS};
 use namada_state::prefix_iter::PrefixIterators;
 use namada_state::{DBIter, State, StateRead, StorageHasher, StorageRead, DB};
+use namada_tx::data::trace::WasmTrace;
 use namada_tx::data::{TxSentinel, TxType};
 use namada_tx::{BatchedTxRef, Commitment, Section, Tx, TxCommitments};
 use namada_vp::vp_host_fns;
@@ -134,7 +135,8 @@ where
 }
 
 /// Execute a transaction code. Returns the set verifiers addresses requested by
-/// the transaction.
+/// the transaction. If a trace is given, the host calls made by the
+/// transaction are recorded in it.
 #[allow(clippy::too_many_arguments)]
 pub fn tx<S, CA>(
     state: &mut S,
@@ -144,6 +146,7 @@ pub fn tx<S, CA>(
     cmt: &TxCommitments,
     vp_wasm_cache: &mut VpCache<CA>,
     tx_wasm_cache: &mut TxCache<CA>,
+    trace: Option<&mut WasmTrace>,
 ) -> Result<BTreeSet<Address>>
 where
     S: StateRead + State + StorageRead,
@@ -216,6 +219,7 @@ where
         &mut verifiers,
         &mut result_buffer,
         &mut yielded_value,
+        trace,
         vp_wasm_cache,
         tx_wasm_cache,
     );
@@ -305,7 +309,8 @@ where
 
 /// Execute a validity predicate code. Returns whether the validity
 /// predicate accepted storage modifications performed by the transaction
-/// that triggered the execution.
+/// that triggered the execution. If a trace is given, the host calls made by
+/// the validity predicate are recorded in it.
 #[allow(clippy::too_many_arguments)]
 pub fn vp<S, CA>(
     vp_code_hash: Hash,
@@ -317,6 +322,7 @@ pub fn vp<S, CA>(
     keys_changed: &BTreeSet<Key>,
     verifiers: &BTreeSet<Address>,
     mut vp_wasm_cache: VpCache<CA>,
+    trace: Option<&mut WasmTrace>,
 ) -> Result<()>
 where
     S: StateRead,
@@ -358,6 +364,7 @@ where
         &mut yielded_value,
         keys_changed,
         &eval_runner,
+        trace,
         &mut vp_wasm_cache,
     );
 
@@ -567,6 +574,7 @@ where
             &mut yielded_value,
             ctx.keys_changed,
             &eval_runner,
+            None,
             &mut vp_wasm_cache,
         );
         eval_runner
@@ -1197,6 +1205,7 @@ mod tests {
             batched_tx.cmt,
             &mut vp_cache,
             &mut tx_cache,
+            None,
         );
         assert!(result.is_ok(), "Expected success, got {:?}", result);
 
@@ -1215,6 +1224,7 @@ mod tests {
             batched_tx.cmt,
             &mut vp_cache,
             &mut tx_cache,
+            None,
         )
         .expect_err("Expected to run out of memory");
 
@@ -1287,6 +1297,7 @@ mod tests {
                 &keys_changed,
                 &verifiers,
                 vp_cache.clone(),
+                None,
             )
             .is_ok()
         );
@@ -1319,6 +1330,7 @@ mod tests {
                 &keys_changed,
                 &verifiers,
                 vp_cache,
+                None,
             )
             .is_err()
         );
@@ -1369,6 +1381,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache.clone(),
+            None,
         );
         assert!(result.is_ok(), "Expected success, got {:?}", result);
 
@@ -1388,6 +1401,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache,
+            None,
         )
         .expect_err("Expected to run out of memory");
 
@@ -1437,6 +1451,7 @@ mod tests {
             batched_tx.cmt,
             &mut vp_cache,
             &mut tx_cache,
+            None,
         );
         // Depending on platform, we get a different error from the running out
         // of memory
@@ -1502,6 +1517,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache,
+            None,
         );
         // Depending on platform, we get a different error from the running out
         // of memory
@@ -1573,6 +1589,7 @@ mod tests {
             batched_tx.cmt,
             &mut vp_cache,
             &mut tx_cache,
+            None,
         )
         .expect_err("Expected to run out of memory");
 
@@ -1630,6 +1647,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache,
+            None,
         )
         .expect_err("Expected to run out of memory");
 
@@ -1707,6 +1725,7 @@ mod tests {
                 &keys_changed,
                 &verifiers,
                 vp_cache,
+                None,
             )
             .is_err()
         );
@@ -1815,6 +1834,7 @@ mod tests {
             batched_tx.cmt,
             &mut vp_cache,
             &mut tx_cache,
+            None,
         );
 
         assert!(matches!(result.unwrap_err(), Error::GasError(_)));
@@ -1854,6 +1874,7 @@ mod tests {
             batched_tx.cmt,
             &mut vp_cache,
             &mut tx_cache,
+            None,
         );
 
         assert!(matches!(result.unwrap_err(), Error::GasError(_)));
@@ -1897,6 +1918,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache.clone(),
+            None,
         );
 
         assert!(matches!(result.unwrap_err(), Error::GasError(_)));
@@ -1941,6 +1963,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache.clone(),
+            None,
         );
 
         assert!(matches!(result.unwrap_err(), Error::GasError(_)));
@@ -2116,6 +2139,7 @@ mod tests {
             &keys_changed,
             &verifiers,
             vp_cache.clone(),
+            None,
         )
     }
 
@@ -2161,6 +2185,7 @@ mod tests {
             batched_tx.cmt,
             vp_cache,
             tx_cache,
+            None,
         )
     }
 
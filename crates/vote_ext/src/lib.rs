@@ -190,6 +190,35 @@ impl EthereumTxData {
         outer_tx
     }
 
+    /// Sign transaction Ethereum data with a function signing the hashes of
+    /// the tx with the key of the given public key, and wrap it in a [`Tx`].
+    pub fn try_sign_with<E>(
+        &self,
+        public_key: common::PublicKey,
+        chain_id: ChainId,
+        sign: impl FnMut(
+            &common::PublicKey,
+            &namada_core::hash::Hash,
+        ) -> Result<common::Signature, E>,
+    ) -> Result<Tx, E> {
+        let (tx_data, tx_type) = self.serialize();
+        let mut outer_tx =
+            Tx::from_type(TxType::Protocol(Box::new(ProtocolTx {
+                pk: public_key.clone(),
+                tx: tx_type,
+            })));
+        outer_tx.header.chain_id = chain_id;
+        outer_tx.set_data(namada_tx::Data::new(tx_data));
+        let authorization = Authorization::try_new_with(
+            outer_tx.sechashes(),
+            [(0, public_key)].into_iter().collect(),
+            None,
+            sign,
+        )?;
+        outer_tx.add_section(namada_tx::Section::Authorization(authorization));
+        Ok(outer_tx)
+    }
+
     /// Serialize Ethereum protocol transaction data.
     pub fn serialize(&self) -> (Vec<u8>, ProtocolTxType) {
         macro_rules! match_of_type {
@@ -212,6 +212,11 @@ impl DerivationPath {
         Self::zip32(0, None)
     }
 
+    /// The derivation path of the shielded account with the given index
+    pub fn for_shielded_account(account: u32) -> Self {
+        Self::zip32(account, None)
+    }
+
     /// Try to parse a derivation path from a string
     pub fn from_path_string(path: &str) -> Result<Self, DerivationPathError> {
         let inner = DerivationPathInner::from_str(path).map_err(|err| {
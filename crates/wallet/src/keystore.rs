@@ -0,0 +1,549 @@
+//! Key storage backends
+//!
+//! The secret keys of a wallet are kept in its TOML store by default. Other
+//! backends can be configured in the store to hold keys outside of it, e.g. to
+//! isolate the keys of a validator from the client wallet:
+//!
+//! - [`Store`]: a wallet store file of the current format
+//! - [`DirectoryKeyStore`]: a directory with a separate encrypted sub-directory
+//!   per key
+//! - [`RemoteKeyStore`]: a remote signer listening on a local Unix socket,
+//!   which never reveals the secret keys
+
+use std::fmt::Debug;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use namada_core::borsh::{BorshDeserialize, BorshSerialize, BorshSerializeExt};
+use namada_core::hash::Hash;
+use namada_core::key::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+use crate::keys::{DecryptionError, EncryptedKeypair};
+use crate::{Store, StoredKeypair};
+
+/// The name of the file holding the public key in a key directory
+const PUBLIC_KEY_FILE: &str = "public_key";
+/// The name of the file holding the encrypted secret key in a key directory
+const SECRET_KEY_FILE: &str = "secret_key";
+/// The maximum length of a message exchanged with a remote signer
+const MAX_MESSAGE_LEN: u32 = 1 << 20;
+
+/// Errors of the key store backends
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum KeyStoreError {
+    #[error("No key matching {0} found in the key store")]
+    KeyNotFound(String),
+    #[error("{0}")]
+    Decryption(DecryptionError),
+    #[error("Key store IO error: {0}")]
+    Io(String),
+    #[error("Remote signer error: {0}")]
+    Remote(String),
+}
+
+impl From<io::Error> for KeyStoreError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err.to_string())
+    }
+}
+
+/// A backend holding secret keys to sign with
+pub trait KeyStore: Debug + Send + Sync {
+    /// Get the public keys of the secret keys held by the backend
+    fn public_keys(&self) -> Result<Vec<common::PublicKey>, KeyStoreError>;
+
+    /// Check if the backend holds the secret key of the given public key
+    fn has_key(&self, pk: &common::PublicKey) -> bool {
+        self.public_keys()
+            .map(|pks| pks.contains(pk))
+            .unwrap_or(false)
+    }
+
+    /// Check if a password is needed to sign with the given key
+    fn is_encrypted(&self, _pk: &common::PublicKey) -> bool {
+        false
+    }
+
+    /// Sign the hash with the secret key of the given public key
+    fn sign(
+        &self,
+        pk: &common::PublicKey,
+        hash: &Hash,
+        password: Option<Zeroizing<String>>,
+    ) -> Result<common::Signature, KeyStoreError>;
+}
+
+/// The configuration of a key store backend in the wallet store
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum KeyStoreConfig {
+    /// A wallet store file of the current format
+    File {
+        /// The path to the wallet store file
+        path: PathBuf,
+    },
+    /// A directory with a separate encrypted sub-directory per key
+    Directory {
+        /// The path to the directory
+        path: PathBuf,
+    },
+    /// A remote signer listening on a local Unix socket
+    Remote {
+        /// The path to the Unix socket
+        socket: PathBuf,
+    },
+}
+
+impl KeyStoreConfig {
+    /// Open the configured backend
+    pub fn open(&self) -> Result<Box<dyn KeyStore>, KeyStoreError> {
+        match self {
+            Self::File { path } => {
+                let data = fs::read(path)?;
+                let store = Store::decode(data)
+                    .map_err(|e| KeyStoreError::Io(e.to_string()))?;
+                Ok(Box::new(store))
+            }
+            Self::Directory { path } => {
+                Ok(Box::new(DirectoryKeyStore::new(path.clone())))
+            }
+            Self::Remote { socket } => {
+                Ok(Box::new(RemoteKeyStore::new(socket.clone())))
+            }
+        }
+    }
+}
+
+impl KeyStore for Store {
+    fn public_keys(&self) -> Result<Vec<common::PublicKey>, KeyStoreError> {
+        Ok(self
+            .get_public_keys()
+            .values()
+            .filter(|pk| self.find_key_by_pk(pk).is_some())
+            .cloned()
+            .collect())
+    }
+
+    fn has_key(&self, pk: &common::PublicKey) -> bool {
+        self.find_key_by_pk(pk).is_some()
+    }
+
+    fn is_encrypted(&self, pk: &common::PublicKey) -> bool {
+        self.find_key_by_pk(pk)
+            .map(StoredKeypair::is_encrypted)
+            .unwrap_or(false)
+    }
+
+    fn sign(
+        &self,
+        pk: &common::PublicKey,
+        hash: &Hash,
+        password: Option<Zeroizing<String>>,
+    ) -> Result<common::Signature, KeyStoreError> {
+        let secret_key = match self.find_key_by_pk(pk) {
+            Some(StoredKeypair::Raw(secret_key)) => secret_key.clone(),
+            Some(StoredKeypair::Encrypted(encrypted)) => {
+                decrypt(encrypted, password)?
+            }
+            None => return Err(KeyStoreError::KeyNotFound(pk.to_string())),
+        };
+        Ok(common::SigScheme::sign(&secret_key, hash))
+    }
+}
+
+/// Key store keeping every key encrypted in its own sub-directory, which is
+/// named after the public key hash
+#[derive(Debug, Clone)]
+pub struct DirectoryKeyStore {
+    dir: PathBuf,
+}
+
+impl DirectoryKeyStore {
+    /// Make a key store in the given directory
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Encrypt the secret key with the password and store it. Returns the
+    /// directory of the key.
+    pub fn insert(
+        &self,
+        secret_key: &common::SecretKey,
+        password: Zeroizing<String>,
+    ) -> Result<PathBuf, KeyStoreError> {
+        if password.is_empty() {
+            return Err(KeyStoreError::Decryption(
+                DecryptionError::EmptyPassword,
+            ));
+        }
+        let pk = secret_key.ref_to();
+        let key_dir = self.key_dir(&PublicKeyHash::from(&pk));
+        create_private_dir(&key_dir)?;
+        let encrypted = EncryptedKeypair::new(secret_key, password);
+        write_private_file(&key_dir.join(PUBLIC_KEY_FILE), pk.to_string())?;
+        write_private_file(
+            &key_dir.join(SECRET_KEY_FILE),
+            encrypted.to_string(),
+        )?;
+        Ok(key_dir)
+    }
+
+    /// Remove the key of the given public key
+    pub fn remove(&self, pk: &common::PublicKey) -> Result<(), KeyStoreError> {
+        let key_dir = self.key_dir(&PublicKeyHash::from(pk));
+        if !key_dir.exists() {
+            return Err(KeyStoreError::KeyNotFound(pk.to_string()));
+        }
+        Ok(fs::remove_dir_all(key_dir)?)
+    }
+
+    fn key_dir(&self, pkh: &PublicKeyHash) -> PathBuf {
+        self.dir.join(pkh.to_string())
+    }
+}
+
+impl KeyStore for DirectoryKeyStore {
+    fn public_keys(&self) -> Result<Vec<common::PublicKey>, KeyStoreError> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+        let mut pks = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path().join(PUBLIC_KEY_FILE);
+            if !path.is_file() {
+                continue;
+            }
+            let pk = fs::read_to_string(&path)?;
+            let pk = common::PublicKey::from_str(pk.trim()).map_err(|e| {
+                KeyStoreError::Io(format!(
+                    "Invalid public key in {}: {e}",
+                    path.to_string_lossy()
+                ))
+            })?;
+            pks.push(pk);
+        }
+        Ok(pks)
+    }
+
+    fn has_key(&self, pk: &common::PublicKey) -> bool {
+        self.key_dir(&PublicKeyHash::from(pk))
+            .join(SECRET_KEY_FILE)
+            .is_file()
+    }
+
+    fn is_encrypted(&self, _pk: &common::PublicKey) -> bool {
+        true
+    }
+
+    fn sign(
+        &self,
+        pk: &common::PublicKey,
+        hash: &Hash,
+        password: Option<Zeroizing<String>>,
+    ) -> Result<common::Signature, KeyStoreError> {
+        let path = self.key_dir(&PublicKeyHash::from(pk)).join(SECRET_KEY_FILE);
+        if !path.is_file() {
+            return Err(KeyStoreError::KeyNotFound(pk.to_string()));
+        }
+        let encrypted = fs::read_to_string(&path)?;
+        let encrypted =
+            EncryptedKeypair::from_str(encrypted.trim()).map_err(|e| {
+                KeyStoreError::Io(format!(
+                    "Invalid secret key in {}: {e}",
+                    path.to_string_lossy()
+                ))
+            })?;
+        let secret_key = decrypt(&encrypted, password)?;
+        if secret_key.ref_to() != *pk {
+            return Err(KeyStoreError::Io(format!(
+                "The secret key in {} doesn't match the public key {pk}",
+                path.to_string_lossy()
+            )));
+        }
+        Ok(common::SigScheme::sign(&secret_key, hash))
+    }
+}
+
+/// A request to a remote signer
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub enum SignerRequest {
+    /// Get the public keys of the keys held by the signer
+    PublicKeys,
+    /// Sign the hash with the key of the public key
+    Sign {
+        /// The public key of the key to sign with
+        public_key: common::PublicKey,
+        /// The hash to be signed
+        hash: Hash,
+    },
+}
+
+/// A response of a remote signer
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub enum SignerResponse {
+    /// The public keys of the keys held by the signer
+    PublicKeys(Vec<common::PublicKey>),
+    /// The signature over the requested hash
+    Signature(common::Signature),
+    /// The request has been rejected
+    Error(String),
+}
+
+/// Handle a request to a remote signer with the keys of the given key store.
+/// This can be used to implement a signer. The keys of the key store must not
+/// be encrypted.
+pub fn handle_signer_request(
+    key_store: &dyn KeyStore,
+    request: SignerRequest,
+) -> SignerResponse {
+    let result = match request {
+        SignerRequest::PublicKeys => {
+            key_store.public_keys().map(SignerResponse::PublicKeys)
+        }
+        SignerRequest::Sign { public_key, hash } => key_store
+            .sign(&public_key, &hash, None)
+            .map(SignerResponse::Signature),
+    };
+    result.unwrap_or_else(|e| SignerResponse::Error(e.to_string()))
+}
+
+/// Write a message to a remote signer connection, prefixed with its length
+pub fn write_signer_message(
+    writer: &mut impl Write,
+    message: &impl BorshSerialize,
+) -> io::Result<()> {
+    let bytes = message.serialize_to_vec();
+    let len = u32::try_from(bytes.len())
+        .ok()
+        .filter(|len| *len <= MAX_MESSAGE_LEN)
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "Message too long")
+        })?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(&bytes)?;
+    writer.flush()
+}
+
+/// Read a message, prefixed with its length, from a remote signer connection
+pub fn read_signer_message<T: BorshDeserialize>(
+    reader: &mut impl Read,
+) -> io::Result<T> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len);
+    if len > MAX_MESSAGE_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Message too long",
+        ));
+    }
+    let mut bytes = vec![0u8; len as usize];
+    reader.read_exact(&mut bytes)?;
+    T::try_from_slice(&bytes)
+}
+
+/// Key store delegating the signing to a remote signer listening on a local
+/// Unix socket. A connection is opened per request.
+#[derive(Debug, Clone)]
+pub struct RemoteKeyStore {
+    socket: PathBuf,
+}
+
+impl RemoteKeyStore {
+    /// Make a key store connecting to the signer at the given socket
+    pub fn new(socket: PathBuf) -> Self {
+        Self { socket }
+    }
+
+    /// Send a request to the remote signer and wait for its response
+    #[cfg(unix)]
+    pub fn request(
+        &self,
+        request: &SignerRequest,
+    ) -> Result<SignerResponse, KeyStoreError> {
+        let mut stream = std::os::unix::net::UnixStream::connect(&self.socket)
+            .map_err(|e| {
+                KeyStoreError::Remote(format!(
+                    "Failed to connect to {}: {e}",
+                    self.socket.to_string_lossy()
+                ))
+            })?;
+        write_signer_message(&mut stream, request)?;
+        Ok(read_signer_message(&mut stream)?)
+    }
+
+    /// Send a request to the remote signer and wait for its response
+    #[cfg(not(unix))]
+    pub fn request(
+        &self,
+        _request: &SignerRequest,
+    ) -> Result<SignerResponse, KeyStoreError> {
+        Err(KeyStoreError::Remote(
+            "Unix sockets aren't supported on this platform".to_string(),
+        ))
+    }
+}
+
+impl KeyStore for RemoteKeyStore {
+    fn public_keys(&self) -> Result<Vec<common::PublicKey>, KeyStoreError> {
+        match self.request(&SignerRequest::PublicKeys)? {
+            SignerResponse::PublicKeys(pks) => Ok(pks),
+            SignerResponse::Error(e) => Err(KeyStoreError::Remote(e)),
+            response => Err(KeyStoreError::Remote(format!(
+                "Unexpected response: {response:?}"
+            ))),
+        }
+    }
+
+    fn sign(
+        &self,
+        pk: &common::PublicKey,
+        hash: &Hash,
+        _password: Option<Zeroizing<String>>,
+    ) -> Result<common::Signature, KeyStoreError> {
+        let request = SignerRequest::Sign {
+            public_key: pk.clone(),
+            hash: *hash,
+        };
+        match self.request(&request)? {
+            SignerResponse::Signature(sig) => {
+                // Don't trust the signer blindly
+                common::SigScheme::verify_signature(pk, hash, &sig).map_err(
+                    |e| {
+                        KeyStoreError::Remote(format!("Invalid signature: {e}"))
+                    },
+                )?;
+                Ok(sig)
+            }
+            SignerResponse::Error(e) => Err(KeyStoreError::Remote(e)),
+            response => Err(KeyStoreError::Remote(format!(
+                "Unexpected response: {response:?}"
+            ))),
+        }
+    }
+}
+
+fn decrypt(
+    encrypted: &EncryptedKeypair<common::SecretKey>,
+    password: Option<Zeroizing<String>>,
+) -> Result<common::SecretKey, KeyStoreError> {
+    let password = password
+        .ok_or(KeyStoreError::Decryption(DecryptionError::EmptyPassword))?;
+    encrypted
+        .decrypt(password)
+        .map_err(KeyStoreError::Decryption)
+}
+
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(())
+}
+
+fn write_private_file(path: &Path, contents: String) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.create(true).write(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(contents.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::key::testing::{keypair_1, keypair_2};
+
+    use super::*;
+
+    #[test]
+    fn test_directory_key_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_store = DirectoryKeyStore::new(dir.path().join("keys"));
+        assert!(key_store.public_keys().unwrap().is_empty());
+
+        let sk = keypair_1();
+        let pk = sk.ref_to();
+        let password = Zeroizing::new("password".to_string());
+        key_store.insert(&sk, password.clone()).unwrap();
+        assert_eq!(key_store.public_keys().unwrap(), vec![pk.clone()]);
+        assert!(key_store.has_key(&pk));
+        assert!(!key_store.has_key(&keypair_2().ref_to()));
+        assert!(key_store.is_encrypted(&pk));
+
+        let hash = Hash::sha256(b"message");
+        let sig = key_store.sign(&pk, &hash, Some(password)).unwrap();
+        assert!(common::SigScheme::verify_signature(&pk, &hash, &sig).is_ok());
+        // The key can't be used without the password
+        assert!(matches!(
+            key_store.sign(&pk, &hash, None),
+            Err(KeyStoreError::Decryption(DecryptionError::EmptyPassword))
+        ));
+        assert!(matches!(
+            key_store.sign(
+                &pk,
+                &hash,
+                Some(Zeroizing::new("wrong".to_string()))
+            ),
+            Err(KeyStoreError::Decryption(DecryptionError::DecryptionError))
+        ));
+
+        key_store.remove(&pk).unwrap();
+        assert!(!key_store.has_key(&pk));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_remote_key_store() {
+        use std::os::unix::net::UnixListener;
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("signer.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+
+        // The signer holds the keys in a wallet store
+        let mut store = Store::default();
+        let sk = keypair_1();
+        let pk = sk.ref_to();
+        store.insert_keypair::<crate::test_utils::TestWalletUtils>(
+            "validator".into(),
+            sk,
+            None,
+            None,
+            None,
+            false,
+        );
+        let signer = std::thread::spawn(move || {
+            // Serve the requests of the test
+            for _ in 0..3 {
+                let (mut stream, _) = listener.accept().unwrap();
+                let request = read_signer_message(&mut stream).unwrap();
+                let response = handle_signer_request(&store, request);
+                write_signer_message(&mut stream, &response).unwrap();
+            }
+        });
+
+        let key_store = RemoteKeyStore::new(socket);
+        assert_eq!(key_store.public_keys().unwrap(), vec![pk.clone()]);
+        let hash = Hash::sha256(b"message");
+        let sig = key_store.sign(&pk, &hash, None).unwrap();
+        assert!(common::SigScheme::verify_signature(&pk, &hash, &sig).is_ok());
+        // The signer doesn't hold the key
+        assert!(matches!(
+            key_store.sign(&keypair_2().ref_to(), &hash, None),
+            Err(KeyStoreError::Remote(_))
+        ));
+        signer.join().unwrap();
+    }
+}
@@ -4,7 +4,9 @@
 pub mod alias;
 mod derivation_path;
 mod keys;
+pub mod keystore;
 pub mod pre_genesis;
+pub mod remote_signer;
 pub mod store;
 
 use std::collections::BTreeMap;
@@ -18,6 +20,7 @@ use namada_core::address::{Address, ImplicitAddress};
 use namada_core::arith::checked;
 use namada_core::chain::BlockHeight;
 use namada_core::collections::{HashMap, HashSet};
+use namada_core::hash::Hash;
 use namada_core::key::*;
 use namada_core::masp::{
     ExtendedSpendingKey, ExtendedViewingKey, PaymentAddress,
@@ -36,6 +39,7 @@ pub use self::keys::{
     DatedKeypair, DatedSpendingKey, DatedViewingKey, DecryptionError,
     StoreSpendingKey, StoredKeypair,
 };
+pub use self::keystore::{KeyStore, KeyStoreConfig};
 pub use self::store::{ConfirmationResponse, ValidatorData, ValidatorKeys};
 use crate::store::{derive_hd_secret_key, derive_hd_spending_key};
 
@@ -265,6 +269,9 @@ pub enum FindKeyError {
     /// Could not decrypt a given key in the wallet
     #[error("{0}")]
     KeyDecryptionError(keys::DecryptionError),
+    /// Could not sign with a key store backend
+    #[error("{0}")]
+    KeyStore(keystore::KeyStoreError),
 }
 
 /// Represents a collection of keys and addresses while caching key decryptions
@@ -547,6 +554,25 @@ impl<U> Wallet<U> {
             .find_spending_key(alias)
             .map(|stored_spend_key| stored_spend_key.is_encrypted())
     }
+
+    /// Add a key store backend to the wallet. Returns `false` if it's already
+    /// configured.
+    pub fn add_key_store(&mut self, config: KeyStoreConfig) -> bool {
+        self.store.add_key_store(config)
+    }
+
+    /// Find the key store backend holding the secret key of the given public
+    /// key. The backends that can't be opened are skipped.
+    pub fn find_key_store(
+        &self,
+        pk: &common::PublicKey,
+    ) -> Option<Box<dyn KeyStore>> {
+        self.store
+            .get_key_stores()
+            .iter()
+            .filter_map(|config| config.open().ok())
+            .find(|key_store| key_store.has_key(pk))
+    }
 }
 
 impl<U: WalletStorage> Wallet<U> {
@@ -581,6 +607,59 @@ impl<U: WalletIo> Wallet<U> {
         prompt_bip39_passphrase: bool,
         password: Option<Zeroizing<String>>,
     ) -> Option<(String, ExtendedSpendingKey)> {
+        let seed = Self::read_zip32_seed(
+            ledger_zip32,
+            mnemonic_passphrase,
+            prompt_bip39_passphrase,
+        )?;
+        // Now ZIP32 derive the extended spending key from the new seed
+        let spend_key = derive_hd_spending_key(&seed, derivation_path.clone());
+
+        self.insert_spending_key(
+            alias,
+            alias_force,
+            spend_key,
+            birthday,
+            password,
+            Some(derivation_path),
+        )
+        .map(|alias| (alias, spend_key))
+    }
+
+    /// Restore the spending keys of the shielded accounts with indices
+    /// `0..accounts` from the user mnemonic code (read from stdin), following
+    /// the ZIP32 derivation paths of these accounts. The keys are not
+    /// inserted into the store, this is left to the caller. Returns each key
+    /// paired with its derivation path.
+    pub fn derive_shielded_accounts_from_mnemonic_code(
+        ledger_zip32: bool,
+        accounts: u32,
+        mnemonic_passphrase: Option<(Mnemonic, Zeroizing<String>)>,
+        prompt_bip39_passphrase: bool,
+    ) -> Option<Vec<(DerivationPath, ExtendedSpendingKey)>> {
+        let seed = Self::read_zip32_seed(
+            ledger_zip32,
+            mnemonic_passphrase,
+            prompt_bip39_passphrase,
+        )?;
+        Some(
+            (0..accounts)
+                .map(|account| {
+                    let path = DerivationPath::for_shielded_account(account);
+                    let spend_key = derive_hd_spending_key(&seed, path.clone());
+                    (path, spend_key)
+                })
+                .collect(),
+        )
+    }
+
+    /// Obtain the seed from which ZIP32 spending keys are derived, using the
+    /// given mnemonic code or reading it from stdin otherwise.
+    fn read_zip32_seed(
+        ledger_zip32: bool,
+        mnemonic_passphrase: Option<(Mnemonic, Zeroizing<String>)>,
+        prompt_bip39_passphrase: bool,
+    ) -> Option<Zeroizing<Vec<u8>>> {
         let (mnemonic, passphrase) =
             if let Some(mnemonic_passphrase) = mnemonic_passphrase {
                 mnemonic_passphrase
@@ -601,7 +680,7 @@ impl<U: WalletIo> Wallet<U> {
                     SchemeType::Ed25519,
                 );
             // Obtain the ZIP32 seed using SLIP10
-            &derive_hd_secret_key(
+            derive_hd_secret_key(
                 SchemeType::Ed25519,
                 seed.as_bytes(),
                 zip32_seed_path,
@@ -609,22 +688,12 @@ impl<U: WalletIo> Wallet<U> {
             .try_to_sk::<ed25519::SecretKey>()
             .expect("Expected Ed25519 key")
             .0
-            .to_bytes()[..]
+            .to_bytes()
+            .to_vec()
         } else {
-            seed.as_bytes()
+            seed.as_bytes().to_vec()
         };
-        // Now ZIP32 derive the extended spending key from the new seed
-        let spend_key = derive_hd_spending_key(seed, derivation_path.clone());
-
-        self.insert_spending_key(
-            alias,
-            alias_force,
-            spend_key,
-            birthday,
-            password,
-            Some(derivation_path),
-        )
-        .map(|alias| (alias, spend_key))
+        Some(Zeroizing::new(seed))
     }
 
     /// Find a derivation path by viewing key
@@ -1051,6 +1120,31 @@ impl<U: WalletIo> Wallet<U> {
         )
     }
 
+    /// Sign the hash with the key store backend holding the secret key of the
+    /// given public key. If the key is encrypted and password not supplied,
+    /// then password will be interactively prompted.
+    pub fn sign_with_key_store(
+        &self,
+        pk: &common::PublicKey,
+        hash: &Hash,
+        password: Option<Zeroizing<String>>,
+    ) -> Result<common::Signature, FindKeyError> {
+        let key_store = self
+            .find_key_store(pk)
+            .ok_or_else(|| FindKeyError::KeyNotFound(pk.to_string()))?;
+        let password = password.or_else(|| {
+            key_store.is_encrypted(pk).then(|| {
+                U::read_password(
+                    false,
+                    Some(&PublicKeyHash::from(pk).to_string()),
+                )
+            })
+        });
+        key_store
+            .sign(pk, hash, password)
+            .map_err(FindKeyError::KeyStore)
+    }
+
     /// Decrypt stored key, if it's not stored un-encrypted.
     /// If a given storage key needs to be decrypted and password is not
     /// supplied, then interactively prompt for password and if successfully
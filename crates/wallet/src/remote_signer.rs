@@ -0,0 +1,976 @@
+//! Remote signer for the protocol key of a validator
+//!
+//! A validator node can leave its protocol key to one or more remote signers
+//! instead of signing with the key of its wallet. The node only sends the
+//! hashes of its messages to the signers, which never reveal the key.
+//!
+//! # Protocol
+//!
+//! The node connects to a signer over TCP (`tcp://<host>:<port>`) or a Unix
+//! socket (`unix://<path>`) and sends a single request per connection. Every
+//! message is Borsh encoded and prefixed with its length as a big-endian
+//! `u32`, the same as for a [`crate::keystore::RemoteKeyStore`]. The node
+//! sends a [`ValidatorSignerRequest`] and the signer replies with a
+//! [`ValidatorSignerResponse`]:
+//!
+//! - [`ValidatorSignerRequest::PublicKey`]: the signer replies with the public
+//!   key of the key it signs with
+//! - [`ValidatorSignerRequest::Sign`]: the signer replies with its signature
+//!   over the hash of a message of a [`SignStep`] at a block height, or with an
+//!   error if it refuses to sign it
+//!
+//! # Authentication
+//!
+//! A Unix socket is only protected by its file permissions. Over TCP, the
+//! node and its signers share a [`SignerAuthKey`]. The signer first sends a
+//! random challenge, and the node sends its request in an
+//! [`AuthenticatedRequest`] with a tag over the challenge and the request.
+//! The signer refuses a request with an invalid tag. The node doesn't need
+//! to authenticate the signer, since it verifies the returned signatures.
+//!
+//! # Double-sign protection
+//!
+//! A signer keeps a high-water mark per step: the last height it has signed
+//! at and the hash it has signed. The mark is persisted before the signature
+//! is returned. Only one hash of a step is signed at a height: a request
+//! below the mark is refused, as is a request at the mark for a different
+//! hash. A request at the mark for the same hash gets the same signature
+//! back, so that the node can retry it. A request too far above the mark is
+//! refused too, so that a single bogus height can't lock the signer.
+//!
+//! # Threshold
+//!
+//! The same key can be held by several signers, each with its own
+//! high-water marks. The node only uses a signature once a threshold of the
+//! signers have returned one. With a threshold over half of the signers, two
+//! nodes sharing them can never both get conflicting messages signed.
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use data_encoding::HEXLOWER_PERMISSIVE;
+use namada_core::borsh::{BorshDeserialize, BorshSerialize, BorshSerializeExt};
+use namada_core::chain::BlockHeight;
+use namada_core::hash::Hash;
+use namada_core::key::*;
+use orion::auth;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+use crate::keystore::{read_signer_message, write_signer_message};
+
+/// The timeout of the connections to the remote signers
+pub const SIGNER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The default number of blocks a signer may sign ahead of its high-water
+/// mark
+pub const DEFAULT_MAX_HEIGHT_JUMP: u64 = 100_000;
+
+/// The length of a key authenticating the requests to the remote signers
+pub const AUTH_KEY_LEN: usize = 32;
+
+/// The length of the challenge sent by a signer over TCP
+const AUTH_CHALLENGE_LEN: usize = 32;
+
+/// Errors of the remote signers
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum RemoteSignerError {
+    #[error("At least one remote signer must be configured")]
+    NoEndpoints,
+    #[error("The remote signer {0} is configured more than once")]
+    DuplicateEndpoint(String),
+    #[error("Invalid remote signer address {0}")]
+    InvalidEndpoint(String),
+    #[error(
+        "The threshold must be a majority of the {endpoints} remote signers, \
+         got {threshold}"
+    )]
+    InvalidThreshold { threshold: usize, endpoints: usize },
+    #[error("Refusing to double sign: {0}")]
+    DoubleSign(String),
+    #[error(
+        "Refusing to sign at height {height}, more than {max_jump} blocks \
+         above the high-water mark at height {mark}"
+    )]
+    HeightJump {
+        height: BlockHeight,
+        mark: BlockHeight,
+        max_jump: u64,
+    },
+    #[error("The remote signer {0} requires an authentication key")]
+    MissingAuthKey(String),
+    #[error("Invalid authentication key: {0}")]
+    InvalidAuthKey(String),
+    #[error(
+        "Only {signed} of the {threshold} required remote signers have \
+         signed: {errors}"
+    )]
+    BelowThreshold {
+        signed: usize,
+        threshold: usize,
+        errors: String,
+    },
+    #[error("Failed to load the signer state: {0}")]
+    State(String),
+    #[error("IO error: {0}")]
+    Io(String),
+}
+
+impl From<io::Error> for RemoteSignerError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err.to_string())
+    }
+}
+
+/// The kinds of messages signed with a protocol key. Each of them has its own
+/// high-water mark.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SignStep {
+    /// The vote extension of the Ethereum events seen by the validator
+    EthEventsVext,
+    /// The vote extension of the Ethereum bridge pool root
+    BridgePoolVext,
+    /// The envelope of a protocol tx of the Ethereum events vote extension
+    EthEventsTx,
+    /// The envelope of a protocol tx of the bridge pool root vote extension
+    BridgePoolTx,
+    /// The envelope of a protocol tx of the validator set update vote
+    /// extension
+    ValSetUpdateTx,
+    /// The envelope of any other protocol tx
+    ProtocolTx,
+    /// The vote extension of the expired Ethereum events retransmitted by
+    /// the validator
+    EthEventsRetransmitVext,
+    /// The envelope of a protocol tx retransmitting expired Ethereum events
+    EthEventsRetransmitTx,
+}
+
+/// A request to sign the hash of a message
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct SignRequest {
+    /// The block height the message is signed at. Only one message of a
+    /// step is signed at a height.
+    pub height: BlockHeight,
+    /// The kind of the message
+    pub step: SignStep,
+    /// The hash of the message
+    pub hash: Hash,
+}
+
+/// A request to a remote signer of a validator
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum ValidatorSignerRequest {
+    /// Get the public key of the key held by the signer
+    PublicKey,
+    /// Sign the hash of a message
+    Sign(SignRequest),
+}
+
+/// A request of a node to a remote signer over TCP, authenticated with the
+/// key they share
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct AuthenticatedRequest {
+    /// The request
+    pub request: ValidatorSignerRequest,
+    /// The tag over the challenge of the signer and the request
+    pub tag: Vec<u8>,
+}
+
+/// A response of a remote signer of a validator
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub enum ValidatorSignerResponse {
+    /// The public key of the key held by the signer
+    PublicKey(common::PublicKey),
+    /// The signature over the requested hash
+    Signature(common::Signature),
+    /// The request has been refused
+    Error(String),
+}
+
+/// The last message of a step signed by a signer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighWaterMark {
+    /// The kind of the message
+    pub step: SignStep,
+    /// The block height the message has been signed at
+    pub height: BlockHeight,
+    /// The hash of the message
+    pub hash: Hash,
+    /// The signature over the hash
+    pub signature: common::Signature,
+}
+
+/// The persisted state of a signer
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SignerState {
+    #[serde(default)]
+    marks: Vec<HighWaterMark>,
+}
+
+/// The key shared by a node and its remote signers to authenticate the
+/// requests sent over TCP
+#[derive(Clone)]
+pub struct SignerAuthKey(Zeroizing<[u8; AUTH_KEY_LEN]>);
+
+impl fmt::Debug for SignerAuthKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SignerAuthKey(..)")
+    }
+}
+
+impl FromStr for SignerAuthKey {
+    type Err = RemoteSignerError;
+
+    /// Parse a hex encoded key
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = HEXLOWER_PERMISSIVE
+            .decode(s.trim().as_bytes())
+            .map_err(|e| RemoteSignerError::InvalidAuthKey(e.to_string()))?;
+        let bytes = Zeroizing::new(bytes);
+        let key: [u8; AUTH_KEY_LEN] =
+            bytes.as_slice().try_into().map_err(|_| {
+                RemoteSignerError::InvalidAuthKey(format!(
+                    "Expected {AUTH_KEY_LEN} bytes, got {}",
+                    bytes.len()
+                ))
+            })?;
+        Ok(Self(Zeroizing::new(key)))
+    }
+}
+
+impl SignerAuthKey {
+    /// Read a hex encoded key from the file at the given path
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, RemoteSignerError> {
+        let hex = Zeroizing::new(fs::read_to_string(path)?);
+        hex.parse()
+    }
+
+    /// The tag of the request sent in reply to the given challenge
+    fn tag(
+        &self,
+        challenge: &[u8; AUTH_CHALLENGE_LEN],
+        request: &ValidatorSignerRequest,
+    ) -> Vec<u8> {
+        let key = auth::SecretKey::from_slice(self.0.as_slice())
+            .expect("The authentication key has a valid length");
+        let data = [challenge.as_slice(), &request.serialize_to_vec()].concat();
+        auth::authenticate(&key, &data)
+            .expect("Authenticating a request shouldn't fail")
+            .unprotected_as_bytes()
+            .to_vec()
+    }
+
+    /// Check the tag of the request sent in reply to the given challenge
+    fn verify(
+        &self,
+        challenge: &[u8; AUTH_CHALLENGE_LEN],
+        request: &ValidatorSignerRequest,
+        tag: &[u8],
+    ) -> bool {
+        let key = auth::SecretKey::from_slice(self.0.as_slice())
+            .expect("The authentication key has a valid length");
+        let data = [challenge.as_slice(), &request.serialize_to_vec()].concat();
+        auth::Tag::from_slice(tag)
+            .and_then(|tag| auth::authenticate_verify(&tag, &key, &data))
+            .is_ok()
+    }
+}
+
+/// A signer of the messages of a validator's protocol key which refuses to
+/// double sign. It can serve the requests of a node connected to it.
+pub struct ValidatorSigner {
+    keypair: common::SecretKey,
+    state_path: PathBuf,
+    state: SignerState,
+    max_height_jump: u64,
+}
+
+impl ValidatorSigner {
+    /// Open a signer of the given key which keeps its high-water marks in
+    /// the file at the given path. The file is created on the first
+    /// signature. The signer refuses to sign more than `max_height_jump`
+    /// blocks above the high-water mark of a step.
+    pub fn open(
+        keypair: common::SecretKey,
+        state_path: PathBuf,
+        max_height_jump: u64,
+    ) -> Result<Self, RemoteSignerError> {
+        let state = match fs::read(&state_path) {
+            Ok(bytes) => toml::from_slice(&bytes)
+                .map_err(|e| RemoteSignerError::State(e.to_string()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                SignerState::default()
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            keypair,
+            state_path,
+            state,
+            max_height_jump,
+        })
+    }
+
+    /// The public key of the signer's key
+    pub fn public_key(&self) -> common::PublicKey {
+        self.keypair.to_public()
+    }
+
+    /// The high-water mark of the given step, if any message of the step
+    /// has been signed
+    pub fn high_water_mark(&self, step: SignStep) -> Option<&HighWaterMark> {
+        self.state.marks.iter().find(|mark| mark.step == step)
+    }
+
+    /// Sign the hash of the requested message, unless it conflicts with a
+    /// message signed before
+    pub fn sign(
+        &mut self,
+        request: &SignRequest,
+    ) -> Result<common::Signature, RemoteSignerError> {
+        if let Some(mark) = self.high_water_mark(request.step) {
+            if request.height < mark.height {
+                return Err(RemoteSignerError::DoubleSign(format!(
+                    "{:?} at height {} is below the high-water mark at height \
+                     {}",
+                    request.step, request.height, mark.height
+                )));
+            }
+            if request.height == mark.height {
+                if mark.hash == request.hash {
+                    return Ok(mark.signature.clone());
+                }
+                return Err(RemoteSignerError::DoubleSign(format!(
+                    "a different {:?} has already been signed at height {}",
+                    request.step, request.height
+                )));
+            }
+            let max_height = mark.height.0.saturating_add(self.max_height_jump);
+            if request.height.0 > max_height {
+                return Err(RemoteSignerError::HeightJump {
+                    height: request.height,
+                    mark: mark.height,
+                    max_jump: self.max_height_jump,
+                });
+            }
+        }
+
+        let signature = common::SigScheme::sign(&self.keypair, request.hash);
+        let mark = HighWaterMark {
+            step: request.step,
+            height: request.height,
+            hash: request.hash,
+            signature: signature.clone(),
+        };
+        // The mark must be persisted before the signature is released
+        let mut state = self.state.clone();
+        state.marks.retain(|mark| mark.step != request.step);
+        state.marks.push(mark);
+        save_state(&self.state_path, &state)?;
+        self.state = state;
+        Ok(signature)
+    }
+
+    /// Handle a request of a node
+    pub fn handle_request(
+        &mut self,
+        request: ValidatorSignerRequest,
+    ) -> ValidatorSignerResponse {
+        match request {
+            ValidatorSignerRequest::PublicKey => {
+                ValidatorSignerResponse::PublicKey(self.public_key())
+            }
+            ValidatorSignerRequest::Sign(request) => {
+                match self.sign(&request) {
+                    Ok(signature) => {
+                        ValidatorSignerResponse::Signature(signature)
+                    }
+                    Err(e) => ValidatorSignerResponse::Error(e.to_string()),
+                }
+            }
+        }
+    }
+
+    /// Serve the request of a node connection
+    pub fn serve_connection(
+        &mut self,
+        stream: &mut (impl Read + Write),
+    ) -> io::Result<()> {
+        let request = read_signer_message(stream)?;
+        let response = self.handle_request(request);
+        write_signer_message(stream, &response)
+    }
+
+    /// Serve the request of a node connection, which must be authenticated
+    /// with the given key
+    pub fn serve_authenticated_connection(
+        &mut self,
+        stream: &mut (impl Read + Write),
+        auth_key: &SignerAuthKey,
+    ) -> io::Result<()> {
+        let mut challenge = [0u8; AUTH_CHALLENGE_LEN];
+        orion::util::secure_rand_bytes(&mut challenge).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "Failed to generate an authentication challenge",
+            )
+        })?;
+        write_signer_message(stream, &challenge)?;
+        let AuthenticatedRequest { request, tag } =
+            read_signer_message(stream)?;
+        if !auth_key.verify(&challenge, &request, &tag) {
+            write_signer_message(
+                stream,
+                &ValidatorSignerResponse::Error(
+                    "The request isn't authenticated".to_string(),
+                ),
+            )?;
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "The request isn't authenticated",
+            ));
+        }
+        let response = self.handle_request(request);
+        write_signer_message(stream, &response)
+    }
+
+    /// Serve the nodes connecting to the given TCP listener, whose requests
+    /// must be authenticated with the given key. A failed connection doesn't
+    /// stop the signer.
+    pub fn serve_tcp(
+        &mut self,
+        listener: &TcpListener,
+        auth_key: &SignerAuthKey,
+    ) -> io::Result<()> {
+        for stream in listener.incoming() {
+            let mut stream = stream?;
+            stream.set_read_timeout(Some(SIGNER_TIMEOUT))?;
+            let _ = self.serve_authenticated_connection(&mut stream, auth_key);
+        }
+        Ok(())
+    }
+
+    /// Serve the nodes connecting to the given Unix socket listener. A
+    /// failed connection doesn't stop the signer.
+    #[cfg(unix)]
+    pub fn serve_unix(
+        &mut self,
+        listener: &std::os::unix::net::UnixListener,
+    ) -> io::Result<()> {
+        for stream in listener.incoming() {
+            let mut stream = stream?;
+            stream.set_read_timeout(Some(SIGNER_TIMEOUT))?;
+            let _ = self.serve_connection(&mut stream);
+        }
+        Ok(())
+    }
+}
+
+/// The address of a remote signer
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SignerEndpoint {
+    /// A TCP address, given as `tcp://<host>:<port>`
+    Tcp(String),
+    /// A Unix socket path, given as `unix://<path>`
+    Unix(PathBuf),
+}
+
+impl FromStr for SignerEndpoint {
+    type Err = RemoteSignerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(addr) = s.strip_prefix("tcp://") {
+            if !addr.is_empty() {
+                return Ok(Self::Tcp(addr.to_string()));
+            }
+        } else if let Some(path) = s.strip_prefix("unix://") {
+            if !path.is_empty() {
+                return Ok(Self::Unix(PathBuf::from(path)));
+            }
+        }
+        Err(RemoteSignerError::InvalidEndpoint(s.to_string()))
+    }
+}
+
+impl Display for SignerEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "tcp://{addr}"),
+            Self::Unix(path) => write!(f, "unix://{}", path.to_string_lossy()),
+        }
+    }
+}
+
+impl SignerEndpoint {
+    /// Send a request to the signer and wait for its response. A request
+    /// over TCP is authenticated with the given key.
+    pub fn request(
+        &self,
+        request: &ValidatorSignerRequest,
+        auth_key: Option<&SignerAuthKey>,
+    ) -> Result<ValidatorSignerResponse, RemoteSignerError> {
+        match self {
+            Self::Tcp(addr) => {
+                let auth_key = auth_key.ok_or_else(|| {
+                    RemoteSignerError::MissingAuthKey(self.to_string())
+                })?;
+                let addr = addr.to_socket_addrs()?.next().ok_or_else(|| {
+                    RemoteSignerError::InvalidEndpoint(self.to_string())
+                })?;
+                let mut stream =
+                    TcpStream::connect_timeout(&addr, SIGNER_TIMEOUT)?;
+                stream.set_read_timeout(Some(SIGNER_TIMEOUT))?;
+                stream.set_write_timeout(Some(SIGNER_TIMEOUT))?;
+                let challenge: [u8; AUTH_CHALLENGE_LEN] =
+                    read_signer_message(&mut stream)?;
+                let request = AuthenticatedRequest {
+                    request: request.clone(),
+                    tag: auth_key.tag(&challenge, request),
+                };
+                write_signer_message(&mut stream, &request)?;
+                Ok(read_signer_message(&mut stream)?)
+            }
+            #[cfg(unix)]
+            Self::Unix(path) => {
+                let mut stream = std::os::unix::net::UnixStream::connect(path)?;
+                stream.set_read_timeout(Some(SIGNER_TIMEOUT))?;
+                stream.set_write_timeout(Some(SIGNER_TIMEOUT))?;
+                write_signer_message(&mut stream, request)?;
+                Ok(read_signer_message(&mut stream)?)
+            }
+            #[cfg(not(unix))]
+            Self::Unix(_) => Err(RemoteSignerError::Io(
+                "Unix sockets aren't supported on this platform".to_string(),
+            )),
+        }
+    }
+}
+
+/// The client of the remote signers of a validator's protocol key, used by
+/// its node
+#[derive(Debug)]
+pub struct RemoteSigner {
+    endpoints: Vec<SignerEndpoint>,
+    threshold: NonZeroUsize,
+    public_key: common::PublicKey,
+    auth_key: Option<SignerAuthKey>,
+}
+
+impl RemoteSigner {
+    /// Sign with the key of the given public key held by the given signers.
+    /// The threshold must be a strict majority of the signers. When no
+    /// threshold is given, the smallest majority is used. The signers over
+    /// TCP require the authentication key.
+    pub fn new(
+        endpoints: Vec<SignerEndpoint>,
+        threshold: Option<usize>,
+        public_key: common::PublicKey,
+        auth_key: Option<SignerAuthKey>,
+    ) -> Result<Self, RemoteSignerError> {
+        let count = endpoints.len();
+        if count == 0 {
+            return Err(RemoteSignerError::NoEndpoints);
+        }
+        let mut unique = BTreeSet::new();
+        if let Some(endpoint) = endpoints.iter().find(|e| !unique.insert(*e)) {
+            return Err(RemoteSignerError::DuplicateEndpoint(
+                endpoint.to_string(),
+            ));
+        }
+        if auth_key.is_none() {
+            if let Some(endpoint) = endpoints
+                .iter()
+                .find(|e| matches!(e, SignerEndpoint::Tcp(_)))
+            {
+                return Err(RemoteSignerError::MissingAuthKey(
+                    endpoint.to_string(),
+                ));
+            }
+        }
+        let majority = (count / 2).saturating_add(1);
+        let threshold = threshold.unwrap_or(majority);
+        if threshold < majority || threshold > count {
+            return Err(RemoteSignerError::InvalidThreshold {
+                threshold,
+                endpoints: count,
+            });
+        }
+        Ok(Self {
+            endpoints,
+            threshold: NonZeroUsize::new(threshold)
+                .expect("The threshold is at least a majority"),
+            public_key,
+            auth_key,
+        })
+    }
+
+    /// The public key of the key held by the signers
+    pub fn public_key(&self) -> &common::PublicKey {
+        &self.public_key
+    }
+
+    /// The number of signers that must sign a message
+    pub fn threshold(&self) -> NonZeroUsize {
+        self.threshold
+    }
+
+    /// Sign the hash of a message of the given step at the given height.
+    /// The signers refuse to sign another message of the same step at the
+    /// same height, but the same message can be signed again.
+    pub fn sign(
+        &self,
+        height: BlockHeight,
+        step: SignStep,
+        hash: &Hash,
+    ) -> Result<common::Signature, RemoteSignerError> {
+        let request = ValidatorSignerRequest::Sign(SignRequest {
+            height,
+            step,
+            hash: *hash,
+        });
+
+        // Every signer is asked, so that they all move their high-water marks
+        let mut signature = None;
+        let mut signed = 0_usize;
+        let mut errors = vec![];
+        for endpoint in &self.endpoints {
+            match endpoint.request(&request, self.auth_key.as_ref()) {
+                Ok(ValidatorSignerResponse::Signature(sig)) => {
+                    // Don't trust the signer blindly
+                    match common::SigScheme::verify_signature(
+                        &self.public_key,
+                        hash,
+                        &sig,
+                    ) {
+                        Ok(()) => {
+                            signed = signed.saturating_add(1);
+                            signature = Some(sig);
+                        }
+                        Err(e) => errors
+                            .push(format!("{endpoint}: invalid signature {e}")),
+                    }
+                }
+                Ok(ValidatorSignerResponse::Error(e)) => {
+                    errors.push(format!("{endpoint}: {e}"))
+                }
+                Ok(response) => errors.push(format!(
+                    "{endpoint}: unexpected response {response:?}"
+                )),
+                Err(e) => errors.push(format!("{endpoint}: {e}")),
+            }
+        }
+        match signature {
+            Some(signature) if signed >= self.threshold.get() => Ok(signature),
+            _ => Err(RemoteSignerError::BelowThreshold {
+                signed,
+                threshold: self.threshold.get(),
+                errors: errors.join("; "),
+            }),
+        }
+    }
+}
+
+fn save_state(path: &Path, state: &SignerState) -> io::Result<()> {
+    let bytes = toml::to_vec(state)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    // Replace the state file atomically so that a crash can't lose the marks
+    let tmp_path = path.with_extension("tmp");
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(&bytes)?;
+    file.sync_all()?;
+    fs::rename(tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::key::testing::{keypair_1, keypair_2};
+
+    use super::*;
+
+    fn request(height: u64, step: SignStep) -> SignRequest {
+        SignRequest {
+            height: height.into(),
+            step,
+            hash: Hash::sha256(format!("{height}/{step:?}")),
+        }
+    }
+
+    fn auth_key(byte: u8) -> SignerAuthKey {
+        HEXLOWER_PERMISSIVE
+            .encode(&[byte; AUTH_KEY_LEN])
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_validator_signer_double_sign() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join("signer_state.toml");
+        let mut signer =
+            ValidatorSigner::open(keypair_1(), state_path.clone(), 10).unwrap();
+
+        let first = request(10, SignStep::EthEventsVext);
+        let sig = signer.sign(&first).unwrap();
+        assert!(
+            common::SigScheme::verify_signature(
+                &signer.public_key(),
+                &first.hash,
+                &sig
+            )
+            .is_ok()
+        );
+        // The same request can be retried
+        assert_eq!(signer.sign(&first).unwrap(), sig);
+        // A different message at the same height is refused
+        let conflict = SignRequest {
+            hash: Hash::sha256(b"conflict"),
+            ..first.clone()
+        };
+        assert!(matches!(
+            signer.sign(&conflict),
+            Err(RemoteSignerError::DoubleSign(_))
+        ));
+        // Other steps have their own marks
+        signer.sign(&request(10, SignStep::EthEventsTx)).unwrap();
+        // The next height can be signed, but not a lower one
+        signer.sign(&request(11, SignStep::EthEventsVext)).unwrap();
+        assert!(matches!(
+            signer.sign(&first),
+            Err(RemoteSignerError::DoubleSign(_))
+        ));
+        // A height too far above the mark is refused
+        assert!(matches!(
+            signer.sign(&request(22, SignStep::EthEventsVext)),
+            Err(RemoteSignerError::HeightJump { max_jump: 10, .. })
+        ));
+        signer.sign(&request(21, SignStep::EthEventsVext)).unwrap();
+
+        // The marks are kept over restarts
+        let mut signer = ValidatorSigner::open(
+            keypair_1(),
+            state_path,
+            DEFAULT_MAX_HEIGHT_JUMP,
+        )
+        .unwrap();
+        let mark = signer.high_water_mark(SignStep::EthEventsVext).unwrap();
+        assert_eq!(mark.height, 21.into());
+        assert!(matches!(
+            signer.sign(&first),
+            Err(RemoteSignerError::DoubleSign(_))
+        ));
+    }
+
+    #[test]
+    fn test_remote_signer_config() {
+        let endpoints = |n: usize| -> Vec<SignerEndpoint> {
+            (0..n)
+                .map(|i| SignerEndpoint::Tcp(format!("127.0.0.1:{i}")))
+                .collect()
+        };
+        let pk = keypair_1().ref_to();
+        let key = || Some(auth_key(1));
+
+        assert_eq!(
+            "tcp://127.0.0.1:26659".parse::<SignerEndpoint>().unwrap(),
+            SignerEndpoint::Tcp("127.0.0.1:26659".to_string())
+        );
+        let unix: SignerEndpoint = "unix:///tmp/signer.sock".parse().unwrap();
+        assert_eq!(unix, SignerEndpoint::Unix("/tmp/signer.sock".into()));
+        assert_eq!(unix.to_string(), "unix:///tmp/signer.sock");
+        assert!("127.0.0.1:26659".parse::<SignerEndpoint>().is_err());
+        assert!("tcp://".parse::<SignerEndpoint>().is_err());
+
+        assert!("ab".repeat(AUTH_KEY_LEN).parse::<SignerAuthKey>().is_ok());
+        assert!(matches!(
+            "ab".parse::<SignerAuthKey>(),
+            Err(RemoteSignerError::InvalidAuthKey(_))
+        ));
+        assert!(matches!(
+            "zz".repeat(AUTH_KEY_LEN).parse::<SignerAuthKey>(),
+            Err(RemoteSignerError::InvalidAuthKey(_))
+        ));
+
+        assert!(matches!(
+            RemoteSigner::new(vec![], None, pk.clone(), key()),
+            Err(RemoteSignerError::NoEndpoints)
+        ));
+        let mut duplicate = endpoints(2);
+        duplicate[1] = duplicate[0].clone();
+        assert!(matches!(
+            RemoteSigner::new(duplicate, None, pk.clone(), key()),
+            Err(RemoteSignerError::DuplicateEndpoint(_))
+        ));
+        // The signers over TCP require the authentication key
+        assert!(matches!(
+            RemoteSigner::new(endpoints(3), None, pk.clone(), None),
+            Err(RemoteSignerError::MissingAuthKey(_))
+        ));
+        RemoteSigner::new(vec![unix], None, pk.clone(), None).unwrap();
+        let signer =
+            RemoteSigner::new(endpoints(3), None, pk.clone(), key()).unwrap();
+        assert_eq!(signer.threshold().get(), 2);
+        assert!(matches!(
+            RemoteSigner::new(endpoints(4), Some(2), pk.clone(), key()),
+            Err(RemoteSignerError::InvalidThreshold {
+                threshold: 2,
+                endpoints: 4
+            })
+        ));
+        assert!(matches!(
+            RemoteSigner::new(endpoints(3), Some(4), pk, key()),
+            Err(RemoteSignerError::InvalidThreshold {
+                threshold: 4,
+                endpoints: 3
+            })
+        ));
+    }
+
+    /// Test that a signer over TCP refuses the requests that aren't
+    /// authenticated with its key
+    #[test]
+    fn test_remote_signer_unauthenticated() {
+        let dir = tempfile::tempdir().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint =
+            SignerEndpoint::Tcp(listener.local_addr().unwrap().to_string());
+        let mut signer = ValidatorSigner::open(
+            keypair_1(),
+            dir.path().join("signer.toml"),
+            DEFAULT_MAX_HEIGHT_JUMP,
+        )
+        .unwrap();
+        let signer = std::thread::spawn(move || {
+            let key = auth_key(1);
+            let (mut stream, _) = listener.accept().unwrap();
+            let denied = signer
+                .serve_authenticated_connection(&mut stream, &key)
+                .unwrap_err();
+            assert_eq!(denied.kind(), io::ErrorKind::PermissionDenied);
+            signer
+        });
+
+        let request =
+            ValidatorSignerRequest::Sign(request(5, SignStep::ProtocolTx));
+        assert!(matches!(
+            endpoint.request(&request, None),
+            Err(RemoteSignerError::MissingAuthKey(_))
+        ));
+        assert!(matches!(
+            endpoint.request(&request, Some(&auth_key(2))).unwrap(),
+            ValidatorSignerResponse::Error(_)
+        ));
+        // Nothing was signed
+        let signer = signer.join().unwrap();
+        assert!(signer.high_water_mark(SignStep::ProtocolTx).is_none());
+    }
+
+    /// Test signing with a threshold of stand-in signers, one of which
+    /// has already signed a conflicting message
+    #[test]
+    fn test_remote_signer_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        let hash = Hash::sha256(b"vote extension");
+        let conflict = SignRequest {
+            height: 5.into(),
+            step: SignStep::BridgePoolVext,
+            hash: Hash::sha256(b"conflict"),
+        };
+
+        let mut endpoints = vec![];
+        let mut signers = vec![];
+        for i in 0..3 {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            endpoints.push(SignerEndpoint::Tcp(
+                listener.local_addr().unwrap().to_string(),
+            ));
+            let mut signer = ValidatorSigner::open(
+                keypair_1(),
+                dir.path().join(format!("signer_{i}.toml")),
+                DEFAULT_MAX_HEIGHT_JUMP,
+            )
+            .unwrap();
+            if i == 0 {
+                signer.sign(&conflict).unwrap();
+            }
+            signers.push(std::thread::spawn(move || {
+                let key = auth_key(1);
+                // Serve the three requests of the test
+                for _ in 0..3 {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    signer
+                        .serve_authenticated_connection(&mut stream, &key)
+                        .unwrap();
+                }
+            }));
+        }
+
+        let remote = RemoteSigner::new(
+            endpoints.clone(),
+            None,
+            keypair_1().ref_to(),
+            Some(auth_key(1)),
+        )
+        .unwrap();
+        // Two of the three signers sign
+        let sig = remote
+            .sign(5.into(), SignStep::BridgePoolVext, &hash)
+            .unwrap();
+        assert!(
+            common::SigScheme::verify_signature(
+                remote.public_key(),
+                &hash,
+                &sig
+            )
+            .is_ok()
+        );
+        // The same message can be signed again
+        assert_eq!(
+            remote
+                .sign(5.into(), SignStep::BridgePoolVext, &hash)
+                .unwrap(),
+            sig
+        );
+        // The signatures of a signer of another key don't count
+        let remote = RemoteSigner::new(
+            endpoints,
+            Some(3),
+            keypair_2().ref_to(),
+            Some(auth_key(1)),
+        )
+        .unwrap();
+        assert!(matches!(
+            remote.sign(6.into(), SignStep::BridgePoolVext, &hash),
+            Err(RemoteSignerError::BelowThreshold {
+                signed: 0,
+                threshold: 3,
+                ..
+            })
+        ));
+        for signer in signers {
+            signer.join().unwrap();
+        }
+    }
+}
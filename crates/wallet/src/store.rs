@@ -22,6 +22,7 @@ use zeroize::Zeroizing;
 use super::alias::{self, Alias};
 use super::derivation_path::DerivationPath;
 use super::pre_genesis;
+use crate::keystore::KeyStoreConfig;
 use crate::{StoreSpendingKey, StoredKeypair, WalletIo};
 
 /// Actions that can be taken when there is an alias conflict
@@ -85,6 +86,9 @@ pub struct Store {
     pub(crate) validator_data: Option<ValidatorData>,
     /// Namada address vp type
     address_vp_types: BTreeMap<AddressVpType, HashSet<Address>>,
+    /// Backends holding keys outside of this store
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    key_stores: Vec<KeyStoreConfig>,
 }
 
 /// Grouping of addresses by validity predicate.
@@ -640,6 +644,7 @@ impl Store {
             pkhs,
             validator_data: _,
             address_vp_types,
+            key_stores,
         } = self;
         birthdays.extend(store.birthdays);
         view_keys.extend(store.view_keys);
@@ -651,6 +656,11 @@ impl Store {
         addresses.extend(store.addresses);
         pkhs.extend(store.pkhs);
         address_vp_types.extend(store.address_vp_types);
+        for config in store.key_stores {
+            if !key_stores.contains(&config) {
+                key_stores.push(config);
+            }
+        }
     }
 
     /// Extend this store from pre-genesis validator wallet.
@@ -733,6 +743,27 @@ impl Store {
             .insert(address);
     }
 
+    /// Get the configured key store backends
+    pub fn get_key_stores(&self) -> &[KeyStoreConfig] {
+        &self.key_stores
+    }
+
+    /// Add a key store backend. Returns `false` if it's already configured.
+    pub fn add_key_store(&mut self, config: KeyStoreConfig) -> bool {
+        if self.key_stores.contains(&config) {
+            return false;
+        }
+        self.key_stores.push(config);
+        true
+    }
+
+    /// Remove a key store backend. Returns `false` if it isn't configured.
+    pub fn remove_key_store(&mut self, config: &KeyStoreConfig) -> bool {
+        let len = self.key_stores.len();
+        self.key_stores.retain(|c| c != config);
+        self.key_stores.len() != len
+    }
+
     /// Decode a Store from the given bytes
     pub fn decode(data: Vec<u8>) -> Result<Self, toml::de::Error> {
         // First try to decode Store from current version (with separate
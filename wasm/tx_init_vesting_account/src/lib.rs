@@ -0,0 +1,66 @@
+//! A tx to initialize a new established address with a given public key, a
+//! validity predicate and a vesting schedule of its tokens.
+
+use namada_tx_prelude::*;
+
+const HASH_LEN: usize = hash::HASH_LENGTH;
+/// The name of the only VP that a vesting account can be initialized with
+const VP_VESTING_WASM: &str = "vp_vesting.wasm";
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let BatchedTx {
+        tx: signed,
+        ref cmt,
+    } = tx_data;
+    let tx_data = account::InitVestingAccount::try_from_slice(&data[..])
+        .wrap_err("Failed to decode InitVestingAccount tx data")?;
+    debug_log!("apply_tx called to init a new vesting account");
+
+    // The vesting schedule is only enforced by the vesting VP, so the account
+    // cannot be initialized with any other VP
+    let vesting_vp_hash: hash::Hash = ctx
+        .read(&storage::Key::wasm_hash(VP_VESTING_WASM))?
+        .ok_or_err_msg("The vesting VP is not stored on this chain")?;
+    if tx_data.vp_code_hash != vesting_vp_hash {
+        const ERR_MSG: &str = "A vesting account must use the vesting VP";
+        debug_log!("{ERR_MSG}");
+        ctx.set_commitment_sentinel();
+        return Err(Error::new_const(ERR_MSG));
+    }
+
+    let vp_code_sec = signed
+        .get_section(&tx_data.vp_code_hash)
+        .ok_or_err_msg("VP code section not found in tx")
+        .inspect_err(|_| {
+            ctx.set_commitment_sentinel();
+        })?
+        .extra_data_sec()
+        .ok_or_err_msg("VP code section must be tagged as extra")
+        .inspect_err(|_| {
+            ctx.set_commitment_sentinel();
+        })?;
+
+    let entropy = {
+        let mut buffer = [0u8; HASH_LEN * 2];
+
+        // Add code hash as entropy
+        buffer[..HASH_LEN].copy_from_slice(&cmt.code_sechash().0);
+
+        // Add data hash as entropy
+        buffer[HASH_LEN..].copy_from_slice(&cmt.data_sechash().0);
+
+        buffer
+    };
+
+    let address = ctx
+        .init_account(vp_code_sec.code.hash(), &vp_code_sec.tag, &entropy)
+        .wrap_err("Failed to generate a new established account address")?;
+
+    account::init_vesting_account(ctx, &address, tx_data)
+        .wrap_err("Vesting account creation failed")?;
+
+    debug_log!("Created vesting account {address}");
+    Ok(())
+}
@@ -0,0 +1,15 @@
+//! A tx to schedule an inner tx for execution at a future block height or
+//! epoch, escrowing its fees.
+
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let scheduled = tx_scheduler::ScheduledTx::try_from_slice(&data[..])
+        .wrap_err("Failed to decode ScheduledTx tx data")?;
+    debug_log!("apply_tx called to schedule tx {}", scheduled.tx_hash);
+
+    tx_scheduler::schedule_tx(ctx, &scheduled)
+        .wrap_err("Failed to schedule the tx")
+}
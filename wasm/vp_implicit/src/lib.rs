@@ -79,8 +79,12 @@ fn validate_tx(
                     source, validator, ..
                 })
                 | PosAction::Withdraw(Withdraw { source, validator })
-                | PosAction::ClaimRewards(ClaimRewards { validator, source }) =>
-                {
+                | PosAction::ClaimRewards(ClaimRewards { validator, source })
+                | PosAction::AutoCompound(AutoCompound {
+                    validator,
+                    source,
+                    ..
+                }) => {
                     let source = source.unwrap_or(validator);
                     gadget.verify_signatures_when(
                         || source == addr,
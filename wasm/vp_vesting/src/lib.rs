@@ -0,0 +1,680 @@
+//! A vesting account VP, which extends the user VP with a time-locked vesting
+//! schedule of the account's tokens.
+//!
+//! On top of the rules of the user VP, this VP rejects debits of the vested
+//! token that would bring the account's balance below the amount that is still
+//! locked by the vesting schedule at the current epoch. Locked native tokens
+//! may still be bonded to a validator with a valid signature(s).
+//!
+//! The vesting schedule cannot be modified and the VP cannot be replaced until
+//! all the tokens are vested.
+
+use booleans::BoolResultUnitExt;
+use namada_vp_prelude::tx::action::*;
+use namada_vp_prelude::*;
+
+#[validity_predicate]
+fn validate_tx(
+    ctx: &Ctx,
+    tx: BatchedTx,
+    addr: Address,
+    keys_changed: BTreeSet<storage::Key>,
+    verifiers: BTreeSet<Address>,
+) -> VpResult {
+    debug_log!(
+        "vp_vesting called with user addr: {}, key_changed: {:?}, verifiers: \
+         {:?}",
+        addr,
+        keys_changed,
+        verifiers
+    );
+
+    let BatchedTx { tx, ref cmt } = tx;
+    // Check if this is a governance proposal first
+    let is_gov_proposal = tx
+        .data(cmt)
+        .and_then(|tx_data| {
+            let proposal_id = u64::try_from_slice(&tx_data).ok()?;
+            Some(is_proposal_accepted(ctx, proposal_id))
+        })
+        .transpose()?
+        .unwrap_or(false);
+    if is_gov_proposal {
+        // Any change from governance is allowed without further checks
+        return Ok(());
+    }
+
+    let mut gadget = VerifySigGadget::new();
+
+    let schedule =
+        account::vesting_schedule(&ctx.pre(), &addr).into_vp_error()?;
+    let locked = match &schedule {
+        Some(schedule) => {
+            let epoch = ctx.get_block_epoch().into_vp_error()?;
+            schedule.locked_amount(epoch)
+        }
+        None => token::Amount::zero(),
+    };
+    // Only the native token can be bonded, so bonds don't affect the locked
+    // balance of any other token
+    let bonds_vested_token = match &schedule {
+        Some(schedule) => {
+            schedule.token == ctx.get_native_token().into_vp_error()?
+        }
+        None => false,
+    };
+    // The amount of tokens bonded from this account in the tx
+    let bonded = match &schedule {
+        Some(schedule) if bonds_vested_token => {
+            bonded_amount(ctx, &addr, &schedule.token, &keys_changed)?
+        }
+        _ => token::Amount::zero(),
+    };
+
+    // Find the actions applied in the tx
+    let actions = ctx.read_actions().into_vp_error()?;
+
+    // Require authorization by signature when the source of an action is this
+    // VP's address
+    for action in actions {
+        match action {
+            Action::Pos(pos_action) => match pos_action {
+                PosAction::BecomeValidator(source)
+                | PosAction::DeactivateValidator(source)
+                | PosAction::ReactivateValidator(source)
+                | PosAction::Unjail(source)
+                | PosAction::CommissionChange(source)
+                | PosAction::MetadataChange(source)
+                | PosAction::ConsensusKeyChange(source)
+                | PosAction::Redelegation(Redelegation {
+                    owner: source, ..
+                }) => gadget.verify_signatures_when(
+                    || source == addr,
+                    ctx,
+                    &tx,
+                    cmt,
+                    &addr,
+                )?,
+                PosAction::Bond(Bond {
+                    source, validator, ..
+                })
+                | PosAction::Unbond(Unbond {
+                    source, validator, ..
+                })
+                | PosAction::Withdraw(Withdraw { source, validator })
+                | PosAction::ClaimRewards(ClaimRewards { validator, source })
+                | PosAction::AutoCompound(AutoCompound {
+                    validator,
+                    source,
+                    ..
+                }) => {
+                    let source = source.unwrap_or(validator);
+                    gadget.verify_signatures_when(
+                        || source == addr,
+                        ctx,
+                        &tx,
+                        cmt,
+                        &addr,
+                    )?
+                }
+            },
+            Action::Gov(
+                GovAction::InitProposal { author: source }
+                | GovAction::VoteProposal { voter: source, .. },
+            )
+            | Action::Pgf(
+                PgfAction::ResignSteward(source)
+                | PgfAction::UpdateStewardCommission(source),
+            ) => gadget.verify_signatures_when(
+                || source == addr,
+                ctx,
+                &tx,
+                cmt,
+                &addr,
+            )?,
+            Action::Masp(MaspAction::MaspAuthorizer(source)) => gadget
+                .verify_signatures_when(
+                    || source == addr,
+                    ctx,
+                    &tx,
+                    cmt,
+                    &addr,
+                )?,
+            Action::Masp(MaspAction::MaspSectionRef(_)) => (),
+            Action::IbcShielding => (),
+        }
+    }
+
+    keys_changed.iter().try_for_each(|key| {
+        let key_type: KeyType = key.into();
+        let mut validate_change = || match key_type {
+            KeyType::TokenBalance { token, owner } => {
+                if owner == &addr {
+                    let pre: token::Amount =
+                        ctx.read_pre(key).into_vp_error()?.unwrap_or_default();
+                    let post: token::Amount =
+                        ctx.read_post(key).into_vp_error()?.unwrap_or_default();
+                    let change =
+                        post.change().checked_sub(pre.change()).unwrap();
+                    gadget.verify_signatures_when(
+                        // NB: debit has to signed, credit doesn't
+                        || change.is_negative(),
+                        ctx,
+                        &tx,
+                        cmt,
+                        &addr,
+                    )?;
+                    if let Some(schedule) = &schedule {
+                        if token == &schedule.token && change.is_negative() {
+                            // The tokens that were bonded are still locked in
+                            // PoS, so they are not counted as spent
+                            let kept =
+                                post.checked_add(bonded).ok_or_else(|| {
+                                    VpError::Erased(
+                                        "Overflow in the kept balance".into(),
+                                    )
+                                })?;
+                            // The tokens credited to the account after its
+                            // creation are never locked, but the account may
+                            // not debit any locked tokens
+                            (kept >= locked.min(pre)).ok_or_else(|| {
+                                VpError::Erased(format!(
+                                    "Debit of {} tokens exceeds the vested \
+                                     amount, {} tokens are still locked",
+                                    pre.checked_sub(post).unwrap_or_default(),
+                                    locked
+                                ))
+                            })?;
+                        }
+                    }
+                    let sign = if change.non_negative() { "" } else { "-" };
+                    debug_log!("token key: {key}, change: {sign}{change:?}");
+                } else {
+                    // If this is not the owner, allow any change
+                    debug_log!(
+                        "This address ({}) is not of owner ({}) of token key: \
+                         {}",
+                        addr,
+                        owner,
+                        key
+                    );
+                }
+                Ok(())
+            }
+            KeyType::TokenMinted => {
+                verifiers.contains(&address::MULTITOKEN).ok_or_else(|| {
+                    VpError::Erased(
+                        "The Multitoken VP should have been a verifier for \
+                         this transaction, since a token was minted"
+                            .into(),
+                    )
+                })
+            }
+            KeyType::TokenMinter(minter_addr) => gadget.verify_signatures_when(
+                || minter_addr == &addr,
+                ctx,
+                &tx,
+                cmt,
+                &addr,
+            ),
+            KeyType::VestingSchedule(owner) => {
+                (owner != &addr).ok_or_else(|| {
+                    VpError::Erased(
+                        "The vesting schedule of an account cannot be modified"
+                            .into(),
+                    )
+                })
+            }
+            KeyType::Vp(owner) => {
+                if owner == &addr {
+                    locked.is_zero().ok_or_else(|| {
+                        VpError::Erased(format!(
+                            "The VP of a vesting account cannot be updated \
+                             while {locked} tokens are still locked"
+                        ))
+                    })?;
+                }
+                let vp_overwritten: bool =
+                    ctx.has_key_post(key).into_vp_error()?;
+                gadget.verify_signatures_when(
+                    || owner == &addr && vp_overwritten,
+                    ctx,
+                    &tx,
+                    cmt,
+                    &addr,
+                )
+            }
+            KeyType::Masp | KeyType::Ibc => Ok(()),
+            KeyType::Unknown => {
+                // Unknown changes require a valid signature
+                gadget.verify_signatures(ctx, &tx, cmt, &addr)
+            }
+        };
+        validate_change().inspect_err(|reason| {
+            log_string(format!(
+                "Modification on key {key} failed vp_vesting: {reason}"
+            ));
+        })
+    })
+}
+
+/// Find the amount of `vested_token` bonded from the `owner`'s account in the
+/// tx from the changes of its bonds. The bonded amount is capped by the
+/// increase of the PoS account's balance, so that only the tokens that have
+/// actually been moved into PoS are counted.
+fn bonded_amount(
+    ctx: &Ctx,
+    owner: &Address,
+    vested_token: &Address,
+    keys_changed: &BTreeSet<storage::Key>,
+) -> VpEnvResult<token::Amount> {
+    let change = |key: &storage::Key| -> VpEnvResult<token::Change> {
+        let pre: token::Amount =
+            ctx.read_pre(key).into_vp_error()?.unwrap_or_default();
+        let post: token::Amount =
+            ctx.read_post(key).into_vp_error()?.unwrap_or_default();
+        post.change().checked_sub(pre.change()).ok_or_else(|| {
+            VpError::Erased("Overflow in the bonded amount".into())
+        })
+    };
+
+    let mut bonds_change = token::Change::zero();
+    for key in keys_changed {
+        if let Some((bond_id, _start)) =
+            proof_of_stake::storage_key::is_bond_key(key)
+        {
+            if &bond_id.source == owner {
+                bonds_change = bonds_change
+                    .checked_add(change(key)?)
+                    .ok_or_else(|| {
+                        VpError::Erased("Overflow in the bonded amount".into())
+                    })?;
+            }
+        }
+    }
+    let pos_balance_change = change(&token::storage_key::balance_key(
+        vested_token,
+        &proof_of_stake::ADDRESS,
+    ))?;
+
+    let bonded = bonds_change.min(pos_balance_change);
+    Ok(if bonded.is_positive() {
+        token::Amount::from_change(bonded)
+    } else {
+        token::Amount::zero()
+    })
+}
+
+enum KeyType<'a> {
+    TokenBalance {
+        token: &'a Address,
+        owner: &'a Address,
+    },
+    TokenMinted,
+    TokenMinter(&'a Address),
+    VestingSchedule(&'a Address),
+    Vp(&'a Address),
+    Masp,
+    Ibc,
+    Unknown,
+}
+
+impl<'a> From<&'a storage::Key> for KeyType<'a> {
+    fn from(key: &'a storage::Key) -> KeyType<'a> {
+        if let Some([token, owner]) =
+            token::storage_key::is_any_token_balance_key(key)
+        {
+            Self::TokenBalance { token, owner }
+        } else if token::storage_key::is_any_minted_balance_key(key).is_some() {
+            Self::TokenMinted
+        } else if let Some(minter) = token::storage_key::is_any_minter_key(key)
+        {
+            Self::TokenMinter(minter)
+        } else if let Some(owner) = account::is_vesting_schedule_key(key) {
+            Self::VestingSchedule(owner)
+        } else if let Some(address) = key.is_validity_predicate() {
+            Self::Vp(address)
+        } else if token::storage_key::is_masp_key(key) {
+            Self::Masp
+        } else if ibc::is_ibc_key(key) {
+            Self::Ibc
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_test_utils::TestWasms;
+    // Use this as `#[test]` annotation to enable logging
+    use namada_tests::log::test;
+    use namada_tests::native_vp::pos::init_pos;
+    use namada_tests::tx::{
+        self, tx_host_env, Authorization, Code, Data, TestTxEnv,
+    };
+    use namada_tests::vp::*;
+    use namada_tx_prelude::chain::Epoch;
+    use namada_tx_prelude::dec::Dec;
+    use namada_tx_prelude::proof_of_stake::parameters::OwnedPosParams;
+    use namada_tx_prelude::proof_of_stake::types::GenesisValidator;
+    use namada_tx_prelude::{StorageWrite, TxEnv};
+    use namada_vp_prelude::account::{AccountPublicKeysMap, VestingSchedule};
+    use namada_vp_prelude::key::RefTo;
+
+    use super::*;
+
+    /// A schedule linearly vesting `amount` tokens from epoch 0 to epoch 10,
+    /// which is fully locked at the genesis epoch
+    fn locked_schedule(token: &Address, amount: u64) -> VestingSchedule {
+        VestingSchedule {
+            token: token.clone(),
+            amount: token::Amount::from_uint(amount, 0).unwrap(),
+            start: Epoch(0),
+            cliff: Epoch(0),
+            end: Epoch(10),
+        }
+    }
+
+    /// Sign the tx in the VP env with the given key and run the VP on it
+    fn validate_signed_tx(
+        vp_owner: Address,
+        keypair: key::common::SecretKey,
+    ) -> VpResult {
+        let pks_map = AccountPublicKeysMap::from_iter(vec![keypair.ref_to()]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.batched_tx.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Authorization(Authorization::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.batch_first_tx();
+        vp_env.batched_tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+    }
+
+    /// Set up a vesting account with a balance of `balance` tokens of which
+    /// `locked` tokens are locked and run a signed transfer of `amount` tokens
+    /// out of it.
+    fn validate_debit(balance: u64, locked: u64, amount: u64) -> VpResult {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let target = address::testing::established_address_2();
+        let token = address::testing::nam();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &target, &token]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key], 1);
+        tx_env
+            .state
+            .write(
+                &account::vesting_schedule_key(&vp_owner),
+                locked_schedule(&token, locked),
+            )
+            .unwrap();
+
+        tx_env.credit_tokens(
+            &vp_owner,
+            &token,
+            token::Amount::from_uint(balance, 0).unwrap(),
+        );
+        // write the denomination of NAM into storage
+        token::write_denom(
+            &mut tx_env.state,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Apply transfer in a transaction
+            tx_host_env::token::transfer(
+                tx::ctx(),
+                address,
+                &target,
+                &token,
+                token::Amount::from_uint(amount, 0).unwrap(),
+            )
+            .unwrap();
+        });
+
+        validate_signed_tx(vp_owner, keypair)
+    }
+
+    /// Test that a signed debit of the unlocked tokens is accepted.
+    #[test]
+    fn test_signed_debit_of_unlocked_tokens_accepted() {
+        assert!(validate_debit(2_000, 1_000, 1_000).is_ok());
+    }
+
+    /// Test that a signed debit of the locked tokens is rejected.
+    #[test]
+    fn test_signed_debit_of_locked_tokens_rejected() {
+        assert!(validate_debit(2_000, 1_000, 1_001).is_err());
+    }
+
+    /// Test that a Bond action that is not backed by an actual bond does not
+    /// unlock the locked tokens.
+    #[test]
+    fn test_debit_of_locked_tokens_with_unbacked_bond_action_rejected() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let target = address::testing::established_address_2();
+        let validator = address::testing::established_address_3();
+        let token = address::testing::nam();
+        let amount = token::Amount::from_uint(1_000, 0).unwrap();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &target, &token]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key], 1);
+        tx_env
+            .state
+            .write(
+                &account::vesting_schedule_key(&vp_owner),
+                locked_schedule(&token, 2_000),
+            )
+            .unwrap();
+        tx_env.credit_tokens(
+            &vp_owner,
+            &token,
+            token::Amount::from_uint(2_000, 0).unwrap(),
+        );
+        // write the denomination of NAM into storage
+        token::write_denom(
+            &mut tx_env.state,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Claim a bond of the transferred tokens without bonding them
+            tx::ctx()
+                .push_action(Action::Pos(PosAction::Bond(Bond {
+                    validator: validator.clone(),
+                    amount,
+                    source: Some(address.clone()),
+                })))
+                .unwrap();
+            tx_host_env::token::transfer(
+                tx::ctx(),
+                address,
+                &target,
+                &token,
+                amount,
+            )
+            .unwrap();
+        });
+
+        assert!(validate_signed_tx(vp_owner, keypair).is_err());
+    }
+
+    /// Test that a signed bond of locked tokens is accepted.
+    #[test]
+    fn test_signed_bond_of_locked_tokens_accepted() {
+        // Init PoS genesis
+        let pos_params = OwnedPosParams::default();
+        let validator = address::testing::established_address_3();
+        let initial_stake = token::Amount::from_uint(10_098_123, 0).unwrap();
+        let consensus_key = key::testing::keypair_2().ref_to();
+        let protocol_key = key::testing::keypair_1().ref_to();
+        let commission_rate = Dec::new(5, 2).unwrap();
+        let max_commission_rate_change = Dec::new(1, 2).unwrap();
+
+        let genesis_validators = [GenesisValidator {
+            address: validator.clone(),
+            tokens: initial_stake,
+            consensus_key,
+            protocol_key,
+            commission_rate,
+            max_commission_rate_change,
+            eth_hot_key: key::common::PublicKey::Secp256k1(
+                key::testing::gen_keypair::<key::secp256k1::SigScheme>()
+                    .ref_to(),
+            ),
+            eth_cold_key: key::common::PublicKey::Secp256k1(
+                key::testing::gen_keypair::<key::secp256k1::SigScheme>()
+                    .ref_to(),
+            ),
+            metadata: Default::default(),
+        }];
+
+        init_pos(&genesis_validators[..], &pos_params, Epoch(0));
+
+        // Initialize a tx environment
+        let mut tx_env = tx_host_env::take();
+
+        let secret_key = key::testing::keypair_1();
+        let public_key = secret_key.ref_to();
+        let vp_owner: Address = address::testing::established_address_2();
+        let token = address::testing::nam();
+        let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+        let bond_amount = token::Amount::from_uint(5_098_123, 0).unwrap();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&token]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key], 1);
+        tx_env
+            .state
+            .write(
+                &account::vesting_schedule_key(&vp_owner),
+                locked_schedule(&token, 10_098_123),
+            )
+            .unwrap();
+
+        // write the denomination of NAM into storage
+        token::write_denom(
+            &mut tx_env.state,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+
+        // Credit the tokens to the VP owner before running the transaction to
+        // be able to bond them
+        tx_env.credit_tokens(&vp_owner, &token, amount);
+
+        // Initialize VP environment from a bond of the locked tokens
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |_address| {
+            tx::ctx()
+                .bond_tokens(Some(&vp_owner), &validator, bond_amount)
+                .unwrap();
+        });
+
+        assert!(validate_signed_tx(vp_owner, secret_key).is_ok());
+    }
+
+    /// Test that a signed modification of the vesting schedule is rejected.
+    #[test]
+    fn test_signed_vesting_schedule_update_rejected() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let token = address::testing::nam();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key], 1);
+        tx_env
+            .state
+            .write(
+                &account::vesting_schedule_key(&vp_owner),
+                locked_schedule(&token, 1_000),
+            )
+            .unwrap();
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Shorten the vesting schedule in a transaction
+            let schedule = VestingSchedule {
+                end: Epoch(0),
+                ..locked_schedule(&token, 1_000)
+            };
+            tx::ctx()
+                .write(&account::vesting_schedule_key(address), schedule)
+                .unwrap();
+        });
+
+        assert!(validate_signed_tx(vp_owner, keypair).is_err());
+    }
+
+    /// Test that a signed validity predicate update is rejected while some
+    /// tokens are still locked.
+    #[test]
+    fn test_signed_vp_update_while_locked_rejected() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+        tx_env.init_parameters(None, None, None);
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let token = address::testing::nam();
+        let vp_code = TestWasms::VpAlwaysTrue.read_bytes();
+        let vp_hash = sha256(&vp_code);
+        // for the update
+        tx_env.store_wasm_code(vp_code);
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key], 1);
+        tx_env
+            .state
+            .write(
+                &account::vesting_schedule_key(&vp_owner),
+                locked_schedule(&token, 1_000),
+            )
+            .unwrap();
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Update VP in a transaction
+            tx::ctx()
+                .update_validity_predicate(address, vp_hash, &None)
+                .unwrap();
+        });
+
+        assert!(validate_signed_tx(vp_owner, keypair).is_err());
+    }
+}